@@ -1,43 +1,105 @@
-use reqwest::Client;
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "ai")]
+use base64::prelude::{BASE64_STANDARD, Engine};
+use chrono::{Duration, Local, NaiveDate};
+use serde::Serialize;
 use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
 
-use crate::database;
+use crate::arguments::models::Todo;
+use crate::{configs, database};
 
 #[derive(Serialize)]
-struct Content {
-    parts: Vec<Part>,
-    role: String,
+#[serde(rename_all = "camelCase")]
+struct Part {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[cfg(feature = "ai")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inline_data: Option<InlineData>,
 }
 
-#[derive(Serialize)]
-struct Part {
-    text: String,
+impl Part {
+    fn text(text: String) -> Self {
+        Self {
+            text: Some(text),
+            #[cfg(feature = "ai")]
+            inline_data: None,
+        }
+    }
 }
 
+// A file sent along with the prompt, base64 encoded
+#[cfg(feature = "ai")]
 #[derive(Serialize)]
-struct RequestBody {
-    contents: Vec<Content>,
+#[serde(rename_all = "camelCase")]
+struct InlineData {
+    mime_type: String,
+    data: String,
 }
 
-#[derive(Deserialize, Debug)]
-struct CandidatePart {
-    text: String,
+// The Gemini API itself, left out of builds without the `ai` feature
+#[cfg(feature = "ai")]
+mod gemini;
+#[cfg(feature = "ai")]
+use gemini::send;
+
+#[cfg(not(feature = "ai"))]
+async fn send(_db: &database::DBtodo, _parts: Vec<Part>) -> Result<Option<String>, Box<dyn Error>> {
+    Err(crate::features::missing("ai"))
 }
 
-#[derive(Deserialize, Debug)]
-struct Candidate {
-    content: CandidateContent,
+// Send one prompt to Gemini, None when it answers without text
+pub async fn generate(
+    db: &database::DBtodo,
+    prompt: String,
+) -> Result<Option<String>, Box<dyn Error>> {
+    send(db, vec![Part::text(prompt)]).await
 }
 
-#[derive(Deserialize, Debug)]
-struct CandidateContent {
-    parts: Vec<CandidatePart>,
+// Write down what is said in an audio file
+#[cfg(feature = "ai")]
+pub async fn transcribe(
+    db: &database::DBtodo,
+    audio: &[u8],
+    mime_type: &str,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let prompt = "Transcribe this voice memo word for word, in its language. Only the transcript, \
+no timestamps or speaker names.";
+    let audio = Part {
+        text: None,
+        inline_data: Some(InlineData {
+            mime_type: mime_type.to_string(),
+            data: BASE64_STANDARD.encode(audio),
+        }),
+    };
+    send(db, vec![Part::text(prompt.to_string()), audio]).await
 }
 
-#[derive(Deserialize, Debug)]
-struct GeminiResponse {
-    candidates: Vec<Candidate>,
+#[cfg(not(feature = "ai"))]
+pub async fn transcribe(
+    _db: &database::DBtodo,
+    _audio: &[u8],
+    _mime_type: &str,
+) -> Result<Option<String>, Box<dyn Error>> {
+    Err(crate::features::missing("ai"))
+}
+
+// The Gemini API key set with -k
+#[cfg(feature = "ai")]
+pub fn api_key(db: &database::DBtodo) -> Result<String, Box<dyn Error>> {
+    let api_key = db.get_api_credentials().map_err(|e| {
+        format!(
+            "Failed to get API credentials: {}. Did you set an API key using the -k flag?",
+            e
+        )
+    })?;
+
+    if api_key.is_empty() {
+        return Err("No API key found. Please set one using the -k flag first.".into());
+    }
+    Ok(api_key)
 }
 
 pub async fn ask_gemini(prompt: String) -> Result<String, Box<dyn std::error::Error>> {
@@ -53,23 +115,6 @@ pub async fn ask_gemini(prompt: String) -> Result<String, Box<dyn std::error::Er
         .get_todos()
         .map_err(|e| format!("Failed to get todos: {}", e))?;
 
-    // Get API key
-    let api_key = db.get_api_credentials().map_err(|e| {
-        format!(
-            "Failed to get API credentials: {}. Did you set an API key using the -k flag?",
-            e
-        )
-    })?;
-
-    if api_key.is_empty() {
-        return Err("No API key found. Please set one using the -k flag first.".into());
-    }
-
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent?key={}",
-        api_key
-    );
-
     // Format todos as text for the prompt
     let todos_text = todos
         .iter()
@@ -79,7 +124,7 @@ pub async fn ask_gemini(prompt: String) -> Result<String, Box<dyn std::error::Er
                 if todo.status == "Done" { "x" } else { " " },
                 todo.text,
                 todo.priority,
-                todo.due,
+                todo.due_display(),
                 todo.desc,
                 todo.subtasks,
                 todo.owner,
@@ -109,30 +154,210 @@ USER REQUEST: {}",
         prompt
     );
 
-    let client = reqwest::Client::new();
-    let body = RequestBody {
-        contents: vec![Content {
-            role: "user".to_string(),
-            parts: vec![Part {
-                text: system_prompt,
-            }],
-        }],
-    };
+    Ok(generate(&db, system_prompt)
+        .await?
+        .unwrap_or_else(|| "❌ No response from Gemini.".into()))
+}
+
+// NOTE SUMMARIES: a short summary on top of the notes, as a quote under a **Summary** header.
+// Summarizing again replaces it.
+const SUMMARY_HEADER: &str = "> **Summary**";
+
+// The notes without the summary on top
+pub fn strip_summary(notes: &str) -> &str {
+    if !notes.starts_with(SUMMARY_HEADER) {
+        return notes;
+    }
+    let end = notes
+        .split_inclusive('\n')
+        .take_while(|line| line.starts_with('>'))
+        .map(str::len)
+        .sum::<usize>();
+    notes[end..].trim_start_matches(['\n', '\r'])
+}
+
+// The notes with `summary` on top, in place of the one they had
+pub fn with_summary(notes: &str, summary: &str, today: chrono::NaiveDate) -> String {
+    let quoted = summary
+        .trim()
+        .lines()
+        .map(|line| format!("> {}", line.trim()).trim_end().to_string())
+        .collect::<Vec<String>>()
+        .join("\n");
+    format!(
+        "{} ({})\n{}\n\n{}",
+        SUMMARY_HEADER,
+        today.format("%Y-%m-%d"),
+        quoted,
+        strip_summary(notes)
+    )
+}
+
+// SUMMARIZE THE NOTES of a todo, the summary goes on top of them
+pub async fn summarize(id: i32) -> Result<String, Box<dyn Error>> {
+    let db = database::DBtodo::new()?;
+    let todo = db
+        .get_todos()?
+        .into_iter()
+        .find(|todo| todo.id == id as usize)
+        .ok_or_else(|| format!("No todo found with id: {}", id))?;
+    let notes = strip_summary(&todo.notes).trim();
+    if notes.is_empty() {
+        return Err(format!("Todo {} has no notes to summarize", id).into());
+    }
+
+    let prompt = format!(
+        "Summarize the notes of the todo \"{}\" in 2 to 4 short sentences: what was decided, \
+what is still open and who does what. Plain text, no headings or lists, in the language of the notes.
+
+NOTES:
+{}",
+        todo.text, notes
+    );
+    let summary = generate(&db, prompt)
+        .await?
+        .ok_or("Gemini sent back no summary")?;
+    db.update_notes(
+        id,
+        with_summary(&todo.notes, &summary, chrono::Local::now().date_naive()),
+    )?;
+    Ok(summary.trim().to_string())
+}
+
+// DUE DATE SUGGESTIONS: Gemini picks a day for a new todo, looking at how many open todos are
+// already due on each of the next two weeks
+const WORKLOAD_DAYS: i64 = 14;
+
+// Open todos due on each of the next `days` days, today first
+pub fn workload(todos: &[Todo], today: NaiveDate, days: i64) -> Vec<(NaiveDate, usize)> {
+    (0..days)
+        .map(|offset| {
+            let day = today + Duration::days(offset);
+            let due = todos
+                .iter()
+                .filter(|todo| !todo.is_done() && todo.due == Some(day))
+                .count();
+            (day, due)
+        })
+        .collect()
+}
+
+// `2025-03-21 | reason` out of Gemini's answer, never a day that has passed
+pub fn parse_due_suggestion(answer: &str, today: NaiveDate) -> Option<(NaiveDate, String)> {
+    answer.lines().find_map(|line| {
+        let line = line.trim().trim_matches('`');
+        let (date, reason) = line.split_once('|').unwrap_or((line, ""));
+        let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?;
+        (date >= today).then(|| (date, reason.trim().to_string()))
+    })
+}
+
+pub async fn suggest_due(text: &str) -> Result<(NaiveDate, String), Box<dyn Error>> {
+    let db = database::DBtodo::new()?;
+    let today = Local::now().date_naive();
+    let load = workload(&db.get_todos()?, today, WORKLOAD_DAYS)
+        .iter()
+        .map(|(day, due)| format!("{}: {} open todo(s) due", day.format("%Y-%m-%d %A"), due))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let prompt = format!(
+        "Suggest a due date for a new todo: \"{}\"
+Today is {}. Open todos already due on each of the next days:
+{}
 
-    let response = client
-        .post(&url)
-        .json(&body)
-        .send()
+Pick a realistic day for how big and urgent the todo sounds, avoiding the busiest days.
+Answer with one line only: YYYY-MM-DD | a reason in a few words",
+        text,
+        today.format("%Y-%m-%d %A"),
+        load
+    );
+    let answer = generate(&db, prompt)
         .await?
-        .error_for_status()?
-        .json::<GeminiResponse>()
-        .await?;
+        .ok_or("Gemini suggested nothing")?;
+    parse_due_suggestion(&answer, today)
+        .ok_or_else(|| format!("Gemini's answer has no due date: {}", answer.trim()).into())
+}
 
-    if let Some(candidate) = response.candidates.first() {
-        if let Some(part) = candidate.content.parts.first() {
-            return Ok(part.text.clone());
+// Suggest a due date for the todo about to be added and ask whether to use it, as the `--due`
+// value. Without a terminal to ask on the suggestion is only shown.
+pub async fn confirm_due(text: &str) -> Option<String> {
+    let (due, reason) = match suggest_due(text).await {
+        Ok(suggestion) => suggestion,
+        Err(e) => {
+            eprintln!("⚠️ No due date suggested: {}", e);
+            return None;
         }
+    };
+    println!(
+        "🤖 Suggested due date: {} ({})",
+        due.format("%a %Y-%m-%d"),
+        reason
+    );
+    if !io::stdin().is_terminal() {
+        println!("   Not set, there is no terminal to confirm it on");
+        return None;
     }
+    print!("   Use it? [Y/n] ");
+    io::stdout().flush().ok()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).ok()?;
+    matches!(answer.trim().to_lowercase().as_str(), "" | "y" | "yes")
+        .then(|| due.format("%Y-%m-%d").to_string())
+}
 
-    Ok("❌ No response from Gemini.".into())
+// A `voido` command run in the background, what it printed once it is done
+pub type Worker = JoinHandle<io::Result<Output>>;
+
+// The TUI keeps drawing while Gemini answers
+pub fn in_background(args: Vec<String>) -> Worker {
+    thread::spawn(move || {
+        let mut command = Command::new(std::env::current_exe()?);
+        if let Some(profile) = configs::active_profile() {
+            command.args(["--profile", profile]);
+        }
+        command.args(args).stdin(Stdio::null()).output()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_replaces_the_previous_one() {
+        let day = chrono::NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let notes = with_summary(
+            "Met with Ana.\n> a quote",
+            "Ana signs off.\nShip Friday.",
+            day,
+        );
+        assert_eq!(
+            notes,
+            "> **Summary** (2025-03-14)\n> Ana signs off.\n> Ship Friday.\n\nMet with Ana.\n> a quote"
+        );
+        assert_eq!(strip_summary(&notes), "Met with Ana.\n> a quote");
+
+        let again = with_summary(&notes, "Shipped.", day);
+        assert_eq!(
+            again,
+            "> **Summary** (2025-03-14)\n> Shipped.\n\nMet with Ana.\n> a quote"
+        );
+        assert_eq!(strip_summary("No summary"), "No summary");
+    }
+
+    #[test]
+    fn test_due_suggestion() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        assert_eq!(
+            parse_due_suggestion("Sure!\n`2025-03-18 | quiet Tuesday`", today),
+            Some((
+                NaiveDate::from_ymd_opt(2025, 3, 18).unwrap(),
+                "quiet Tuesday".to_string()
+            ))
+        );
+        assert_eq!(parse_due_suggestion("2025-03-13 | yesterday", today), None);
+        assert_eq!(parse_due_suggestion("next week", today), None);
+        assert_eq!(workload(&[], today, 3)[2], (today + Duration::days(2), 0));
+    }
 }
@@ -0,0 +1,152 @@
+// GEMINI API: one generateContent request, with the [GEMINI] settings of config.toml
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+use super::{Part, api_key};
+use crate::{configs, database};
+
+#[derive(Serialize)]
+struct Content {
+    parts: Vec<Part>,
+    role: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_output_tokens: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct SafetySetting {
+    category: &'static str,
+    threshold: &'static str,
+}
+
+// What [GEMINI] safety applies to
+const HARM_CATEGORIES: [&str; 4] = [
+    "HARM_CATEGORY_HARASSMENT",
+    "HARM_CATEGORY_HATE_SPEECH",
+    "HARM_CATEGORY_SEXUALLY_EXPLICIT",
+    "HARM_CATEGORY_DANGEROUS_CONTENT",
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestBody {
+    contents: Vec<Content>,
+    generation_config: GenerationConfig,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    safety_settings: Vec<SafetySetting>,
+}
+
+impl RequestBody {
+    fn new(parts: Vec<Part>, config: &configs::GeminiConfig) -> Self {
+        Self {
+            contents: vec![Content {
+                role: "user".to_string(),
+                parts,
+            }],
+            generation_config: GenerationConfig {
+                temperature: config.temperature,
+                max_output_tokens: config.max_tokens,
+            },
+            safety_settings: config
+                .safety
+                .map(|threshold| {
+                    HARM_CATEGORIES
+                        .iter()
+                        .map(|category| SafetySetting {
+                            category,
+                            threshold,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct CandidatePart {
+    text: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Candidate {
+    content: Option<CandidateContent>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CandidateContent {
+    #[serde(default)]
+    parts: Vec<CandidatePart>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PromptFeedback {
+    block_reason: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<Candidate>,
+    prompt_feedback: Option<PromptFeedback>,
+}
+
+pub async fn send(
+    db: &database::DBtodo,
+    parts: Vec<Part>,
+) -> Result<Option<String>, Box<dyn Error>> {
+    crate::arguments::diff::offline("Gemini")?;
+    let api_key = api_key(db)?;
+    let config = configs::GeminiConfig::load();
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+        config.model, api_key
+    );
+
+    let client = reqwest::Client::new();
+    let body = RequestBody::new(parts, &config);
+
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<GeminiResponse>()
+        .await?;
+
+    let blocked = response
+        .prompt_feedback
+        .and_then(|feedback| feedback.block_reason)
+        .or(response
+            .candidates
+            .first()
+            .and_then(|candidate| candidate.finish_reason.clone())
+            .filter(|reason| reason == "SAFETY"));
+    let text = response
+        .candidates
+        .into_iter()
+        .next()
+        .and_then(|candidate| candidate.content)
+        .and_then(|content| content.parts.into_iter().next())
+        .map(|part| part.text);
+    match (text, blocked) {
+        (None, Some(reason)) => Err(format!(
+            "Gemini blocked the answer ({}), see [GEMINI] safety in config.toml",
+            reason.to_lowercase()
+        )
+        .into()),
+        (text, _) => Ok(text),
+    }
+}
@@ -1,9 +1,9 @@
 // In arguments/add_todo.rs
-use crate::{arguments::models::Subtask, database::DBtodo};
-use chrono::Local;
+use crate::{arguments::models::Subtask, configs, database::DBtodo, dates, text_width};
+use chrono::{Duration, Local, NaiveDate, Utc};
 use std::error::Error;
 
-use super::models::Todo;
+use super::models::{Todo, normalize_context};
 
 pub fn add_todo(
     text: String,
@@ -13,71 +13,61 @@ pub fn add_todo(
     due: Option<String>,
     desc: Option<String>,
     subtasks: Vec<String>,
-) -> Result<(), Box<dyn Error>> {
-    let date_added = Local::now().format("%d-%m-%y").to_string();
-    let topic = topic.unwrap_or_else(|| "General".to_string());
-
-    // handle priority
-    let priority = priority.unwrap_or_else(|| "normal".to_string());
-    let priority = priority.to_lowercase();
-    // Uppercase only the first letter
-    let priority = priority
-        .chars()
-        .next()
-        .unwrap()
-        .to_ascii_uppercase()
-        .to_string()
-        + &priority[1..];
-
-    // Handle the owner string
-    let owner = ownder.unwrap_or_else(|| "You".to_string());
-
-    // Ensure the first letter is cased if the user passed argument
-    let owner = owner
-        .chars()
-        .next()
-        .unwrap()
-        .to_ascii_uppercase()
-        .to_string()
-        + &owner[1..];
-
-    // ensure the topic is always capital cased on the first letter
-    let topic = topic
-        .chars()
-        .next()
-        .unwrap()
-        .to_ascii_uppercase()
-        .to_string()
-        + &topic[1..];
-
-    // Format topic with "..." if it goes above the length
-    let topic = if topic.len() > 15 {
-        format!("{}...", &topic[..15])
-    } else {
-        topic
+) -> Result<i64, Box<dyn Error>> {
+    // Reject due dates we can't read instead of silently dropping them
+    let (due, due_at) = match due {
+        Some(due) => {
+            let (date, due_at) = dates::parse_due_at(&due, Local::now().date_naive())
+                .ok_or_else(|| format!("Invalid due date: {}", due))?;
+            (Some(date), due_at)
+        }
+        None => (None, None),
     };
+    let mut new_todo = build_todo(text, topic, priority, ownder, due, desc, subtasks);
+    new_todo.due_at = due_at;
 
-    // Ensure the text first chartacter is always capital cased
-    let text = text
-        .chars()
-        .next()
-        .unwrap()
-        .to_ascii_uppercase()
-        .to_string()
-        + &text[1..];
+    let db = DBtodo::new()?;
+    let id = db.add_todo(&new_todo)?;
+    Ok(id)
+}
+
+// Apply defaults and casing rules to the raw fields and build a new todo
+pub fn build_todo(
+    text: String,
+    topic: Option<String>,
+    priority: Option<String>,
+    ownder: Option<String>,
+    due: Option<NaiveDate>,
+    desc: Option<String>,
+    subtasks: Vec<String>,
+) -> Todo {
+    let date_added = Utc::now();
+    let defaults = configs::defaults_config().clone();
+    let topic = topic.unwrap_or(defaults.topic);
+
+    // handle priority, uppercase only the first letter
+    let priority = priority.unwrap_or(defaults.priority);
+    let priority = capitalize(&priority.to_lowercase());
 
-    // Handle the date
-    let due_date = due.unwrap_or_else(|| "-".to_string());
+    // Handle the owner string, ensure the first letter is cased if the user passed argument
+    let owner = capitalize(&ownder.unwrap_or(defaults.owner));
+
+    // Fall back to the configured due offset
+    let due = due.or_else(|| {
+        defaults
+            .due_offset
+            .map(|days| Local::now().date_naive() + Duration::days(days))
+    });
+
+    // ensure the topic is always capital cased and not too long
+    let topic = format_topic(&topic);
+
+    // Ensure the text first chartacter is always capital cased
+    let text = capitalize(&text);
 
     // Ensure the first letter is cased if the user passed argument
     let desc = desc.unwrap_or_else(|| "No description provided".to_string());
-    let desc = desc
-        .chars()
-        .next()
-        .unwrap()
-        .to_ascii_uppercase()
-        .to_string()
-        + &desc[1..];
+    let desc = capitalize(&desc);
 
     // handle subtasks
     let subtasks = subtasks
@@ -87,26 +77,65 @@ pub fn add_todo(
             subtask_id: 0, // Add now but the DB will automatically add
             text,
             status: "Pending".to_string(),
+            due: None,
+            owner: None,
+            note: String::new(),
         })
         .collect::<Vec<Subtask>>();
 
-    let db = DBtodo::new()?;
-
-    let new_todo = Todo {
+    Todo {
         id: 0, // Will be auto-incremented by SQLite
         priority,
         topic,
         text,
         desc,
         date_added,
-        due: due_date,
+        due,
         status: "Pending".to_string(),
         owner,
         subtasks,
         notes: String::new(), // Initialize notes as empty string
-    };
+        pinned: false,
+        updated_at: Some(date_added),
+        archived: false,
+        waiting_on: None,
+        context: None,
+        external_url: None,
+        due_at: None,
+    }
+}
 
-    db.add_todo(&new_todo)?;
+// Uppercase the first letter, leave the rest untouched
+pub fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+// Topics are capital cased and shortened with "..." if they go above the length
+pub fn format_topic(topic: &str) -> String {
+    let topic = capitalize(topic);
+    if text_width::width(&topic) > 15 {
+        format!("{}...", text_width::split_at_width(&topic, 15).0)
+    } else {
+        topic
+    }
+}
+
+// SET THE CONTEXT of an existing todo, `none` clears it
+pub fn set_context(id: i32, context: &str) -> Result<(), Box<dyn Error>> {
+    let context = normalize_context(context);
+    let db = DBtodo::new()?;
+    if !db.set_context(id, context.as_deref())? {
+        println!("❌ No todo found with id: {}", id);
+        return Ok(());
+    }
+    match context {
+        Some(context) => println!("📍 Todo {} set to @{}", id, context),
+        None => println!("📍 Todo {} has no context", id),
+    }
     Ok(())
 }
 
@@ -0,0 +1,238 @@
+// AI TRIAGE: Gemini proposes a topic, a priority and an effort for each inbox todo, and every
+// proposal is reviewed in the terminal before anything is saved. The effort goes in the notes,
+// as an `Effort: 2h` line.
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+
+use super::add_todo::format_topic;
+use crate::ai;
+use crate::configs;
+use crate::database::DBtodo;
+use crate::macros::MacroAction;
+use crate::triage::{Triage, is_inbox};
+
+// Oldest first, the rest waits for the next run
+const BATCH: usize = 25;
+const EFFORT_LABEL: &str = "Effort:";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Proposal {
+    pub id: usize,
+    pub topic: String,
+    pub priority: String,
+    pub effort: String,
+    pub reason: String,
+}
+
+// `ID | topic | priority | effort | reason` lines of Gemini's answer, for the todos asked about
+pub fn parse_proposals(answer: &str, ids: &[usize]) -> Vec<Proposal> {
+    let mut proposals: Vec<Proposal> = Vec::new();
+    for line in answer.lines() {
+        let fields = line
+            .trim()
+            .trim_matches('`')
+            .split('|')
+            .map(str::trim)
+            .collect::<Vec<&str>>();
+        let [id, topic, priority, effort, rest @ ..] = fields.as_slice() else {
+            continue;
+        };
+        let Ok(id) = id.trim_start_matches('#').parse::<usize>() else {
+            continue;
+        };
+        let Ok(MacroAction::Priority(priority)) =
+            MacroAction::parse(&format!("priority {}", priority))
+        else {
+            continue;
+        };
+        if !ids.contains(&id) || topic.is_empty() || proposals.iter().any(|p| p.id == id) {
+            continue;
+        }
+        proposals.push(Proposal {
+            id,
+            topic: format_topic(topic),
+            priority,
+            effort: effort.to_string(),
+            reason: rest.join(" | "),
+        });
+    }
+    proposals
+}
+
+// Changes typed over a proposal: `#topic`, `!priority` and `~effort`, in any order
+pub fn adjust(proposal: &mut Proposal, input: &str) -> Result<(), String> {
+    for word in input.split_whitespace() {
+        if let Some(topic) = word.strip_prefix('#').filter(|topic| !topic.is_empty()) {
+            proposal.topic = format_topic(topic);
+        } else if let Some(priority) = word.strip_prefix('!') {
+            match MacroAction::parse(&format!("priority {}", priority))? {
+                MacroAction::Priority(priority) => proposal.priority = priority,
+                _ => unreachable!(),
+            }
+        } else if let Some(effort) = word.strip_prefix('~').filter(|effort| !effort.is_empty()) {
+            proposal.effort = effort.to_string();
+        } else {
+            return Err(format!(
+                "`{}` is not a change, use #topic, !priority or ~effort",
+                word
+            ));
+        }
+    }
+    Ok(())
+}
+
+// The notes with their effort line set to `effort`
+pub fn with_effort(notes: &str, effort: &str) -> String {
+    let line = format!("{} {}", EFFORT_LABEL, effort);
+    let mut lines = notes.lines().map(str::to_string).collect::<Vec<String>>();
+    match lines
+        .iter_mut()
+        .find(|existing| existing.starts_with(EFFORT_LABEL))
+    {
+        Some(existing) => *existing = line,
+        None if notes.trim().is_empty() => return line,
+        None => lines.extend([String::new(), line]),
+    }
+    lines.join("\n")
+}
+
+fn ask(prompt: &str) -> Result<String, Box<dyn Error>> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+pub async fn triage() -> Result<(), Box<dyn Error>> {
+    if !io::stdin().is_terminal() {
+        return Err("The proposals are reviewed one by one, run it in a terminal".into());
+    }
+    let mut db = DBtodo::new()?;
+    let todos = db.get_todos()?;
+    let inbox = configs::defaults_config().inbox_topic.clone();
+    let mut queue = todos
+        .iter()
+        .filter(|todo| is_inbox(todo, &inbox))
+        .collect::<Vec<_>>();
+    queue.sort_by_key(|todo| todo.id);
+    queue.truncate(BATCH);
+    if queue.is_empty() {
+        println!("✓ The {} topic is empty, nothing to triage", inbox);
+        return Ok(());
+    }
+
+    let topics = Triage::new(&todos, &inbox).topics;
+    let prompt = format!(
+        "Triage these todos from an inbox. For each one pick a topic (prefer one of the existing \
+topics: {}), a priority (Low, Medium or High) and an effort estimate like 15m, 2h or 3d.
+Answer with one line per todo and nothing else: ID | topic | priority | effort | reason in a few words
+
+TODOS:
+{}",
+        if topics.is_empty() {
+            "none yet".to_string()
+        } else {
+            topics.join(", ")
+        },
+        queue
+            .iter()
+            .map(|todo| format!("{} | {} | {}", todo.id, todo.text, todo.desc))
+            .collect::<Vec<String>>()
+            .join("\n")
+    );
+    println!("🤖 Asking Gemini about {} inbox todo(s)...", queue.len());
+    let answer = ai::generate(&db, prompt)
+        .await?
+        .ok_or("Gemini proposed nothing")?;
+    let ids = queue.iter().map(|todo| todo.id).collect::<Vec<usize>>();
+    let proposals = parse_proposals(&answer, &ids);
+    if proposals.is_empty() {
+        return Err(format!("No proposals in Gemini's answer: {}", answer.trim()).into());
+    }
+
+    println!("Enter accepts, s skips, q stops, or type changes first: #topic !priority ~effort\n");
+    let (mut accepted, mut skipped) = (0, 0);
+    let total = proposals.len();
+    for (index, mut proposal) in proposals.into_iter().enumerate() {
+        let Some(todo) = queue.iter().find(|todo| todo.id == proposal.id) else {
+            continue;
+        };
+        println!("[{}/{}] #{} {}", index + 1, total, todo.id, todo.text);
+        let accept = loop {
+            println!(
+                "      → topic {} · priority {} · effort {}  ({})",
+                proposal.topic, proposal.priority, proposal.effort, proposal.reason
+            );
+            match ask("      > ")?.as_str() {
+                "" => break true,
+                "s" => break false,
+                "q" => {
+                    println!("✅ {} todo(s) triaged, {} skipped", accepted, skipped);
+                    return Ok(());
+                }
+                changes => {
+                    if let Err(e) = adjust(&mut proposal, changes) {
+                        println!("      ❌ {}", e);
+                    }
+                }
+            }
+        };
+        if !accept {
+            skipped += 1;
+            continue;
+        }
+
+        db.apply_macro(
+            todo.id as i32,
+            &[
+                MacroAction::Topic(proposal.topic),
+                MacroAction::Priority(proposal.priority),
+            ],
+        )?;
+        db.update_notes(todo.id as i32, with_effort(&todo.notes, &proposal.effort))?;
+        accepted += 1;
+    }
+    println!("✅ {} todo(s) triaged, {} skipped", accepted, skipped);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_adjust_proposals() {
+        let answer = "Here you go:\n14 | work | high | 2h | client is waiting\n\
+            `15 | home | urgent | 1d | no such priority`\n99 | work | low | 1h | not asked\n\
+            16 | errands | Low | 15m | quick";
+        let mut proposals = parse_proposals(answer, &[14, 15, 16]);
+        assert_eq!(
+            proposals.iter().map(|p| p.id).collect::<Vec<_>>(),
+            vec![14, 16]
+        );
+        assert_eq!(
+            (proposals[0].topic.as_str(), proposals[0].priority.as_str()),
+            ("Work", "High")
+        );
+
+        adjust(&mut proposals[1], "!medium ~30m #garden").unwrap();
+        assert_eq!(
+            (
+                proposals[1].topic.as_str(),
+                proposals[1].priority.as_str(),
+                proposals[1].effort.as_str()
+            ),
+            ("Garden", "Medium", "30m")
+        );
+        assert!(adjust(&mut proposals[1], "!soon").is_err());
+        assert!(adjust(&mut proposals[1], "later").is_err());
+
+        assert_eq!(with_effort("", "2h"), "Effort: 2h");
+        assert_eq!(with_effort("Call Ana", "2h"), "Call Ana\n\nEffort: 2h");
+        assert_eq!(
+            with_effort("Call Ana\n\nEffort: 2h", "1d"),
+            "Call Ana\n\nEffort: 1d"
+        );
+    }
+}
@@ -0,0 +1,324 @@
+// BATCH OPERATIONS: `voido apply ops.yaml` runs a list of adds, updates, completions and
+// deletes from a YAML or JSON file in one transaction, so a script or a migration doesn't
+// have to start VoiDo once per todo. Nothing is written when one of them fails.
+//
+//   - add: { text: Ship v1, topic: Release, priority: high, due: fri }
+//   - update: { topic: Release, status: pending }
+//     set: { priority: high, owner: Joe }
+//   - complete: { id: 12 }
+//   - delete: { topic: Scratch, status: done }
+//
+// Updates, completions and deletes pick the unarchived todos their selector matches, done ones
+// included.
+use std::error::Error;
+use std::fs;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::Deserialize;
+
+use super::models::{Todo, normalize_context};
+use crate::database::DBtodo;
+use crate::import_export::seed::{self, TodoFixture};
+use crate::macros::MacroAction;
+
+// Which todos an operation works on, every field it sets has to match
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Selector {
+    pub id: Option<usize>,
+    pub topic: Option<String>,
+    pub owner: Option<String>,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub context: Option<String>,
+    // Part of the text, in any case
+    pub text: Option<String>,
+    // An empty selector is a mistake, `all: true` is how to pick every unarchived todo
+    #[serde(default)]
+    pub all: bool,
+}
+
+impl Selector {
+    fn label(&self) -> String {
+        let mut fields = Vec::new();
+        if let Some(id) = self.id {
+            fields.push(format!("id {}", id));
+        }
+        for (name, value) in [
+            ("topic", &self.topic),
+            ("owner", &self.owner),
+            ("status", &self.status),
+            ("priority", &self.priority),
+            ("context", &self.context),
+            ("text", &self.text),
+        ] {
+            if let Some(value) = value {
+                fields.push(format!("{} {}", name, value));
+            }
+        }
+        if fields.is_empty() {
+            "all".to_string()
+        } else {
+            fields.join(", ")
+        }
+    }
+}
+
+// What an update sets on every todo it picks
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Set {
+    status: Option<String>,
+    priority: Option<String>,
+    owner: Option<String>,
+    topic: Option<String>,
+    pinned: Option<bool>,
+    // Anything `--due` takes
+    due: Option<String>,
+    // "none" takes the context off
+    context: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Changes {
+    pub actions: Vec<MacroAction>,
+    pub due: Option<(NaiveDate, Option<DateTime<Utc>>)>,
+    pub context: Option<Option<String>>,
+}
+
+// One entry of the file, it names a single operation
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Entry {
+    add: Option<TodoFixture>,
+    update: Option<Selector>,
+    set: Option<Set>,
+    complete: Option<Selector>,
+    delete: Option<Selector>,
+}
+
+#[derive(Debug)]
+pub enum Operation {
+    // The todo and the name of its milestone
+    Add(Todo, Option<String>),
+    Update(Selector, Changes),
+    Complete(Selector),
+    Delete(Selector),
+}
+
+impl Operation {
+    pub fn label(&self) -> String {
+        match self {
+            Operation::Add(todo, _) => format!("add \"{}\"", todo.text),
+            Operation::Update(selector, _) => format!("update {}", selector.label()),
+            Operation::Complete(selector) => format!("complete {}", selector.label()),
+            Operation::Delete(selector) => format!("delete {}", selector.label()),
+        }
+    }
+}
+
+fn selector(mut selector: Selector) -> Result<Selector, String> {
+    // Normalized before the check, `context: none` or a blank topic would pick everything
+    if let Some(status) = selector.status.take() {
+        selector.status = Some(seed::status(Some(status))?);
+    }
+    selector.context = selector.context.as_deref().and_then(normalize_context);
+    for value in [
+        &mut selector.topic,
+        &mut selector.owner,
+        &mut selector.priority,
+        &mut selector.text,
+    ] {
+        *value = value.take().filter(|value| !value.trim().is_empty());
+    }
+
+    let empty = selector.id.is_none()
+        && [
+            &selector.topic,
+            &selector.owner,
+            &selector.status,
+            &selector.priority,
+            &selector.context,
+            &selector.text,
+        ]
+        .iter()
+        .all(|value| value.is_none());
+    if empty && !selector.all {
+        return Err("the selector is empty, `all: true` picks every unarchived todo".to_string());
+    }
+    if !empty && selector.all {
+        return Err("`all: true` goes alone".to_string());
+    }
+    Ok(selector)
+}
+
+fn changes(set: Set, today: NaiveDate) -> Result<Changes, String> {
+    // The same checks and spellings as key macros
+    let mut actions = [
+        ("status", set.status),
+        ("priority", set.priority),
+        ("owner", set.owner),
+        ("topic", set.topic),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| {
+        value.map(|value| MacroAction::parse(&format!("{} {}", name, value)))
+    })
+    .collect::<Result<Vec<MacroAction>, String>>()?;
+    if let Some(pinned) = set.pinned {
+        actions.push(MacroAction::Pin(pinned));
+    }
+
+    let due = match seed::due_at(set.due, today)? {
+        (Some(date), due_at) => Some((date, due_at)),
+        (None, _) => None,
+    };
+    let context = set.context.map(|context| normalize_context(&context));
+    if actions.is_empty() && due.is_none() && context.is_none() {
+        return Err("`set` changes nothing".to_string());
+    }
+    Ok(Changes {
+        actions,
+        due,
+        context,
+    })
+}
+
+fn operation(entry: Entry, today: NaiveDate) -> Result<Operation, String> {
+    match entry {
+        Entry {
+            add: Some(fixture),
+            update: None,
+            set: None,
+            complete: None,
+            delete: None,
+        } => {
+            let (todo, milestone) = seed::todo(fixture, today)?;
+            Ok(Operation::Add(todo, milestone))
+        }
+        Entry {
+            add: None,
+            update: Some(update),
+            set: Some(set),
+            complete: None,
+            delete: None,
+        } => Ok(Operation::Update(selector(update)?, changes(set, today)?)),
+        Entry {
+            update: Some(_),
+            set: None,
+            ..
+        } => Err("`update` needs a `set` with what to change".to_string()),
+        Entry {
+            add: None,
+            update: None,
+            set: None,
+            complete: Some(complete),
+            delete: None,
+        } => Ok(Operation::Complete(selector(complete)?)),
+        Entry {
+            add: None,
+            update: None,
+            set: None,
+            complete: None,
+            delete: Some(delete),
+        } => Ok(Operation::Delete(selector(delete)?)),
+        _ => Err("an entry is one of add, update (with set), complete or delete".to_string()),
+    }
+}
+
+// Check every entry before anything runs, naming the one that is wrong
+fn operations(entries: Vec<Entry>, today: NaiveDate) -> Result<Vec<Operation>, String> {
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            operation(entry, today).map_err(|e| format!("operation {}: {}", index + 1, e))
+        })
+        .collect()
+}
+
+pub fn apply(file_path: &str) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(file_path)?;
+    let entries: Vec<Entry> = seed::parse_fixture(file_path, &content)
+        .map_err(|e| format!("{} is not a list of operations: {}", file_path, e))?;
+    let operations = operations(entries, Local::now().date_naive())?;
+
+    let counts = DBtodo::new()?.apply_batch(&operations)?;
+    for (operation, count) in operations.iter().zip(&counts) {
+        println!("  {:<40}  {} todo(s)", operation.label(), count);
+    }
+    println!(
+        "✅ Applied {} operation(s) from {}, {} todo(s) touched",
+        operations.len(),
+        file_path,
+        counts.iter().sum::<usize>()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const OPERATIONS: &str = r#"
+- add: { text: Ship v1, topic: Release, priority: high }
+- add: { text: Write the changelog, topic: Release }
+- add: { text: Old spike, topic: Scratch, status: done }
+- update: { topic: release, status: pending }
+  set: { priority: medium, owner: Joe, context: "@Office" }
+- complete: { text: CHANGELOG }
+- delete: { topic: Scratch, status: done }
+"#;
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_apply_operations() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let entries: Vec<Entry> = seed::parse_fixture("ops.yaml", OPERATIONS).unwrap();
+        let batch = operations(entries, today).unwrap();
+        assert_eq!(batch[3].label(), "update topic release, status Pending");
+
+        let mut db = DBtodo::open(Path::new(":memory:")).unwrap();
+        assert_eq!(db.apply_batch(&batch).unwrap(), vec![1, 1, 1, 2, 1, 1]);
+        let todos = db.get_todos().unwrap();
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[0].priority, "Medium");
+        assert_eq!(todos[0].context.as_deref(), Some("office"));
+        assert_eq!(todos[1].status, "Done");
+
+        // A failing operation rolls back the ones before it
+        let entries: Vec<Entry> = seed::parse_fixture(
+            "ops.json",
+            r#"[{"add": {"text": "Never added"}}, {"complete": {"id": 99}}]"#,
+        )
+        .unwrap();
+        let error = db
+            .apply_batch(&operations(entries, today).unwrap())
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "operation 2 (complete id 99): no unarchived todo with id 99"
+        );
+        assert_eq!(db.get_todos().unwrap().len(), 2);
+
+        // Mistakes are found before anything runs
+        let entries: Vec<Entry> =
+            seed::parse_fixture("ops.yaml", "- delete: {}\n- update: { id: 1 }\n").unwrap();
+        assert_eq!(
+            operations(entries, today).unwrap_err(),
+            "operation 1: the selector is empty, `all: true` picks every unarchived todo"
+        );
+        for selector in ["{ context: none }", "{ context: \"@\" }", "{ text: \" \" }"] {
+            let entries: Vec<Entry> =
+                seed::parse_fixture("ops.yaml", &format!("- delete: {}\n", selector)).unwrap();
+            assert!(
+                operations(entries, today)
+                    .unwrap_err()
+                    .contains("the selector is empty"),
+                "{}",
+                selector
+            );
+        }
+    }
+}
@@ -0,0 +1,140 @@
+// Archiving: auto-archive rules from the config and restoring archived todos
+use chrono::{Local, NaiveDate};
+use std::error::Error;
+
+use super::models::Todo;
+use super::stale::last_touched;
+use crate::configs::ArchiveConfig;
+use crate::database::DBtodo;
+
+// Todos matched by any enabled rule, skipping the excluded topics
+pub fn todos_to_archive<'a>(
+    todos: &'a [Todo],
+    config: &ArchiveConfig,
+    today: NaiveDate,
+) -> Vec<&'a Todo> {
+    if !config.enabled {
+        return Vec::new();
+    }
+
+    todos
+        .iter()
+        .filter(|todo| {
+            !config
+                .skip_topics
+                .iter()
+                .any(|topic| topic.eq_ignore_ascii_case(&todo.topic))
+        })
+        .filter(|todo| {
+            config.rules.iter().any(|rule| {
+                rule.status.eq_ignore_ascii_case(&todo.status)
+                    && (today - last_touched(todo)).num_days() >= rule.days
+            })
+        })
+        .collect()
+}
+
+// Apply the auto-archive rules, returns the todos that were moved
+pub fn auto_archive() -> Result<Vec<Todo>, Box<dyn Error>> {
+    let config = ArchiveConfig::load();
+    if !config.enabled {
+        return Ok(Vec::new());
+    }
+
+    let mut db = DBtodo::new()?;
+    let todos = db.get_todos()?;
+    let archived = todos_to_archive(&todos, &config, Local::now().date_naive())
+        .into_iter()
+        .cloned()
+        .collect::<Vec<Todo>>();
+
+    if !archived.is_empty() {
+        let ids = archived
+            .iter()
+            .map(|todo| todo.id as i32)
+            .collect::<Vec<i32>>();
+        db.archive_todos(&ids)?;
+    }
+    Ok(archived)
+}
+
+// One line summary of what the auto-archive moved
+pub fn archive_report(archived: &[Todo]) -> String {
+    let ids = archived
+        .iter()
+        .map(|todo| format!("#{}", todo.id))
+        .collect::<Vec<String>>()
+        .join(", ");
+    format!("📦 Auto-archived {} todo(s): {}", archived.len(), ids)
+}
+
+// PRINT THE ARCHIVED TODOS
+pub fn list_archived() -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let todos = db.get_archived_todos()?;
+
+    if todos.is_empty() {
+        println!("No archived todos");
+        return Ok(());
+    }
+
+    println!();
+    for todo in &todos {
+        println!(
+            "  {:>4}  {:<40}  {:<8}  {}",
+            todo.id, todo.text, todo.status, todo.topic
+        );
+    }
+    println!();
+    Ok(())
+}
+
+pub fn restore(id: i32) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    match db.unarchive_todo(id)? {
+        0 => println!("❌ No archived todo found with id: {}", id),
+        _ => println!("✅ Todo {} restored", id),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::ArchiveRule;
+
+    fn todo(id: usize, status: &str, topic: &str, updated_at: &str) -> Todo {
+        Todo {
+            id,
+            topic: topic.to_string(),
+            status: status.to_string(),
+            updated_at: crate::dates::parse_timestamp(updated_at),
+            ..Todo::sample("Test")
+        }
+    }
+
+    #[test]
+    fn test_rules_and_skipped_topics() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let config = ArchiveConfig {
+            enabled: true,
+            rules: vec![ArchiveRule {
+                status: "Done".to_string(),
+                days: 14,
+            }],
+            skip_topics: vec!["Work".to_string()],
+        };
+        let todos = vec![
+            todo(1, "Done", "General", "2025-02-01 10:00:00"),
+            todo(2, "Done", "General", "2025-02-25 10:00:00"),
+            todo(3, "Pending", "General", "2025-02-01 10:00:00"),
+            todo(4, "Done", "Work", "2025-02-01 10:00:00"),
+        ];
+
+        let ids = todos_to_archive(&todos, &config, today)
+            .iter()
+            .map(|todo| todo.id)
+            .collect::<Vec<usize>>();
+        assert_eq!(ids, vec![1]);
+    }
+}
@@ -0,0 +1,18 @@
+use std::error::Error;
+
+use super::add_todo::{capitalize, format_topic};
+use crate::database::DBtodo;
+
+// Rename a topic on every todo that uses it, returns the number of todos changed
+pub fn rename_topic(from: &str, to: &str) -> Result<usize, Box<dyn Error>> {
+    let db = DBtodo::new()?;
+
+    db.rename_topic(from, &format_topic(to))
+}
+
+// Move todos from one owner to another, optionally limited to a topic
+pub fn reassign_owner(from: &str, to: &str, topic: Option<&str>) -> Result<usize, Box<dyn Error>> {
+    let db = DBtodo::new()?;
+
+    db.reassign_owner(from, &capitalize(to), topic)
+}
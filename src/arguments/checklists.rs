@@ -0,0 +1,73 @@
+// Checklists: save the subtasks of a todo under a name and add them to other todos later
+use std::error::Error;
+
+use crate::database::DBtodo;
+
+// Save the subtasks of a todo as a checklist, replacing a checklist with the same name
+pub fn save_checklist(name: &str, todo_id: i32) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    let items = db.get_subtasks(todo_id as usize)?;
+    if items.is_empty() {
+        println!("❌ Todo {} has no subtasks to save", todo_id);
+        return Ok(());
+    }
+
+    db.save_checklist(name.trim(), &items)?;
+    println!(
+        "✅ Checklist '{}' saved with {} item(s)",
+        name.trim(),
+        items.len()
+    );
+    Ok(())
+}
+
+// Add every item of a checklist to a todo as a Pending subtask
+pub fn apply_checklist(name: &str, todo_id: i32) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    let Some(checklist) = db.find_checklist(name)? else {
+        println!("❌ No checklist named: {}", name);
+        return Ok(());
+    };
+
+    match db.add_subtasks(todo_id, &checklist.items)? {
+        0 if !checklist.items.is_empty() => println!("❌ No todo found with id: {}", todo_id),
+        count => println!(
+            "✅ {} subtask(s) from '{}' added to todo {}",
+            count, checklist.name, todo_id
+        ),
+    }
+    Ok(())
+}
+
+pub fn remove_checklist(name: &str) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    match db.find_checklist(name)? {
+        Some(checklist) => {
+            db.delete_checklist(checklist.id)?;
+            println!("✅ Checklist '{}' removed", checklist.name);
+        }
+        None => println!("❌ No checklist named: {}", name),
+    }
+    Ok(())
+}
+
+// PRINT EVERY CHECKLIST WITH ITS ITEMS
+pub fn list_checklists() -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let checklists = db.get_checklists()?;
+
+    if checklists.is_empty() {
+        println!("No checklists yet, save one with `voido checklist save <name> --from <ID>`");
+        return Ok(());
+    }
+
+    println!();
+    for checklist in &checklists {
+        println!("  📋 {} ({} items)", checklist.name, checklist.items.len());
+        for item in &checklist.items {
+            println!("     - {}", item);
+        }
+        println!();
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+// Comments: a timestamped thread per todo, for shared lists where the notes get overwritten
+use std::error::Error;
+
+use crate::configs;
+use crate::database::DBtodo;
+
+pub fn add_comment(id: i32, text: &str, author: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("The comment cannot be empty".into());
+    }
+    let author = author
+        .map(|author| author.trim().to_string())
+        .filter(|author| !author.is_empty())
+        .unwrap_or_else(|| configs::defaults_config().owner.clone());
+
+    let db = DBtodo::new()?;
+    db.add_comment(id, &author, text)?;
+    println!("💬 Comment added to todo {} by {}", id, author);
+    Ok(())
+}
+
+// PRINT THE THREAD OF A TODO
+pub fn print_comments(id: i32) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let comments = db.get_comments(Some(id as i64))?;
+    if comments.is_empty() {
+        println!(
+            "No comments on todo {}, add one with `voido comment {} \"text\"`",
+            id, id
+        );
+        return Ok(());
+    }
+
+    println!();
+    for comment in &comments {
+        println!("  {} · {}", comment.author, comment.when());
+        for line in comment.text.lines() {
+            println!("    {}", line);
+        }
+        println!();
+    }
+    Ok(())
+}
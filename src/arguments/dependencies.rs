@@ -0,0 +1,214 @@
+// DEPENDENCIES: todos blocked by other todos, and the blocked-by tree of one todo
+use std::error::Error;
+
+use super::models::Todo;
+use crate::database::DBtodo;
+
+// One line of the blocked-by tree, `prefix` holds the tree branches
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphLine {
+    pub prefix: String,
+    pub id: usize,
+    pub text: String,
+    pub status: String,
+    pub done: bool,
+    // The todo is already further up this branch, so it is not expanded again
+    pub cycle: bool,
+}
+
+impl GraphLine {
+    pub fn label(&self) -> String {
+        format!(
+            "{}#{} [{}] {}{}",
+            self.prefix,
+            self.id,
+            self.status,
+            self.text,
+            if self.cycle { " ↻" } else { "" }
+        )
+    }
+}
+
+// Todos `id` waits on, from (todo id, blocked by) links
+fn blockers(links: &[(i64, i64)], id: usize) -> Vec<usize> {
+    let mut blockers = links
+        .iter()
+        .filter(|(todo_id, _)| *todo_id == id as i64)
+        .map(|(_, blocked_by)| *blocked_by as usize)
+        .collect::<Vec<usize>>();
+    blockers.sort_unstable();
+    blockers
+}
+
+// Does `from` wait on `to`, directly or through other todos
+fn depends_on(links: &[(i64, i64)], from: usize, to: usize) -> bool {
+    let mut stack = vec![from];
+    let mut seen = Vec::new();
+    while let Some(id) = stack.pop() {
+        if id == to {
+            return true;
+        }
+        if !seen.contains(&id) {
+            seen.push(id);
+            stack.extend(blockers(links, id));
+        }
+    }
+    false
+}
+
+// The todo and everything it waits on, drawn as a tree
+pub fn blocked_by_tree(todos: &[Todo], links: &[(i64, i64)], root: usize) -> Vec<GraphLine> {
+    let mut lines = Vec::new();
+    add_branch(
+        todos,
+        links,
+        root,
+        String::new(),
+        String::new(),
+        &mut Vec::new(),
+        &mut lines,
+    );
+    lines
+}
+
+fn add_branch(
+    todos: &[Todo],
+    links: &[(i64, i64)],
+    id: usize,
+    prefix: String,
+    child_prefix: String,
+    path: &mut Vec<usize>,
+    lines: &mut Vec<GraphLine>,
+) {
+    let todo = todos.iter().find(|todo| todo.id == id);
+    let cycle = path.contains(&id);
+    lines.push(GraphLine {
+        prefix,
+        id,
+        text: todo.map_or("(archived or deleted)".to_string(), |todo| {
+            todo.text.clone()
+        }),
+        status: todo.map_or("-".to_string(), |todo| todo.status.clone()),
+        done: todo.is_some_and(Todo::is_done),
+        cycle,
+    });
+    if cycle {
+        return;
+    }
+
+    path.push(id);
+    let blockers = blockers(links, id);
+    for (index, blocker) in blockers.iter().enumerate() {
+        let last = index + 1 == blockers.len();
+        add_branch(
+            todos,
+            links,
+            *blocker,
+            format!("{}{}", child_prefix, if last { "└── " } else { "├── " }),
+            format!("{}{}", child_prefix, if last { "    " } else { "│   " }),
+            path,
+            lines,
+        );
+    }
+    path.pop();
+}
+
+// Mark `id` as blocked by `on`
+pub fn add_dependency(id: i32, on: i32) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let todos = db.get_todos()?;
+    for todo_id in [id, on] {
+        if !todos.iter().any(|todo| todo.id == todo_id as usize) {
+            return Err(format!("No todo found with id: {}", todo_id).into());
+        }
+    }
+    if id == on {
+        return Err("A todo can't depend on itself".into());
+    }
+    // The other way round already exists, adding this would make a loop
+    if depends_on(&db.dependency_links()?, on as usize, id as usize) {
+        return Err(format!("Todo {} already waits on todo {}", on, id).into());
+    }
+
+    if db.add_dependency(id, on)? {
+        println!("✅ Todo {} is now blocked by todo {}", id, on);
+    } else {
+        println!("Todo {} was already blocked by todo {}", id, on);
+    }
+    Ok(())
+}
+
+pub fn remove_dependency(id: i32, on: i32) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    if db.remove_dependency(id, on)? == 0 {
+        println!("❌ Todo {} is not blocked by todo {}", id, on);
+    } else {
+        println!("✅ Todo {} is no longer blocked by todo {}", id, on);
+    }
+    Ok(())
+}
+
+// PRINT THE BLOCKED-BY TREE OF A TODO
+pub fn print_graph(id: i32) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let todos = db.get_todos()?;
+    if !todos.iter().any(|todo| todo.id == id as usize) {
+        println!("❌ No todo found with id: {}", id);
+        return Ok(());
+    }
+
+    let lines = blocked_by_tree(&todos, &db.dependency_links()?, id as usize);
+    for line in &lines {
+        println!("{}", line.label());
+    }
+    if lines.len() == 1 {
+        println!("Nothing blocks this todo");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: usize, status: &str) -> Todo {
+        Todo {
+            id,
+            status: status.to_string(),
+            ..Todo::sample(&format!("Task {}", id))
+        }
+    }
+
+    #[test]
+    fn test_blocked_by_tree() {
+        let todos = vec![
+            todo(1, "Pending"),
+            todo(2, "Done"),
+            todo(3, "Ongoing"),
+            todo(4, "Pending"),
+        ];
+        // 1 waits on 2 and 3, 3 waits on 4
+        let links = vec![(1, 3), (1, 2), (3, 4)];
+        let labels = blocked_by_tree(&todos, &links, 1)
+            .iter()
+            .map(GraphLine::label)
+            .collect::<Vec<String>>();
+        assert_eq!(
+            labels,
+            vec![
+                "#1 [Pending] Task 1",
+                "├── #2 [Done] Task 2",
+                "└── #3 [Ongoing] Task 3",
+                "    └── #4 [Pending] Task 4",
+            ]
+        );
+
+        assert!(depends_on(&links, 1, 4));
+        assert!(!depends_on(&links, 4, 1));
+
+        // A loop is shown once and not followed
+        let lines = blocked_by_tree(&todos, &[(1, 2), (2, 1)], 1);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].cycle);
+    }
+}
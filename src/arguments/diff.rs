@@ -0,0 +1,435 @@
+// SNAPSHOT DIFF: `voido diff backup.json` lists what changed between a JSON export and the
+// database, todo by todo and field by field, before restoring it or after a risky import
+use std::error::Error;
+use std::fs;
+
+use super::models::Todo;
+use crate::database::DBtodo;
+use crate::import_export::sql;
+
+// Tables the dry run doesn't list row by row: the todos and subtasks are compared field by
+// field, the others are bookkeeping of those changes
+const DIFFED_ELSEWHERE: [&str; 6] = [
+    "todos",
+    "subtasks",
+    "history",
+    "sync_journal",
+    "sync_state",
+    "sync_links",
+];
+
+// One field that differs, with its value in the snapshot and now
+#[derive(Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Default)]
+pub struct TodoDiff<'a> {
+    pub added: Vec<&'a Todo>,
+    pub removed: Vec<&'a Todo>,
+    pub modified: Vec<(&'a Todo, Vec<FieldChange>)>,
+}
+
+impl TodoDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+fn optional(value: &Option<String>) -> String {
+    value.clone().unwrap_or_default()
+}
+
+// Long texts are shown by their first line and how many lines they have
+fn short(value: &str) -> String {
+    let lines = value.lines().count();
+    let first = value.lines().next().unwrap_or_default();
+    if lines > 1 {
+        format!("{} (+{} lines)", first, lines - 1)
+    } else {
+        first.to_string()
+    }
+}
+
+// The fields that differ between two versions of a todo, subtasks matched by their text
+pub fn field_changes(before: &Todo, after: &Todo) -> Vec<FieldChange> {
+    let fields = |todo: &Todo| {
+        [
+            ("text", todo.text.clone()),
+            ("description", short(&todo.desc)),
+            ("status", todo.status.clone()),
+            ("priority", todo.priority.clone()),
+            ("topic", todo.topic.clone()),
+            ("owner", todo.owner.clone()),
+            (
+                "due",
+                todo.due.map(|due| due.to_string()).unwrap_or_default(),
+            ),
+            (
+                "due time",
+                todo.due_at
+                    .map(|due_at| due_at.to_rfc3339())
+                    .unwrap_or_default(),
+            ),
+            ("notes", short(&todo.notes)),
+            ("pinned", todo.pinned.to_string()),
+            ("archived", todo.archived.to_string()),
+            ("waiting on", optional(&todo.waiting_on)),
+            ("context", optional(&todo.context)),
+            ("link", optional(&todo.external_url)),
+        ]
+    };
+    let mut changes = fields(before)
+        .into_iter()
+        .zip(fields(after))
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((field, old), (_, new))| FieldChange {
+            field: field.to_string(),
+            before: old,
+            after: new,
+        })
+        .collect::<Vec<FieldChange>>();
+    // Long texts can differ past their first line only
+    for (field, old, new) in [
+        ("description", &before.desc, &after.desc),
+        ("notes", &before.notes, &after.notes),
+    ] {
+        if old != new && !changes.iter().any(|change| change.field == field) {
+            changes.push(FieldChange {
+                field: field.to_string(),
+                before: short(old),
+                after: format!("{} (edited)", short(new)),
+            });
+        }
+    }
+
+    for subtask in &before.subtasks {
+        match after
+            .subtasks
+            .iter()
+            .find(|other| other.text == subtask.text)
+        {
+            Some(other) if other.status != subtask.status => changes.push(FieldChange {
+                field: format!("subtask \"{}\"", subtask.text),
+                before: subtask.status.clone(),
+                after: other.status.clone(),
+            }),
+            Some(_) => {}
+            None => changes.push(FieldChange {
+                field: format!("subtask \"{}\"", subtask.text),
+                before: subtask.status.clone(),
+                after: "removed".to_string(),
+            }),
+        }
+    }
+    for subtask in &after.subtasks {
+        if !before
+            .subtasks
+            .iter()
+            .any(|other| other.text == subtask.text)
+        {
+            changes.push(FieldChange {
+                field: format!("subtask \"{}\"", subtask.text),
+                before: "added".to_string(),
+                after: subtask.status.clone(),
+            });
+        }
+    }
+    changes
+}
+
+// Todos are matched by ID
+pub fn diff_todos<'a>(snapshot: &'a [Todo], current: &'a [Todo]) -> TodoDiff<'a> {
+    let mut diff = TodoDiff::default();
+    for todo in current {
+        match snapshot.iter().find(|old| old.id == todo.id) {
+            Some(old) => {
+                let changes = field_changes(old, todo);
+                if !changes.is_empty() {
+                    diff.modified.push((todo, changes));
+                }
+            }
+            None => diff.added.push(todo),
+        }
+    }
+    diff.removed = snapshot
+        .iter()
+        .filter(|old| !current.iter().any(|todo| todo.id == old.id))
+        .collect();
+    diff
+}
+
+pub fn diff(file: &str) -> Result<(), Box<dyn Error>> {
+    let json = fs::read_to_string(file).map_err(|e| format!("Could not read {}: {}", file, e))?;
+    let snapshot: Vec<Todo> = serde_json::from_str(&json)
+        .map_err(|e| format!("{} is not a VoiDo JSON export: {}", file, e))?;
+
+    // Archived todos are compared too, archiving one shows as a change
+    let current = all_todos(&DBtodo::new()?)?;
+
+    let diff = diff_todos(&snapshot, &current);
+    if diff.is_empty() {
+        println!("✅ The database matches {}", file);
+        return Ok(());
+    }
+
+    println!();
+    println!("📸 Changes since {}:", file);
+    print_diff(&diff);
+    Ok(())
+}
+
+// Every todo and field of the diff, under the header the caller printed
+pub fn print_diff(diff: &TodoDiff) {
+    if !diff.added.is_empty() {
+        println!();
+        println!("  ➕ Added ({})", diff.added.len());
+        for todo in &diff.added {
+            println!("  {:>4}  {}", todo.id, todo.text);
+        }
+    }
+    if !diff.removed.is_empty() {
+        println!();
+        println!("  ➖ Removed ({})", diff.removed.len());
+        for todo in &diff.removed {
+            println!("  {:>4}  {}", todo.id, todo.text);
+        }
+    }
+    if !diff.modified.is_empty() {
+        println!();
+        println!("  ✏️ Modified ({})", diff.modified.len());
+        for (todo, changes) in &diff.modified {
+            println!("  {:>4}  {}", todo.id, todo.text);
+            for change in changes {
+                println!(
+                    "          {}: {} → {}",
+                    change.field,
+                    if change.before.is_empty() {
+                        "(none)"
+                    } else {
+                        &change.before
+                    },
+                    if change.after.is_empty() {
+                        "(none)"
+                    } else {
+                        &change.after
+                    }
+                );
+            }
+        }
+    }
+    println!();
+}
+
+// All the todos, archived ones too, by ID
+pub fn all_todos(db: &DBtodo) -> Result<Vec<Todo>, Box<dyn Error>> {
+    let mut todos = db.get_todos()?;
+    todos.extend(db.get_archived_todos()?);
+    todos.sort_by_key(|todo| todo.id);
+    Ok(todos)
+}
+
+// The rows of the other tables (owners, milestones, comments, checklists...), as the INSERT
+// lines of a dump
+fn other_rows(db: &DBtodo) -> Result<Vec<String>, Box<dyn Error>> {
+    Ok(sql::dump_sql(&db.connection)?
+        .lines()
+        .filter(|line| line.starts_with("INSERT INTO"))
+        .filter(|line| table_of(line).is_some_and(|table| !DIFFED_ELSEWHERE.contains(&table)))
+        .map(str::to_string)
+        .collect())
+}
+
+fn table_of(insert: &str) -> Option<&str> {
+    insert.strip_prefix("INSERT INTO \"")?.split('"').next()
+}
+
+// (table, rows added, rows removed) by table name. A changed row is one of each.
+pub fn table_changes(before: &[String], after: &[String]) -> Vec<(String, usize, usize)> {
+    let mut changes: Vec<(String, usize, usize)> = Vec::new();
+    let mut count = |row: &String, added: bool| {
+        let Some(table) = table_of(row) else {
+            return;
+        };
+        let index = match changes.iter().position(|(name, _, _)| name == table) {
+            Some(index) => index,
+            None => {
+                changes.push((table.to_string(), 0, 0));
+                changes.len() - 1
+            }
+        };
+        if added {
+            changes[index].1 += 1;
+        } else {
+            changes[index].2 += 1;
+        }
+    };
+    for row in after.iter().filter(|row| !before.contains(row)) {
+        count(row, true);
+    }
+    for row in before.iter().filter(|row| !after.contains(row)) {
+        count(row, false);
+    }
+    changes.sort();
+    changes
+}
+
+// `--dry-run`: the command writes to a copy of the database, what it changed there is printed
+// when VoiDo is done, early returns included
+pub struct DryRun {
+    before: Vec<Todo>,
+    rows: Vec<String>,
+    copy: DBtodo,
+}
+
+impl DryRun {
+    pub fn start() -> Result<DryRun, Box<dyn Error>> {
+        let copy = DBtodo::open_dry_run()?;
+        let before = all_todos(&copy)?;
+        let rows = other_rows(&copy)?;
+        Ok(DryRun { before, rows, copy })
+    }
+}
+
+// `--dry-run` sends nothing out either, a command that needs an answer from `service` stops
+// here
+#[cfg(feature = "ai")]
+pub fn offline(service: &str) -> Result<(), Box<dyn Error>> {
+    if DBtodo::is_dry_run() {
+        return Err(format!("dry run, nothing was sent to {}", service).into());
+    }
+    Ok(())
+}
+
+impl Drop for DryRun {
+    fn drop(&mut self) {
+        let (after, rows) =
+            match all_todos(&self.copy).and_then(|after| Ok((after, other_rows(&self.copy)?))) {
+                Ok(after) => after,
+                Err(e) => {
+                    eprintln!("❌ Could not compare the dry run: {}", e);
+                    return;
+                }
+            };
+        let diff = diff_todos(&self.before, &after);
+        let tables = table_changes(&self.rows, &rows);
+        if diff.is_empty() && tables.is_empty() {
+            println!("🧪 Dry run: nothing would change");
+            return;
+        }
+        println!();
+        println!("🧪 Dry run, nothing was written. This is what would change:");
+        if !diff.is_empty() {
+            print_diff(&diff);
+        }
+        if !tables.is_empty() {
+            println!(
+                "  🗂️ Other tables: {}",
+                tables
+                    .iter()
+                    .map(|(table, added, removed)| match (added, removed) {
+                        (0, removed) => format!("{} (-{})", table, removed),
+                        (added, 0) => format!("{} (+{})", table, added),
+                        (added, removed) => format!("{} (+{} -{})", table, added, removed),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            );
+            println!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arguments::models::Subtask;
+
+    fn todo(id: usize, text: &str, status: &str) -> Todo {
+        Todo {
+            id,
+            status: status.to_string(),
+            ..Todo::sample(text)
+        }
+    }
+
+    fn subtask(text: &str, status: &str) -> Subtask {
+        Subtask {
+            todo_id: 1,
+            subtask_id: 0,
+            text: text.to_string(),
+            status: status.to_string(),
+            due: None,
+            owner: None,
+            note: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_diff_added_removed_and_fields() {
+        let mut kept = todo(1, "Ship v1", "Pending");
+        kept.notes = "Plan\nstaging first".to_string();
+        kept.subtasks = vec![subtask("Tag", "Pending"), subtask("Announce", "Pending")];
+        let snapshot = vec![kept.clone(), todo(2, "Old idea", "Pending")];
+
+        kept.status = "Done".to_string();
+        kept.notes = "Plan\nproduction first".to_string();
+        kept.subtasks = vec![subtask("Tag", "Done"), subtask("Blog post", "Pending")];
+        let current = vec![kept, todo(3, "New idea", "Pending")];
+
+        let diff = diff_todos(&snapshot, &current);
+        assert_eq!(
+            diff.added.iter().map(|todo| todo.id).collect::<Vec<_>>(),
+            vec![3]
+        );
+        assert_eq!(
+            diff.removed.iter().map(|todo| todo.id).collect::<Vec<_>>(),
+            vec![2]
+        );
+        let (todo, changes) = &diff.modified[0];
+        assert_eq!(todo.id, 1);
+        let fields = changes
+            .iter()
+            .map(|change| {
+                (
+                    change.field.as_str(),
+                    change.before.as_str(),
+                    change.after.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![
+                ("status", "Pending", "Done"),
+                ("notes", "Plan (+1 lines)", "Plan (+1 lines) (edited)"),
+                ("subtask \"Tag\"", "Pending", "Done"),
+                ("subtask \"Announce\"", "Pending", "removed"),
+                ("subtask \"Blog post\"", "added", "Pending"),
+            ]
+        );
+
+        assert!(diff_todos(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn test_table_changes() {
+        let before = vec![
+            "INSERT INTO \"owners\" (\"id\", \"name\") VALUES (1, 'Joe');".to_string(),
+            "INSERT INTO \"comments\" (\"id\", \"text\") VALUES (1, 'old');".to_string(),
+        ];
+        let after = vec![
+            "INSERT INTO \"owners\" (\"id\", \"name\") VALUES (1, 'Joe');".to_string(),
+            "INSERT INTO \"owners\" (\"id\", \"name\") VALUES (2, 'Ana');".to_string(),
+            "INSERT INTO \"comments\" (\"id\", \"text\") VALUES (1, 'new');".to_string(),
+        ];
+        assert_eq!(
+            table_changes(&before, &after),
+            vec![("comments".to_string(), 1, 1), ("owners".to_string(), 1, 0)]
+        );
+        assert!(table_changes(&after, &after).is_empty());
+    }
+}
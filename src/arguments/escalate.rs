@@ -0,0 +1,150 @@
+// Priority escalation: raise the priority of open todos as their due date gets close
+use chrono::{Local, NaiveDate};
+use std::error::Error;
+
+use super::models::Todo;
+use crate::configs::EscalationConfig;
+use crate::database::DBtodo;
+
+// (todo id, old priority, new priority)
+pub type PriorityChange = (i32, String, String);
+
+// Low < Normal/Medium < High, unknown priorities are never escalated
+fn priority_rank(priority: &str) -> Option<u8> {
+    match priority.to_lowercase().as_str() {
+        "low" => Some(0),
+        "normal" | "medium" => Some(1),
+        "high" => Some(2),
+        _ => None,
+    }
+}
+
+// New priority for a todo, if any rule raises it. Rules never lower a priority.
+pub fn escalated_priority(
+    todo: &Todo,
+    config: &EscalationConfig,
+    today: NaiveDate,
+) -> Option<String> {
+    if todo.is_done() {
+        return None;
+    }
+    let days_left = (todo.next_due()? - today).num_days();
+    let current = priority_rank(&todo.priority)?;
+
+    config
+        .rules
+        .iter()
+        .filter(|rule| days_left <= rule.within_days)
+        .filter_map(|rule| Some((priority_rank(&rule.priority)?, &rule.priority)))
+        .filter(|(rank, _)| *rank > current)
+        .max_by_key(|(rank, _)| *rank)
+        .map(|(_, priority)| super::add_todo::capitalize(&priority.to_lowercase()))
+}
+
+// Apply the escalation rules, returns every change that was made
+pub fn escalate_priorities() -> Result<Vec<PriorityChange>, Box<dyn Error>> {
+    let config = EscalationConfig::load();
+    if !config.enabled {
+        return Ok(Vec::new());
+    }
+
+    let mut db = DBtodo::new()?;
+    let today = Local::now().date_naive();
+    let changes = db
+        .get_todos()?
+        .iter()
+        .filter_map(|todo| {
+            escalated_priority(todo, &config, today)
+                .map(|priority| (todo.id as i32, todo.priority.clone(), priority))
+        })
+        .collect::<Vec<_>>();
+
+    if !changes.is_empty() {
+        db.escalate_priorities(&changes)?;
+    }
+    Ok(changes)
+}
+
+// One line summary of the escalated todos
+pub fn escalation_report(changes: &[PriorityChange]) -> String {
+    let todos = changes
+        .iter()
+        .map(|(id, _, to)| format!("#{} → {}", id, to))
+        .collect::<Vec<String>>()
+        .join(", ");
+    format!("⏫ Escalated {} todo(s): {}", changes.len(), todos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::EscalationRule;
+
+    fn todo(priority: &str, due: &str) -> Todo {
+        Todo {
+            id: 1,
+            priority: priority.to_string(),
+            due: crate::dates::parse_date(due),
+            ..Todo::sample("Test")
+        }
+    }
+
+    #[test]
+    fn test_escalation_only_raises() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let config = EscalationConfig {
+            enabled: true,
+            rules: vec![
+                EscalationRule {
+                    within_days: 7,
+                    priority: "Medium".to_string(),
+                },
+                EscalationRule {
+                    within_days: 2,
+                    priority: "High".to_string(),
+                },
+            ],
+        };
+
+        let high = Some("High".to_string());
+        let medium = Some("Medium".to_string());
+        assert_eq!(
+            escalated_priority(&todo("Low", "05-03-25"), &config, today),
+            medium
+        );
+        assert_eq!(
+            escalated_priority(&todo("Low", "02-03-25"), &config, today),
+            high
+        );
+        // Overdue todos match every rule
+        assert_eq!(
+            escalated_priority(&todo("Medium", "20-02-25"), &config, today),
+            high
+        );
+        assert_eq!(
+            escalated_priority(&todo("High", "05-03-25"), &config, today),
+            None
+        );
+        assert_eq!(escalated_priority(&todo("Low", "-"), &config, today), None);
+
+        // Open subtasks due earlier than the todo count, finished ones don't
+        let mut with_subtasks = todo("Low", "-");
+        with_subtasks.subtasks = ["Pending", "Done"]
+            .iter()
+            .map(|status| crate::arguments::models::Subtask {
+                todo_id: 1,
+                subtask_id: 1,
+                text: "Step".to_string(),
+                status: status.to_string(),
+                due: crate::dates::parse_date(if *status == "Done" {
+                    "20-02-25"
+                } else {
+                    "05-03-25"
+                }),
+                owner: None,
+                note: String::new(),
+            })
+            .collect();
+        assert_eq!(escalated_priority(&with_subtasks, &config, today), medium);
+    }
+}
@@ -0,0 +1,118 @@
+// Explode: turn the Markdown bullet and checkbox lines of a todo's description and notes into subtasks
+use std::error::Error;
+
+use crate::database::DBtodo;
+
+// A list line taken out of the text, `done` for checked boxes (`- [x]`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListItem {
+    pub text: String,
+    pub done: bool,
+}
+
+// `- item`, `* item`, `+ item`, optionally with a `[ ]` / `[x]` checkbox
+fn parse_list_line(line: &str) -> Option<ListItem> {
+    let rest = line
+        .trim_start()
+        .strip_prefix(['-', '*', '+'])?
+        .strip_prefix([' ', '\t'])?
+        .trim();
+
+    let (text, done) = if let Some(text) = rest.strip_prefix("[ ]") {
+        (text, false)
+    } else if let Some(text) = rest
+        .strip_prefix("[x]")
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        (text, true)
+    } else {
+        (rest, false)
+    };
+
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(ListItem {
+        text: text.to_string(),
+        done,
+    })
+}
+
+// Split the list items out of a text, returning what is left and the items in order.
+// Blank lines left behind by a removed list collapse into one.
+pub fn split_list_items(text: &str) -> (String, Vec<ListItem>) {
+    let mut items = Vec::new();
+    let mut kept: Vec<&str> = Vec::new();
+
+    for line in text.lines() {
+        match parse_list_line(line) {
+            Some(item) => items.push(item),
+            None if line.trim().is_empty() && kept.last().is_none_or(|l| l.trim().is_empty()) => {}
+            None => kept.push(line),
+        }
+    }
+
+    (kept.join("\n").trim().to_string(), items)
+}
+
+// EXPLODE A TODO: move its list lines into subtasks and remove them from the text
+pub fn explode_todo(id: i32) -> Result<usize, Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    let Some(todo) = db
+        .get_todos()?
+        .into_iter()
+        .find(|todo| todo.id == id as usize)
+    else {
+        return Err(format!("No todo found with id: {}", id).into());
+    };
+
+    let (desc, mut items) = split_list_items(&todo.desc);
+    let (notes, note_items) = split_list_items(&todo.notes);
+    items.extend(note_items);
+    if items.is_empty() {
+        return Ok(0);
+    }
+
+    let desc = if desc.is_empty() {
+        "No description provided".to_string()
+    } else {
+        desc
+    };
+    db.explode_todo(id, &desc, &notes, &items)?;
+    Ok(items.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, done: bool) -> ListItem {
+        ListItem {
+            text: text.to_string(),
+            done,
+        }
+    }
+
+    #[test]
+    fn test_split_list_items() {
+        let text = "Steps for the release:\n\n- Tag the build\n* [ ] Write notes\n  + [x] Bump version\n\nThen announce it.";
+        let (rest, items) = split_list_items(text);
+        assert_eq!(rest, "Steps for the release:\n\nThen announce it.");
+        assert_eq!(
+            items,
+            vec![
+                item("Tag the build", false),
+                item("Write notes", false),
+                item("Bump version", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_list_items_keeps_other_lines() {
+        let (rest, items) = split_list_items("-not a bullet\n---\n- [ ]\n**bold**");
+        assert!(items.is_empty());
+        assert_eq!(rest, "-not a bullet\n---\n- [ ]\n**bold**");
+    }
+}
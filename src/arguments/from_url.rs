@@ -0,0 +1,345 @@
+// CAPTURE FROM A LINK: `voido --from-url <link>` adds a "read or review this later" todo named
+// after the page title, with the page's own description (or a Gemini summary) as the description
+// and the link as the todo's external link. `-` reads the links from stdin, one per line, and
+// `voido://<encoded link>` is what a bookmarklet sends through a URL scheme handler.
+use std::error::Error;
+use std::io::{self, BufRead};
+
+use super::add_todo::build_todo;
+use crate::ai;
+use crate::database::DBtodo;
+
+// Page text Gemini reads for a summary, in characters
+const SUMMARY_INPUT: usize = 12_000;
+
+const HANDLER_SCHEME: &str = "voido://";
+
+// The link itself, out of `voido://https%3A%2F%2F...` when it comes from the bookmarklet
+pub fn unwrap_link(link: &str) -> String {
+    let link = link.trim();
+    let Some(encoded) = link.strip_prefix(HANDLER_SCHEME) else {
+        return link.to_string();
+    };
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = (bytes[index] == b'%')
+            .then(|| encoded.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded)
+        .trim_end_matches('/')
+        .to_string()
+}
+
+// A `name` tag opens at `at` of the lowercased page
+fn is_tag(lower: &str, at: usize, name: &str) -> bool {
+    lower[at..]
+        .strip_prefix('<')
+        .and_then(|tag| tag.strip_prefix(name))
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_whitespace() || c == '>' || c == '/')
+}
+
+// Where the next `name` tag starts in the lowercased page
+fn find_tag(lower: &str, name: &str, from: usize) -> Option<usize> {
+    let open = format!("<{}", name);
+    let mut start = from;
+    while let Some(found) = lower[start..].find(&open) {
+        let at = start + found;
+        if is_tag(lower, at, name) {
+            return Some(at);
+        }
+        start = at + open.len();
+    }
+    None
+}
+
+// `&amp;` and friends, and `&#39;` / `&#x27;` style numbers
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(at) = rest.find('&') {
+        decoded.push_str(&rest[..at]);
+        rest = &rest[at..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 8)
+            .map(|end| &rest[1..=end]);
+        let character = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or(entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#')?.parse().ok())
+                .and_then(char::from_u32),
+        });
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn clean(text: &str) -> Option<String> {
+    let text = decode_entities(text)
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+// The value of `attribute` in a tag like `<meta name="description" content="...">`
+fn attribute(tag: &str, attribute: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut start = 0;
+    while let Some(found) = lower[start..].find(attribute) {
+        let at = start + found;
+        start = at + attribute.len();
+        // Part of a longer attribute name like `data-content`, or of a value
+        let Some(value) = tag[start..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        if lower[..at]
+            .chars()
+            .next_back()
+            .is_some_and(|c| !c.is_whitespace())
+        {
+            continue;
+        }
+        let value = value.trim_start();
+        let (quote, value) = match value.chars().next()? {
+            quote @ ('"' | '\'') => (quote, &value[1..]),
+            _ => (' ', value),
+        };
+        let end = value
+            .find(|c: char| c == quote || (quote == ' ' && c == '>'))
+            .unwrap_or(value.len());
+        return Some(value[..end].to_string());
+    }
+    None
+}
+
+// `content` of the first meta tag whose name or property is one of `names`, in that order
+fn meta(html: &str, names: &[&str]) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let mut tags = Vec::new();
+    let mut from = 0;
+    while let Some(at) = find_tag(&lower, "meta", from) {
+        let end = html[at..].find('>').map_or(html.len(), |end| at + end);
+        tags.push(&html[at..end]);
+        from = end;
+    }
+    names.iter().find_map(|name| {
+        tags.iter().find_map(|tag| {
+            let key = attribute(tag, "property").or_else(|| attribute(tag, "name"))?;
+            if key.eq_ignore_ascii_case(name) {
+                attribute(tag, "content").and_then(|content| clean(&content))
+            } else {
+                None
+            }
+        })
+    })
+}
+
+// The og:title, or the <title> when the page has none
+pub fn page_title(html: &str) -> Option<String> {
+    meta(html, &["og:title", "twitter:title"]).or_else(|| {
+        let lower = html.to_ascii_lowercase();
+        let start = find_tag(&lower, "title", 0)?;
+        let start = start + html[start..].find('>')? + 1;
+        let end = lower[start..].find("</title")?;
+        clean(&html[start..start + end])
+    })
+}
+
+pub fn page_description(html: &str) -> Option<String> {
+    meta(
+        html,
+        &["description", "og:description", "twitter:description"],
+    )
+}
+
+// The readable text of the page, without its tags, scripts and styles
+pub fn page_text(html: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let mut text = String::new();
+    let mut position = find_tag(&lower, "body", 0).unwrap_or(0);
+    while position < html.len() {
+        let Some(open) = html[position..].find('<') else {
+            text.push_str(&html[position..]);
+            break;
+        };
+        text.push_str(&html[position..position + open]);
+        text.push(' ');
+        let tag = position + open;
+        // Skip what is inside scripts and styles, not only their tags
+        let skip_to = ["script", "style", "noscript"]
+            .iter()
+            .find(|name| is_tag(&lower, tag, name))
+            .and_then(|name| lower[tag..].find(&format!("</{}", name)))
+            .map(|end| tag + end);
+        let from = skip_to.unwrap_or(tag);
+        position = html[from..]
+            .find('>')
+            .map_or(html.len(), |end| from + end + 1);
+    }
+    clean(&text).unwrap_or_default()
+}
+
+async fn summarize(db: &DBtodo, title: &str, text: &str) -> Result<String, Box<dyn Error>> {
+    let text = text.chars().take(SUMMARY_INPUT).collect::<String>();
+    let prompt = format!(
+        "Summarize the page \"{}\" in 2 or 3 short sentences: what it is about and why someone \
+would read it. Plain text, no headings or lists, in the language of the page.
+
+PAGE:
+{}",
+        title, text
+    );
+    Ok(ai::generate(db, prompt)
+        .await?
+        .ok_or("Gemini sent back no summary")?
+        .trim()
+        .to_string())
+}
+
+// Add one todo for `url`, named after the page when it can be fetched
+async fn add_link(db: &DBtodo, url: &str, summary: bool) -> Result<(), Box<dyn Error>> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("'{}' is not an http(s) link", url).into());
+    }
+
+    let html = match fetch(url).await {
+        Ok(html) => Some(html),
+        Err(e) => {
+            eprintln!("⚠️ Could not read {}: {}, adding the link as it is", url, e);
+            None
+        }
+    };
+    let title = html.as_deref().and_then(page_title);
+    let mut desc = html.as_deref().and_then(page_description);
+    if summary && let Some(html) = &html {
+        match summarize(db, title.as_deref().unwrap_or(url), &page_text(html)).await {
+            Ok(summary) => desc = Some(summary),
+            Err(e) => eprintln!("⚠️ No summary of {}: {}", url, e),
+        }
+    }
+
+    let text = title.unwrap_or_else(|| url.to_string());
+    let mut todo = build_todo(text, None, None, None, None, desc, Vec::new());
+    todo.external_url = Some(url.to_string());
+    let id = db.add_todo(&todo)?;
+    println!("✅ Todo {} added: {}", id, todo.text);
+    println!("   🔗 {}", url);
+    Ok(())
+}
+
+#[cfg(feature = "web")]
+async fn fetch(url: &str) -> Result<String, Box<dyn Error>> {
+    let html = reqwest::Client::new()
+        .get(url)
+        .header("User-Agent", concat!("voido/", env!("CARGO_PKG_VERSION")))
+        .header("Accept", "text/html")
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    Ok(html)
+}
+
+#[cfg(not(feature = "web"))]
+async fn fetch(_url: &str) -> Result<String, Box<dyn Error>> {
+    Err(crate::features::missing("web"))
+}
+
+pub async fn add_from_url(link: &str, summary: bool) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    if link != "-" {
+        return add_link(&db, &unwrap_link(link), summary).await;
+    }
+
+    // Piped links, the ones that fail are reported and skipped
+    let links = io::stdin()
+        .lock()
+        .lines()
+        .collect::<Result<Vec<String>, _>>()?;
+    for link in links
+        .iter()
+        .map(|link| link.trim())
+        .filter(|link| !link.is_empty())
+    {
+        if let Err(e) = add_link(&db, &unwrap_link(link), summary).await {
+            eprintln!("❌ {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_title_description_and_text() {
+        let html = r#"<!doctype html><html><head>
+            <TITLE>
+              Invoices &amp; billing &#8211; Docs
+            </TITLE>
+            <meta data-name="x" name="description" content="How invoices are sent &quot;monthly&quot;">
+            <style>body { color: red }</style>
+        </head><body><h1>Invoices</h1><script>var a = "<b>no</b>";</script>
+            <p>Sent on the <b>first</b> day.</p></body></html>"#;
+        assert_eq!(page_title(html).unwrap(), "Invoices & billing – Docs");
+        assert_eq!(
+            page_description(html).unwrap(),
+            "How invoices are sent \"monthly\""
+        );
+        assert_eq!(page_text(html), "Invoices Sent on the first day.");
+
+        // og:title wins over the <title>
+        let html = "<head><title>Site</title>\
+            <meta property='og:title' content='The article'></head>";
+        assert_eq!(page_title(html).unwrap(), "The article");
+        assert_eq!(page_description(html), None);
+        assert_eq!(
+            unwrap_link("voido://https%3A%2F%2Fexample.com%2Fa%3Fq%3D1%20x/"),
+            "https://example.com/a?q=1 x"
+        );
+        assert_eq!(
+            unwrap_link(" https://example.com/ "),
+            "https://example.com/"
+        );
+        assert_eq!(
+            decode_entities("R&D &unknown; &#x27;a&#39;"),
+            "R&D &unknown; 'a'"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+// HABITS: recurring todos that come back every day or week. Each completion is a Done entry
+// in the history table, the streak is how many periods in a row have one.
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use std::collections::BTreeSet;
+use std::error::Error;
+
+use super::models::{Todo, is_done_status};
+use crate::configs::WorkflowConfig;
+use crate::database::{DBtodo, StatusChange};
+use crate::dates;
+use crate::ui::truncate_text;
+
+// Periods shown in the streak calendar
+pub const CALENDAR_DAYS: usize = 28;
+pub const CALENDAR_WEEKS: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cadence {
+    Daily,
+    Weekly,
+}
+
+impl Cadence {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "daily" | "day" | "d" => Some(Self::Daily),
+            "weekly" | "week" | "w" => Some(Self::Weekly),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Weekly => "weekly",
+        }
+    }
+
+    // First day of the period `date` falls in
+    pub fn period_start(&self, date: NaiveDate, week_start: Weekday) -> NaiveDate {
+        match self {
+            Self::Daily => date,
+            Self::Weekly => {
+                let into_week = (date.weekday().num_days_from_monday() as i64
+                    - week_start.num_days_from_monday() as i64)
+                    .rem_euclid(7);
+                date - Duration::days(into_week)
+            }
+        }
+    }
+
+    fn length(&self) -> Duration {
+        match self {
+            Self::Daily => Duration::days(1),
+            Self::Weekly => Duration::weeks(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Habit {
+    pub todo_id: usize,
+    pub text: String,
+    pub cadence: Cadence,
+    // Periods in a row with a completion, the current one counts once it is done
+    pub streak: usize,
+    pub done_now: bool,
+    // Oldest period first, true when it had a completion
+    pub calendar: Vec<bool>,
+}
+
+// Periods (by their first day) with at least one completion
+fn completed_periods(
+    cadence: Cadence,
+    completions: &[NaiveDate],
+    week_start: Weekday,
+) -> BTreeSet<NaiveDate> {
+    completions
+        .iter()
+        .map(|date| cadence.period_start(*date, week_start))
+        .collect()
+}
+
+// The current period doesn't break the streak until it is over
+pub fn streak(
+    cadence: Cadence,
+    completions: &[NaiveDate],
+    today: NaiveDate,
+    week_start: Weekday,
+) -> usize {
+    let periods = completed_periods(cadence, completions, week_start);
+    let mut period = cadence.period_start(today, week_start);
+    if !periods.contains(&period) {
+        period -= cadence.length();
+    }
+    let mut streak = 0;
+    while periods.contains(&period) {
+        streak += 1;
+        period -= cadence.length();
+    }
+    streak
+}
+
+pub fn calendar(
+    cadence: Cadence,
+    completions: &[NaiveDate],
+    today: NaiveDate,
+    week_start: Weekday,
+    count: usize,
+) -> Vec<bool> {
+    let periods = completed_periods(cadence, completions, week_start);
+    let current = cadence.period_start(today, week_start);
+    (0..count)
+        .rev()
+        .map(|back| periods.contains(&(current - cadence.length() * back as i32)))
+        .collect()
+}
+
+// Streaks and calendars of every habit, in todo order
+pub fn habit_summaries(
+    todos: &[Todo],
+    habits: &[(i64, Cadence)],
+    history: &[StatusChange],
+    today: NaiveDate,
+    week_start: Weekday,
+) -> Vec<Habit> {
+    todos
+        .iter()
+        .filter_map(|todo| {
+            let (_, cadence) = habits
+                .iter()
+                .find(|(todo_id, _)| *todo_id == todo.id as i64)?;
+            let completions = history
+                .iter()
+                .filter(|(todo_id, status, _)| *todo_id == todo.id as i64 && is_done_status(status))
+                .map(|(_, _, at)| at.with_timezone(&Local).date_naive())
+                .collect::<Vec<NaiveDate>>();
+            let count = match cadence {
+                Cadence::Daily => CALENDAR_DAYS,
+                Cadence::Weekly => CALENDAR_WEEKS,
+            };
+            let calendar = calendar(*cadence, &completions, today, week_start, count);
+            Some(Habit {
+                todo_id: todo.id,
+                text: todo.text.clone(),
+                cadence: *cadence,
+                streak: streak(*cadence, &completions, today, week_start),
+                done_now: calendar.last() == Some(&true),
+                calendar,
+            })
+        })
+        .collect()
+}
+
+pub fn load_habits(db: &DBtodo) -> Result<Vec<Habit>, Box<dyn Error>> {
+    Ok(habit_summaries(
+        &db.get_todos()?,
+        &db.get_habits()?,
+        &db.status_history()?,
+        Local::now().date_naive(),
+        dates::week_starts_on(),
+    ))
+}
+
+// One cell per period: ■ done, · missed
+pub fn calendar_cells(calendar: &[bool]) -> String {
+    calendar
+        .iter()
+        .map(|done| if *done { '■' } else { '·' })
+        .collect()
+}
+
+// Habits done in an earlier period go back to Pending, returns their IDs
+pub fn reopen_habits() -> Result<Vec<usize>, Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let todos = db.get_todos()?;
+    let ids = load_habits(&db)?
+        .iter()
+        .filter(|habit| !habit.done_now)
+        .filter(|habit| {
+            todos
+                .iter()
+                .any(|todo| todo.id == habit.todo_id && todo.is_done())
+        })
+        .map(|habit| habit.todo_id)
+        .collect::<Vec<usize>>();
+    // Coming back is part of being a habit, not a step the workflow has to allow
+    for id in &ids {
+        db.set_status(*id as i32, "Pending", None, &WorkflowConfig::default())?;
+    }
+    Ok(ids)
+}
+
+pub fn set_habit(id: i32, every: &str) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    if every.eq_ignore_ascii_case("off") {
+        if db.set_habit(id, None)? {
+            println!("✅ Todo {} is no longer a habit", id);
+        } else {
+            println!("❌ Todo {} is not a habit", id);
+        }
+        return Ok(());
+    }
+
+    let cadence = Cadence::parse(every)
+        .ok_or_else(|| format!("\"{}\" should be daily, weekly or off", every))?;
+    db.set_habit(id, Some(cadence))?;
+    println!(
+        "🔁 Todo {} is a {} habit, mark it Done to keep the streak going",
+        id,
+        cadence.name()
+    );
+    Ok(())
+}
+
+// PRINT EVERY HABIT WITH ITS STREAK AND CALENDAR
+pub fn print_habits() -> Result<(), Box<dyn Error>> {
+    let habits = load_habits(&DBtodo::new()?)?;
+    if habits.is_empty() {
+        println!("No habits yet, make a todo one with `voido habit <id> daily`");
+        return Ok(());
+    }
+
+    println!();
+    for habit in &habits {
+        println!(
+            "  {:>3}  {:<30} {:<7} 🔥 {:<3} {}",
+            habit.todo_id,
+            truncate_text(&habit.text, 30),
+            habit.cadence.name(),
+            habit.streak,
+            calendar_cells(&habit.calendar)
+        );
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaks() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let done = [day(3), day(4), day(6), day(7), day(7)];
+
+        // Today isn't over yet, the streak runs up to yesterday
+        assert_eq!(streak(Cadence::Daily, &done, day(8), Weekday::Mon), 2);
+        assert_eq!(streak(Cadence::Daily, &done, day(7), Weekday::Mon), 2);
+        assert_eq!(streak(Cadence::Daily, &done, day(9), Weekday::Mon), 0);
+        assert_eq!(
+            calendar(Cadence::Daily, &done, day(8), Weekday::Mon, 6),
+            vec![true, true, false, true, true, false]
+        );
+
+        // 2025-03-03 is a Monday, the 9th is a Sunday
+        assert_eq!(Cadence::Weekly.period_start(day(9), Weekday::Mon), day(3));
+        assert_eq!(Cadence::Weekly.period_start(day(9), Weekday::Sun), day(9));
+        let weekly = [day(3), NaiveDate::from_ymd_opt(2025, 2, 25).unwrap()];
+        assert_eq!(streak(Cadence::Weekly, &weekly, day(12), Weekday::Mon), 2);
+        assert_eq!(calendar_cells(&[true, false]), "■·");
+    }
+}
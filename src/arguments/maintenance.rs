@@ -0,0 +1,109 @@
+// DATABASE MAINTENANCE: `voido db vacuum|check|stats`
+use std::error::Error;
+
+use crate::database::DBtodo;
+
+// 1536 -> "1.5 KB"
+fn format_size(bytes: u64) -> String {
+    let units = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < units.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, units[unit])
+    }
+}
+
+fn file_size(db: &DBtodo) -> Option<u64> {
+    let path = db.connection.path()?;
+    std::fs::metadata(path).ok().map(|metadata| metadata.len())
+}
+
+pub fn vacuum() -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let before = file_size(&db).unwrap_or(0);
+    db.vacuum()?;
+    let after = file_size(&db).unwrap_or(0);
+    println!(
+        "✅ Database vacuumed: {} → {}",
+        format_size(before),
+        format_size(after)
+    );
+    Ok(())
+}
+
+pub fn check(repair: bool) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+
+    let problems = db.integrity_check()?;
+    if problems == ["ok"] {
+        println!("✅ Integrity check passed");
+    } else {
+        println!("❌ Integrity check found {} problem(s):", problems.len());
+        for problem in &problems {
+            println!("   {}", problem);
+        }
+    }
+
+    let subtasks = db.orphaned_subtasks()?;
+    let dependencies = db.orphaned_dependencies()?;
+    if subtasks.is_empty() && dependencies.is_empty() {
+        println!("✅ No orphaned subtasks or dependencies");
+        return Ok(());
+    }
+    for (id, todo_id, text) in &subtasks {
+        println!(
+            "⚠️ Subtask {} \"{}\" belongs to deleted todo {}",
+            id, text, todo_id
+        );
+    }
+    for (todo_id, blocked_by) in &dependencies {
+        println!(
+            "⚠️ Dependency {} → {} points at a deleted todo",
+            todo_id, blocked_by
+        );
+    }
+
+    if repair {
+        let (subtasks, dependencies) = db.delete_orphans()?;
+        println!(
+            "✅ Removed {} orphaned subtask(s) and {} dependency(ies)",
+            subtasks, dependencies
+        );
+    } else {
+        println!("Run `voido db check --repair` to remove them");
+    }
+    Ok(())
+}
+
+pub fn stats() -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    println!();
+    if let Some(path) = db.connection.path() {
+        println!("  File:  {}", path);
+    }
+    println!("  Size:  {}", format_size(file_size(&db).unwrap_or(0)));
+    println!();
+    for (table, count) in db.table_counts()? {
+        println!("  {:<16} {:>6}", table, count);
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+    }
+}
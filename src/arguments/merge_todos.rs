@@ -0,0 +1,9 @@
+use std::error::Error;
+
+use crate::database::DBtodo;
+
+// Merge the todo `other` into `keep`, `other` is removed afterwards
+pub fn merge_todos(keep: i32, other: i32) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    db.merge_todos(keep, other)
+}
@@ -0,0 +1,159 @@
+// Milestones: named goals with an optional target date that todos can be linked to
+use chrono::Local;
+use std::error::Error;
+
+use super::models::Todo;
+use super::report::{self, progress_bar};
+use crate::database::DBtodo;
+use crate::dates;
+
+// Create a milestone or change its target date
+pub fn add_milestone(name: &str, target: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let target = match target {
+        Some(date) => Some(
+            dates::parse_due(date, Local::now().date_naive())
+                .ok_or_else(|| format!("Invalid date: {}", date))?,
+        ),
+        None => None,
+    };
+
+    let db = DBtodo::new()?;
+    db.save_milestone(name.trim(), target)?;
+    println!(
+        "✅ Milestone '{}' saved{}",
+        name.trim(),
+        target
+            .map(|target| format!(", target {}", dates::format_date(target)))
+            .unwrap_or_default()
+    );
+    Ok(())
+}
+
+pub fn remove_milestone(name: &str) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    match db.find_milestone(name)? {
+        Some(milestone) => {
+            db.delete_milestone(milestone.id)?;
+            println!("✅ Milestone '{}' removed", milestone.name);
+        }
+        None => println!("❌ No milestone named: {}", name),
+    }
+    Ok(())
+}
+
+// Link todos to a milestone, or unlink them when `name` is None
+pub fn link_todos(name: Option<&str>, ids: &[i32]) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    let milestone = match name {
+        Some(name) => match db.find_milestone(name)? {
+            Some(milestone) => Some(milestone),
+            None => {
+                println!("❌ No milestone named: {}", name);
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
+    let count = db.set_milestone(ids, milestone.as_ref().map(|milestone| milestone.id))?;
+    match milestone {
+        Some(milestone) => println!("✅ {} todo(s) linked to '{}'", count, milestone.name),
+        None => println!("✅ {} todo(s) unlinked from their milestone", count),
+    }
+    Ok(())
+}
+
+fn target_label(target: Option<chrono::NaiveDate>) -> String {
+    target
+        .map(|target| format!("due {}", dates::format_date(target)))
+        .unwrap_or_else(|| "no target".to_string())
+}
+
+// PRINT EVERY MILESTONE WITH ITS PROGRESS
+pub fn list_milestones() -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let progress = report::load_milestone_progress(&db)?;
+
+    if progress.is_empty() {
+        println!("No milestones yet, create one with `voido milestone add <name>`");
+        return Ok(());
+    }
+
+    println!();
+    for entry in &progress {
+        println!(
+            "  {:<20} {}  {:>3}/{:<3} {:>3}%  {}",
+            entry.name,
+            progress_bar(entry.done, entry.total, 24),
+            entry.done,
+            entry.total,
+            entry.percent(),
+            target_label(entry.target)
+        );
+    }
+    println!();
+    Ok(())
+}
+
+// PRINT THE OUTSTANDING WORK OF ONE MILESTONE
+pub fn show_milestone(name: &str) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let Some(milestone) = db.find_milestone(name)? else {
+        println!("❌ No milestone named: {}", name);
+        return Ok(());
+    };
+
+    let linked = db
+        .milestone_links()?
+        .into_iter()
+        .filter(|(_, milestone_id)| *milestone_id == milestone.id)
+        .map(|(todo_id, _)| todo_id)
+        .collect::<Vec<i64>>();
+    let mut todos = db.get_todos()?;
+    todos.extend(db.get_archived_todos()?);
+    let todos = todos
+        .into_iter()
+        .filter(|todo| linked.contains(&(todo.id as i64)))
+        .collect::<Vec<Todo>>();
+    let done = todos.iter().filter(|todo| todo.is_done()).count();
+
+    println!();
+    println!(
+        "  🎯 {}  {}  {}/{}  {}",
+        milestone.name,
+        progress_bar(done, todos.len(), 24),
+        done,
+        todos.len(),
+        target_label(milestone.target)
+    );
+    if let Some(target) = milestone.target {
+        let days = (target - Local::now().date_naive()).num_days();
+        match days {
+            0 => println!("     Due today"),
+            days if days < 0 => println!("     {} days overdue", -days),
+            days => println!("     {} days left", days),
+        }
+    }
+    println!();
+
+    let outstanding = todos
+        .iter()
+        .filter(|todo| !todo.is_done())
+        .collect::<Vec<&Todo>>();
+    if outstanding.is_empty() {
+        println!("  ✅ Nothing outstanding");
+    }
+    for todo in outstanding {
+        println!(
+            "  {:>4}  {:<40}  {:<8}  {:<8}  {:<10}  {}",
+            todo.id,
+            todo.text,
+            todo.status,
+            todo.priority,
+            todo.owner,
+            todo.due_display()
+        );
+    }
+    println!();
+    Ok(())
+}
@@ -1,6 +1,30 @@
 pub mod add_todo;
+pub mod ai_triage;
+pub mod apply;
+pub mod archive;
+pub mod bulk_update;
+pub mod checklists;
+pub mod comments;
 pub mod delete_todo;
+pub mod dependencies;
+pub mod diff;
+pub mod escalate;
+pub mod explode;
+pub mod from_url;
+pub mod habits;
+pub mod maintenance;
+pub mod merge_todos;
+pub mod milestones;
 pub mod models;
+pub mod owners;
+pub mod planner;
 pub mod print;
+#[cfg(feature = "web")]
+pub mod publish;
+pub mod quick_add;
+pub mod report;
+pub mod search;
+pub mod stale;
+pub mod subtasks;
 pub mod update_todo;
-
+pub mod voice_memo;
@@ -1,6 +1,9 @@
-use clap::Parser;
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 
+use crate::dates;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Todo {
     pub id: usize,
@@ -8,12 +11,123 @@ pub struct Todo {
     pub topic: String,
     pub text: String,
     pub desc: String,
-    pub date_added: String,
+    #[serde(with = "dates::timestamp_serde")]
+    pub date_added: DateTime<Utc>,
     pub status: String,
     pub owner: String,
-    pub due: String,
+    #[serde(default, with = "dates::due_serde")]
+    pub due: Option<NaiveDate>,
+    // Time of day the todo is due, the date part matches `due` on the local clock
+    #[serde(default, with = "dates::optional_timestamp_serde")]
+    pub due_at: Option<DateTime<Utc>>,
     pub subtasks: Vec<Subtask>,
     pub notes: String,
+    #[serde(default)]
+    pub pinned: bool,
+    // Last status/notes/priority change, empty for todos created before it was tracked
+    #[serde(default, with = "dates::optional_timestamp_serde")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub archived: bool,
+    // Who a todo in Review is waiting on
+    #[serde(default)]
+    pub waiting_on: Option<String>,
+    // Where the todo can be done (home, office, errands), apart from what it is about
+    #[serde(default)]
+    pub context: Option<String>,
+    // Ticket, PR or page the todo tracks, a full URL or an id like PROJ-123
+    #[serde(default)]
+    pub external_url: Option<String>,
+}
+
+// The status a todo waits in until someone signs it off
+pub const REVIEW_STATUS: &str = "Review";
+
+// Done and Completed both close a todo or a subtask
+pub fn is_done_status(status: &str) -> bool {
+    status == "Done" || status == "Completed"
+}
+
+// Contexts are kept lowercase without the `@`, "none" or nothing clears it
+pub fn normalize_context(value: &str) -> Option<String> {
+    let context = value.trim().trim_start_matches('@').to_lowercase();
+    (!context.is_empty() && context != "none").then_some(context)
+}
+
+// Links are kept as typed, "none" or nothing clears it
+pub fn normalize_external_url(value: &str) -> Option<String> {
+    let url = value.trim();
+    (!url.is_empty() && !url.eq_ignore_ascii_case("none")).then(|| url.to_string())
+}
+
+impl Todo {
+    pub fn is_done(&self) -> bool {
+        is_done_status(&self.status)
+    }
+
+    pub fn created_display(&self) -> String {
+        dates::format_timestamp(&self.date_added)
+    }
+
+    pub fn due_display(&self) -> String {
+        dates::format_due_at(self.due, self.due_at)
+    }
+
+    // Earliest due date of the todo and its open subtasks, used for overdue checks
+    pub fn next_due(&self) -> Option<NaiveDate> {
+        self.subtasks
+            .iter()
+            .filter(|subtask| !subtask.is_done())
+            .filter_map(|subtask| subtask.due)
+            .chain(self.due)
+            .min()
+    }
+
+    // Days until the due date, negative once it has passed
+    pub fn days_left(&self, today: NaiveDate) -> Option<i64> {
+        self.due.map(|due| (due - today).num_days())
+    }
+
+    // Doable in `context`: todos without a context can be done anywhere
+    pub fn fits_context(&self, context: &str) -> bool {
+        self.context
+            .as_deref()
+            .is_none_or(|own| own.eq_ignore_ascii_case(context))
+    }
+
+    // Open and due today or earlier, or being worked on: what `voido today` shows
+    pub fn is_for_today(&self, today: NaiveDate) -> bool {
+        !self.is_done()
+            && (self.status == "Ongoing" || self.next_due().is_some_and(|due| due <= today))
+    }
+}
+
+// A pending todo with nothing else set, tests fill in the fields they look at:
+// `Todo { status: "Done".to_string(), ..Todo::sample("Ship") }`
+#[cfg(test)]
+impl Todo {
+    pub fn sample(text: &str) -> Self {
+        Self {
+            id: 0,
+            priority: "Low".to_string(),
+            topic: "Work".to_string(),
+            text: text.to_string(),
+            desc: String::new(),
+            date_added: dates::parse_timestamp("2025-01-01").unwrap(),
+            status: "Pending".to_string(),
+            owner: "Joe".to_string(),
+            due: None,
+            due_at: None,
+            subtasks: Vec::new(),
+            notes: String::new(),
+            pinned: false,
+            updated_at: None,
+            archived: false,
+            waiting_on: None,
+            context: None,
+            external_url: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +136,90 @@ pub struct Subtask {
     pub subtask_id: usize,
     pub text: String,
     pub status: String,
+    #[serde(default, with = "dates::due_serde")]
+    pub due: Option<NaiveDate>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    // Short remark, e.g. why a step was skipped
+    #[serde(default)]
+    pub note: String,
+}
+
+impl Subtask {
+    pub fn is_done(&self) -> bool {
+        is_done_status(&self.status)
+    }
+
+    // `@owner · due 01-03-25`, empty when neither is set
+    pub fn details(&self) -> String {
+        let owner = self.owner.as_ref().map(|owner| format!("@{}", owner));
+        let due = self
+            .due
+            .map(|due| format!("due {}", dates::format_date(due)));
+        owner
+            .into_iter()
+            .chain(due)
+            .collect::<Vec<String>>()
+            .join(" · ")
+    }
+}
+
+// A named goal todos can be linked to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Milestone {
+    pub id: i64,
+    pub name: String,
+    pub target: Option<NaiveDate>,
+}
+
+// A timestamped remark on a todo, the thread is shown oldest first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    pub id: i64,
+    pub todo_id: i64,
+    pub author: String,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Comment {
+    // Date and local time of the comment
+    pub fn when(&self) -> String {
+        format!(
+            "{} {}",
+            dates::format_timestamp(&self.created_at),
+            self.created_at
+                .with_timezone(&chrono::Local)
+                .format("%H:%M")
+        )
+    }
+}
+
+// Someone todos are assigned to. Todos store `name`, the display name and color are for showing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Owner {
+    pub id: i64,
+    pub name: String,
+    pub display_name: String,
+    pub color: String,
+}
+
+impl Owner {
+    pub fn label(&self) -> &str {
+        if self.display_name.trim().is_empty() {
+            &self.name
+        } else {
+            &self.display_name
+        }
+    }
+}
+
+// A saved list of subtasks that can be added to any todo
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checklist {
+    pub id: i64,
+    pub name: String,
+    pub items: Vec<String>,
 }
 
 #[derive(Debug, Parser)]
@@ -29,10 +227,29 @@ pub struct Subtask {
 #[command(version = "1.0")]
 #[command(about = "A powerful and intuitive command-line (CLI) todo application built with Rust, supercharged with AI capabilities.", long_about = None)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+
+    /// Use a [profile.<NAME>] from config.toml, with its own database (or set VOIDO_PROFILE)
+    #[arg(long, value_name = "NAME", global = true)]
+    pub profile: Option<String>,
+
+    /// Show dates and due times in this timezone ("local", "UTC", "+05:30"), over [DISPLAY] timezone
+    #[arg(long, value_name = "ZONE", global = true)]
+    pub tz: Option<String>,
+
+    /// Run the command on a copy of the database and print what would change, nothing is written
+    #[arg(long, global = true)]
+    pub dry_run: bool,
+
     /// List all todos in a terminal UI
     #[arg(short, long)]
     pub list: bool,
 
+    /// Open the terminal UI on a throwaway in-memory database filled with demo todos
+    #[arg(long)]
+    pub demo: bool,
+
     /// Export todos into an Excel file
     #[arg(short = 'E', long)]
     pub export: bool,
@@ -41,6 +258,22 @@ pub struct Cli {
     #[arg(short = 'a', long, value_name = "TEXT", num_args = 1.., value_delimiter = ' ')]
     pub add: Option<Vec<String>>,
 
+    /// Read todos from stdin, one per line, using the quick-add syntax (same as `--add -`)
+    #[arg(long)]
+    pub stdin: bool,
+
+    /// Transcribe a voice memo (m4a, mp3, wav...) and add the todos in it once confirmed
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["add", "stdin"])]
+    pub audio: Option<String>,
+
+    /// Add a todo named after the page at this link, with the link set; `-` reads links from stdin
+    #[arg(long, value_name = "LINK", conflicts_with_all = ["add", "stdin", "audio"])]
+    pub from_url: Option<String>,
+
+    /// Describe the page with a Gemini summary instead of its own description (requires --from-url)
+    #[arg(long, requires = "from_url")]
+    pub summarize: bool,
+
     /// A more detailed description for the todo.
     /// Ownder of the todo (requires --add)
     #[arg(short = 'w', long, value_name = "DESCRIPTION", num_args = 1.., value_delimiter = ' ', requires = "add")]
@@ -54,25 +287,45 @@ pub struct Cli {
     #[arg(short = 'p', long, value_name = "PRIORITY", requires = "add")]
     pub priority: Option<String>,
 
+    /// Where the todo can be done, e.g. home, office, errands (requires --add)
+    #[arg(long, value_name = "CONTEXT", requires = "add")]
+    pub context: Option<String>,
+
+    /// Ticket, PR or page the todo tracks, `none` clears it (with --add or --update-id)
+    #[arg(long, value_name = "URL")]
+    pub url: Option<String>,
+
+    /// Ask Gemini for a due date when --due isn't given, [GEMINI] suggest_due always does (requires --add)
+    #[arg(long, requires = "add")]
+    pub suggest_due: bool,
+
     /// Print all todos to the console
     #[arg(short = 'P', long)]
     pub print: bool,
 
-    /// Delete a todo by ID
-    #[arg(short = 'D', long = "delete", value_name = "ID")]
-    pub delete: Option<i32>,
+    /// Delete a todo by ID, pick it from a list when no ID is given
+    #[arg(short = 'D', long = "delete", value_name = "ID", num_args = 0..=1)]
+    pub delete: Option<Option<i32>>,
 
-    /// ID of the todo to update
-    #[arg(short = 'u', long, value_name = "ID")]
-    pub update_id: Option<i32>,
+    /// ID of the todo to update, pick it from a list when no ID is given
+    #[arg(short = 'u', long, value_name = "ID", num_args = 0..=1)]
+    pub update_id: Option<Option<i32>>,
 
     /// New status for the todo (requires --update-id)
     #[arg(long, value_name = "STATUS", requires = "update_id")]
     pub status: Option<String>,
 
-    /// Mark a todo as done by ID
-    #[arg(short = 'c', long = "done", value_name = "ID")]
-    pub done: Option<i32>,
+    /// Mark a todo as done by ID, pick it from a list when no ID is given
+    #[arg(short = 'c', long = "done", value_name = "ID", num_args = 0..=1)]
+    pub done: Option<Option<i32>>,
+
+    /// Pin a todo so it always stays at the top of the list
+    #[arg(long, value_name = "ID")]
+    pub pin: Option<i32>,
+
+    /// Unpin a previously pinned todo
+    #[arg(long, value_name = "ID")]
+    pub unpin: Option<i32>,
 
     /// Clear all todos
     #[arg(short = 'C', long)]
@@ -86,7 +339,7 @@ pub struct Cli {
     #[arg(short, long, value_name = "OWNER", requires = "add")]
     pub owner: Option<String>,
 
-    /// A due date for the task.
+    /// A due date for the task (e.g. 2025-06-01, tomorrow, "fri 09:30").
     #[arg(short = 'd', long, value_name = "DUE DATE", requires = "add")]
     pub due: Option<String>,
 
@@ -128,6 +381,709 @@ pub struct Cli {
     pub subtasks: Vec<(i32, String)>,
 }
 
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    /// Manage topics across all todos
+    Topic {
+        #[command(subcommand)]
+        action: TopicCommand,
+    },
+
+    /// Reassign todos from one owner to another
+    Assign {
+        /// Current owner (case-insensitive)
+        #[arg(long, value_name = "OWNER")]
+        from: String,
+
+        /// New owner
+        #[arg(long, value_name = "OWNER")]
+        to: String,
+
+        /// Only reassign todos in this topic
+        #[arg(long, value_name = "TOPIC")]
+        topic: Option<String>,
+    },
+
+    /// Find todos by what they are about with [SEARCH] semantic on, by their words otherwise
+    Search {
+        /// What to look for, e.g. "that thing about invoices"
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+
+        /// How many todos to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+
+        /// Match the words like the TUI search bar, even with [SEARCH] semantic on
+        #[arg(long)]
+        words: bool,
+    },
+
+    /// List todos that have not been touched for a while
+    Stale {
+        /// Number of days without a status, notes or priority change
+        #[arg(long, default_value_t = super::stale::DEFAULT_STALE_DAYS)]
+        days: i64,
+
+        /// Archive every stale todo
+        #[arg(long, conflicts_with = "reschedule")]
+        archive: bool,
+
+        /// Set a new due date on every stale todo (e.g. fri, tomorrow, 2025-06-01)
+        #[arg(long, value_name = "DATE")]
+        reschedule: Option<String>,
+    },
+
+    /// List archived todos or bring one back
+    Archive {
+        /// Restore the archived todo with this ID
+        #[arg(long, value_name = "ID")]
+        restore: Option<i32>,
+    },
+
+    /// Manage subtasks without opening the TUI
+    Subtask {
+        #[command(subcommand)]
+        action: SubtaskCommand,
+    },
+
+    /// Reusable lists of subtasks
+    Checklist {
+        #[command(subcommand)]
+        action: ChecklistCommand,
+    },
+
+    /// Todos that can't start before other todos are done
+    Depend {
+        #[command(subcommand)]
+        action: DependCommand,
+    },
+
+    /// Known owners with their display names and colors, listed when no action is given
+    Owners {
+        #[command(subcommand)]
+        action: Option<OwnerCommand>,
+    },
+
+    /// Group todos under milestones with a target date
+    Milestone {
+        #[command(subcommand)]
+        action: MilestoneCommand,
+    },
+
+    /// Progress reports, topic progress when no report is given
+    #[command(args_conflicts_with_subcommands = true)]
+    Report {
+        #[command(subcommand)]
+        action: Option<ReportCommand>,
+
+        /// Write the stats, topic progress and every todo to a self-contained HTML page
+        #[arg(long, value_name = "FILE")]
+        html: Option<String>,
+    },
+
+    /// Export todos to a file, optionally only the ones matching a search, status or topic
+    Export {
+        /// File format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Excel)]
+        format: ExportFormat,
+
+        /// Only todos matching this search, same matching as the TUI search bar
+        #[arg(long, value_name = "QUERY")]
+        filter: Option<String>,
+
+        /// Only todos with this status (case-insensitive)
+        #[arg(long, value_name = "STATUS")]
+        status: Option<String>,
+
+        /// Only todos in this topic (case-insensitive)
+        #[arg(long, value_name = "TOPIC")]
+        topic: Option<String>,
+
+        /// One sheet (Excel) or one file (other formats) per topic or owner
+        #[arg(long, value_enum, value_name = "FIELD")]
+        split_by: Option<SplitBy>,
+    },
+
+    /// Import todos from an Excel/JSON file, a Google Sheet, Microsoft To Do or Notion
+    Import {
+        /// Excel (.xlsx) or JSON file
+        #[arg(
+            required_unless_present_any = ["gsheet", "mstodo", "notion"],
+            conflicts_with_all = ["gsheet", "mstodo", "notion"]
+        )]
+        file: Option<String>,
+
+        /// Share or "Publish to web" link of a Google Sheet laid out like the Excel export
+        #[arg(long, value_name = "URL", conflicts_with_all = ["mstodo", "notion"])]
+        gsheet: Option<String>,
+
+        /// Add the tasks from Microsoft To Do, each list becomes a topic (keeps the current todos)
+        #[arg(long, conflicts_with = "notion")]
+        mstodo: bool,
+
+        /// CSV export of a Notion database, asks which column goes to which field (keeps the current todos)
+        #[arg(long, value_name = "CSV")]
+        notion: Option<String>,
+    },
+
+    /// Print every table as plain SQL, e.g. `voido dump > todos.sql`
+    Dump,
+
+    /// Replace the database with a dump written by `voido dump`
+    Load {
+        /// SQL file
+        file: String,
+    },
+
+    /// Show the todos added, removed and changed since a JSON export
+    Diff {
+        /// JSON export or backup to compare the database with
+        file: String,
+    },
+
+    /// POST the todo counts as JSON to the [PUBLISH] url of config.toml
+    Publish {
+        /// Post here instead of the configured url
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Keep running and post every MINUTES minutes
+        #[arg(long, value_name = "MINUTES", value_parser = clap::value_parser!(u64).range(1..))]
+        every: Option<u64>,
+    },
+
+    /// Run a read-only HTTP server with Prometheus metrics at /metrics
+    Serve {
+        /// Address to listen on, 0.0.0.0 for every network interface
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        #[arg(short, long, default_value_t = 7878)]
+        port: u16,
+
+        /// Also serve a read-only HTML board of the todos at /
+        #[arg(long)]
+        board: bool,
+
+        /// Also serve the due dates as a calendar to subscribe to at /calendar.ics (and /tasks.ics)
+        #[arg(long)]
+        calendar: bool,
+    },
+
+    /// Sync the todos with another service
+    Sync {
+        #[arg(value_enum)]
+        backend: SyncTarget,
+
+        /// Forget the stored sign-in instead of syncing
+        #[arg(long)]
+        logout: bool,
+
+        /// Compare every todo instead of the ones changed since the last sync
+        #[arg(long, conflicts_with = "logout")]
+        full: bool,
+
+        /// Store an access token for the git server, to push over HTTPS and create the repository
+        #[arg(long, conflicts_with_all = ["logout", "full"])]
+        token: bool,
+
+        // Run by the TUI to flush queued changes: never stops to ask for a sign-in
+        #[arg(long, hide = true)]
+        background: bool,
+    },
+
+    /// Keep running and sync each backend on its [SYNC.schedule] in config.toml
+    Daemon,
+
+    /// Add the todos, milestones and checklists of a YAML or JSON fixture
+    Seed {
+        /// Fixture file (.yaml, .yml or .json)
+        file: String,
+
+        /// Delete every todo, milestone and checklist first
+        #[arg(long)]
+        wipe: bool,
+
+        /// Don't ask before wiping
+        #[arg(short, long, requires = "wipe")]
+        yes: bool,
+    },
+
+    /// Run the adds, updates, completions and deletes of a YAML or JSON file in one go
+    Apply {
+        /// Operations file (.yaml, .yml or .json)
+        file: String,
+    },
+
+    /// Add the todos of another VoiDo database, skipping the ones already here
+    MergeDb {
+        /// Path to the other todos.db
+        file: String,
+    },
+
+    /// Export or import the settings, theme and key macros of config.toml
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+
+    /// Ask Gemini to work on your todos
+    Ai {
+        #[command(subcommand)]
+        action: AiCommand,
+    },
+
+    /// Database maintenance
+    Db {
+        #[command(subcommand)]
+        action: DbCommand,
+    },
+
+    /// List todos, `--plain` prints `ID<TAB>status<TAB>text` lines for fzf/rofi
+    List {
+        /// One todo per line without colors, open the TUI otherwise
+        #[arg(long)]
+        plain: bool,
+
+        /// Leave out Done todos
+        #[arg(long)]
+        open: bool,
+
+        /// Only the todos doable in this context, and the ones without a context
+        #[arg(long, value_name = "CONTEXT")]
+        context: Option<String>,
+    },
+
+    /// Open the TUI with only the todos due today or overdue, and the Ongoing ones
+    Today,
+
+    /// Open the TUI on the inbox, one captured todo at a time
+    Triage,
+
+    /// Print a one-page planner of the week, with a column per day and the backlog below
+    Planner {
+        /// The week this day falls in (today, nextweek, 2025-03-14...), this week when left out
+        #[arg(
+            long,
+            value_name = "DAY",
+            num_args = 0..=1,
+            default_missing_value = "today"
+        )]
+        week: Option<String>,
+
+        /// Write it to an HTML page to print (or save as PDF from the browser)
+        #[arg(long, value_name = "FILE")]
+        html: Option<String>,
+    },
+
+    /// Print every detail of a todo, pick it from a list when no ID is given
+    Show {
+        /// ID of the todo
+        id: Option<i32>,
+    },
+
+    /// Turn the bullet and checkbox lines of a todo's description and notes into subtasks
+    Explode {
+        /// ID of the todo, pick it from a list when omitted
+        id: Option<i32>,
+    },
+
+    /// Add a comment to a todo, or print its comments when no text is given
+    Comment {
+        /// ID of the todo
+        id: i32,
+
+        text: Option<String>,
+
+        /// Who wrote it, the default owner when left out
+        #[arg(long, value_name = "NAME")]
+        author: Option<String>,
+    },
+
+    /// Send a todo to Review, waiting on the owner who signs it off
+    Review {
+        /// ID of the todo
+        id: i32,
+
+        /// Who the review waits on
+        waiting_on: Option<String>,
+    },
+
+    /// Make a todo a habit that comes back every day or week, `off` makes it a plain todo
+    Habit {
+        /// ID of the todo
+        id: i32,
+
+        /// daily, weekly or off
+        every: String,
+    },
+
+    /// List the habits with their streaks and the periods they were done
+    Habits,
+
+    /// Capture a todo into the inbox as fast as possible, for binding to a global hotkey
+    Q {
+        /// The todo, quotes are optional
+        #[arg(required = true, num_args = 1..)]
+        text: Vec<String>,
+    },
+
+    /// Set where a todo can be done (home, office, errands...), `none` clears it
+    Context {
+        /// ID of the todo
+        id: i32,
+
+        context: String,
+    },
+
+    /// Merge a duplicate todo into another one
+    Merge {
+        /// ID of the todo to keep
+        keep: i32,
+
+        /// ID of the duplicate, its description, notes and subtasks move to the kept todo
+        other: i32,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ReportCommand {
+    /// Done/total and a progress bar for every topic
+    Topics,
+
+    /// Open todos per owner, split by priority
+    Owners {
+        /// Print a table or JSON
+        #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+    },
+
+    /// Open todos per day for everything due on or before a date
+    Burndown {
+        /// Last due date to include (YYYY-MM-DD, today, friday...)
+        #[arg(long, value_name = "DATE")]
+        due_by: String,
+
+        /// First day of the chart, defaults to the oldest todo in range
+        #[arg(long, value_name = "DATE")]
+        from: Option<String>,
+
+        /// Print the data as CSV instead of a chart
+        #[arg(long)]
+        csv: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Json,
+}
+
+// Where `voido sync` syncs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SyncTarget {
+    /// Commit and push the todos to a git repository on GitHub, GitLab, Gitea or any git server
+    #[value(alias = "git")]
+    Github,
+    /// Two-way sync of the mapped topics with Google Tasks lists
+    Google,
+}
+
+impl SyncTarget {
+    // The backend's key in the sync state and in [SYNC] of config.toml
+    #[cfg(feature = "sync")]
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Github => "github",
+            Self::Google => "google",
+        }
+    }
+}
+
+// The parts of config.toml `voido config export` and `import` take
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConfigPart {
+    /// Everything that isn't the theme or the key macros
+    Settings,
+    /// Theme, density, icons, topic colors and styling rules
+    Theme,
+    /// Key macros
+    Keys,
+}
+
+impl ConfigPart {
+    pub const ALL: [ConfigPart; 3] = [ConfigPart::Settings, ConfigPart::Theme, ConfigPart::Keys];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Settings => "settings",
+            Self::Theme => "theme",
+            Self::Keys => "keys",
+        }
+    }
+}
+
+// What a split export makes one sheet or file per
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SplitBy {
+    Topic,
+    Owner,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Excel,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TopicCommand {
+    /// Rename a topic on every todo that uses it
+    Rename {
+        /// Current topic name (case-insensitive)
+        from: String,
+
+        /// New topic name
+        to: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SubtaskCommand {
+    /// Move a subtask to another todo, keeping its status
+    Move {
+        /// ID of the subtask (shown in the details modal)
+        subtask_id: i32,
+
+        /// ID of the todo to move it to
+        todo_id: i32,
+    },
+
+    /// Mark a subtask as Done
+    Done {
+        /// ID of the todo the subtask belongs to
+        todo_id: i32,
+
+        /// Position in the list (1, 2...) or the subtask ID prefixed with # (#14)
+        subtask: String,
+    },
+
+    /// Set the due date or owner of a subtask, `none` clears them
+    Set {
+        /// ID of the todo the subtask belongs to
+        todo_id: i32,
+
+        /// Position in the list (1, 2...) or the subtask ID prefixed with # (#14)
+        subtask: String,
+
+        /// Due date (YYYY-MM-DD, friday, +3d...)
+        #[arg(long, value_name = "DATE")]
+        due: Option<String>,
+
+        /// Person responsible for the subtask
+        #[arg(long, value_name = "OWNER")]
+        owner: Option<String>,
+    },
+
+    /// Attach a short note to a subtask, an empty note removes it
+    Note {
+        /// ID of the todo the subtask belongs to
+        todo_id: i32,
+
+        /// Position in the list (1, 2...) or the subtask ID prefixed with # (#14)
+        subtask: String,
+
+        note: String,
+    },
+
+    /// Delete a subtask
+    Rm {
+        /// ID of the todo the subtask belongs to
+        todo_id: i32,
+
+        /// Position in the list (1, 2...) or the subtask ID prefixed with # (#14)
+        subtask: String,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ChecklistCommand {
+    /// Save the subtasks of a todo as a checklist
+    Save {
+        name: String,
+
+        /// ID of the todo whose subtasks make up the checklist
+        #[arg(long, value_name = "ID")]
+        from: i32,
+    },
+
+    /// Add the items of a checklist to a todo as Pending subtasks
+    Apply {
+        /// Checklist name (case-insensitive)
+        name: String,
+
+        /// ID of the todo to add the subtasks to
+        id: i32,
+    },
+
+    /// List every checklist and its items
+    List,
+
+    /// Delete a checklist
+    Remove { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DependCommand {
+    /// Mark a todo as blocked by another one
+    Add {
+        id: i32,
+
+        /// ID of the todo that has to be done first
+        #[arg(long, value_name = "ID")]
+        on: i32,
+    },
+
+    /// Remove a dependency
+    Remove {
+        id: i32,
+
+        #[arg(long, value_name = "ID")]
+        on: i32,
+    },
+
+    /// Draw what a todo is waiting on as a tree, pick the todo from a list when no ID is given
+    Graph { id: Option<i32> },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbCommand {
+    /// Rebuild the database file to reclaim the space of deleted rows
+    Vacuum,
+
+    /// Run SQLite's integrity check and look for subtasks and dependencies of deleted todos
+    Check {
+        /// Delete the orphaned rows that were found
+        #[arg(long)]
+        repair: bool,
+    },
+
+    /// Row count of every table and the size of the file
+    Stats,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Write config.toml (settings, theme and key macros) to one file to share or carry over
+    Export {
+        #[arg(default_value = "voido-settings.toml")]
+        file: String,
+        /// Only these parts
+        #[arg(long, value_delimiter = ',')]
+        only: Vec<ConfigPart>,
+        /// Keep the tokens and client secrets in the file
+        #[arg(long)]
+        secrets: bool,
+    },
+
+    /// Apply an exported file to config.toml, keeping a copy of the old one
+    Import {
+        file: String,
+        /// Only these parts
+        #[arg(long, value_delimiter = ',')]
+        only: Vec<ConfigPart>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AiCommand {
+    /// Put a short summary of a todo's notes on top of them, replacing the last one
+    Summarize {
+        /// ID of the todo, pick it from a list when omitted
+        id: Option<i32>,
+    },
+
+    /// Let Gemini propose a topic, priority and effort for the inbox todos, and review each one
+    Triage,
+
+    /// Suggest a due date for a todo text, printed as `YYYY-MM-DD  reason`
+    Due {
+        #[arg(required = true, num_args = 1..)]
+        text: Vec<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MilestoneCommand {
+    /// Create a milestone, or change the target date of an existing one
+    Add {
+        name: String,
+
+        /// Target date (YYYY-MM-DD, friday, +2w...)
+        #[arg(long, value_name = "DATE")]
+        target: Option<String>,
+    },
+
+    /// Link todos to a milestone
+    Link {
+        /// Milestone name (case-insensitive)
+        name: String,
+
+        #[arg(required = true, value_name = "ID")]
+        ids: Vec<i32>,
+    },
+
+    /// Remove todos from their milestone
+    Unlink {
+        #[arg(required = true, value_name = "ID")]
+        ids: Vec<i32>,
+    },
+
+    /// List every milestone with its progress
+    List,
+
+    /// Show the outstanding todos of a milestone
+    Show { name: String },
+
+    /// Delete a milestone, its todos are kept
+    Remove { name: String },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum OwnerCommand {
+    /// List the owners with their open todos
+    List,
+
+    /// Add an owner, or change the display name and color of an existing one
+    Add {
+        name: String,
+
+        /// Name shown in the table instead of `name`, "" to clear it
+        #[arg(long, value_name = "NAME")]
+        display: Option<String>,
+
+        /// Color of the owner in the table (#e07a5f, red, lightblue...), "" to clear it
+        #[arg(long)]
+        color: Option<String>,
+    },
+
+    /// Move every todo of one owner to another and remove the first one, for "joe" and "Joseph"
+    Merge {
+        /// Owner to remove (case-insensitive)
+        from: String,
+
+        /// Owner that gets the todos
+        into: String,
+    },
+
+    /// Remove an owner nothing is assigned to
+    Remove { name: String },
+}
+
 // Parses a string in the format `ID:TEXT` into `(i32, String)`
 fn parse_subtask(s: &str) -> Result<(i32, String), String> {
     let Some((id_part, text_part)) = s.split_once(':') else {
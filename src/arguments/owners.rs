@@ -0,0 +1,102 @@
+// Owners: the people todos are assigned to, with a display name and a color for the table
+use std::error::Error;
+
+use super::add_todo::capitalize;
+use crate::colors;
+use crate::database::DBtodo;
+
+// Create an owner or change how it is shown
+pub fn add_owner(
+    name: &str,
+    display: Option<&str>,
+    color: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    // Same casing as the owners of new todos
+    let name = capitalize(name.trim());
+    if name.is_empty() {
+        return Err("The owner name cannot be empty".into());
+    }
+    if let Some(color) = color.filter(|color| !color.trim().is_empty())
+        && colors::parse_color(color).is_none()
+    {
+        return Err(format!("\"{}\" is not a color, try #e07a5f or lightblue", color).into());
+    }
+
+    let db = DBtodo::new()?;
+    let owner = db.save_owner(&name, display.map(str::trim), color.map(str::trim))?;
+    println!("✅ Owner '{}' saved", owner.label());
+    Ok(())
+}
+
+// Move the todos of `from` over to `into` and forget `from`
+pub fn merge_owners(from: &str, into: &str) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    let Some(from) = db.find_owner(from)? else {
+        println!("❌ No owner named: {}", from);
+        return Ok(());
+    };
+    let Some(into) = db.find_owner(into)? else {
+        println!("❌ No owner named: {}", into);
+        return Ok(());
+    };
+    if from.id == into.id {
+        println!("❌ {} and {} are the same owner", from.name, into.name);
+        return Ok(());
+    }
+
+    let changes = db.merge_owners(&from, &into)?;
+    println!(
+        "✅ {} todo(s) and subtask(s) moved from '{}' to '{}'",
+        changes, from.name, into.name
+    );
+    Ok(())
+}
+
+pub fn remove_owner(name: &str) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let Some(owner) = db.find_owner(name)? else {
+        println!("❌ No owner named: {}", name);
+        return Ok(());
+    };
+    let usage = db.owner_usage(&owner.name)?;
+    if usage > 0 {
+        println!(
+            "❌ {} todo(s) and subtask(s) are assigned to '{}', move them with `voido owners merge {} <owner>`",
+            usage, owner.name, owner.name
+        );
+        return Ok(());
+    }
+
+    db.delete_owner(owner.id)?;
+    println!("✅ Owner '{}' removed", owner.name);
+    Ok(())
+}
+
+// PRINT EVERY OWNER WITH THE NUMBER OF OPEN TODOS
+pub fn list_owners() -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let owners = db.get_owners()?;
+    if owners.is_empty() {
+        println!("No owners yet, add one with `voido owners add <name>`");
+        return Ok(());
+    }
+    let todos = db.get_todos()?;
+
+    println!();
+    println!(
+        "  {:<16} {:<24} {:<10} {:>5}",
+        "Name", "Display name", "Color", "Open"
+    );
+    for owner in &owners {
+        let open = todos
+            .iter()
+            .filter(|todo| todo.owner.eq_ignore_ascii_case(&owner.name) && !todo.is_done())
+            .count();
+        println!(
+            "  {:<16} {:<24} {:<10} {:>5}",
+            owner.name, owner.display_name, owner.color, open
+        );
+    }
+    println!();
+    Ok(())
+}
@@ -0,0 +1,304 @@
+// WEEKLY PLANNER: one page with a column per day and the open todos due that day, the overdue
+// ones and a backlog of the open todos without a due date. Printed to the terminal, or written
+// as an HTML page to print on paper (or to PDF from the browser).
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use std::{error::Error, fs};
+
+use super::habits::Cadence;
+use super::models::Todo;
+use crate::database::DBtodo;
+use crate::dates;
+use crate::import_export::html::escape;
+use crate::text_width;
+use crate::ui::{truncate_text, wrap_text};
+
+#[derive(Debug)]
+pub struct Week<'a> {
+    pub days: Vec<(NaiveDate, Vec<&'a Todo>)>,
+    pub overdue: Vec<&'a Todo>,
+    pub backlog: Vec<&'a Todo>,
+}
+
+// The week `day` falls in, starting on `week_start`
+pub fn plan_week(todos: &[Todo], day: NaiveDate, week_start: Weekday) -> Week<'_> {
+    let start = Cadence::Weekly.period_start(day, week_start);
+    let open = todos.iter().filter(|todo| !todo.is_done());
+    Week {
+        days: (0..7)
+            .map(|offset| {
+                let date = start + Duration::days(offset);
+                (
+                    date,
+                    open.clone().filter(|todo| todo.due == Some(date)).collect(),
+                )
+            })
+            .collect(),
+        overdue: open
+            .clone()
+            .filter(|todo| todo.due.is_some_and(|due| due < start))
+            .collect(),
+        backlog: open.filter(|todo| todo.due.is_none()).collect(),
+    }
+}
+
+fn day_title(date: NaiveDate) -> String {
+    format!("{} {}", date.format("%a"), date.day())
+}
+
+fn pad(text: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        text,
+        " ".repeat(width.saturating_sub(text_width::width(text)))
+    )
+}
+
+// Todo lines of a day column: a box to tick, the text wrapped under it
+fn cell_lines(todos: &[&Todo], width: usize) -> Vec<String> {
+    todos
+        .iter()
+        .flat_map(|todo| {
+            let marker = if todo.priority.eq_ignore_ascii_case("high") {
+                "☐!"
+            } else {
+                "☐ "
+            };
+            wrap_text(&todo.text, width.saturating_sub(3).max(1))
+                .into_iter()
+                .enumerate()
+                .map(move |(index, line)| {
+                    format!("{}{}", if index == 0 { marker } else { "  " }, line)
+                })
+        })
+        .collect()
+}
+
+pub fn render_week(week: &Week, today: NaiveDate, width: usize) -> String {
+    let column = (width.saturating_sub(8) / 7).max(8);
+    let rule = |left: &str, middle: &str, right: &str| {
+        format!(
+            "{}{}{}\n",
+            left,
+            vec!["─".repeat(column); 7].join(middle),
+            right
+        )
+    };
+    let row = |cells: Vec<String>| {
+        format!(
+            "│{}│\n",
+            cells
+                .iter()
+                .map(|cell| pad(&truncate_text(cell, column), column))
+                .collect::<Vec<String>>()
+                .join("│")
+        )
+    };
+
+    let (first, last) = (week.days[0].0, week.days[6].0);
+    let mut out = format!(
+        "\n  Week of {} to {}\n",
+        dates::format_date(first),
+        dates::format_date(last)
+    );
+    out.push_str(&rule("┌", "┬", "┐"));
+    out.push_str(&row(week
+        .days
+        .iter()
+        .map(|(date, _)| {
+            let marker = if *date == today { "▸" } else { " " };
+            format!("{}{}", marker, day_title(*date))
+        })
+        .collect()));
+    out.push_str(&rule("├", "┼", "┤"));
+
+    let cells = week
+        .days
+        .iter()
+        .map(|(_, todos)| cell_lines(todos, column))
+        .collect::<Vec<Vec<String>>>();
+    // Room to write on the paper copy, even on empty days
+    let height = cells.iter().map(Vec::len).max().unwrap_or(0).max(4);
+    for line in 0..height {
+        out.push_str(&row(cells
+            .iter()
+            .map(|lines| lines.get(line).cloned().unwrap_or_default())
+            .collect()));
+    }
+    out.push_str(&rule("└", "┴", "┘"));
+
+    for (title, todos) in [("Overdue", &week.overdue), ("Backlog", &week.backlog)] {
+        if todos.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n  {} ({})\n", title, todos.len()));
+        for todo in todos {
+            let due = todo
+                .due
+                .map(|due| format!(", due {}", dates::format_date(due)))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "  ☐ #{} {} ({}{})\n",
+                todo.id,
+                truncate_text(&todo.text, width.saturating_sub(30).max(20)),
+                todo.topic,
+                due
+            ));
+        }
+    }
+    out
+}
+
+const STYLE: &str = r#"@page { size: A4 landscape; margin: 1cm; }
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 1rem; color: #222; }
+h1 { font-size: 1.3rem; margin: 0 0 0.75rem; }
+h2 { font-size: 1rem; margin: 1.25rem 0 0.4rem; }
+table { border-collapse: collapse; width: 100%; table-layout: fixed; }
+th, td { border: 1px solid #999; padding: 0.35rem; vertical-align: top; text-align: left; }
+th { background: #f0f0f0; }
+th.today { background: #dde8f5; }
+td { height: 12rem; font-size: 0.85rem; }
+.todo { margin-bottom: 0.35rem; }
+.high { font-weight: bold; }
+ul { list-style: none; padding: 0; columns: 2; font-size: 0.85rem; }
+li { margin-bottom: 0.2rem; }"#;
+
+fn html_todo(todo: &Todo) -> String {
+    format!(
+        "<div class=\"todo{}\">☐ {}</div>",
+        if todo.priority.eq_ignore_ascii_case("high") {
+            " high"
+        } else {
+            ""
+        },
+        escape(&todo.text)
+    )
+}
+
+pub fn render_week_html(week: &Week, today: NaiveDate) -> String {
+    let (first, last) = (week.days[0].0, week.days[6].0);
+    let title = format!(
+        "Week of {} to {}",
+        dates::format_date(first),
+        dates::format_date(last)
+    );
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>VoiDo planner, {}</title>\n",
+        escape(&title)
+    ));
+    html.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE));
+    html.push_str(&format!("<h1>{}</h1>\n<table>\n<tr>", escape(&title)));
+    for (date, _) in &week.days {
+        html.push_str(&format!(
+            "<th{}>{}</th>",
+            if *date == today {
+                " class=\"today\""
+            } else {
+                ""
+            },
+            day_title(*date)
+        ));
+    }
+    html.push_str("</tr>\n<tr>");
+    for (_, todos) in &week.days {
+        html.push_str(&format!(
+            "<td>{}</td>",
+            todos.iter().map(|todo| html_todo(todo)).collect::<String>()
+        ));
+    }
+    html.push_str("</tr>\n</table>\n");
+
+    for (title, todos) in [("Overdue", &week.overdue), ("Backlog", &week.backlog)] {
+        if todos.is_empty() {
+            continue;
+        }
+        html.push_str(&format!("<h2>{} ({})</h2>\n<ul>\n", title, todos.len()));
+        for todo in todos {
+            html.push_str(&format!(
+                "<li>☐ #{} {} <small>({})</small></li>\n",
+                todo.id,
+                escape(&todo.text),
+                escape(&todo.topic)
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+// PRINT THE PLANNER of the week `day` falls in (this week by default), or write it as HTML
+pub fn planner(day: Option<&str>, html: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let today = Local::now().date_naive();
+    let day = match day {
+        Some(value) => dates::parse_due(value.trim(), today)
+            .ok_or_else(|| format!("\"{}\" is not a date", value))?,
+        None => today,
+    };
+    let todos = DBtodo::new()?.get_todos()?;
+    let week = plan_week(&todos, day, dates::week_starts_on());
+
+    match html {
+        Some(path) if DBtodo::is_dry_run() => println!(
+            "🧪 Dry run: the planner would be written to {}, no file was written",
+            path
+        ),
+        Some(path) => {
+            fs::write(path, render_week_html(&week, today))?;
+            println!(
+                "🗓️ Planner written to {}, print it from the browser (or save it as PDF)",
+                path
+            );
+        }
+        None => {
+            let width = crossterm::terminal::size()
+                .map(|(width, _)| width as usize)
+                .unwrap_or(120);
+            print!("{}", render_week(&week, today, width));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::demo_todos;
+
+    #[test]
+    fn test_plan_week() {
+        // 2025-03-12 is a Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let mut todos = demo_todos();
+        for (index, todo) in todos.iter_mut().enumerate() {
+            todo.id = index + 1;
+            todo.due = None;
+        }
+        todos[0].due = Some(today);
+        todos[1].due = NaiveDate::from_ymd_opt(2025, 3, 16);
+        todos[2].due = NaiveDate::from_ymd_opt(2025, 3, 9);
+        // Next week is not on this page
+        todos[3].due = NaiveDate::from_ymd_opt(2025, 3, 17);
+
+        let week = plan_week(&todos, today, Weekday::Mon);
+        assert_eq!(
+            week.days[0].0,
+            NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
+        );
+        assert_eq!(week.days[2].1[0].id, 1);
+        assert_eq!(week.days[6].1[0].id, 2);
+        assert_eq!(week.overdue.len(), 1);
+        // The Done demo todo is left out
+        assert_eq!(
+            week.backlog.iter().map(|todo| todo.id).collect::<Vec<_>>(),
+            vec![5, 6, 8]
+        );
+
+        let page = render_week(&week, today, 100);
+        assert!(page.contains("▸Wed 12"));
+        assert!(page.contains("☐!Ship the"));
+        assert!(render_week_html(&week, today).contains("<th class=\"today\">Wed 12</th>"));
+    }
+}
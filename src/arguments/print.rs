@@ -1,4 +1,8 @@
+use std::error::Error;
+
+use super::models::{Todo, normalize_context};
 use crate::data;
+use crate::database::DBtodo;
 
 pub fn print_todos() {
     let todos = data::sample_todos();
@@ -6,15 +10,134 @@ pub fn print_todos() {
     println!("Todos: ,{:?} ", todos);
 
     for todo in todos {
+        if todo.pinned {
+            println!("📌 Pinned");
+        }
         println!("ID: {}", todo.id);
         println!("Priority: {}", todo.priority);
         println!("Topic: {}", todo.topic);
         println!("Text: {}", todo.text);
-        println!("Date Added: {}", todo.date_added);
+        println!("Date Added: {}", todo.created_display());
         println!("Status: {}", todo.status);
         println!("Owner: {}", todo.owner);
-        println!("Due Date: {}", todo.due);
+        println!("Due Date: {}", todo.due_display());
         println!("Subtasks: {:?} ", todo.subtasks);
         println!();
     }
 }
+
+// PRINT EVERY DETAIL OF ONE TODO
+pub fn show_todo(id: i32) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let Some(todo) = db
+        .get_todos()?
+        .into_iter()
+        .find(|todo| todo.id == id as usize)
+    else {
+        println!("❌ No todo found with id: {}", id);
+        return Ok(());
+    };
+
+    println!();
+    println!("  {}{}", if todo.pinned { "📌 " } else { "" }, todo.text);
+    println!();
+    println!("  ID:        {}", todo.id);
+    println!("  Status:    {}", todo.status);
+    if let Some(waiting_on) = &todo.waiting_on {
+        println!("  Waiting:   {}", waiting_on);
+    }
+    println!("  Priority:  {}", todo.priority);
+    println!("  Topic:     {}", todo.topic);
+    println!("  Owner:     {}", todo.owner);
+    if let Some(context) = &todo.context {
+        println!("  Context:   @{}", context);
+    }
+    if let Some(url) = &todo.external_url {
+        println!("  Link:      {}", url);
+    }
+    println!("  Due:       {}", todo.due_display());
+    println!("  Added:     {}", todo.created_display());
+    println!();
+    println!("  {}", todo.desc);
+    if !todo.subtasks.is_empty() {
+        println!();
+        for (position, subtask) in todo.subtasks.iter().enumerate() {
+            let details = subtask.details();
+            println!(
+                "  {:>2}. [{}] {}{}",
+                position + 1,
+                if subtask.is_done() { "x" } else { " " },
+                subtask.text,
+                if details.is_empty() {
+                    String::new()
+                } else {
+                    format!("  ({})", details)
+                }
+            );
+            if !subtask.note.is_empty() {
+                println!("        {}", subtask.note);
+            }
+        }
+    }
+    if !todo.notes.trim().is_empty() {
+        println!();
+        for line in todo.notes.lines() {
+            println!("  {}", line);
+        }
+    }
+    let comments = db.get_comments(Some(todo.id as i64))?;
+    if !comments.is_empty() {
+        println!();
+        println!("  💬 Comments");
+        for comment in &comments {
+            println!(
+                "  {} · {}: {}",
+                comment.author,
+                comment.when(),
+                comment.text
+            );
+        }
+    }
+    println!();
+    Ok(())
+}
+
+// `ID<TAB>status<TAB>text`, tabs and line breaks in the text become spaces
+pub fn plain_line(todo: &Todo) -> String {
+    let text = todo.text.replace(['\t', '\n', '\r'], " ");
+    format!("{}\t{}\t{}", todo.id, todo.status, text)
+}
+
+// PRINT ONE TODO PER LINE FOR fzf/rofi, returns how many were printed
+pub fn print_plain(open_only: bool, context: Option<&str>) -> Result<usize, Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let context = context.and_then(normalize_context);
+    let todos = db
+        .get_todos()?
+        .into_iter()
+        .filter(|todo| !open_only || !todo.is_done())
+        .filter(|todo| {
+            context
+                .as_deref()
+                .is_none_or(|context| todo.fits_context(context))
+        })
+        .collect::<Vec<Todo>>();
+    for todo in &todos {
+        println!("{}", plain_line(todo));
+    }
+    Ok(todos.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_line_keeps_three_columns() {
+        let todo = Todo {
+            id: 7,
+            ..Todo::sample("Ship\tthe\nrelease")
+        };
+        assert_eq!(plain_line(&todo), "7\tPending\tShip the release");
+    }
+}
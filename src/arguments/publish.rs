@@ -0,0 +1,65 @@
+// PUBLISH: POST the stats snapshot as JSON to the URL in [PUBLISH], once or every few minutes,
+// for teams feeding a shared status dashboard.
+use std::{error::Error, time::Duration};
+
+use chrono::Local;
+use reqwest::Client;
+
+use super::report::{self, StatsSnapshot};
+use crate::configs::AppConfigs;
+use crate::database::DBtodo;
+
+fn snapshot() -> Result<StatsSnapshot, Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    Ok(report::stats_snapshot(
+        &db.get_todos()?,
+        &db.status_history()?,
+        Local::now().date_naive(),
+    ))
+}
+
+async fn post_snapshot(client: &Client, url: &str, token: &str) -> Result<(), Box<dyn Error>> {
+    let snapshot = snapshot()?;
+    let mut request = client.post(url).json(&snapshot);
+    if !token.is_empty() {
+        request = request.bearer_auth(token);
+    }
+    request.send().await?.error_for_status()?;
+    println!(
+        "✅ Published {} todos ({} open, {} overdue) to {}",
+        snapshot.total, snapshot.open, snapshot.overdue, url
+    );
+    Ok(())
+}
+
+pub async fn publish(url: Option<String>, every: Option<u64>) -> Result<(), Box<dyn Error>> {
+    let configs = AppConfigs::new()?;
+    let url = url
+        .or(Some(configs.publish_url))
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .ok_or("Set url in the [PUBLISH] section of config.toml, or pass --url")?;
+    let token = configs.publish_token.trim().to_string();
+    if DBtodo::is_dry_run() {
+        let snapshot = snapshot()?;
+        println!(
+            "🧪 Dry run: {} todos ({} open, {} overdue) would be published to {}, nothing was sent",
+            snapshot.total, snapshot.open, snapshot.overdue, url
+        );
+        return Ok(());
+    }
+    let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
+
+    let Some(minutes) = every else {
+        return post_snapshot(&client, &url, &token).await;
+    };
+    println!("📤 Publishing every {} minute(s), Ctrl-C to stop", minutes);
+    let mut interval = tokio::time::interval(Duration::from_secs(minutes * 60));
+    loop {
+        interval.tick().await;
+        // A dashboard that is down for a while shouldn't stop the schedule
+        if let Err(e) = post_snapshot(&client, &url, &token).await {
+            println!("❌ Publishing to {} failed: {}", url, e);
+        }
+    }
+}
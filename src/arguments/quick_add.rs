@@ -0,0 +1,209 @@
+// Compact inline syntax shared by the TUI quick-add bar and batch adds:
+//   Fix login bug #work !high @joe due:fri@09:30 ctx:office
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use std::error::Error;
+use std::io::BufRead;
+
+use super::add_todo::build_todo;
+use super::models::{Todo, normalize_context};
+use crate::configs;
+use crate::database::DBtodo;
+use crate::dates::parse_due_at;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct QuickAdd {
+    pub text: String,
+    pub topic: Option<String>,
+    pub priority: Option<String>,
+    pub owner: Option<String>,
+    pub due: Option<NaiveDate>,
+    pub due_at: Option<DateTime<Utc>>,
+    pub context: Option<String>,
+}
+
+impl QuickAdd {
+    fn into_todo(self) -> Todo {
+        let mut todo = build_todo(
+            self.text,
+            self.topic,
+            self.priority,
+            self.owner,
+            self.due,
+            None,
+            Vec::new(),
+        );
+        todo.due_at = self.due_at;
+        todo.context = self.context;
+        todo
+    }
+}
+
+pub fn parse_quick_add(input: &str) -> QuickAdd {
+    parse_quick_add_on(input, Local::now().date_naive())
+}
+
+// Parse relative to a given day so due dates like `due:fri` are deterministic
+pub fn parse_quick_add_on(input: &str, today: NaiveDate) -> QuickAdd {
+    let mut parsed = QuickAdd::default();
+    let mut words = Vec::new();
+
+    for word in input.split_whitespace() {
+        if let Some(topic) = word.strip_prefix('#').filter(|t| !t.is_empty()) {
+            parsed.topic = Some(topic.to_string());
+        } else if let Some(priority) = word.strip_prefix('!').filter(|p| !p.is_empty()) {
+            parsed.priority = Some(parse_priority(priority));
+        } else if let Some(owner) = word.strip_prefix('@').filter(|o| !o.is_empty()) {
+            parsed.owner = Some(owner.to_string());
+        } else if let Some((due, due_at)) = word
+            .strip_prefix("due:")
+            .and_then(|due| parse_due_at(due, today))
+        {
+            parsed.due = Some(due);
+            parsed.due_at = due_at;
+        } else if let Some(context) = word.strip_prefix("ctx:").and_then(normalize_context) {
+            parsed.context = Some(context);
+        } else {
+            words.push(word);
+        }
+    }
+
+    parsed.text = words.join(" ");
+    parsed
+}
+
+// OWNER COMPLETION: `@jo` at the end of the input becomes `@Joe ` when a known owner starts
+// with it. Names with spaces can't be typed after `@`, they are left out.
+pub fn complete_owner(input: &str, owners: &[String]) -> Option<String> {
+    let word = input.rsplit(char::is_whitespace).next()?;
+    let prefix = word.strip_prefix('@')?.to_lowercase();
+    let owner = owners.iter().find(|owner| {
+        !owner.contains(char::is_whitespace) && owner.to_lowercase().starts_with(&prefix)
+    })?;
+    Some(format!("{}@{} ", &input[..input.len() - word.len()], owner))
+}
+
+fn parse_priority(value: &str) -> String {
+    match value.to_lowercase().as_str() {
+        "h" | "hi" | "high" | "1" => "High".to_string(),
+        "m" | "med" | "medium" | "2" => "Medium".to_string(),
+        "l" | "lo" | "low" | "3" => "Low".to_string(),
+        other => other.to_string(),
+    }
+}
+
+// Parse the compact syntax and store the todo, returning its new ID
+pub fn quick_add(input: &str) -> Result<i64, Box<dyn Error>> {
+    let parsed = parse_quick_add(input);
+    if parsed.text.is_empty() {
+        return Err("Todo text cannot be empty".into());
+    }
+
+    let db = DBtodo::new()?;
+    db.add_todo(&parsed.into_todo())
+}
+
+// QUICK CAPTURE: `voido q`, the text as it is in the inbox topic with the configured defaults.
+// Sorting it out is left for later, so nothing here asks or checks anything.
+pub fn capture(text: &str) -> Result<i64, Box<dyn Error>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Todo text cannot be empty".into());
+    }
+
+    let todo = build_todo(
+        text.to_string(),
+        Some(configs::defaults_config().inbox_topic.clone()),
+        None,
+        None,
+        None,
+        None,
+        Vec::new(),
+    );
+    DBtodo::capture(&todo)
+}
+
+// Add one todo per input line, all in a single transaction.
+// Returns how many todos were created and how many lines were skipped.
+pub fn batch_add(reader: impl BufRead) -> Result<(usize, usize), Box<dyn Error>> {
+    let mut todos = Vec::new();
+    let mut skipped = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let parsed = parse_quick_add(&line);
+        if parsed.text.is_empty() {
+            skipped += 1;
+            continue;
+        }
+
+        todos.push(parsed.into_todo());
+    }
+
+    let mut db = DBtodo::new()?;
+    let added = db.add_todos(&todos)?;
+    Ok((added, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_all_fields() {
+        // 2025-01-01 is a Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let parsed =
+            parse_quick_add_on("Fix login bug #work !high @joe due:fri ctx:@Office", today);
+        assert_eq!(
+            parsed,
+            QuickAdd {
+                text: "Fix login bug".to_string(),
+                topic: Some("work".to_string()),
+                priority: Some("High".to_string()),
+                owner: Some("joe".to_string()),
+                due: NaiveDate::from_ymd_opt(2025, 1, 3),
+                due_at: None,
+                context: Some("office".to_string()),
+            }
+        );
+
+        let parsed = parse_quick_add_on("Standup due:tomorrow@09:30", today);
+        assert_eq!(parsed.text, "Standup");
+        assert_eq!(parsed.due, NaiveDate::from_ymd_opt(2025, 1, 2));
+        assert_eq!(
+            parsed
+                .due_at
+                .map(|due_at| due_at.with_timezone(&Local).naive_local()),
+            NaiveDate::from_ymd_opt(2025, 1, 2).and_then(|date| date.and_hms_opt(9, 30, 0))
+        );
+    }
+
+    #[test]
+    fn test_plain_text_only() {
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let parsed = parse_quick_add_on("Buy milk", today);
+        assert_eq!(parsed.text, "Buy milk");
+        assert_eq!(parsed.topic, None);
+        assert_eq!(parsed.due, None);
+    }
+
+    #[test]
+    fn test_complete_owner() {
+        let owners = vec![
+            "Ana Lopez".to_string(),
+            "Anna".to_string(),
+            "Joe".to_string(),
+        ];
+        assert_eq!(
+            complete_owner("Fix login #work @jo", &owners),
+            Some("Fix login #work @Joe ".to_string())
+        );
+        assert_eq!(complete_owner("@an", &owners), Some("@Anna ".to_string()));
+        assert_eq!(complete_owner("Fix login @zed", &owners), None);
+        assert_eq!(complete_owner("Fix login ", &owners), None);
+    }
+}
@@ -0,0 +1,488 @@
+// Reports: progress summaries shared by `voido report` and the TUI stats panel
+use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
+use serde::Serialize;
+#[cfg(any(feature = "web", feature = "serve"))]
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use super::models::{Milestone, ReportFormat, Todo, is_done_status};
+use crate::database::{DBtodo, StatusChange};
+use crate::dates;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TopicProgress {
+    pub topic: String,
+    pub done: usize,
+    pub total: usize,
+}
+
+impl TopicProgress {
+    pub fn percent(&self) -> usize {
+        (self.done * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+// Done/total per topic, topics compared case-insensitively and sorted by name
+pub fn topic_progress(todos: &[Todo]) -> Vec<TopicProgress> {
+    let mut progress: Vec<TopicProgress> = Vec::new();
+    for todo in todos {
+        let index = match progress
+            .iter()
+            .position(|entry| entry.topic.eq_ignore_ascii_case(&todo.topic))
+        {
+            Some(index) => index,
+            None => {
+                progress.push(TopicProgress {
+                    topic: todo.topic.clone(),
+                    done: 0,
+                    total: 0,
+                });
+                progress.len() - 1
+            }
+        };
+        progress[index].total += 1;
+        if todo.is_done() {
+            progress[index].done += 1;
+        }
+    }
+    progress.sort_by_key(|entry| entry.topic.to_lowercase());
+    progress
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct MilestoneProgress {
+    pub name: String,
+    pub target: Option<NaiveDate>,
+    pub done: usize,
+    pub total: usize,
+}
+
+impl MilestoneProgress {
+    pub fn percent(&self) -> usize {
+        (self.done * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+// Done/total of the todos linked to every milestone, `links` are (todo id, milestone id)
+pub fn milestone_progress(
+    milestones: &[Milestone],
+    links: &[(i64, i64)],
+    todos: &[Todo],
+) -> Vec<MilestoneProgress> {
+    milestones
+        .iter()
+        .map(|milestone| {
+            let linked = todos
+                .iter()
+                .filter(|todo| links.contains(&(todo.id as i64, milestone.id)))
+                .collect::<Vec<&Todo>>();
+            MilestoneProgress {
+                name: milestone.name.clone(),
+                target: milestone.target,
+                done: linked.iter().filter(|todo| todo.is_done()).count(),
+                total: linked.len(),
+            }
+        })
+        .collect()
+}
+
+// Milestones with their progress, archived todos count too
+pub fn load_milestone_progress(db: &DBtodo) -> Result<Vec<MilestoneProgress>, Box<dyn Error>> {
+    let mut todos = db.get_todos()?;
+    todos.extend(db.get_archived_todos()?);
+    Ok(milestone_progress(
+        &db.get_milestones()?,
+        &db.milestone_links()?,
+        &todos,
+    ))
+}
+
+// Text progress bar like `████████░░░░`
+pub fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    let filled = (done * width).checked_div(total).unwrap_or(0);
+    format!("{}{}", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+// PRINT THE PROGRESS OF EVERY TOPIC
+pub fn report_topics() -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let progress = topic_progress(&db.get_todos()?);
+
+    if progress.is_empty() {
+        println!("No todos yet");
+        return Ok(());
+    }
+
+    println!();
+    for entry in &progress {
+        println!(
+            "  {:<20} {}  {:>3}/{:<3} {:>3}%",
+            entry.topic,
+            progress_bar(entry.done, entry.total, 24),
+            entry.done,
+            entry.total,
+            entry.percent()
+        );
+    }
+    println!();
+    Ok(())
+}
+
+// Open work of one owner. There are no effort estimates on todos, so open subtasks stand in
+// for the size of the work.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct OwnerWorkload {
+    pub owner: String,
+    pub high: usize,
+    pub medium: usize,
+    pub normal: usize,
+    pub low: usize,
+    pub open: usize,
+    pub open_subtasks: usize,
+}
+
+// Open todos per owner, owners compared case-insensitively, busiest first
+pub fn owner_workload(todos: &[Todo]) -> Vec<OwnerWorkload> {
+    let mut workload: Vec<OwnerWorkload> = Vec::new();
+    for todo in todos.iter().filter(|todo| !todo.is_done()) {
+        let index = match workload
+            .iter()
+            .position(|entry| entry.owner.eq_ignore_ascii_case(&todo.owner))
+        {
+            Some(index) => index,
+            None => {
+                workload.push(OwnerWorkload {
+                    owner: todo.owner.clone(),
+                    ..Default::default()
+                });
+                workload.len() - 1
+            }
+        };
+        let entry = &mut workload[index];
+        match todo.priority.to_lowercase().as_str() {
+            "high" => entry.high += 1,
+            "medium" => entry.medium += 1,
+            "normal" => entry.normal += 1,
+            "low" => entry.low += 1,
+            _ => {}
+        }
+        entry.open += 1;
+        entry.open_subtasks += todo
+            .subtasks
+            .iter()
+            .filter(|subtask| !subtask.is_done())
+            .count();
+    }
+    workload.sort_by(|a, b| {
+        b.open
+            .cmp(&a.open)
+            .then_with(|| a.owner.to_lowercase().cmp(&b.owner.to_lowercase()))
+    });
+    workload
+}
+
+// PRINT THE OPEN TODOS OF EVERY OWNER
+pub fn report_owners(format: ReportFormat) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let workload = owner_workload(&db.get_todos()?);
+
+    if format == ReportFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&workload)?);
+        return Ok(());
+    }
+
+    if workload.is_empty() {
+        println!("No open todos");
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "  {:<20} {:>5} {:>7} {:>7} {:>5} {:>6} {:>9}",
+        "Owner", "High", "Medium", "Normal", "Low", "Open", "Subtasks"
+    );
+    for entry in &workload {
+        println!(
+            "  {:<20} {:>5} {:>7} {:>7} {:>5} {:>6} {:>9}",
+            entry.owner,
+            entry.high,
+            entry.medium,
+            entry.normal,
+            entry.low,
+            entry.open,
+            entry.open_subtasks
+        );
+    }
+    println!();
+    Ok(())
+}
+
+fn local_date(timestamp: &DateTime<Utc>) -> NaiveDate {
+    timestamp.with_timezone(&Local).date_naive()
+}
+
+// STATS SNAPSHOT: the counts `voido publish` posts to dashboards and `/metrics` exposes.
+// Archived todos are left out, completions come from the status history.
+#[cfg(any(feature = "web", feature = "serve"))]
+#[derive(Debug, Serialize)]
+pub struct StatsSnapshot {
+    #[serde(with = "dates::timestamp_serde")]
+    pub generated_at: DateTime<Utc>,
+    pub total: usize,
+    pub open: usize,
+    pub overdue: usize,
+    pub completed_today: usize,
+    pub completed_total: usize,
+    pub by_status: BTreeMap<String, usize>,
+    pub open_by_priority: BTreeMap<String, usize>,
+    pub open_by_owner: BTreeMap<String, usize>,
+}
+
+#[cfg(any(feature = "web", feature = "serve"))]
+pub fn stats_snapshot(todos: &[Todo], history: &[StatusChange], today: NaiveDate) -> StatsSnapshot {
+    let open_todos = todos.iter().filter(|todo| !todo.is_done());
+    let mut by_status = BTreeMap::new();
+    for todo in todos {
+        *by_status.entry(todo.status.clone()).or_default() += 1;
+    }
+    let mut open_by_priority = BTreeMap::new();
+    for todo in open_todos.clone() {
+        *open_by_priority.entry(todo.priority.clone()).or_default() += 1;
+    }
+    let completions = history
+        .iter()
+        .filter(|(_, status, _)| is_done_status(status));
+
+    StatsSnapshot {
+        generated_at: Utc::now(),
+        total: todos.len(),
+        open: open_todos.clone().count(),
+        overdue: open_todos
+            .filter(|todo| todo.due.is_some_and(|due| due < today))
+            .count(),
+        completed_today: completions
+            .clone()
+            .filter(|(_, _, at)| local_date(at) == today)
+            .count(),
+        completed_total: completions.count(),
+        by_status,
+        open_by_priority,
+        open_by_owner: owner_workload(todos)
+            .into_iter()
+            .map(|entry| (entry.owner, entry.open))
+            .collect(),
+    }
+}
+
+// Open todos per day for everything due on or before `due_by`.
+// A todo counts as open on a day when it already existed and its last recorded status change
+// up to that day was not a completion. Todos without history use their current status, done
+// ones counting as completed on their last update.
+pub fn burndown(
+    todos: &[Todo],
+    history: &[StatusChange],
+    from: Option<NaiveDate>,
+    due_by: NaiveDate,
+    today: NaiveDate,
+) -> Vec<(NaiveDate, usize)> {
+    let scope: Vec<&Todo> = todos
+        .iter()
+        .filter(|todo| todo.due.is_some_and(|due| due <= due_by))
+        .collect();
+
+    let Some(start) = from.or_else(|| scope.iter().map(|todo| local_date(&todo.date_added)).min())
+    else {
+        return Vec::new();
+    };
+    let end = due_by.min(today);
+
+    let is_open = |todo: &Todo, day: NaiveDate| {
+        if local_date(&todo.date_added) > day {
+            return false;
+        }
+        let mut changes = history
+            .iter()
+            .filter(|(id, _, _)| *id == todo.id as i64)
+            .peekable();
+        if changes.peek().is_none() {
+            return match (todo.is_done(), todo.updated_at) {
+                (true, Some(updated_at)) => local_date(&updated_at) > day,
+                (done, _) => !done,
+            };
+        }
+        match changes.rfind(|(_, _, at)| local_date(at) <= day) {
+            Some((_, status, _)) => !is_done_status(status),
+            None => true,
+        }
+    };
+
+    let mut days = Vec::new();
+    let mut day = start;
+    while day <= end {
+        let open = scope.iter().filter(|todo| is_open(todo, day)).count();
+        days.push((day, open));
+        day += Duration::days(1);
+    }
+    days
+}
+
+// PRINT THE OPEN TODOS PER DAY UNTIL A DUE DATE, AS A BAR CHART OR CSV
+pub fn report_burndown(due_by: &str, from: Option<&str>, csv: bool) -> Result<(), Box<dyn Error>> {
+    let today = Local::now().date_naive();
+    let due_by =
+        dates::parse_due(due_by, today).ok_or(format!("`{}` is not a valid date", due_by))?;
+    let from = match from {
+        Some(from) => {
+            Some(dates::parse_due(from, today).ok_or(format!("`{}` is not a valid date", from))?)
+        }
+        None => None,
+    };
+
+    let db = DBtodo::new()?;
+    let mut todos = db.get_todos()?;
+    todos.extend(db.get_archived_todos()?);
+    let days = burndown(&todos, &db.status_history()?, from, due_by, today);
+
+    if csv {
+        println!("date,remaining");
+        for (day, open) in &days {
+            println!("{},{}", day.format("%Y-%m-%d"), open);
+        }
+        return Ok(());
+    }
+
+    if days.is_empty() {
+        println!("No todos due by {}", dates::format_date(due_by));
+        return Ok(());
+    }
+
+    let most = days.iter().map(|(_, open)| *open).max().unwrap_or(0);
+    println!();
+    println!("  Open todos due by {}", dates::format_date(due_by));
+    println!();
+    for (day, open) in &days {
+        println!(
+            "  {} │{} {}",
+            day.format("%Y-%m-%d"),
+            "█".repeat((open * 40).checked_div(most).unwrap_or(0)),
+            open
+        );
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(topic: &str, status: &str) -> Todo {
+        Todo {
+            id: 1,
+            priority: "Normal".to_string(),
+            topic: topic.to_string(),
+            status: status.to_string(),
+            ..Todo::sample("Test")
+        }
+    }
+
+    #[test]
+    fn test_milestone_progress() {
+        let milestones = [
+            Milestone {
+                id: 1,
+                name: "v1.0".to_string(),
+                target: None,
+            },
+            Milestone {
+                id: 2,
+                name: "v2.0".to_string(),
+                target: None,
+            },
+        ];
+        let mut second = todo("Work", "Pending");
+        second.id = 2;
+        let mut unlinked = todo("Work", "Pending");
+        unlinked.id = 3;
+        let todos = [todo("Work", "Done"), second, unlinked];
+
+        let progress = milestone_progress(&milestones, &[(1, 1), (2, 1)], &todos);
+        assert_eq!((progress[0].done, progress[0].total), (1, 2));
+        assert_eq!((progress[1].done, progress[1].total), (0, 0));
+    }
+
+    #[test]
+    fn test_owner_workload() {
+        let mut high = todo("Work", "Pending");
+        high.owner = "ana".to_string();
+        high.priority = "High".to_string();
+        let mut done = todo("Work", "Done");
+        done.owner = "Joe".to_string();
+        let mut ana = todo("Work", "Ongoing");
+        ana.owner = "Ana".to_string();
+
+        let workload = owner_workload(&[high, done, ana]);
+        assert_eq!(workload.len(), 1);
+        assert_eq!(
+            (workload[0].open, workload[0].high, workload[0].normal),
+            (2, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_burndown() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        let at = |d: u32| {
+            day(d)
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_local_timezone(Local)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        let mut first = todo("Work", "Done");
+        first.date_added = at(1);
+        first.due = Some(day(10));
+        let mut second = todo("Work", "Done");
+        second.id = 2;
+        second.date_added = at(2);
+        second.due = Some(day(10));
+        second.updated_at = Some(at(4));
+        let mut later = todo("Work", "Pending");
+        later.id = 3;
+        later.date_added = at(1);
+        later.due = Some(day(20));
+
+        let history = [(1, "Done".to_string(), at(3))];
+        let days = burndown(&[first, second, later], &history, None, day(10), day(5));
+        assert_eq!(
+            days,
+            vec![
+                (day(1), 1),
+                (day(2), 2),
+                (day(3), 1),
+                (day(4), 0),
+                (day(5), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_topic_progress() {
+        let todos = [
+            todo("Work", "Done"),
+            todo("work", "Pending"),
+            todo("Home", "Completed"),
+        ];
+        let progress = topic_progress(&todos);
+        assert_eq!(progress.len(), 2);
+        assert_eq!(
+            (progress[0].topic.as_str(), progress[0].percent()),
+            ("Home", 100)
+        );
+        assert_eq!((progress[1].done, progress[1].total), (1, 2));
+        assert_eq!(progress_bar(1, 2, 4), "██░░");
+        assert_eq!(progress_bar(0, 0, 2), "░░");
+    }
+}
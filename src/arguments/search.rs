@@ -0,0 +1,55 @@
+// `voido search`: semantic search when [SEARCH] semantic is on, the search bar's fuzzy matching
+// otherwise or when the embeddings can't be had
+use std::error::Error;
+
+use super::models::Todo;
+use crate::configs::SearchConfig;
+use crate::database::DBtodo;
+use crate::embeddings;
+use crate::search::fuzzy_ranked;
+
+fn print_found(todos: &[Todo], found: &[(usize, String)]) {
+    println!();
+    for (id, score) in found {
+        if let Some(todo) = todos.iter().find(|todo| todo.id == *id) {
+            println!(
+                "  {:>4}  {:<40}  {:<8}  {:<15}  {}",
+                todo.id, todo.text, todo.status, todo.topic, score
+            );
+        }
+    }
+    println!();
+}
+
+pub async fn search(query: &str, limit: usize, words: bool) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    let todos = db.get_todos()?;
+
+    if !words && SearchConfig::load().semantic {
+        match embeddings::semantic_search(&mut db, &todos, query, limit).await {
+            Ok(found) => {
+                println!("🧭 Todos about \"{}\":", query);
+                let found = found
+                    .into_iter()
+                    .map(|(id, score)| (id, format!("{:.0}%", score.max(0.0) * 100.0)))
+                    .collect::<Vec<_>>();
+                print_found(&todos, &found);
+                return Ok(());
+            }
+            Err(e) => eprintln!("⚠️ Semantic search failed, matching words instead: {}", e),
+        }
+    }
+
+    let found = fuzzy_ranked(&todos, query)
+        .into_iter()
+        .take(limit)
+        .map(|(index, _)| (todos[index].id, String::new()))
+        .collect::<Vec<_>>();
+    if found.is_empty() {
+        println!("🔍 No todo matches \"{}\"", query);
+        return Ok(());
+    }
+    println!("🔍 Todos matching \"{}\":", query);
+    print_found(&todos, &found);
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// Stale todos: open todos with no status, notes or priority change for a while
+use chrono::{Local, NaiveDate};
+use std::error::Error;
+
+use super::models::Todo;
+use crate::database::DBtodo;
+use crate::dates::{self, parse_due};
+
+pub const DEFAULT_STALE_DAYS: i64 = 30;
+
+// Local day of the last change, falls back to the creation date for older todos
+pub fn last_touched(todo: &Todo) -> NaiveDate {
+    todo.updated_at
+        .unwrap_or(todo.date_added)
+        .with_timezone(&Local)
+        .date_naive()
+}
+
+pub fn is_stale(todo: &Todo, days: i64, today: NaiveDate) -> bool {
+    if todo.is_done() {
+        return false;
+    }
+    (today - last_touched(todo)).num_days() >= days
+}
+
+// LIST STALE TODOS, optionally archiving or rescheduling all of them
+pub fn stale(days: i64, archive: bool, reschedule: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    let today = Local::now().date_naive();
+
+    let reschedule = match reschedule {
+        Some(date) => {
+            Some(parse_due(date, today).ok_or_else(|| format!("Invalid date: {}", date))?)
+        }
+        None => None,
+    };
+
+    let stale = db
+        .get_todos()?
+        .into_iter()
+        .filter(|todo| is_stale(todo, days, today))
+        .collect::<Vec<Todo>>();
+
+    if stale.is_empty() {
+        println!("✅ No todos untouched for {} days or more", days);
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "💤 {} todo(s) untouched for {} days or more:",
+        stale.len(),
+        days
+    );
+    println!();
+    for todo in &stale {
+        println!(
+            "  {:>4}  {:<40}  {:<8}  {:<15}  idle {} days",
+            todo.id,
+            todo.text,
+            todo.status,
+            todo.topic,
+            (today - last_touched(todo)).num_days()
+        );
+    }
+    println!();
+
+    let ids = stale
+        .iter()
+        .map(|todo| todo.id as i32)
+        .collect::<Vec<i32>>();
+    if archive {
+        let count = db.archive_todos(&ids)?;
+        println!("📦 {} todo(s) archived", count);
+    } else if let Some(due) = reschedule {
+        let count = db.reschedule_todos(&ids, due, None)?;
+        println!(
+            "📅 {} todo(s) rescheduled to {}",
+            count,
+            dates::format_date(due)
+        );
+    } else {
+        println!("Use --archive or --reschedule <DATE> to act on all of them.");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(status: &str, date_added: &str, updated_at: &str) -> Todo {
+        Todo {
+            id: 1,
+            date_added: dates::parse_timestamp(date_added).unwrap(),
+            status: status.to_string(),
+            updated_at: dates::parse_timestamp(updated_at),
+            ..Todo::sample("Test")
+        }
+    }
+
+    #[test]
+    fn test_is_stale_uses_last_update() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        assert!(is_stale(&todo("Pending", "01-01-25", ""), 30, today));
+        assert!(!is_stale(
+            &todo("Pending", "01-01-25", "2025-02-20 10:00:00"),
+            30,
+            today
+        ));
+    }
+
+    #[test]
+    fn test_done_todos_are_never_stale() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        assert!(!is_stale(&todo("Done", "01-01-25", ""), 30, today));
+    }
+}
@@ -0,0 +1,152 @@
+// Subtask commands: change subtasks from the command line
+use chrono::Local;
+use std::error::Error;
+
+use super::add_todo::capitalize;
+use super::models::Subtask;
+use crate::database::DBtodo;
+use crate::dates;
+
+// `2` is the second subtask in the list, `#14` the subtask with ID 14
+pub fn find_subtask<'a>(subtasks: &'a [Subtask], reference: &str) -> Option<&'a Subtask> {
+    let reference = reference.trim();
+    match reference.strip_prefix('#') {
+        Some(id) => {
+            let id = id.parse::<usize>().ok()?;
+            subtasks.iter().find(|subtask| subtask.subtask_id == id)
+        }
+        None => {
+            let position = reference.parse::<usize>().ok()?;
+            subtasks.get(position.checked_sub(1)?)
+        }
+    }
+}
+
+// Look up a subtask of a todo, printing why when it can't be found
+fn lookup_subtask(
+    db: &DBtodo,
+    todo_id: i32,
+    reference: &str,
+) -> Result<Option<Subtask>, Box<dyn Error>> {
+    let Some(todo) = db
+        .get_todos()?
+        .into_iter()
+        .find(|todo| todo.id == todo_id as usize)
+    else {
+        println!("❌ No todo found with id: {}", todo_id);
+        return Ok(None);
+    };
+
+    let subtask = find_subtask(&todo.subtasks, reference).cloned();
+    if subtask.is_none() {
+        println!("❌ Todo {} has no subtask {}", todo_id, reference);
+    }
+    Ok(subtask)
+}
+
+pub fn move_subtask(subtask_id: i32, todo_id: i32) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    match db.move_subtask(subtask_id, todo_id)? {
+        0 => println!("❌ No subtask {} or no todo {} found", subtask_id, todo_id),
+        _ => println!("✅ Subtask {} moved to todo {}", subtask_id, todo_id),
+    }
+    Ok(())
+}
+
+pub fn complete_subtask(todo_id: i32, reference: &str) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    if let Some(subtask) = lookup_subtask(&db, todo_id, reference)? {
+        db.change_subtask_status(todo_id, subtask.subtask_id as i32, "Done".to_string())?;
+        println!("✅ Subtask '{}' marked as Done", subtask.text);
+    }
+    Ok(())
+}
+
+// Change the due date and/or owner of a subtask, `none` clears a value
+pub fn set_subtask_details(
+    todo_id: i32,
+    reference: &str,
+    due: Option<&str>,
+    owner: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let is_none = |value: &str| value.trim().eq_ignore_ascii_case("none");
+    let db = DBtodo::new()?;
+    let Some(subtask) = lookup_subtask(&db, todo_id, reference)? else {
+        return Ok(());
+    };
+
+    let due = match due {
+        Some(value) if is_none(value) => None,
+        Some(value) => Some(
+            dates::parse_due(value, Local::now().date_naive())
+                .ok_or_else(|| format!("Invalid date: {}", value))?,
+        ),
+        None => subtask.due,
+    };
+    let owner = match owner {
+        Some(value) if is_none(value) => None,
+        Some(value) => Some(capitalize(value.trim())),
+        None => subtask.owner.clone(),
+    };
+
+    db.update_subtask_details(subtask.subtask_id as i32, due, owner.as_deref())?;
+    let updated = Subtask {
+        due,
+        owner,
+        ..subtask
+    };
+    match updated.details().as_str() {
+        "" => println!("✅ Subtask '{}' has no due date or owner", updated.text),
+        details => println!("✅ Subtask '{}': {}", updated.text, details),
+    }
+    Ok(())
+}
+
+pub fn set_subtask_note(todo_id: i32, reference: &str, note: &str) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    if let Some(subtask) = lookup_subtask(&db, todo_id, reference)? {
+        db.update_subtask_note(subtask.subtask_id as i32, note.trim())?;
+        match note.trim() {
+            "" => println!("✅ Note removed from '{}'", subtask.text),
+            _ => println!("✅ Note added to '{}'", subtask.text),
+        }
+    }
+    Ok(())
+}
+
+pub fn remove_subtask(todo_id: i32, reference: &str) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    if let Some(subtask) = lookup_subtask(&db, todo_id, reference)? {
+        db.delete_subtask(subtask.subtask_id as i32)?;
+        println!("✅ Subtask '{}' deleted", subtask.text);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subtask(subtask_id: usize, text: &str) -> Subtask {
+        Subtask {
+            todo_id: 1,
+            subtask_id,
+            text: text.to_string(),
+            status: "Pending".to_string(),
+            due: None,
+            owner: None,
+            note: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_subtask_by_position_or_id() {
+        let subtasks = [subtask(14, "Alpha"), subtask(20, "Beta")];
+        assert_eq!(find_subtask(&subtasks, "2").unwrap().text, "Beta");
+        assert_eq!(find_subtask(&subtasks, "#14").unwrap().text, "Alpha");
+        assert!(find_subtask(&subtasks, "0").is_none());
+        assert!(find_subtask(&subtasks, "3").is_none());
+        assert!(find_subtask(&subtasks, "#2").is_none());
+        assert!(find_subtask(&subtasks, "first").is_none());
+    }
+}
@@ -1,5 +1,7 @@
 use std::error::Error;
 
+use crate::arguments::models::{REVIEW_STATUS, normalize_external_url};
+use crate::configs::WorkflowConfig;
 use crate::database::DBtodo;
 
 pub fn update_todo(id: i32, status: String) -> Result<(), Box<dyn Error>> {
@@ -9,3 +11,38 @@ pub fn update_todo(id: i32, status: String) -> Result<(), Box<dyn Error>> {
 
     db.update_todo(id, status)
 }
+
+pub fn send_to_review(id: i32, waiting_on: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let waiting_on = waiting_on.map(str::trim).filter(|owner| !owner.is_empty());
+    if !db.set_status(id, REVIEW_STATUS, waiting_on, &WorkflowConfig::load())? {
+        println!("❌ No todo found with id: {}", id);
+        return Ok(());
+    }
+    match waiting_on {
+        Some(owner) => println!("🔍 Todo {} is in review, waiting on {}", id, owner),
+        None => println!("🔍 Todo {} is in review", id),
+    }
+    Ok(())
+}
+
+pub fn set_pinned(id: i32, pinned: bool) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+
+    db.set_pinned(id, pinned)
+}
+
+// SET THE LINK of an existing todo, `none` clears it
+pub fn set_external_url(id: i32, url: &str) -> Result<(), Box<dyn Error>> {
+    let url = normalize_external_url(url);
+    let db = DBtodo::new()?;
+    if !db.set_external_url(id, url.as_deref())? {
+        println!("❌ No todo found with id: {}", id);
+        return Ok(());
+    }
+    match url {
+        Some(url) => println!("🔗 Todo {} links to {}", id, url),
+        None => println!("🔗 Todo {} has no link", id),
+    }
+    Ok(())
+}
@@ -0,0 +1,253 @@
+// VOICE MEMOS: `voido --audio memo.m4a` transcribes the memo, Gemini turns the transcript into
+// quick-add lines (`Call the plumber #home !high due:2025-03-20`) and they are added once confirmed
+use chrono::Local;
+#[cfg(feature = "ai")]
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+
+use super::quick_add::{batch_add, parse_quick_add};
+use crate::ai;
+use crate::configs::{self, AudioConfig};
+use crate::database::DBtodo;
+use crate::triage::Triage;
+
+// What Gemini and Whisper both take, by file extension
+const AUDIO_TYPES: [(&str, &str); 8] = [
+    ("m4a", "audio/mp4"),
+    ("mp4", "audio/mp4"),
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("ogg", "audio/ogg"),
+    ("flac", "audio/flac"),
+    ("aac", "audio/aac"),
+    ("webm", "audio/webm"),
+];
+
+#[cfg(feature = "ai")]
+const BOUNDARY: &str = "voido-voice-memo-boundary";
+
+#[cfg(feature = "ai")]
+#[derive(Deserialize)]
+struct Transcription {
+    text: String,
+}
+
+pub fn mime_type(path: &Path) -> Result<&'static str, Box<dyn Error>> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    AUDIO_TYPES
+        .iter()
+        .find(|(known, _)| *known == extension)
+        .map(|(_, mime)| *mime)
+        .ok_or_else(|| {
+            format!(
+                "{} is not a voice memo VoiDo can send, use one of: {}",
+                path.display(),
+                AUDIO_TYPES.map(|(extension, _)| extension).join(", ")
+            )
+            .into()
+        })
+}
+
+// The multipart/form-data body of a Whisper-compatible transcription request
+#[cfg(feature = "ai")]
+pub fn whisper_form(model: &str, file_name: &str, mime: &str, audio: &[u8]) -> Vec<u8> {
+    let mut body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"model\"\r\n\r\n{}\r\n\
+--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: {}\r\n\r\n",
+        model,
+        file_name.replace('"', ""),
+        mime,
+        b = BOUNDARY
+    )
+    .into_bytes();
+    body.extend_from_slice(audio);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+    body
+}
+
+#[cfg(feature = "ai")]
+async fn whisper(
+    config: &AudioConfig,
+    file_name: &str,
+    mime: &str,
+    audio: &[u8],
+) -> Result<String, Box<dyn Error>> {
+    super::diff::offline(&config.url)?;
+    let mut request = reqwest::Client::new()
+        .post(&config.url)
+        .header(
+            "Content-Type",
+            format!("multipart/form-data; boundary={}", BOUNDARY),
+        )
+        .body(whisper_form(&config.model, file_name, mime, audio));
+    if !config.token.is_empty() {
+        request = request.bearer_auth(&config.token);
+    }
+    let transcription = request
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<Transcription>()
+        .await?;
+    Ok(transcription.text)
+}
+
+#[cfg(not(feature = "ai"))]
+async fn whisper(
+    _config: &AudioConfig,
+    _file_name: &str,
+    _mime: &str,
+    _audio: &[u8],
+) -> Result<String, Box<dyn Error>> {
+    Err(crate::features::missing("ai"))
+}
+
+// The quick-add lines of Gemini's answer, without list markers, headings or empty todos
+pub fn todo_lines(answer: &str) -> Vec<String> {
+    answer
+        .lines()
+        .map(|line| {
+            let line = line.trim().trim_matches('`');
+            let line = line
+                .strip_prefix("- ")
+                .or(line.strip_prefix("* "))
+                .unwrap_or(line);
+            match line.split_once(". ") {
+                Some((number, rest)) if number.chars().all(|c| c.is_ascii_digit()) => rest,
+                _ => line,
+            }
+            .trim()
+            .to_string()
+        })
+        .filter(|line| !line.ends_with(':') && !parse_quick_add(line).text.is_empty())
+        .collect()
+}
+
+async fn extract_todos(db: &DBtodo, transcript: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let todos = db.get_todos()?;
+    let topics = Triage::new(&todos, &configs::defaults_config().inbox_topic).topics;
+    let today = Local::now().date_naive();
+    let prompt = format!(
+        "Turn this voice memo into todos. Write one todo per line and nothing else, in this syntax:
+Call the plumber about the leak #home !high @Ana due:2025-03-20
+#topic (prefer one of: {}), !priority (high, medium or low), @owner and due:YYYY-MM-DD are \
+optional, leave out what the memo doesn't say. Today is {}.
+
+MEMO:
+{}",
+        if topics.is_empty() {
+            "none yet".to_string()
+        } else {
+            topics.join(", ")
+        },
+        today.format("%A %Y-%m-%d"),
+        transcript
+    );
+    let lines = ai::generate(db, prompt)
+        .await?
+        .map(|answer| todo_lines(&answer))
+        .unwrap_or_default();
+    if lines.is_empty() {
+        return Err("Gemini found no todo in the memo".into());
+    }
+    Ok(lines)
+}
+
+pub async fn add_from_audio(file: &str) -> Result<(), Box<dyn Error>> {
+    let path = Path::new(file);
+    let mime = mime_type(path)?;
+    let audio = fs::read(path).map_err(|e| format!("Could not read {}: {}", file, e))?;
+    let db = DBtodo::new()?;
+    let config = AudioConfig::load();
+
+    println!("🎙️ Transcribing {}...", file);
+    let transcript = match config.provider.as_str() {
+        "whisper" => {
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("memo");
+            whisper(&config, file_name, mime, &audio).await?
+        }
+        _ => ai::transcribe(&db, &audio, mime)
+            .await?
+            .ok_or("Gemini sent back no transcript")?,
+    };
+    let transcript = transcript.trim();
+    if transcript.is_empty() {
+        return Err("Nothing was said in the memo".into());
+    }
+    println!("   \"{}\"", transcript);
+
+    // Without fields the transcript is still a todo
+    let lines = match extract_todos(&db, transcript).await {
+        Ok(lines) => lines,
+        Err(e) => {
+            eprintln!("⚠️ No todos picked out of the memo: {}", e);
+            vec![
+                transcript
+                    .split_whitespace()
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+            ]
+        }
+    };
+    println!("🤖 Todos in the memo:");
+    for line in &lines {
+        println!("   • {}", line);
+    }
+    if !io::stdin().is_terminal() {
+        println!("   Not added, there is no terminal to confirm them on");
+        return Ok(());
+    }
+    print!("   Add them? [Y/n] ");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    if !matches!(answer.trim().to_lowercase().as_str(), "" | "y" | "yes") {
+        println!("   Nothing was added");
+        return Ok(());
+    }
+
+    let (added, _) = batch_add(lines.join("\n").as_bytes())?;
+    println!("✅ {} todo(s) added", added);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memo_requests_and_answers() {
+        assert_eq!(mime_type(Path::new("memo.M4A")).unwrap(), "audio/mp4");
+        assert!(mime_type(Path::new("notes.txt")).is_err());
+
+        let answer = "Here are the todos:\n\n- Call the plumber #home !high\n\
+            2. Send the invoice @Ana due:2025-03-20\n`#work`\n";
+        assert_eq!(
+            todo_lines(answer),
+            vec![
+                "Call the plumber #home !high",
+                "Send the invoice @Ana due:2025-03-20"
+            ]
+        );
+    }
+
+    #[cfg(feature = "ai")]
+    #[test]
+    fn test_whisper_form() {
+        let form =
+            String::from_utf8(whisper_form("whisper-1", "memo.m4a", "audio/mp4", b"RIFF")).unwrap();
+        assert!(form.contains("name=\"model\"\r\n\r\nwhisper-1\r\n"));
+        assert!(form.contains("filename=\"memo.m4a\"\r\nContent-Type: audio/mp4\r\n\r\nRIFF\r\n"));
+        assert!(form.ends_with("--voido-voice-memo-boundary--\r\n"));
+    }
+}
@@ -0,0 +1,27 @@
+// BROWSER: hand a URL to the system, which opens it in the default browser (or mail client)
+use std::error::Error;
+use std::process::{Command, Stdio};
+
+pub fn open(url: &str) -> Result<(), Box<dyn Error>> {
+    if !url.contains("://") && !url.starts_with("mailto:") {
+        return Err(format!("'{}' is not a full URL", url).into());
+    }
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    // Its output would draw over the TUI
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Could not open {}: {}", url, e))?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+#[cfg(feature = "clipboard")]
+use arboard::Clipboard;
+use std::error::Error;
+
+use crate::arguments::models::Todo;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    Markdown,
+    Plain,
+}
+
+// Format a single todo (with its subtasks) for pasting elsewhere
+pub fn format_todo(todo: &Todo, format: CopyFormat) -> String {
+    let mut out = String::new();
+
+    match format {
+        CopyFormat::Markdown => {
+            out.push_str(&format!(
+                "- [{}] **{}** (Priority: {}, Topic: {}, Status: {}, Due: {}, Owner: {})\n",
+                if todo.is_done() { "x" } else { " " },
+                todo.text,
+                todo.priority,
+                todo.topic,
+                todo.status,
+                todo.due_display(),
+                todo.owner
+            ));
+            if !todo.desc.is_empty() {
+                out.push_str(&format!("  > {}\n", todo.desc));
+            }
+            for subtask in &todo.subtasks {
+                out.push_str(&format!(
+                    "  - [{}] {}\n",
+                    if subtask.is_done() { "x" } else { " " },
+                    subtask.text
+                ));
+            }
+        }
+        CopyFormat::Plain => {
+            out.push_str(&format!(
+                "#{} {} [{}] - {} | {} | Due: {} | {}\n",
+                todo.id,
+                todo.text,
+                todo.status,
+                todo.priority,
+                todo.topic,
+                todo.due_display(),
+                todo.owner
+            ));
+            if !todo.desc.is_empty() {
+                out.push_str(&format!("   {}\n", todo.desc));
+            }
+            for subtask in &todo.subtasks {
+                out.push_str(&format!("   • {} ({})\n", subtask.text, subtask.status));
+            }
+        }
+    }
+
+    out
+}
+
+// Format a list of todos, one block per todo
+pub fn format_todos(todos: &[&Todo], format: CopyFormat) -> String {
+    todos
+        .iter()
+        .map(|todo| format_todo(todo, format))
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+#[cfg(feature = "clipboard")]
+pub fn copy_to_clipboard(text: &str) -> Result<(), Box<dyn Error>> {
+    let mut clipboard = Clipboard::new()?;
+    clipboard.set_text(text.to_string())?;
+    Ok(())
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn copy_to_clipboard(_text: &str) -> Result<(), Box<dyn Error>> {
+    Err(crate::features::missing("clipboard"))
+}
+
+// Text currently on the system clipboard
+#[cfg(feature = "clipboard")]
+pub fn paste_from_clipboard() -> Result<String, Box<dyn Error>> {
+    let mut clipboard = Clipboard::new()?;
+    Ok(clipboard.get_text()?)
+}
+
+#[cfg(not(feature = "clipboard"))]
+pub fn paste_from_clipboard() -> Result<String, Box<dyn Error>> {
+    Err(crate::features::missing("clipboard"))
+}
@@ -1,5 +1,110 @@
+use ratatui::style::Color;
+
+use crate::configs;
+
 pub enum AppColors {
     Primary,
     Secondary,
     Tertiary,
 }
+
+// UI THEMES, picked with `theme` in the [DISPLAY] config section
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub background: Color,
+    pub accent: Color,
+    pub border: Color,
+    pub text_primary: Color,
+    pub text_secondary: Color,
+    pub highlight: Color,
+    pub selection: Color,
+}
+
+impl Theme {
+    pub const NAMES: [&'static str; 3] = ["voido", "ocean", "forest"];
+
+    pub fn by_name(name: &str) -> Theme {
+        match name {
+            "ocean" => Theme {
+                background: Color::Rgb(15, 22, 32),
+                accent: Color::Rgb(80, 160, 220),
+                border: Color::Rgb(120, 170, 210),
+                text_primary: Color::Rgb(220, 232, 240),
+                text_secondary: Color::Rgb(170, 195, 215),
+                highlight: Color::Rgb(30, 45, 60),
+                selection: Color::Rgb(50, 100, 150),
+            },
+            "forest" => Theme {
+                background: Color::Rgb(18, 26, 20),
+                accent: Color::Rgb(110, 190, 120),
+                border: Color::Rgb(150, 200, 150),
+                text_primary: Color::Rgb(225, 238, 225),
+                text_secondary: Color::Rgb(180, 205, 180),
+                highlight: Color::Rgb(35, 55, 40),
+                selection: Color::Rgb(70, 120, 80),
+            },
+            // The original purple palette
+            _ => Theme {
+                background: Color::Rgb(25, 15, 30),
+                accent: Color::Rgb(150, 80, 220),
+                border: Color::Rgb(180, 140, 220),
+                text_primary: Color::Rgb(230, 220, 240),
+                text_secondary: Color::Rgb(200, 180, 220),
+                highlight: Color::Rgb(50, 30, 60),
+                selection: Color::Rgb(120, 80, 190),
+            },
+        }
+    }
+
+    pub fn current() -> Theme {
+        Theme::by_name(&configs::display_config().theme)
+    }
+}
+
+// TOPIC COLORS: readable on all the themes, handed out to topics by name
+const TOPIC_PALETTE: [Color; 8] = [
+    Color::Rgb(224, 122, 95),
+    Color::Rgb(129, 178, 154),
+    Color::Rgb(242, 204, 143),
+    Color::Rgb(110, 170, 230),
+    Color::Rgb(214, 140, 200),
+    Color::Rgb(160, 210, 110),
+    Color::Rgb(240, 150, 110),
+    Color::Rgb(150, 150, 230),
+];
+
+// "#e07a5f", "red", "lightblue"...
+pub fn parse_color(value: &str) -> Option<Color> {
+    value.trim().parse::<Color>().ok()
+}
+
+// The color set in [DISPLAY.topic_colors], otherwise one picked from the name so the
+// same topic always gets the same color
+pub fn topic_color(topic: &str) -> Color {
+    let topic = topic.trim().to_lowercase();
+    let configured = configs::display_config()
+        .topic_colors
+        .iter()
+        .find(|(name, _)| *name == topic)
+        .and_then(|(_, color)| parse_color(color));
+    configured.unwrap_or_else(|| TOPIC_PALETTE[topic_hash(&topic) as usize % TOPIC_PALETTE.len()])
+}
+
+// FNV-1a, unlike the std hasher it gives the same result on every build
+fn topic_hash(topic: &str) -> u64 {
+    topic.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topic_color_is_stable_and_case_insensitive() {
+        assert_eq!(topic_color("Work"), topic_color(" work"));
+        assert_eq!(parse_color("#e07a5f"), Some(Color::Rgb(224, 122, 95)));
+        assert_eq!(parse_color("not a color"), None);
+    }
+}
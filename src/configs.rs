@@ -1,14 +1,39 @@
 use anyhow::{Context, Result};
 use directories::BaseDirs;
+use serde::Deserialize;
+
+use crate::database::DBtodo;
+use crate::dates;
+use crate::icons::IconSet;
+use crate::macros::KeyMacro;
+use crate::styling::StyleRule;
+use crate::ui::{ColumnLayout, RowNumbers, Stat, parse_alignment};
 use std::path::PathBuf;
+use std::sync::{LazyLock, OnceLock, RwLock, RwLockReadGuard};
+use std::time::SystemTime;
 
 #[derive(Debug)]
 pub struct AppConfigs {
-    pub model: String,
+    #[cfg(feature = "sync")]
     pub repo_name: String,
+    // Any git server to sync with instead of github.com, and which one it is when the host
+    // doesn't say
+    #[cfg(feature = "sync")]
+    pub git_remote: String,
+    #[cfg(feature = "sync")]
+    pub git_forge: String,
+    // Azure app registration used to sign in to Microsoft To Do
+    #[cfg(feature = "sync")]
+    pub ms_client_id: String,
+    // Where `voido publish` posts the stats snapshot, with an optional bearer token
+    #[cfg(feature = "web")]
+    pub publish_url: String,
+    #[cfg(feature = "web")]
+    pub publish_token: String,
 }
 
 impl AppConfigs {
+    #[cfg(feature = "web")]
     pub fn new() -> Result<Self> {
         let config_file = Self::get_config_path()?;
 
@@ -17,22 +42,35 @@ impl AppConfigs {
             Self::create_default_config()?;
         }
 
-        let config_content = std::fs::read_to_string(&config_file)
-            .with_context(|| format!("Failed to read config at {:?}", config_file))?;
+        Ok(Self::from_toml(&Self::read_config_value()?))
+    }
 
-        let config: toml::Value =
-            toml::from_str(&config_content).context("Failed to parse config file")?;
-
-        Ok(Self {
-            model: config["GEMINI"]["model"]
-                .as_str()
-                .context("Missing or invalid model in config")?
-                .to_string(),
-            repo_name: config["GITHUB"]["repo_name"]
-                .as_str()
-                .context("Missing or invalid github_repo in config")?
-                .to_string(),
-        })
+    // Missing sections fall back to the defaults instead of failing
+    #[cfg(feature = "web")]
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let text = |section: &str, key: &str, default: &str| {
+            config
+                .get(section)
+                .and_then(|section| section.get(key))
+                .and_then(|value| value.as_str())
+                .unwrap_or(default)
+                .to_string()
+        };
+
+        Self {
+            #[cfg(feature = "sync")]
+            repo_name: text("GITHUB", "repo_name", "voido_sync"),
+            #[cfg(feature = "sync")]
+            git_remote: text("GITHUB", "remote", ""),
+            #[cfg(feature = "sync")]
+            git_forge: text("GITHUB", "forge", ""),
+            #[cfg(feature = "sync")]
+            ms_client_id: text("MICROSOFT", "client_id", ""),
+            #[cfg(feature = "web")]
+            publish_url: text("PUBLISH", "url", ""),
+            #[cfg(feature = "web")]
+            publish_token: text("PUBLISH", "token", ""),
+        }
     }
 
     pub fn get_config_path() -> Result<PathBuf> {
@@ -43,6 +81,11 @@ impl AppConfigs {
 
     pub fn create_default_config() -> Result<()> {
         let config_file = Self::get_config_path()?;
+
+        // Never overwrite the user's own settings, and write nothing in a dry run
+        if config_file.exists() || DBtodo::is_dry_run() {
+            return Ok(());
+        }
         let config_dir = config_file
             .parent()
             .context("Invalid config directory path")?;
@@ -55,16 +98,192 @@ impl AppConfigs {
         }
 
         // Write default config
-        let default_config = r#"
+        let default_config = r##"
 [GEMINI]
-model = "gemini-pro"
+# Which model answers, e.g. "gemini-2.0-flash" (fast) or "gemini-2.5-pro" (slower, more careful)
+model = "gemini-2.0-flash"
+# 0.0 (always the likeliest answer) to 2.0 (most varied), left to the model when commented out
+# temperature = 0.7
+# Longest answer in tokens, left to the model when commented out
+# max_tokens = 1024
+# What Gemini blocks: "none", "few", "some" or "most", the model's default when empty
+safety = ""
+# Ask Gemini for a due date when a todo is added without one, judging by the text and how many
+# todos are already due each day. You confirm it before it is set
+suggest_due = false
 
+# `voido --audio memo.m4a`: "gemini" transcribes with the [GEMINI] model, "whisper" posts the memo
+# to an OpenAI-compatible /audio/transcriptions endpoint with the model and token below
+[AUDIO]
+provider = "gemini"
+url = "https://api.openai.com/v1/audio/transcriptions"
+model = "whisper-1"
+token = ""
+
+# Git sync: github.com/<git user.name>/<repo_name>, or any remote, e.g.
+# "git@gitlab.com:me/voido.git" or "https://git.example.com/me/voido.git"
 [GITHUB]
 repo_name = "voido_sync"
+remote = ""
+# "github", "gitlab" or "gitea" (Forgejo, Codeberg), guessed from the remote's host when empty
+forge = ""
+
+# Microsoft To Do import: the Application (client) ID of an Azure app registration
+# with "Allow public client flows" on and the Tasks.Read permission
+[MICROSOFT]
+client_id = ""
+
+# Google Tasks sync: the client ID and secret of an OAuth client of type "TVs and Limited Input
+# devices" in a Google Cloud project with the Tasks API enabled
+[GOOGLE_TASKS]
+client_id = ""
+client_secret = ""
+
+# Which topic syncs with which Google Tasks list, topics left out stay on this machine
+[GOOGLE_TASKS.lists]
+# Work = "My Tasks"
+
+[SYNC]
+# `voido daemon` waits up to this many seconds more before each scheduled sync
+jitter = 60
+
+# Which topics each sync backend (github, google) gets, the ones left out get every topic
+[SYNC.topics]
+# github = ["Personal", "Home"]
+
+# When `voido daemon` syncs each backend: an interval ("90s", "30m", "2h", "1d") or a cron
+# expression ("minute hour day month weekday")
+[SYNC.schedule]
+# github = "30m"
+# google = "*/15 8-18 * * 1-5"
+
+# `voido publish` posts the todo counts as JSON here, token is sent as a Bearer token if set
+[PUBLISH]
+url = ""
+token = ""
+
+[DISPLAY]
+# "eu" (31/12/2025), "us" (12/31/2025), "iso" (2025-12-31) or a strftime pattern like "%d %b %Y"
+date_format = "%d-%m-%y"
+# "monday" or "sunday"
+week_starts_on = "monday"
+# "voido", "ocean" or "forest"
+theme = "voido"
+# "comfortable" (long todos wrap onto a second line) or "compact" (one line each, no spacing)
+density = "comfortable"
+# Table columns to hide: "id", "priority", "topic", "subtasks", "created", "due", "status", "owner"
+hidden_columns = []
+# Priority and status as glyphs in one narrow column: "off", "emoji" or "nerd" (needs a Nerd Font)
+icons = "off"
+# Days left until the due date in a DAYS column, negative once overdue
+countdown = false
+# Timezone due times and dates are shown in: "local", "UTC" or an offset like "+05:30"
+timezone = "local"
+# Row numbers in front of the table: "off", "absolute" or "relative" (how far each row is from the
+# selected one, for count-prefixed moves)
+row_numbers = "off"
+# Counters on the stats bar, in order: "total", "open", "done", "ongoing", "pending", "planned",
+# "review", "overdue", "due_today", "high_priority", "pinned"
+stats = ["total", "done", "ongoing", "pending"]
+
+# Topics get a color of their own, set one here to override it ("#e07a5f", "red", "lightblue"...)
+[DISPLAY.topic_colors]
+# Work = "#e07a5f"
+
+# Width and alignment of the table columns: width (fixed), min and max in cells, and align
+# ("left", "center" or "right")
+[DISPLAY.columns]
+# id = { align = "right" }
+# status = { align = "center", width = 12 }
+
+# Used when a new todo doesn't set these
+[DEFAULTS]
+default_owner = "You"
+default_topic = "General"
+default_priority = "normal"
+# Due date in days from today, leave out for no due date
+# default_due_offset = 7
+# Topic of the todos captured with `voido q`
+inbox_topic = "Inbox"
+
+# Color whole rows: "<field> <op> <value> => <style>", later rules win
+# Fields: id, priority, topic, todo, status, owner, due, created
+# Operators: ==, !=, <, <=, >, >=, contains. Dates can be today, today+3, fri, 2025-12-31
+[STYLING]
+rules = [
+    # "due < today => fg red bold",
+    # 'owner == "me" => bg darkgray',
+]
+
+# One key runs several actions on the selected todo: status, priority, owner, topic, pin, unpin
+# Keys the TUI already uses keep their meaning, the digits are free
+[MACROS]
+# 1 = ["priority High", "status Ongoing", "pin"]
+# 2 = ["status Done", "unpin"]
+
+# Archive todos automatically on startup
+[ARCHIVE]
+enabled = false
+# Topics that are never auto-archived
+skip_topics = []
+
+[[ARCHIVE.rules]]
+status = "Done"
+days = 14
+
+# Raise the priority of open todos as their due date gets close
+[ESCALATION]
+enabled = false
+
+[[ESCALATION.rules]]
+within_days = 7
+priority = "Medium"
+
+[[ESCALATION.rules]]
+within_days = 2
+priority = "High"
+
+# Only allow these status changes, e.g. nothing goes Done without a Review first.
+# Review todos can wait on an owner: `voido review 12 Ana`
+[WORKFLOW]
+enabled = false
 
+[WORKFLOW.transitions]
+Pending = ["Planned", "Ongoing"]
+Planned = ["Pending", "Ongoing"]
+Ongoing = ["Pending", "Review"]
+Review = ["Ongoing", "Done"]
+Done = ["Pending"]
 
+[NOTES]
+# Ticking a `- [ ]` checkbox of the notes (X in the details) also ticks the subtask of the same
+# text, adding it when the todo doesn't have one
+checkbox_subtasks = false
+# Where links and images point, after their text: "inline" (`[docs](→ https://...)`) or "hidden"
+urls = "inline"
 
-"#;
+# Semantic search: `voido search` finds todos by what they are about ("that thing about invoices")
+# instead of the words typed. "gemini" embeds them with the key set by -k, "ollama" with a local
+# Ollama server at `url`. The model is text-embedding-004 or nomic-embed-text when empty
+[SEARCH]
+semantic = false
+provider = "gemini"
+model = ""
+url = "http://localhost:11434"
+
+# Profiles, picked with `voido --profile work` or VOIDO_PROFILE=work. Each one has its own
+# database (profiles/<name>/todos.db here unless db_path is set) and can override the
+# theme, the default owner and the git sync repository
+# [profile.work]
+# db_path = "~/work/voido.db"
+# theme = "ocean"
+# default_owner = "Ana"
+# repo_name = "voido_work"
+# remote = "git@gitlab.example.com:team/voido.git"
+
+
+
+"##;
 
         std::fs::write(&config_file, default_config.trim())
             .with_context(|| format!("Failed to write config to {:?}", config_file))?;
@@ -72,23 +291,2325 @@ repo_name = "voido_sync"
         Ok(())
     }
 
-    pub fn read_configs_from_file() -> Result<AppConfigs> {
+    // Parsed config file, for sections that are optional
+    pub fn read_config_value() -> Result<toml::Value> {
         let config_file = Self::get_config_path()?;
+        // The first read writes config.toml, every setting in it to edit from there
+        let _ = Self::create_default_config();
         let config_content = std::fs::read_to_string(&config_file)
             .with_context(|| format!("Failed to read config at {:?}", config_file))?;
 
-        let config: toml::Value =
-            toml::from_str(&config_content).context("Failed to parse config file")?;
-
-        Ok(AppConfigs {
-            model: config["GEMINI"]["model"]
-                .as_str()
-                .context("Missing or invalid model in config")?
-                .to_string(),
-            repo_name: config["GITHUB"]["repo_name"]
-                .as_str()
-                .context("Missing or invalid github_repo in config")?
-                .to_string(),
+        let mut config = toml::from_str(&config_content).context("Failed to parse config file")?;
+        if let Some(profile) = active_profile() {
+            apply_profile(&mut config, profile);
+        }
+        Ok(config)
+    }
+
+    #[cfg(feature = "sync")]
+    pub fn read_configs_from_file() -> Result<AppConfigs> {
+        Ok(Self::from_toml(&Self::read_config_value()?))
+    }
+}
+
+// CONFIG PROFILES: a [profile.<name>] section overrides a few settings and keeps its own
+// database, picked with `--profile <name>` or the VOIDO_PROFILE environment variable
+pub const PROFILE_ENV: &str = "VOIDO_PROFILE";
+
+// Keys a profile can set and the setting each one overrides, db_path has no section
+const PROFILE_KEYS: [(&str, Option<(&str, &str)>); 5] = [
+    ("db_path", None),
+    ("theme", Some(("DISPLAY", "theme"))),
+    ("default_owner", Some(("DEFAULTS", "default_owner"))),
+    ("repo_name", Some(("GITHUB", "repo_name"))),
+    ("remote", Some(("GITHUB", "remote"))),
+];
+
+static PROFILE: OnceLock<Option<String>> = OnceLock::new();
+
+// Pick the profile from the command line, before any setting is read
+pub fn set_profile(name: Option<String>) {
+    let name = name.or_else(|| std::env::var(PROFILE_ENV).ok());
+    let _ = PROFILE.set(name.filter(|name| !name.trim().is_empty()));
+}
+
+static TIMEZONE_OVERRIDE: OnceLock<dates::Timezone> = OnceLock::new();
+
+// `--tz` shows this run in another timezone without touching the config
+pub fn set_timezone_override(value: &str) -> Result<(), String> {
+    let timezone = dates::Timezone::parse(value).ok_or_else(|| {
+        format!(
+            "\"{}\" is not a timezone, use \"local\", \"UTC\" or an offset like \"+05:30\"",
+            value
+        )
+    })?;
+    let _ = TIMEZONE_OVERRIDE.set(timezone);
+    Ok(())
+}
+
+pub fn active_profile() -> Option<&'static str> {
+    PROFILE
+        .get_or_init(|| {
+            std::env::var(PROFILE_ENV)
+                .ok()
+                .filter(|name| !name.trim().is_empty())
+        })
+        .as_deref()
+}
+
+// Copy the profile's values over the sections they override, false when there is no such profile
+fn apply_profile(config: &mut toml::Value, name: &str) -> bool {
+    let Some(profile) = config
+        .get("profile")
+        .and_then(|profiles| profiles.get(name))
+        .and_then(|profile| profile.as_table())
+        .cloned()
+    else {
+        return false;
+    };
+    let Some(root) = config.as_table_mut() else {
+        return false;
+    };
+
+    for (key, target) in PROFILE_KEYS {
+        let (Some((section, field)), Some(value)) = (target, profile.get(key)) else {
+            continue;
+        };
+        let section = root
+            .entry(section)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if let Some(section) = section.as_table_mut() {
+            section.insert(field.to_string(), value.clone());
+        }
+    }
+    true
+}
+
+// A typo in --profile must not quietly open a new, empty database
+pub fn check_profile() -> Result<()> {
+    let Some(profile) = active_profile() else {
+        return Ok(());
+    };
+    let config_file = AppConfigs::get_config_path()?;
+    let mut config = std::fs::read_to_string(&config_file)
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+        .unwrap_or(toml::Value::Table(toml::Table::new()));
+    if !apply_profile(&mut config, profile) {
+        anyhow::bail!("There is no [profile.{}] in {:?}", profile, config_file);
+    }
+    Ok(())
+}
+
+// Where the profile keeps its files: the config folder, or profiles/<name> inside it
+pub fn data_dir() -> Result<PathBuf> {
+    let config_file = AppConfigs::get_config_path()?;
+    let config_dir = config_file
+        .parent()
+        .context("Invalid config directory path")?;
+    Ok(match active_profile() {
+        Some(profile) => config_dir.join("profiles").join(profile),
+        None => config_dir.to_path_buf(),
+    })
+}
+
+// The database of the active profile, its db_path when it sets one
+pub fn db_path() -> Result<PathBuf> {
+    let db_path = active_profile().and_then(|profile| {
+        let config = std::fs::read_to_string(AppConfigs::get_config_path().ok()?).ok()?;
+        let config = toml::from_str::<toml::Value>(&config).ok()?;
+        let path = config
+            .get("profile")?
+            .get(profile)?
+            .get("db_path")?
+            .as_str()?
+            .trim()
+            .to_string();
+        (!path.is_empty()).then_some(path)
+    });
+
+    match db_path {
+        Some(path) => match path.strip_prefix("~/") {
+            Some(rest) => Ok(BaseDirs::new()
+                .context("Could not determine home directory")?
+                .home_dir()
+                .join(rest)),
+            None => Ok(PathBuf::from(path)),
+        },
+        None => Ok(data_dir()?.join("todos.db")),
+    }
+}
+
+// AUTO-ARCHIVE RULES, from the [ARCHIVE] section
+#[derive(Debug, Default)]
+pub struct ArchiveConfig {
+    pub enabled: bool,
+    pub rules: Vec<ArchiveRule>,
+    pub skip_topics: Vec<String>,
+}
+
+// Archive todos with `status` that have not changed for `days`
+#[derive(Debug)]
+pub struct ArchiveRule {
+    pub status: String,
+    pub days: i64,
+}
+
+impl ArchiveConfig {
+    // A missing or broken section simply means no rules
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let Some(section) = config.get("ARCHIVE") else {
+            return Self::default();
+        };
+
+        let rules = section
+            .get("rules")
+            .and_then(|rules| rules.as_array())
+            .map(|rules| {
+                rules
+                    .iter()
+                    .filter_map(|rule| {
+                        Some(ArchiveRule {
+                            status: rule.get("status")?.as_str()?.to_string(),
+                            days: rule.get("days")?.as_integer()?,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let skip_topics = section
+            .get("skip_topics")
+            .and_then(|topics| topics.as_array())
+            .map(|topics| {
+                topics
+                    .iter()
+                    .filter_map(|topic| topic.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            enabled: section
+                .get("enabled")
+                .and_then(|enabled| enabled.as_bool())
+                .unwrap_or(false),
+            rules,
+            skip_topics,
+        }
+    }
+}
+
+// GOOGLE TASKS SYNC, from the [GOOGLE_TASKS] section
+#[cfg(feature = "sync")]
+#[derive(Debug, Default)]
+pub struct GoogleTasksConfig {
+    pub client_id: String,
+    pub client_secret: String,
+    // (topic, Google Tasks list title) pairs, in the order of the config
+    pub lists: Vec<(String, String)>,
+}
+
+#[cfg(feature = "sync")]
+impl GoogleTasksConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let Some(section) = config.get("GOOGLE_TASKS") else {
+            return Self::default();
+        };
+        let text = |key: &str| {
+            section
+                .get(key)
+                .and_then(|value| value.as_str())
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        };
+
+        let lists = section
+            .get("lists")
+            .and_then(|lists| lists.as_table())
+            .map(|lists| {
+                lists
+                    .iter()
+                    .filter_map(|(topic, list)| {
+                        Some((topic.trim().to_string(), list.as_str()?.trim().to_string()))
+                    })
+                    .filter(|(topic, list)| !topic.is_empty() && !list.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            client_id: text("client_id"),
+            client_secret: text("client_secret"),
+            lists,
+        }
+    }
+
+    // The list a topic syncs with
+    pub fn list_for(&self, topic: &str) -> Option<&str> {
+        self.lists
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(topic))
+            .map(|(_, list)| list.as_str())
+    }
+}
+
+#[cfg(feature = "sync")]
+const DEFAULT_SYNC_JITTER: u64 = 60;
+
+// SELECTIVE SYNC from [SYNC.topics], and when `voido daemon` syncs from [SYNC.schedule]
+#[cfg(feature = "sync")]
+#[derive(Debug, Default)]
+pub struct SyncConfig {
+    // (backend, topics) pairs, a backend that isn't here gets every topic
+    pub topics: Vec<(String, Vec<String>)>,
+    // (backend, interval or cron expression) pairs
+    pub schedule: Vec<(String, String)>,
+    // Up to this many seconds are added to each scheduled run
+    pub jitter: u64,
+}
+
+#[cfg(feature = "sync")]
+impl SyncConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let topics = config
+            .get("SYNC")
+            .and_then(|section| section.get("topics"))
+            .and_then(|topics| topics.as_table())
+            .map(|topics| {
+                topics
+                    .iter()
+                    .map(|(backend, list)| {
+                        (
+                            backend.trim().to_lowercase(),
+                            list.as_array()
+                                .map(|list| {
+                                    list.iter()
+                                        .filter_map(|topic| topic.as_str())
+                                        .map(|topic| topic.trim().to_string())
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let schedule = config
+            .get("SYNC")
+            .and_then(|section| section.get("schedule"))
+            .and_then(|schedule| schedule.as_table())
+            .map(|schedule| {
+                schedule
+                    .iter()
+                    .filter_map(|(backend, when)| {
+                        Some((
+                            backend.trim().to_lowercase(),
+                            when.as_str()?.trim().to_string(),
+                        ))
+                    })
+                    .filter(|(_, when)| !when.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let jitter = config
+            .get("SYNC")
+            .and_then(|section| section.get("jitter"))
+            .and_then(|jitter| jitter.as_integer())
+            .map_or(DEFAULT_SYNC_JITTER, |jitter| jitter.max(0) as u64);
+        Self {
+            topics,
+            schedule,
+            jitter,
+        }
+    }
+
+    // Whether the todos of a topic go to a backend
+    pub fn includes(&self, backend: &str, topic: &str) -> bool {
+        self.topics
+            .iter()
+            .find(|(known, _)| known == backend)
+            .is_none_or(|(_, topics)| topics.iter().any(|known| known.eq_ignore_ascii_case(topic)))
+    }
+}
+
+// PRIORITY ESCALATION RULES, from the [ESCALATION] section
+#[derive(Debug, Default)]
+pub struct EscalationConfig {
+    pub enabled: bool,
+    pub rules: Vec<EscalationRule>,
+}
+
+// Raise open todos to `priority` once they are due within `within_days` (or overdue)
+#[derive(Debug)]
+pub struct EscalationRule {
+    pub within_days: i64,
+    pub priority: String,
+}
+
+impl EscalationConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let Some(section) = config.get("ESCALATION") else {
+            return Self::default();
+        };
+
+        let rules = section
+            .get("rules")
+            .and_then(|rules| rules.as_array())
+            .map(|rules| {
+                rules
+                    .iter()
+                    .filter_map(|rule| {
+                        Some(EscalationRule {
+                            within_days: rule.get("within_days")?.as_integer()?,
+                            priority: rule.get("priority")?.as_str()?.to_string(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            enabled: section
+                .get("enabled")
+                .and_then(|enabled| enabled.as_bool())
+                .unwrap_or(false),
+            rules,
+        }
+    }
+}
+
+// STATUS WORKFLOW, from the [WORKFLOW] section
+#[derive(Debug, Default, Clone)]
+pub struct WorkflowConfig {
+    pub enabled: bool,
+    // Status → the statuses it can move to
+    pub transitions: Vec<(String, Vec<String>)>,
+}
+
+impl WorkflowConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let Some(section) = config.get("WORKFLOW") else {
+            return Self::default();
+        };
+
+        let transitions = section
+            .get("transitions")
+            .and_then(|transitions| transitions.as_table())
+            .map(|transitions| {
+                transitions
+                    .iter()
+                    .filter_map(|(from, to)| {
+                        let to = to
+                            .as_array()?
+                            .iter()
+                            .filter_map(|status| status.as_str().map(str::to_string))
+                            .collect();
+                        Some((from.clone(), to))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            enabled: section
+                .get("enabled")
+                .and_then(|enabled| enabled.as_bool())
+                .unwrap_or(false),
+            transitions,
+        }
+    }
+
+    // Statuses a todo can move to, None when the workflow doesn't restrict `from`
+    pub fn next(&self, from: &str) -> Option<&[String]> {
+        if !self.enabled {
+            return None;
+        }
+        self.transitions
+            .iter()
+            .find(|(status, _)| status.eq_ignore_ascii_case(from))
+            .map(|(_, to)| to.as_slice())
+    }
+
+    pub fn allows(&self, from: &str, to: &str) -> bool {
+        from.eq_ignore_ascii_case(to)
+            || self
+                .next(from)
+                .is_none_or(|next| next.iter().any(|status| status.eq_ignore_ascii_case(to)))
+    }
+
+    // Why a change is refused, naming the statuses that are allowed instead
+    pub fn check(&self, from: &str, to: &str) -> Result<(), String> {
+        if self.allows(from, to) {
+            return Ok(());
+        }
+        let next = self.next(from).unwrap_or_default();
+        Err(format!(
+            "can't go from {} to {}, next: {}",
+            from,
+            to,
+            if next.is_empty() {
+                "nothing".to_string()
+            } else {
+                next.join(", ")
+            }
+        ))
+    }
+}
+
+// AI SETTINGS, from the [GEMINI] section
+pub const DEFAULT_MODEL: &str = "gemini-2.0-flash";
+// Models Google no longer serves, older configs were written with "gemini-pro"
+const RETIRED_MODELS: [&str; 3] = ["gemini-pro", "gemini-1.0-pro", "gemini-pro-vision"];
+pub const TEMPERATURES: std::ops::RangeInclusive<f64> = 0.0..=2.0;
+// `safety` values and the threshold Gemini takes for them
+pub const SAFETY_LEVELS: [(&str, &str); 4] = [
+    ("none", "BLOCK_NONE"),
+    ("few", "BLOCK_ONLY_HIGH"),
+    ("some", "BLOCK_MEDIUM_AND_ABOVE"),
+    ("most", "BLOCK_LOW_AND_ABOVE"),
+];
+
+#[derive(Debug, Clone)]
+pub struct GeminiConfig {
+    // Ask for a due date when a todo is added without one
+    pub suggest_due: bool,
+    #[cfg_attr(not(feature = "ai"), allow(dead_code))]
+    pub model: String,
+    // Unset ones are left to the model
+    #[cfg_attr(not(feature = "ai"), allow(dead_code))]
+    pub temperature: Option<f64>,
+    #[cfg_attr(not(feature = "ai"), allow(dead_code))]
+    pub max_tokens: Option<u32>,
+    // The threshold for every harm category, e.g. "BLOCK_ONLY_HIGH"
+    #[cfg_attr(not(feature = "ai"), allow(dead_code))]
+    pub safety: Option<&'static str>,
+}
+
+impl Default for GeminiConfig {
+    fn default() -> Self {
+        Self {
+            suggest_due: false,
+            model: DEFAULT_MODEL.to_string(),
+            temperature: None,
+            max_tokens: None,
+            safety: None,
+        }
+    }
+}
+
+fn safety_threshold(level: &str) -> Option<&'static str> {
+    SAFETY_LEVELS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(level.trim()))
+        .map(|(_, threshold)| *threshold)
+}
+
+impl GeminiConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let Some(section) = config.get("GEMINI") else {
+            return Self::default();
+        };
+        let model = section
+            .get("model")
+            .and_then(|model| model.as_str())
+            .map(str::trim)
+            .filter(|model| !model.is_empty() && !RETIRED_MODELS.contains(model))
+            .unwrap_or(DEFAULT_MODEL);
+        Self {
+            suggest_due: section
+                .get("suggest_due")
+                .and_then(|enabled| enabled.as_bool())
+                .unwrap_or(false),
+            model: model.to_string(),
+            temperature: section
+                .get("temperature")
+                .and_then(|temperature| {
+                    temperature
+                        .as_float()
+                        .or(temperature.as_integer().map(|t| t as f64))
+                })
+                .filter(|temperature| TEMPERATURES.contains(temperature)),
+            max_tokens: section
+                .get("max_tokens")
+                .and_then(|tokens| tokens.as_integer())
+                .and_then(|tokens| u32::try_from(tokens).ok())
+                .filter(|tokens| *tokens > 0),
+            safety: section
+                .get("safety")
+                .and_then(|level| level.as_str())
+                .and_then(safety_threshold),
+        }
+    }
+}
+
+// VOICE MEMOS, from the [AUDIO] section
+pub const AUDIO_PROVIDERS: [&str; 2] = ["gemini", "whisper"];
+
+#[derive(Debug, Clone)]
+pub struct AudioConfig {
+    // One of AUDIO_PROVIDERS
+    pub provider: String,
+    // The Whisper-compatible endpoint, its model and bearer token
+    pub url: String,
+    pub model: String,
+    pub token: String,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            provider: "gemini".to_string(),
+            url: "https://api.openai.com/v1/audio/transcriptions".to_string(),
+            model: "whisper-1".to_string(),
+            token: String::new(),
+        }
+    }
+}
+
+impl AudioConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let defaults = Self::default();
+        let Some(section) = config.get("AUDIO") else {
+            return defaults;
+        };
+        let text = |key: &str, default: String| {
+            section
+                .get(key)
+                .and_then(|value| value.as_str())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .unwrap_or(default)
+        };
+        let provider = text("provider", defaults.provider.clone()).to_lowercase();
+        Self {
+            provider: if AUDIO_PROVIDERS.contains(&provider.as_str()) {
+                provider
+            } else {
+                defaults.provider
+            },
+            url: text("url", defaults.url),
+            model: text("model", defaults.model),
+            token: text("token", defaults.token),
+        }
+    }
+}
+
+// NOTES, from the [NOTES] section
+#[derive(Debug, Clone)]
+pub struct NotesConfig {
+    // Ticking a checkbox in the notes ticks the subtask of the same text, adding it if missing
+    pub checkbox_subtasks: bool,
+    // Link and image destinations shown after their text, `urls = "hidden"` leaves them out
+    pub show_urls: bool,
+}
+
+impl Default for NotesConfig {
+    fn default() -> Self {
+        Self {
+            checkbox_subtasks: false,
+            show_urls: true,
+        }
+    }
+}
+
+impl NotesConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let Some(section) = config.get("NOTES") else {
+            return Self::default();
+        };
+        Self {
+            checkbox_subtasks: section
+                .get("checkbox_subtasks")
+                .and_then(|enabled| enabled.as_bool())
+                .unwrap_or(false),
+            show_urls: !section
+                .get("urls")
+                .and_then(|urls| urls.as_str())
+                .is_some_and(|urls| urls.eq_ignore_ascii_case("hidden")),
+        }
+    }
+}
+
+// SEMANTIC SEARCH, from the [SEARCH] section
+pub const EMBEDDING_PROVIDERS: [&str; 2] = ["gemini", "ollama"];
+
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    // Off: `voido search` matches words like the TUI search bar
+    pub semantic: bool,
+    // One of EMBEDDING_PROVIDERS, the model it embeds with and where Ollama runs
+    pub provider: String,
+    pub model: String,
+    pub url: String,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            semantic: false,
+            provider: "gemini".to_string(),
+            model: "text-embedding-004".to_string(),
+            url: "http://localhost:11434".to_string(),
+        }
+    }
+}
+
+impl SearchConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let defaults = Self::default();
+        let Some(section) = config.get("SEARCH") else {
+            return defaults;
+        };
+        let text = |key: &str| {
+            section
+                .get(key)
+                .and_then(|value| value.as_str())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let provider = text("provider")
+            .map(|provider| provider.to_lowercase())
+            .filter(|provider| EMBEDDING_PROVIDERS.contains(&provider.as_str()))
+            .unwrap_or(defaults.provider);
+        // Each provider has its own models
+        let model = text("model").unwrap_or_else(|| match provider.as_str() {
+            "ollama" => "nomic-embed-text".to_string(),
+            _ => defaults.model,
+        });
+        Self {
+            semantic: section
+                .get("semantic")
+                .and_then(|enabled| enabled.as_bool())
+                .unwrap_or(false),
+            provider,
+            model,
+            url: text("url").unwrap_or(defaults.url),
+        }
+    }
+}
+
+// DISPLAY SETTINGS, from the [DISPLAY] section
+#[derive(Debug, Clone)]
+pub struct DisplayConfig {
+    pub date_format: String,
+    pub week_starts_on: chrono::Weekday,
+    pub theme: String,
+    // Compact density: no column spacing, one line per todo
+    pub compact: bool,
+    // Table columns the user switched off, by `Column::key`
+    pub hidden_columns: Vec<String>,
+    // Colors picked for some topics, by lowercase topic name
+    pub topic_colors: Vec<(String, String)>,
+    // Glyphs in place of the priority and status columns
+    pub icons: IconSet,
+    // Days left until the due date, in a column of its own
+    pub countdown: bool,
+    // Where due times are read and shown, `--tz` overrides it for one run
+    pub timezone: dates::Timezone,
+    // Counters on the stats bar, in order
+    pub stats: Vec<Stat>,
+    // Width and alignment set for some columns, by `Column::key`
+    pub columns: Vec<(String, ColumnLayout)>,
+    // A # column before the others
+    pub row_numbers: RowNumbers,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            date_format: "%d-%m-%y".to_string(),
+            week_starts_on: chrono::Weekday::Mon,
+            theme: "voido".to_string(),
+            compact: false,
+            hidden_columns: Vec::new(),
+            topic_colors: Vec::new(),
+            icons: IconSet::Off,
+            countdown: false,
+            timezone: dates::Timezone::Local,
+            stats: Stat::DEFAULT.to_vec(),
+            columns: Vec::new(),
+            row_numbers: RowNumbers::Off,
+        }
+    }
+}
+
+impl DisplayConfig {
+    pub fn load() -> Self {
+        let mut config = AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default();
+        if let Some(timezone) = TIMEZONE_OVERRIDE.get() {
+            config.timezone = *timezone;
+        }
+        config
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let defaults = Self::default();
+        let Some(section) = config.get("DISPLAY") else {
+            return defaults;
+        };
+
+        let date_format = section
+            .get("date_format")
+            .and_then(|format| format.as_str())
+            .and_then(date_format_pattern)
+            .unwrap_or(defaults.date_format);
+
+        let week_starts_on = match section.get("week_starts_on").and_then(|day| day.as_str()) {
+            Some(day) if day.eq_ignore_ascii_case("sunday") => chrono::Weekday::Sun,
+            _ => chrono::Weekday::Mon,
+        };
+
+        let theme = section
+            .get("theme")
+            .and_then(|theme| theme.as_str())
+            .map(|theme| theme.to_lowercase())
+            .unwrap_or(defaults.theme);
+
+        let compact = section
+            .get("density")
+            .and_then(|density| density.as_str())
+            .is_some_and(|density| density.eq_ignore_ascii_case("compact"));
+
+        let hidden_columns = section
+            .get("hidden_columns")
+            .and_then(|columns| columns.as_array())
+            .map(|columns| {
+                columns
+                    .iter()
+                    .filter_map(|column| column.as_str().map(|column| column.to_lowercase()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let topic_colors = section
+            .get("topic_colors")
+            .and_then(|colors| colors.as_table())
+            .map(|colors| {
+                colors
+                    .iter()
+                    .filter_map(|(topic, color)| {
+                        Some((topic.trim().to_lowercase(), color.as_str()?.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let icons = section
+            .get("icons")
+            .and_then(|icons| icons.as_str())
+            .and_then(IconSet::by_name)
+            .unwrap_or(defaults.icons);
+
+        let countdown = section
+            .get("countdown")
+            .and_then(|countdown| countdown.as_bool())
+            .unwrap_or(defaults.countdown);
+
+        let timezone = section
+            .get("timezone")
+            .and_then(|timezone| timezone.as_str())
+            .and_then(dates::Timezone::parse)
+            .unwrap_or(defaults.timezone);
+
+        // Unknown names are left out, an empty list leaves only the sync status
+        let stats = section
+            .get("stats")
+            .and_then(|stats| stats.as_array())
+            .map(|stats| {
+                stats
+                    .iter()
+                    .filter_map(|stat| stat.as_str().and_then(Stat::by_key))
+                    .collect()
+            })
+            .unwrap_or(defaults.stats);
+
+        let row_numbers = section
+            .get("row_numbers")
+            .and_then(|numbers| numbers.as_str())
+            .and_then(RowNumbers::by_name)
+            .unwrap_or(defaults.row_numbers);
+
+        let columns = section
+            .get("columns")
+            .and_then(|columns| columns.as_table())
+            .map(|columns| {
+                columns
+                    .iter()
+                    .map(|(column, layout)| {
+                        let size = |key: &str| {
+                            layout
+                                .get(key)
+                                .and_then(|size| size.as_integer())
+                                .and_then(|size| u16::try_from(size).ok())
+                        };
+                        let layout = ColumnLayout {
+                            width: size("width"),
+                            min: size("min"),
+                            max: size("max"),
+                            align: layout
+                                .get("align")
+                                .and_then(|align| align.as_str())
+                                .and_then(parse_alignment),
+                        };
+                        (column.trim().to_lowercase(), layout)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            date_format,
+            week_starts_on,
+            theme,
+            compact,
+            hidden_columns,
+            topic_colors,
+            icons,
+            countdown,
+            timezone,
+            stats,
+            columns,
+            row_numbers,
+        }
+    }
+
+    // Write the settings back to the [DISPLAY] section, keeping the rest of the file as is
+    pub fn save(&self) -> Result<()> {
+        edit_config_section("DISPLAY", |section| {
+            section["date_format"] = toml_edit::value(self.date_format.as_str());
+            section["week_starts_on"] = toml_edit::value(match self.week_starts_on {
+                chrono::Weekday::Sun => "sunday",
+                _ => "monday",
+            });
+            section["theme"] = toml_edit::value(self.theme.as_str());
+            section["density"] = toml_edit::value(if self.compact {
+                "compact"
+            } else {
+                "comfortable"
+            });
+            section["hidden_columns"] = toml_edit::value(
+                self.hidden_columns
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<toml_edit::Array>(),
+            );
+            section["icons"] = toml_edit::value(self.icons.name());
+            section["countdown"] = toml_edit::value(self.countdown);
+            section["row_numbers"] = toml_edit::value(self.row_numbers.name());
+        })
+    }
+}
+
+// Update one section of config.toml in place, keeping comments and the other sections
+fn edit_config_section(name: &str, edit: impl FnOnce(&mut toml_edit::Item)) -> Result<()> {
+    // A dry run keeps the new settings for this session only
+    if DBtodo::is_dry_run() {
+        return Ok(());
+    }
+    let config_file = AppConfigs::get_config_path()?;
+    // The first setting saved goes into the full default file, not a file of its own
+    AppConfigs::create_default_config()?;
+    let content = std::fs::read_to_string(&config_file).unwrap_or_default();
+    let mut document = content
+        .parse::<toml_edit::DocumentMut>()
+        .context("Failed to parse config file")?;
+
+    if !document.contains_table(name) {
+        document[name] = toml_edit::table();
+    }
+    let before = document[name].clone();
+    edit(&mut document[name]);
+
+    // Settings the active profile overrides are saved in the profile instead
+    if let Some(profile) = active_profile() {
+        for (key, target) in PROFILE_KEYS {
+            let Some((section, field)) = target else {
+                continue;
+            };
+            let overridden = document
+                .get("profile")
+                .and_then(|profiles| profiles.get(profile))
+                .and_then(|profile| profile.get(key))
+                .is_some();
+            if section != name || !overridden {
+                continue;
+            }
+            document["profile"][profile][key] = document[name][field].clone();
+            match before.get(field) {
+                Some(value) => document[name][field] = value.clone(),
+                None => {
+                    if let Some(table) = document[name].as_table_like_mut() {
+                        table.remove(field);
+                    }
+                }
+            }
+        }
+    }
+
+    std::fs::write(&config_file, document.to_string())
+        .with_context(|| format!("Failed to write config to {:?}", config_file))?;
+    Ok(())
+}
+
+// DEFAULTS FOR NEW TODOS, from the [DEFAULTS] section
+#[derive(Debug, Clone)]
+pub struct DefaultsConfig {
+    pub owner: String,
+    pub topic: String,
+    pub priority: String,
+    // Due date for new todos, in days from today
+    pub due_offset: Option<i64>,
+    // Topic of the todos captured with `voido q`
+    pub inbox_topic: String,
+}
+
+impl Default for DefaultsConfig {
+    fn default() -> Self {
+        Self {
+            owner: "You".to_string(),
+            topic: "General".to_string(),
+            priority: "normal".to_string(),
+            due_offset: None,
+            inbox_topic: "Inbox".to_string(),
+        }
+    }
+}
+
+impl DefaultsConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let defaults = Self::default();
+        let Some(section) = config.get("DEFAULTS") else {
+            return defaults;
+        };
+
+        let text = |key: &str| {
+            section
+                .get(key)
+                .and_then(|value| value.as_str())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+        };
+
+        Self {
+            owner: text("default_owner").unwrap_or(defaults.owner),
+            topic: text("default_topic").unwrap_or(defaults.topic),
+            priority: text("default_priority").unwrap_or(defaults.priority),
+            due_offset: section
+                .get("default_due_offset")
+                .and_then(|offset| offset.as_integer()),
+            inbox_topic: text("inbox_topic").unwrap_or(defaults.inbox_topic),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        edit_config_section("DEFAULTS", |section| {
+            section["default_owner"] = toml_edit::value(self.owner.as_str());
+            section["default_topic"] = toml_edit::value(self.topic.as_str());
+            section["default_priority"] = toml_edit::value(self.priority.as_str());
+            match self.due_offset {
+                Some(offset) => section["default_due_offset"] = toml_edit::value(offset),
+                None => {
+                    if let Some(table) = section.as_table_like_mut() {
+                        table.remove("default_due_offset");
+                    }
+                }
+            }
+        })
+    }
+}
+
+// CONDITIONAL ROW STYLING, from the [STYLING] section
+#[derive(Debug, Clone, Default)]
+pub struct StylingConfig {
+    pub rules: Vec<StyleRule>,
+}
+
+impl StylingConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    // Rules that don't parse are skipped, the config check reports them
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let rules = config
+            .get("STYLING")
+            .and_then(|section| section.get("rules"))
+            .and_then(|rules| rules.as_array())
+            .map(|rules| {
+                rules
+                    .iter()
+                    .filter_map(|rule| StyleRule::parse(rule.as_str()?).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { rules }
+    }
+}
+
+// KEY MACROS, from the [MACROS] section
+#[derive(Debug, Clone, Default)]
+pub struct MacrosConfig {
+    pub macros: Vec<KeyMacro>,
+}
+
+impl MacrosConfig {
+    pub fn load() -> Self {
+        AppConfigs::read_config_value()
+            .map(|config| Self::from_toml(&config))
+            .unwrap_or_default()
+    }
+
+    // Macros that don't parse are skipped, the config check reports them
+    pub fn from_toml(config: &toml::Value) -> Self {
+        let macros = config
+            .get("MACROS")
+            .and_then(|section| section.as_table())
+            .map(|section| {
+                section
+                    .iter()
+                    .filter_map(|(key, actions)| {
+                        let actions = actions
+                            .as_array()?
+                            .iter()
+                            .map(|action| action.as_str())
+                            .collect::<Option<Vec<&str>>>()?;
+                        KeyMacro::parse(key, &actions).ok()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self { macros }
+    }
+
+    pub fn find(&self, key: char) -> Option<&KeyMacro> {
+        self.macros.iter().find(|key_macro| key_macro.key == key)
+    }
+}
+
+// Display settings shared by the whole app, swapped on reload
+static DISPLAY: LazyLock<RwLock<DisplayConfig>> =
+    LazyLock::new(|| RwLock::new(DisplayConfig::load()));
+
+pub fn display_config() -> RwLockReadGuard<'static, DisplayConfig> {
+    DISPLAY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn set_display_config(config: DisplayConfig) {
+    *DISPLAY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = config;
+}
+
+// Defaults for new todos, shared the same way
+static DEFAULTS: LazyLock<RwLock<DefaultsConfig>> =
+    LazyLock::new(|| RwLock::new(DefaultsConfig::load()));
+
+pub fn defaults_config() -> RwLockReadGuard<'static, DefaultsConfig> {
+    DEFAULTS
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn set_defaults_config(config: DefaultsConfig) {
+    *DEFAULTS
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = config;
+}
+
+// Row styling rules, parsed once instead of on every frame
+static STYLING: LazyLock<RwLock<StylingConfig>> =
+    LazyLock::new(|| RwLock::new(StylingConfig::load()));
+
+pub fn styling_config() -> RwLockReadGuard<'static, StylingConfig> {
+    STYLING
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// The [NOTES] settings, read once instead of on every frame with notes
+static NOTES: LazyLock<RwLock<NotesConfig>> = LazyLock::new(|| RwLock::new(NotesConfig::load()));
+
+pub fn notes_config() -> RwLockReadGuard<'static, NotesConfig> {
+    NOTES
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Re-read the config file after it changed on disk
+pub fn reload_configs() {
+    set_display_config(DisplayConfig::load());
+    set_defaults_config(DefaultsConfig::load());
+    *STYLING
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = StylingConfig::load();
+    *NOTES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = NotesConfig::load();
+}
+
+// Notices when config.toml is modified, checked from the TUI loop
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    pub fn new() -> Self {
+        Self {
+            modified: Self::modified_time(),
+        }
+    }
+
+    fn modified_time() -> Option<SystemTime> {
+        let config_file = AppConfigs::get_config_path().ok()?;
+        std::fs::metadata(config_file).ok()?.modified().ok()
+    }
+
+    // True once per change to the file
+    pub fn changed(&mut self) -> bool {
+        let modified = Self::modified_time();
+        if modified != self.modified {
+            self.modified = modified;
+            return true;
+        }
+        false
+    }
+
+    // Forget our own writes so they don't count as outside changes
+    pub fn sync(&mut self) {
+        self.modified = Self::modified_time();
+    }
+}
+
+// Presets offered by the settings screen
+pub const DATE_FORMAT_PRESETS: [&str; 4] = ["%d-%m-%y", "%Y-%m-%d", "%m/%d/%Y", "%d/%m/%Y"];
+pub const PRIORITY_PRESETS: [&str; 4] = ["normal", "low", "medium", "high"];
+pub const DUE_OFFSET_PRESETS: [Option<i64>; 5] = [None, Some(0), Some(1), Some(3), Some(7)];
+
+// Resolve a preset name or validate a strftime pattern
+pub fn date_format_pattern(format: &str) -> Option<String> {
+    let pattern = match format.to_lowercase().as_str() {
+        "iso" => "%Y-%m-%d",
+        "us" => "%m/%d/%Y",
+        "eu" => "%d/%m/%Y",
+        _ => format,
+    };
+    // An invalid pattern would make chrono fail at display time
+    chrono::format::StrftimeItems::new(pattern)
+        .parse()
+        .ok()
+        .map(|_| pattern.to_string())
+}
+
+// CONFIG VALIDATION: type errors, unknown keys and odd values, with line numbers.
+// Nothing here stops VoiDo from starting, every broken value falls back to its default.
+#[derive(Debug, Default)]
+pub struct ConfigReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ConfigReport {
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+
+    // One line per problem, ready to print or show in the status bar
+    pub fn messages(&self) -> Vec<String> {
+        self.errors
+            .iter()
+            .map(|error| format!("❌ config.toml {}", error))
+            .chain(
+                self.warnings
+                    .iter()
+                    .map(|warning| format!("⚠️ config.toml {}", warning)),
+            )
+            .collect()
+    }
+}
+
+// What `voido sync` takes, for [SYNC.topics]
+pub const SYNC_BACKENDS: [&str; 2] = ["github", "google"];
+
+// Keys of each [DISPLAY.columns] entry
+const COLUMN_KEYS: [&str; 4] = ["width", "min", "max", "align"];
+
+// Every section and key VoiDo reads, anything else gets a warning.
+// [MACROS] takes any key, so it is checked by check_values instead
+const KNOWN_KEYS: [(&str, &[&str]); 17] = [
+    (
+        "GEMINI",
+        &[
+            "model",
+            "temperature",
+            "max_tokens",
+            "safety",
+            "suggest_due",
+        ],
+    ),
+    ("AUDIO", &["provider", "url", "model", "token"]),
+    ("GITHUB", &["repo_name", "remote", "forge"]),
+    ("MICROSOFT", &["client_id"]),
+    ("GOOGLE_TASKS", &["client_id", "client_secret", "lists"]),
+    ("SYNC", &["topics", "schedule", "jitter"]),
+    ("PUBLISH", &["url", "token"]),
+    (
+        "DISPLAY",
+        &[
+            "date_format",
+            "week_starts_on",
+            "theme",
+            "density",
+            "hidden_columns",
+            "topic_colors",
+            "icons",
+            "countdown",
+            "timezone",
+            "stats",
+            "columns",
+            "row_numbers",
+        ],
+    ),
+    (
+        "DEFAULTS",
+        &[
+            "default_owner",
+            "default_topic",
+            "default_priority",
+            "default_due_offset",
+            "inbox_topic",
+        ],
+    ),
+    ("ARCHIVE", &["enabled", "skip_topics", "rules"]),
+    ("ESCALATION", &["enabled", "rules"]),
+    ("WORKFLOW", &["enabled", "transitions"]),
+    ("NOTES", &["checkbox_subtasks", "urls"]),
+    ("SEARCH", &["semantic", "provider", "model", "url"]),
+    ("STYLING", &["rules"]),
+    ("MACROS", &[]),
+    ("profile", &[]),
+];
+const KNOWN_RULE_KEYS: [(&str, &[&str]); 2] = [
+    ("ARCHIVE", &["status", "days"]),
+    ("ESCALATION", &["within_days", "priority"]),
+];
+
+// The expected shape of config.toml, all sections and keys are optional
+#[derive(Debug, Default, Deserialize)]
+struct ConfigSchema {
+    #[serde(rename = "GEMINI")]
+    gemini: Option<GeminiSchema>,
+    #[serde(rename = "AUDIO")]
+    audio: Option<AudioSchema>,
+    #[serde(rename = "GITHUB")]
+    github: Option<GithubSchema>,
+    #[serde(rename = "MICROSOFT")]
+    microsoft: Option<MicrosoftSchema>,
+    #[serde(rename = "GOOGLE_TASKS")]
+    google_tasks: Option<GoogleTasksSchema>,
+    #[serde(rename = "SYNC")]
+    sync: Option<SyncSchema>,
+    #[serde(rename = "PUBLISH")]
+    publish: Option<PublishSchema>,
+    #[serde(rename = "DISPLAY")]
+    display: Option<DisplaySchema>,
+    #[serde(rename = "DEFAULTS")]
+    defaults: Option<DefaultsSchema>,
+    #[serde(rename = "ARCHIVE")]
+    archive: Option<ArchiveSchema>,
+    #[serde(rename = "ESCALATION")]
+    escalation: Option<EscalationSchema>,
+    #[serde(rename = "WORKFLOW")]
+    workflow: Option<WorkflowSchema>,
+    #[serde(rename = "NOTES")]
+    notes: Option<NotesSchema>,
+    #[serde(rename = "SEARCH")]
+    search: Option<SearchSchema>,
+    #[serde(rename = "STYLING")]
+    styling: Option<StylingSchema>,
+    #[serde(rename = "MACROS")]
+    macros: Option<std::collections::BTreeMap<String, Vec<String>>>,
+    #[serde(rename = "profile")]
+    profiles: Option<std::collections::BTreeMap<String, ProfileSchema>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiSchema {
+    model: Option<String>,
+    temperature: Option<f64>,
+    max_tokens: Option<i64>,
+    safety: Option<String>,
+    suggest_due: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AudioSchema {
+    provider: Option<String>,
+    url: Option<String>,
+    model: Option<String>,
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubSchema {
+    repo_name: Option<String>,
+    #[cfg(feature = "sync")]
+    remote: Option<String>,
+    #[cfg(feature = "sync")]
+    forge: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MicrosoftSchema {
+    client_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleTasksSchema {
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    lists: Option<std::collections::BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SyncSchema {
+    topics: Option<std::collections::BTreeMap<String, Vec<String>>>,
+    schedule: Option<std::collections::BTreeMap<String, String>>,
+    #[cfg(feature = "sync")]
+    jitter: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileSchema {
+    db_path: Option<String>,
+    theme: Option<String>,
+    default_owner: Option<String>,
+    repo_name: Option<String>,
+    #[cfg(feature = "sync")]
+    remote: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PublishSchema {
+    url: Option<String>,
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DisplaySchema {
+    date_format: Option<String>,
+    week_starts_on: Option<String>,
+    theme: Option<String>,
+    density: Option<String>,
+    hidden_columns: Option<Vec<String>>,
+    topic_colors: Option<std::collections::BTreeMap<String, String>>,
+    icons: Option<String>,
+    countdown: Option<bool>,
+    timezone: Option<String>,
+    stats: Option<Vec<String>>,
+    columns: Option<std::collections::BTreeMap<String, ColumnSchema>>,
+    row_numbers: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ColumnSchema {
+    width: Option<u16>,
+    min: Option<u16>,
+    max: Option<u16>,
+    align: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DefaultsSchema {
+    default_owner: Option<String>,
+    default_topic: Option<String>,
+    default_priority: Option<String>,
+    default_due_offset: Option<i64>,
+    inbox_topic: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveSchema {
+    enabled: Option<bool>,
+    skip_topics: Option<Vec<String>>,
+    #[serde(default)]
+    rules: Vec<ArchiveRuleSchema>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveRuleSchema {
+    status: String,
+    days: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct EscalationSchema {
+    enabled: Option<bool>,
+    #[serde(default)]
+    rules: Vec<EscalationRuleSchema>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EscalationRuleSchema {
+    within_days: i64,
+    priority: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkflowSchema {
+    enabled: Option<bool>,
+    transitions: Option<std::collections::BTreeMap<String, Vec<String>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotesSchema {
+    checkbox_subtasks: Option<bool>,
+    urls: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchSchema {
+    semantic: Option<bool>,
+    provider: Option<String>,
+    model: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StylingSchema {
+    #[serde(default)]
+    rules: Vec<String>,
+}
+
+// Check the config file on disk, a missing file is fine
+pub fn check_config_file() -> ConfigReport {
+    let Ok(config_file) = AppConfigs::get_config_path() else {
+        return ConfigReport::default();
+    };
+    // The first thing most commands read, a first run starts from the default file
+    let _ = AppConfigs::create_default_config();
+    match std::fs::read_to_string(&config_file) {
+        Ok(content) => check_config(&content),
+        Err(_) => ConfigReport::default(),
+    }
+}
+
+pub fn check_config(content: &str) -> ConfigReport {
+    let mut report = ConfigReport::default();
+
+    // Broken TOML: nothing else can be checked
+    let document = match toml_edit::Document::parse(content) {
+        Ok(document) => document,
+        Err(e) => {
+            report.errors.push(format!(
+                "{}: {}, using the default settings",
+                line_label(content, e.span()),
+                e.message().trim()
+            ));
+            return report;
+        }
+    };
+
+    warn_unknown_keys(content, &document, &mut report);
+
+    // Wrong types, e.g. `enabled = "yes"`. Serde stops at the first one.
+    match toml::from_str::<ConfigSchema>(content) {
+        Ok(schema) => check_values(&schema, &mut report),
+        Err(e) => {
+            let line = line_number(content, e.span());
+            let source = line
+                .and_then(|line| content.lines().nth(line - 1))
+                .map(|source| format!(" (`{}`)", source.trim()))
+                .unwrap_or_default();
+            report.errors.push(format!(
+                "{}: {}{}, using the default",
+                line_label(content, e.span()),
+                e.message().trim(),
+                source
+            ));
+        }
+    }
+
+    report
+}
+
+fn warn_unknown_keys(
+    content: &str,
+    document: &toml_edit::Document<&str>,
+    report: &mut ConfigReport,
+) {
+    let root = document.as_table();
+    for (name, item) in root.iter() {
+        let key_span = root.key(name).and_then(|key| key.span());
+        let Some((_, known)) = KNOWN_KEYS.iter().find(|(section, _)| *section == name) else {
+            report.warnings.push(format!(
+                "{}: unknown section [{}] is ignored",
+                line_label(content, key_span),
+                name
+            ));
+            continue;
+        };
+        let Some(section) = item.as_table_like() else {
+            continue;
+        };
+        if name == "MACROS" {
+            continue;
+        }
+        // [profile.<name>] tables, each with the profile keys
+        if name == "profile" {
+            let known = PROFILE_KEYS.map(|(key, _)| key);
+            for (profile, item) in section.iter() {
+                if let Some(table) = item.as_table_like() {
+                    let label = format!("profile.{}", profile);
+                    warn_unknown_in(content, table, &known, &label, report);
+                }
+            }
+            continue;
+        }
+        warn_unknown_in(content, section, known, name, report);
+
+        if name == "DISPLAY"
+            && let Some(columns) = section.get("columns").and_then(|c| c.as_table_like())
+        {
+            for (column, layout) in columns.iter() {
+                if let Some(layout) = layout.as_table_like() {
+                    let label = format!("DISPLAY.columns.{}", column);
+                    warn_unknown_in(content, layout, &COLUMN_KEYS, &label, report);
+                }
+            }
+        }
+
+        // Every [[SECTION.rules]] entry has its own set of keys
+        let rule_keys = KNOWN_RULE_KEYS.iter().find(|(section, _)| *section == name);
+        let rules = section
+            .get("rules")
+            .and_then(|rules| rules.as_array_of_tables());
+        if let (Some((_, known)), Some(rules)) = (rule_keys, rules) {
+            for rule in rules.iter() {
+                warn_unknown_in(content, rule, known, &format!("{}.rules", name), report);
+            }
+        }
+    }
+}
+
+fn warn_unknown_in(
+    content: &str,
+    table: &dyn toml_edit::TableLike,
+    known: &[&str],
+    section: &str,
+    report: &mut ConfigReport,
+) {
+    for (key, _) in table.iter() {
+        if known.contains(&key) {
+            continue;
+        }
+        let span = table.get_key_value(key).and_then(|(key, _)| key.span());
+        report.warnings.push(format!(
+            "{}: unknown key `{}` in [{}] is ignored (expected one of: {})",
+            line_label(content, span),
+            key,
+            section,
+            known.join(", ")
+        ));
+    }
+}
+
+fn is_guid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(index, c)| match index {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
         })
+}
+
+// Values with the right type that VoiDo still can't use
+fn check_values(schema: &ConfigSchema, report: &mut ConfigReport) {
+    let mut warn = |message: String| report.warnings.push(message);
+
+    if let Some(gemini) = &schema.gemini {
+        let model = gemini.model.as_deref().map(str::trim);
+        if model == Some("") {
+            warn(format!(
+                "[GEMINI] model is empty, using \"{}\"",
+                DEFAULT_MODEL
+            ));
+        } else if let Some(model) = model.filter(|model| RETIRED_MODELS.contains(model)) {
+            warn(format!(
+                "[GEMINI] model \"{}\" is no longer served by Google, using \"{}\"",
+                model, DEFAULT_MODEL
+            ));
+        }
+        if let Some(temperature) = gemini.temperature
+            && !TEMPERATURES.contains(&temperature)
+        {
+            warn(format!(
+                "[GEMINI] temperature {} is not between 0.0 and 2.0, leaving it to the model",
+                temperature
+            ));
+        }
+        if let Some(tokens) = gemini.max_tokens
+            && !(1..=u32::MAX as i64).contains(&tokens)
+        {
+            warn(format!(
+                "[GEMINI] max_tokens {} is not a positive number, leaving it to the model",
+                tokens
+            ));
+        }
+        if let Some(level) = gemini.safety.as_deref()
+            && !level.trim().is_empty()
+            && safety_threshold(level).is_none()
+        {
+            warn(format!(
+                "[GEMINI] safety \"{}\" is not one of: {}, using the model's default",
+                level,
+                SAFETY_LEVELS.map(|(name, _)| name).join(", ")
+            ));
+        }
+    }
+    if let Some(audio) = &schema.audio {
+        let provider = audio.provider.as_deref().unwrap_or_default().trim();
+        if !provider.is_empty() && !AUDIO_PROVIDERS.contains(&provider.to_lowercase().as_str()) {
+            warn(format!(
+                "[AUDIO] provider \"{}\" is not one of: {}, using \"gemini\"",
+                provider,
+                AUDIO_PROVIDERS.join(", ")
+            ));
+        }
+        let url = audio.url.as_deref().unwrap_or_default().trim();
+        if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") {
+            warn(format!(
+                "[AUDIO] url \"{}\" should start with http:// or https://",
+                url
+            ));
+        }
+    }
+    if let Some(repo) = schema
+        .github
+        .as_ref()
+        .and_then(|github| github.repo_name.as_ref())
+        && repo.trim().is_empty()
+    {
+        warn("[GITHUB] repo_name is empty, using \"voido_sync\"".to_string());
+    }
+    #[cfg(feature = "sync")]
+    if let Some(github) = &schema.github {
+        let remote = github.remote.as_deref().unwrap_or_default().trim();
+        if !remote.is_empty() && crate::sync::forge::RemoteUrl::parse(remote).is_none() {
+            warn(format!(
+                "[GITHUB] remote \"{}\" is not a git URL like https://host/owner/repo.git or git@host:owner/repo.git",
+                remote
+            ));
+        }
+        let forge = github.forge.as_deref().unwrap_or_default().trim();
+        if !forge.is_empty() && crate::sync::forge::Forge::parse(forge).is_none() {
+            warn(format!(
+                "[GITHUB] forge \"{}\" is not one of: {}",
+                forge,
+                crate::sync::forge::Forge::NAMES.join(", ")
+            ));
+        }
+    }
+    // Azure shows the client ID as a GUID, e.g. 4f2c8a1e-0b7d-4e5a-9c3f-2d6b8e1a7c90
+    if let Some(client_id) = schema
+        .microsoft
+        .as_ref()
+        .and_then(|microsoft| microsoft.client_id.as_ref())
+        && !client_id.is_empty()
+        && !is_guid(client_id)
+    {
+        warn(format!(
+            "[MICROSOFT] client_id \"{}\" is not an Application (client) ID",
+            client_id
+        ));
+    }
+
+    if let Some(google) = &schema.google_tasks {
+        let client_id = google.client_id.as_deref().unwrap_or_default().trim();
+        if !client_id.is_empty() && !client_id.ends_with(".apps.googleusercontent.com") {
+            warn(format!(
+                "[GOOGLE_TASKS] client_id \"{}\" should end in .apps.googleusercontent.com",
+                client_id
+            ));
+        }
+        if !client_id.is_empty()
+            && google
+                .client_secret
+                .as_deref()
+                .is_none_or(|secret| secret.trim().is_empty())
+        {
+            warn(
+                "[GOOGLE_TASKS] client_secret is empty, Google asks for it to sign in".to_string(),
+            );
+        }
+        if client_id.is_empty() && google.lists.as_ref().is_some_and(|lists| !lists.is_empty()) {
+            warn(
+                "[GOOGLE_TASKS] lists are set but client_id is empty, nothing will sync"
+                    .to_string(),
+            );
+        }
+    }
+
+    let backends = schema.sync.iter().flat_map(|sync| {
+        let topics = sync
+            .topics
+            .iter()
+            .flatten()
+            .map(|(backend, _)| ("topics", backend));
+        let schedule = sync
+            .schedule
+            .iter()
+            .flatten()
+            .map(|(backend, _)| ("schedule", backend));
+        topics.chain(schedule)
+    });
+    for (table, backend) in backends {
+        if !SYNC_BACKENDS.contains(&backend.trim().to_lowercase().as_str()) {
+            warn(format!(
+                "[SYNC.{}] \"{}\" is not a sync backend, expected one of: {}",
+                table,
+                backend,
+                SYNC_BACKENDS.join(", ")
+            ));
+        }
+    }
+    #[cfg(feature = "sync")]
+    for (backend, when) in schema
+        .sync
+        .iter()
+        .flat_map(|sync| sync.schedule.iter().flatten())
+    {
+        let jitter = schema
+            .sync
+            .as_ref()
+            .and_then(|sync| sync.jitter)
+            .unwrap_or(DEFAULT_SYNC_JITTER);
+        match crate::sync::daemon::Schedule::parse(when) {
+            Err(e) => warn(format!("[SYNC.schedule] {}: {}", backend, e)),
+            Ok(crate::sync::daemon::Schedule::Every(every)) if jitter >= every.as_secs() => {
+                warn(format!(
+                    "[SYNC] jitter of {}s is as long as the {} schedule \"{}\", its syncs won't be evenly spaced",
+                    jitter, backend, when
+                ))
+            }
+            Ok(_) => {}
+        }
+    }
+
+    if let Some(publish) = &schema.publish {
+        let url = publish.url.as_deref().unwrap_or_default().trim();
+        if !url.is_empty() && !url.starts_with("http://") && !url.starts_with("https://") {
+            warn(format!(
+                "[PUBLISH] url \"{}\" should start with http:// or https://",
+                url
+            ));
+        }
+        if url.is_empty()
+            && publish
+                .token
+                .as_ref()
+                .is_some_and(|token| !token.is_empty())
+        {
+            warn("[PUBLISH] token is set but url is empty".to_string());
+        }
+    }
+
+    if let Some(display) = &schema.display {
+        if let Some(format) = &display.date_format
+            && date_format_pattern(format).is_none()
+        {
+            warn(format!(
+                "[DISPLAY] date_format \"{}\" is not a valid date pattern, using \"%d-%m-%y\"",
+                format
+            ));
+        }
+        if let Some(day) = &display.week_starts_on
+            && !["monday", "sunday"].contains(&day.to_lowercase().as_str())
+        {
+            warn(format!(
+                "[DISPLAY] week_starts_on \"{}\" should be \"monday\" or \"sunday\", using \"monday\"",
+                day
+            ));
+        }
+        if let Some(theme) = &display.theme
+            && !crate::colors::Theme::NAMES.contains(&theme.to_lowercase().as_str())
+        {
+            warn(format!(
+                "[DISPLAY] theme \"{}\" does not exist, using \"voido\" (available: {})",
+                theme,
+                crate::colors::Theme::NAMES.join(", ")
+            ));
+        }
+        if let Some(density) = &display.density
+            && !["compact", "comfortable"].contains(&density.to_lowercase().as_str())
+        {
+            warn(format!(
+                "[DISPLAY] density \"{}\" should be \"compact\" or \"comfortable\", using \"comfortable\"",
+                density
+            ));
+        }
+        if let Some(icons) = &display.icons
+            && IconSet::by_name(icons).is_none()
+        {
+            warn(format!(
+                "[DISPLAY] icons \"{}\" should be one of {}, using \"off\"",
+                icons,
+                IconSet::NAMES.join(", ")
+            ));
+        }
+        if let Some(timezone) = &display.timezone
+            && dates::Timezone::parse(timezone).is_none()
+        {
+            warn(format!(
+                "[DISPLAY] timezone \"{}\" should be \"local\", \"UTC\" or an offset like \"+05:30\", using \"local\"",
+                timezone
+            ));
+        }
+        for column in display.hidden_columns.iter().flatten() {
+            if !crate::ui::Column::ALL
+                .iter()
+                .any(|known| known.key() == column.to_lowercase())
+            {
+                warn(format!(
+                    "[DISPLAY] hidden_columns: there is no \"{}\" column",
+                    column
+                ));
+            }
+        }
+        if let Some(row_numbers) = &display.row_numbers
+            && RowNumbers::by_name(row_numbers).is_none()
+        {
+            warn(format!(
+                "[DISPLAY] row_numbers \"{}\" should be one of {}, using \"off\"",
+                row_numbers,
+                RowNumbers::NAMES.join(", ")
+            ));
+        }
+        for (column, layout) in display.columns.iter().flatten() {
+            if !crate::ui::Column::ALL
+                .iter()
+                .any(|known| known.key() == column.trim().to_lowercase())
+            {
+                warn(format!(
+                    "[DISPLAY.columns] there is no \"{}\" column",
+                    column
+                ));
+            }
+            if let Some(align) = &layout.align
+                && parse_alignment(align).is_none()
+            {
+                warn(format!(
+                    "[DISPLAY.columns] {} align \"{}\" should be \"left\", \"center\" or \"right\", using \"left\"",
+                    column, align
+                ));
+            }
+            if layout.width == Some(0) {
+                warn(format!(
+                    "[DISPLAY.columns] {} width 0 leaves it empty, hidden_columns hides a column",
+                    column
+                ));
+            }
+            if let (Some(min), Some(max)) = (layout.min, layout.max)
+                && min > max
+            {
+                warn(format!(
+                    "[DISPLAY.columns] {} min {} is more than its max {}",
+                    column, min, max
+                ));
+            }
+        }
+        for stat in display.stats.iter().flatten() {
+            if Stat::by_key(stat).is_none() {
+                warn(format!(
+                    "[DISPLAY] stats: there is no \"{}\" counter, it should be one of {}",
+                    stat,
+                    Stat::ALL.map(Stat::key).join(", ")
+                ));
+            }
+        }
+        for (topic, color) in display.topic_colors.iter().flatten() {
+            if crate::colors::parse_color(color).is_none() {
+                warn(format!(
+                    "[DISPLAY.topic_colors] {} = \"{}\" is not a color, using the automatic one",
+                    topic, color
+                ));
+            }
+        }
+    }
+
+    if let Some(defaults) = &schema.defaults {
+        for (key, value) in [
+            ("default_owner", &defaults.default_owner),
+            ("default_topic", &defaults.default_topic),
+            ("inbox_topic", &defaults.inbox_topic),
+        ] {
+            if value.as_ref().is_some_and(|value| value.trim().is_empty()) {
+                warn(format!(
+                    "[DEFAULTS] {} is empty, using the built-in default",
+                    key
+                ));
+            }
+        }
+        if let Some(priority) = &defaults.default_priority
+            && !PRIORITY_PRESETS.contains(&priority.trim().to_lowercase().as_str())
+        {
+            warn(format!(
+                "[DEFAULTS] default_priority \"{}\" is not one of: {}",
+                priority,
+                PRIORITY_PRESETS.join(", ")
+            ));
+        }
+        if defaults.default_due_offset.is_some_and(|days| days < 0) {
+            warn(
+                "[DEFAULTS] default_due_offset is negative, new todos will be overdue".to_string(),
+            );
+        }
+    }
+
+    if let Some(archive) = &schema.archive {
+        if archive.enabled == Some(true) && archive.rules.is_empty() {
+            warn("[ARCHIVE] is enabled but has no [[ARCHIVE.rules]]".to_string());
+        }
+        if archive
+            .skip_topics
+            .iter()
+            .flatten()
+            .any(|topic| topic.trim().is_empty())
+        {
+            warn("[ARCHIVE] skip_topics contains an empty topic".to_string());
+        }
+        for rule in &archive.rules {
+            if rule.days < 0 {
+                warn(format!(
+                    "[[ARCHIVE.rules]] status \"{}\": days can't be negative, rule ignored",
+                    rule.status
+                ));
+            }
+        }
+    }
+
+    if let Some(escalation) = &schema.escalation {
+        if escalation.enabled == Some(true) && escalation.rules.is_empty() {
+            warn("[ESCALATION] is enabled but has no [[ESCALATION.rules]]".to_string());
+        }
+        for rule in &escalation.rules {
+            if !["low", "medium", "high"].contains(&rule.priority.to_lowercase().as_str()) {
+                warn(format!(
+                    "[[ESCALATION.rules]] priority \"{}\" should be Low, Medium or High",
+                    rule.priority
+                ));
+            }
+            if rule.within_days < 0 {
+                warn(format!(
+                    "[[ESCALATION.rules]] within_days {} can't be negative",
+                    rule.within_days
+                ));
+            }
+        }
+    }
+
+    if let Some(workflow) = &schema.workflow {
+        let transitions = workflow.transitions.clone().unwrap_or_default();
+        if workflow.enabled == Some(true) && transitions.is_empty() {
+            warn("[WORKFLOW] is enabled but has no [WORKFLOW.transitions]".to_string());
+        }
+        // A status nothing leads out of is a dead end, usually a typo
+        for (from, to) in &transitions {
+            for status in to {
+                if !transitions
+                    .keys()
+                    .any(|known| known.eq_ignore_ascii_case(status))
+                {
+                    warn(format!(
+                        "[WORKFLOW.transitions] {} → \"{}\": \"{}\" has no transitions of its own",
+                        from, status, status
+                    ));
+                }
+            }
+        }
+    }
+
+    if let Some(urls) = schema.notes.as_ref().and_then(|notes| notes.urls.as_ref())
+        && !["inline", "hidden"].contains(&urls.to_lowercase().as_str())
+    {
+        warn(format!(
+            "[NOTES] urls \"{}\" should be \"inline\" or \"hidden\", using \"inline\"",
+            urls
+        ));
+    }
+    if let Some(provider) = schema
+        .search
+        .as_ref()
+        .and_then(|search| search.provider.as_ref())
+        && !EMBEDDING_PROVIDERS.contains(&provider.trim().to_lowercase().as_str())
+    {
+        warn(format!(
+            "[SEARCH] provider \"{}\" is not one of: {}, using \"gemini\"",
+            provider,
+            EMBEDDING_PROVIDERS.join(", ")
+        ));
+    }
+
+    for rule in schema.styling.iter().flat_map(|styling| &styling.rules) {
+        if let Err(e) = StyleRule::parse(rule) {
+            warn(format!("[STYLING] rule `{}`: {}, rule ignored", rule, e));
+        }
+    }
+
+    for (name, profile) in schema.profiles.iter().flatten() {
+        if let Some(theme) = &profile.theme
+            && !crate::colors::Theme::NAMES.contains(&theme.to_lowercase().as_str())
+        {
+            warn(format!(
+                "[profile.{}] theme \"{}\" does not exist (available: {})",
+                name,
+                theme,
+                crate::colors::Theme::NAMES.join(", ")
+            ));
+        }
+        for (key, value) in [
+            ("db_path", &profile.db_path),
+            ("default_owner", &profile.default_owner),
+            ("repo_name", &profile.repo_name),
+        ] {
+            if value.as_ref().is_some_and(|value| value.trim().is_empty()) {
+                warn(format!(
+                    "[profile.{}] {} is empty, using the shared setting",
+                    name, key
+                ));
+            }
+        }
+        // Empty is github.com again
+        #[cfg(feature = "sync")]
+        if let Some(remote) = &profile.remote
+            && !remote.trim().is_empty()
+            && crate::sync::forge::RemoteUrl::parse(remote).is_none()
+        {
+            warn(format!(
+                "[profile.{}] remote \"{}\" is not a git URL like git@host:owner/repo.git",
+                name, remote
+            ));
+        }
+    }
+
+    for (key, actions) in schema.macros.iter().flatten() {
+        let actions = actions.iter().map(String::as_str).collect::<Vec<&str>>();
+        if let Err(e) = KeyMacro::parse(key, &actions) {
+            warn(format!("[MACROS] {}: {}, macro ignored", key, e));
+        }
+    }
+}
+
+// 1-based line of a byte offset in the file
+fn line_number(content: &str, span: Option<std::ops::Range<usize>>) -> Option<usize> {
+    let start = span?.start.min(content.len());
+    Some(content[..start].matches('\n').count() + 1)
+}
+
+fn line_label(content: &str, span: Option<std::ops::Range<usize>>) -> String {
+    match line_number(content, span) {
+        Some(line) => format!("line {}", line),
+        None => "error".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_config_reports_type_errors_with_line() {
+        let report = check_config("[ARCHIVE]\nenabled = \"yes\"\n");
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].starts_with("line 2:"));
+        assert!(report.errors[0].contains("enabled = \"yes\""));
+    }
+
+    #[test]
+    fn test_check_config_warns_about_unknown_keys() {
+        let report = check_config("[DISPLAY]\ntheme = \"ocean\"\nthem = \"forest\"\n\n[EXTRA]\n");
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings[0].starts_with("line 3: unknown key `them`"));
+        assert!(report.warnings[1].starts_with("line 5: unknown section [EXTRA]"));
+    }
+
+    #[test]
+    fn test_macros_accept_any_key_and_skip_broken_ones() {
+        let content = "[MACROS]\n1 = [\"priority High\", \"pin\"]\n2 = [\"archive\"]\n";
+        let report = check_config(content);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].starts_with("[MACROS] 2: unknown action `archive`"));
+
+        let config = toml::from_str::<toml::Value>(content).unwrap();
+        let macros = MacrosConfig::from_toml(&config);
+        assert_eq!(macros.macros.len(), 1);
+        assert!(macros.find('1').is_some());
+        assert!(macros.find('2').is_none());
+    }
+
+    #[test]
+    fn test_profile_overrides_settings() {
+        let content = "[DISPLAY]\ntheme = \"voido\"\ndensity = \"compact\"\n\n\
+            [profile.work]\ntheme = \"ocean\"\ndefault_owner = \"Ana\"\nrepo_name = \"voido_work\"\n";
+        assert!(check_config(content).is_clean());
+        assert_eq!(
+            check_config("[profile.work]\nthem = \"ocean\"\n")
+                .warnings
+                .len(),
+            1
+        );
+
+        let mut config = toml::from_str::<toml::Value>(content).unwrap();
+        assert!(!apply_profile(&mut config.clone(), "home"));
+        assert!(apply_profile(&mut config, "work"));
+        let display = DisplayConfig::from_toml(&config);
+        assert_eq!(display.theme, "ocean");
+        assert!(display.compact);
+        assert_eq!(DefaultsConfig::from_toml(&config).owner, "Ana");
+        #[cfg(feature = "sync")]
+        assert_eq!(AppConfigs::from_toml(&config).repo_name, "voido_work");
+    }
+
+    #[test]
+    fn test_workflow_transitions() {
+        let content = "[WORKFLOW]\nenabled = true\n\n[WORKFLOW.transitions]\n\
+            Ongoing = [\"Review\"]\nReview = [\"Done\", \"Ongoing\"]\nDone = [\"Archvied\"]\n";
+        let report = check_config(content);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("\"Archvied\" has no transitions"));
+
+        let config = toml::from_str::<toml::Value>(content).unwrap();
+        let mut workflow = WorkflowConfig::from_toml(&config);
+        assert!(workflow.allows("Ongoing", "review"));
+        assert!(!workflow.allows("Ongoing", "Done"));
+        assert!(workflow.allows("Done", "Done"));
+        // Statuses the workflow doesn't list can go anywhere
+        assert!(workflow.allows("Pending", "Done"));
+        assert_eq!(workflow.next("review").unwrap(), ["Done", "Ongoing"]);
+        workflow.enabled = false;
+        assert!(workflow.allows("Ongoing", "Done"));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_sync_topics() {
+        let content = "[SYNC.topics]\nGitHub = [\"Personal\"]\ngitlub = [\"Work\"]\n";
+        let report = check_config(content);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("gitlub"));
+
+        let config = toml::from_str::<toml::Value>(content).unwrap();
+        let scope = SyncConfig::from_toml(&config);
+        assert!(scope.includes("github", "personal"));
+        assert!(!scope.includes("github", "Work"));
+        // Backends it doesn't list get every topic
+        assert!(scope.includes("google", "Work"));
+    }
+
+    #[test]
+    fn test_gemini_settings() {
+        let content = "[GEMINI]\nmodel = \"gemini-2.5-pro\"\ntemperature = 1\nmax_tokens = 512\n\
+            safety = \"Few\"\n";
+        assert!(check_config(content).is_clean());
+        let config = toml::from_str::<toml::Value>(content).unwrap();
+        let gemini = GeminiConfig::from_toml(&config);
+        assert_eq!(gemini.model, "gemini-2.5-pro");
+        assert_eq!(gemini.temperature, Some(1.0));
+        assert_eq!(gemini.max_tokens, Some(512));
+        assert_eq!(gemini.safety, Some("BLOCK_ONLY_HIGH"));
+
+        // Anything VoiDo can't send is reported and left to the model
+        let content = "[GEMINI]\nmodel = \"gemini-pro\"\ntemperature = 3.5\nmax_tokens = 0\n\
+            safety = \"strict\"\n";
+        assert_eq!(check_config(content).warnings.len(), 4);
+        let config = toml::from_str::<toml::Value>(content).unwrap();
+        let gemini = GeminiConfig::from_toml(&config);
+        assert_eq!(gemini.model, DEFAULT_MODEL);
+        assert_eq!(
+            (gemini.temperature, gemini.max_tokens, gemini.safety),
+            (None, None, None)
+        );
+    }
+
+    #[test]
+    fn test_missing_sections_fall_back_to_defaults() {
+        assert!(check_config("").is_clean());
+        let config = toml::from_str::<toml::Value>("[DISPLAY]\ntheme = \"ocean\"").unwrap();
+        assert_eq!(GeminiConfig::from_toml(&config).model, DEFAULT_MODEL);
+        #[cfg(feature = "sync")]
+        assert_eq!(AppConfigs::from_toml(&config).repo_name, "voido_sync");
     }
 }
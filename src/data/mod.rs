@@ -1,4 +1,9 @@
-use crate::{arguments::models::Todo, database::DBtodo};
+use chrono::{Duration, Local, Utc};
+
+use crate::{
+    arguments::models::{Subtask, Todo},
+    database::DBtodo,
+};
 
 pub fn sample_todos() -> Vec<Todo> {
     let db = DBtodo::new().unwrap();
@@ -6,3 +11,120 @@ pub fn sample_todos() -> Vec<Todo> {
     let todos = db.get_todos().unwrap().iter().cloned().collect();
     todos
 }
+
+// DEMO TODOS: a small realistic list for `voido --demo` and the empty-state screen.
+// Dates are relative to today so the due, countdown and today views always have something to show.
+pub fn demo_todos() -> Vec<Todo> {
+    let today = Local::now().date_naive();
+    let subtask = |text: &str, done: bool| Subtask {
+        todo_id: 0,
+        subtask_id: 0,
+        text: text.to_string(),
+        status: if done { "Done" } else { "Pending" }.to_string(),
+        due: None,
+        owner: None,
+        note: String::new(),
+    };
+    let todo = |priority: &str, topic: &str, text: &str, status: &str, owner: &str| Todo {
+        id: 0,
+        priority: priority.to_string(),
+        topic: topic.to_string(),
+        text: text.to_string(),
+        desc: String::new(),
+        date_added: Utc::now(),
+        status: status.to_string(),
+        owner: owner.to_string(),
+        due: None,
+        subtasks: Vec::new(),
+        notes: String::new(),
+        pinned: false,
+        updated_at: None,
+        archived: false,
+        waiting_on: None,
+        context: None,
+        external_url: None,
+        due_at: None,
+    };
+
+    vec![
+        Todo {
+            desc: "Everything the team needs before the v2 launch".to_string(),
+            due: Some(today + Duration::days(3)),
+            pinned: true,
+            subtasks: vec![
+                subtask("Freeze the feature list", true),
+                subtask("Write the release notes", false),
+                subtask("Tag the release", false),
+            ],
+            notes: "## Launch\n- [x] Staging is green\n- [ ] Announce on the blog".to_string(),
+            ..todo("High", "Work", "Ship the v2 release", "Ongoing", "You")
+        },
+        Todo {
+            due: Some(today - Duration::days(1)),
+            ..todo(
+                "High",
+                "Work",
+                "Reply to the security audit",
+                "Pending",
+                "Ana",
+            )
+        },
+        Todo {
+            due: Some(today),
+            subtasks: vec![
+                subtask("Collect the receipts", true),
+                subtask("Fill in the form", false),
+            ],
+            ..todo(
+                "Medium",
+                "Home",
+                "Submit the expense report",
+                "Pending",
+                "You",
+            )
+        },
+        Todo {
+            due: Some(today + Duration::days(10)),
+            ..todo("Medium", "Work", "Plan the Q3 roadmap", "Planned", "Joe")
+        },
+        Todo {
+            desc: "Ask about the knee".to_string(),
+            context: Some("phone".to_string()),
+            due: Some(today + Duration::days(6)),
+            ..todo(
+                "Low",
+                "Health",
+                "Book a physio appointment",
+                "Pending",
+                "You",
+            )
+        },
+        Todo {
+            context: Some("errands".to_string()),
+            subtasks: vec![
+                subtask("Paint", false),
+                subtask("Brushes", false),
+                subtask("Drop cloth", false),
+            ],
+            ..todo("Low", "Home", "Repaint the spare room", "Pending", "You")
+        },
+        Todo {
+            date_added: Utc::now() - Duration::days(5),
+            updated_at: Some(Utc::now() - Duration::days(2)),
+            ..todo(
+                "Medium",
+                "Work",
+                "Review the onboarding docs",
+                "Done",
+                "Ana",
+            )
+        },
+        todo(
+            "Low",
+            "General",
+            "Read \"The Pragmatic Programmer\"",
+            "Pending",
+            "You",
+        ),
+    ]
+}
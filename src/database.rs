@@ -1,52 +1,697 @@
 use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use directories::BaseDirs;
-use rusqlite::{Connection, Result, params};
+use chrono::{DateTime, NaiveDate, Utc};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, Result, params};
+#[cfg(feature = "sync")]
+use serde::Serialize;
 
-use crate::arguments::models::{Subtask, Todo};
+use crate::arguments::apply::{Operation, Selector};
+use crate::arguments::explode::ListItem;
+use crate::arguments::habits::Cadence;
+use crate::arguments::models::{
+    Checklist, Comment, Milestone, Owner, REVIEW_STATUS, Subtask, Todo,
+};
+use crate::configs::{self, WorkflowConfig};
+use crate::dates;
+use crate::macros::MacroAction;
 
-pub struct ConfigDir {
-    pub config_dir: String,
+// Schema version kept in `PRAGMA user_version`, bumped by each data migration
+const SCHEMA_VERSION: i64 = 3;
+
+// Subtasks and dependencies go away with their todo (ON DELETE CASCADE)
+const SUBTASKS_COLUMNS: &str = "
+               id INTEGER PRIMARY KEY AUTOINCREMENT,
+               todo_id INTEGER NOT NULL,
+               text TEXT NOT NULL,
+               status TEXT NOT NULL,
+               due TEXT,
+               owner TEXT,
+               note TEXT NOT NULL DEFAULT '',
+               FOREIGN KEY (todo_id) REFERENCES todos(id) ON DELETE CASCADE
+";
+const DEPENDENCIES_COLUMNS: &str = "
+               todo_id INTEGER NOT NULL,
+               blocked_by INTEGER NOT NULL,
+               PRIMARY KEY (todo_id, blocked_by),
+               FOREIGN KEY (todo_id) REFERENCES todos(id) ON DELETE CASCADE,
+               FOREIGN KEY (blocked_by) REFERENCES todos(id) ON DELETE CASCADE
+";
+
+// `voido --demo`: one in-memory database shared by every connection, gone when VoiDo exits
+const DEMO_DATABASE: &str = "file:voido-demo?mode=memory&cache=shared";
+static DEMO: AtomicBool = AtomicBool::new(false);
+
+// `--dry-run`: an in-memory copy of the real database takes the writes instead
+const DRY_RUN_DATABASE: &str = "file:voido-dry-run?mode=memory&cache=shared";
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+// The database file whose tables this run already checked, later connections to it skip `open`'s
+// CREATE TABLE statements and migrations
+static SCHEMA_CHECKED: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+// Timestamps are stored as RFC 3339 in UTC
+fn now_timestamp() -> String {
+    chrono::Utc::now().to_rfc3339()
+}
+
+fn due_to_sql(due: Option<NaiveDate>) -> Option<String> {
+    due.map(|date| date.format(dates::ISO_DATE_FORMAT).to_string())
 }
 
+// (todo id, the text it was embedded from, vector) in the embeddings table
+pub type Embedding = (usize, String, Vec<f32>);
+
 pub struct DBtodo {
     pub connection: rusqlite::Connection,
 }
 
-impl ConfigDir {
-    pub fn new() -> ConfigDir {
-        let base_dirs = BaseDirs::new().unwrap();
-        let config_dir = base_dirs.config_dir().join("voido");
-        ConfigDir {
-            config_dir: config_dir.to_str().unwrap().to_string(),
+// Add a column to an existing table if an older database doesn't have it yet
+fn ensure_column(
+    connection: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), Box<dyn Error>> {
+    let mut stmt = connection.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if !columns.iter().any(|name| name == column) {
+        connection.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+// Add the owner if it is new and return the spelling it was first added with, so "joe" and
+// "Joe" end up as one owner
+fn register_owner(connection: &Connection, name: &str) -> Result<String, Box<dyn Error>> {
+    if name.trim().is_empty() {
+        return Ok(name.to_string());
+    }
+    connection.execute(
+        "INSERT OR IGNORE INTO owners (name) VALUES (?1)",
+        params![name],
+    )?;
+    let name = connection.query_row(
+        "SELECT name FROM owners WHERE name = ?1",
+        params![name],
+        |row| row.get(0),
+    )?;
+    Ok(name)
+}
+
+// Refuse a status change the [WORKFLOW] transitions don't allow, a missing todo passes
+fn check_transition(
+    connection: &Connection,
+    id: i32,
+    status: &str,
+    workflow: &WorkflowConfig,
+) -> Result<(), Box<dyn Error>> {
+    let current: Option<String> = connection
+        .query_row(
+            "SELECT status FROM todos WHERE id = ?",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match current {
+        Some(current) => workflow
+            .check(&current, status)
+            .map_err(|e| format!("Todo {} {}", id, e).into()),
+        None => Ok(()),
+    }
+}
+
+// Insert a todo and its subtasks, returning the new todo ID
+fn insert_todo(connection: &Connection, todo: &Todo) -> Result<i64, Box<dyn Error>> {
+    let owner = register_owner(connection, &todo.owner)?;
+    let waiting_on = todo
+        .waiting_on
+        .as_deref()
+        .map(|owner| register_owner(connection, owner))
+        .transpose()?;
+    // First insert the todo and get its ID. Batch adds run these statements once per todo.
+    connection
+        .prepare_cached(
+            "INSERT INTO todos (priority, topic, text, desc, date_added, due, status, owner, notes, pinned, updated_at, archived, waiting_on, context, due_at, external_url)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+        )?
+        .execute(params![
+            &todo.priority,
+            &todo.topic,
+            &todo.text,
+            &todo.desc,
+            todo.date_added.to_rfc3339(),
+            due_to_sql(todo.due),
+            &todo.status,
+            owner,
+            &todo.notes,
+            &todo.pinned,
+            todo.updated_at
+                .map(|updated_at| updated_at.to_rfc3339())
+                .unwrap_or_default(),
+            &todo.archived,
+            waiting_on,
+            &todo.context,
+            todo.due_at.map(|due_at| due_at.to_rfc3339()),
+            &todo.external_url
+        ])?;
+
+    // Get the last inserted row ID (the todo's ID)
+    let todo_id = connection.last_insert_rowid();
+
+    // Now insert subtasks with the correct todo_id
+    for subtask in &todo.subtasks {
+        let owner = subtask
+            .owner
+            .as_deref()
+            .map(|owner| register_owner(connection, owner))
+            .transpose()?;
+        connection
+            .prepare_cached(
+                "INSERT INTO subtasks (todo_id, text, status, due, owner, note) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )?
+            .execute(params![
+                todo_id,
+                &subtask.text,
+                &subtask.status,
+                due_to_sql(subtask.due),
+                owner,
+                &subtask.note
+            ])?;
+    }
+    Ok(todo_id)
+}
+
+fn insert_comment(
+    connection: &Connection,
+    todo_id: i64,
+    author: &str,
+    text: &str,
+    created_at: DateTime<Utc>,
+) -> Result<i64, Box<dyn Error>> {
+    connection.execute(
+        "INSERT INTO comments (todo_id, author, text, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![todo_id, author, text, created_at.to_rfc3339()],
+    )?;
+    Ok(connection.last_insert_rowid())
+}
+
+// Create a checklist or replace the items of the one with that name
+fn write_checklist(
+    connection: &Connection,
+    name: &str,
+    items: &[String],
+) -> Result<(), Box<dyn Error>> {
+    connection.execute(
+        "INSERT INTO checklists (name) VALUES (?1) ON CONFLICT(name) DO NOTHING",
+        params![name],
+    )?;
+    let id: i64 = connection.query_row(
+        "SELECT id FROM checklists WHERE name = ?1",
+        params![name],
+        |row| row.get(0),
+    )?;
+    connection.execute(
+        "DELETE FROM checklist_items WHERE checklist_id = ?",
+        params![id],
+    )?;
+    for (position, text) in items.iter().enumerate() {
+        connection.execute(
+            "INSERT INTO checklist_items (checklist_id, position, text) VALUES (?1, ?2, ?3)",
+            params![id, position as i64, text],
+        )?;
+    }
+    Ok(())
+}
+
+// Bring rows written by older versions up to the current schema version
+fn migrate_data(connection: &mut Connection) -> Result<(), Box<dyn Error>> {
+    let version: i64 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version >= SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let tx = connection.transaction()?;
+
+    // v1: dates move from "%d-%m-%y" strings to ISO-8601
+    if version < 1 {
+        let rows = {
+            let mut stmt = tx.prepare("SELECT id, date_added, due, updated_at FROM todos")?;
+            stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<String>>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+        };
+
+        for (id, date_added, due, updated_at) in rows {
+            let date_added = dates::parse_timestamp(&date_added)
+                .map(|date_added| date_added.to_rfc3339())
+                .unwrap_or(date_added);
+            let due = due_to_sql(due.as_deref().and_then(dates::parse_date));
+            let updated_at = updated_at
+                .as_deref()
+                .and_then(dates::parse_timestamp)
+                .map(|updated_at| updated_at.to_rfc3339())
+                .unwrap_or_default();
+            tx.execute(
+                "UPDATE todos SET date_added = ?1, due = ?2, updated_at = ?3 WHERE id = ?4",
+                params![date_added, due, updated_at, id],
+            )?;
+        }
+
+        let history = {
+            let mut stmt = tx.prepare("SELECT id, created_at FROM history")?;
+            stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?
+        };
+        for (id, created_at) in history {
+            if let Some(created_at) = dates::parse_timestamp(&created_at) {
+                tx.execute(
+                    "UPDATE history SET created_at = ?1 WHERE id = ?2",
+                    params![created_at.to_rfc3339(), id],
+                )?;
+            }
+        }
+    }
+
+    // v2: rebuild subtasks and dependencies with ON DELETE CASCADE (SQLite can't add it to an
+    // existing table), leaving out the rows of todos deleted before
+    if version < 2 {
+        tx.execute_batch(&format!(
+            "CREATE TABLE subtasks_v2 ({});
+             INSERT INTO subtasks_v2 (id, todo_id, text, status, due, owner, note)
+                 SELECT id, todo_id, text, status, due, owner, note FROM subtasks
+                 WHERE todo_id IN (SELECT id FROM todos);
+             DROP TABLE subtasks;
+             ALTER TABLE subtasks_v2 RENAME TO subtasks;
+             CREATE TABLE dependencies_v2 ({});
+             INSERT INTO dependencies_v2 (todo_id, blocked_by)
+                 SELECT todo_id, blocked_by FROM dependencies
+                 WHERE todo_id IN (SELECT id FROM todos) AND blocked_by IN (SELECT id FROM todos);
+             DROP TABLE dependencies;
+             ALTER TABLE dependencies_v2 RENAME TO dependencies;",
+            SUBTASKS_COLUMNS, DEPENDENCIES_COLUMNS
+        ))?;
+    }
+
+    // v3: owners get a table of their own. The spelling used most becomes the name, the other
+    // spellings of the same name ("joe", "JOE") are changed to it.
+    if version < 3 {
+        tx.execute_batch(
+            "INSERT OR IGNORE INTO owners (name)
+                 SELECT owner FROM (
+                     SELECT owner FROM todos UNION ALL
+                     SELECT owner FROM subtasks WHERE owner IS NOT NULL
+                 )
+                 WHERE trim(owner) != '' GROUP BY owner ORDER BY COUNT(*) DESC, owner;
+             UPDATE todos SET owner = (SELECT name FROM owners WHERE name = todos.owner)
+                 WHERE EXISTS (SELECT 1 FROM owners WHERE name = todos.owner);
+             UPDATE subtasks SET owner = (SELECT name FROM owners WHERE name = subtasks.owner)
+                 WHERE EXISTS (SELECT 1 FROM owners WHERE name = subtasks.owner);",
+        )?;
+    }
+
+    tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+    tx.commit()?;
+    Ok(())
+}
+
+// (todo id, new status, when) from the history table
+pub type StatusChange = (i64, String, DateTime<Utc>);
+
+// Where a todo lives on a sync backend, and when the two last agreed
+#[cfg(feature = "sync")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncLink {
+    pub todo_id: i64,
+    pub remote_id: String,
+    // The remote list or collection the item is in, when the backend has them
+    pub remote_list: String,
+    pub synced_at: DateTime<Utc>,
+}
+
+// A change to a todo: "insert", "update" or "delete"
+#[cfg(feature = "sync")]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JournalEntry {
+    pub uuid: String,
+    pub todo_id: i64,
+    pub action: String,
+    // The topic of the todo before the change, its first one for an insert
+    pub topic: String,
+    #[serde(with = "dates::timestamp_serde")]
+    pub changed_at: DateTime<Utc>,
+}
+
+// Where the last successful sync of a backend left off: the last journal entry it had seen and
+// when it started
+#[cfg(feature = "sync")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncCursor {
+    pub journal_id: i64,
+    pub synced_at: DateTime<Utc>,
+}
+
+// How far a sync backend got: the last journal entry it has seen and how its last sync went
+#[cfg(feature = "sync")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncState {
+    pub backend: String,
+    pub error: Option<String>,
+    // Todos changed since its last successful sync
+    pub pending: usize,
+    // When `voido daemon` syncs it next, none without a daemon running
+    pub next_run: Option<DateTime<Utc>>,
+}
+
+// One macro action on one todo, how many rows it changed
+fn apply_action(
+    connection: &Connection,
+    id: i32,
+    action: &MacroAction,
+    workflow: &WorkflowConfig,
+) -> Result<usize, Box<dyn Error>> {
+    let changes = match action {
+        MacroAction::Status(status) => {
+            check_transition(connection, id, status, workflow)?;
+            let changes = connection.execute(
+                "UPDATE todos SET status = ?1, updated_at = ?2,
+                    waiting_on = CASE WHEN ?1 = ?4 THEN waiting_on END
+                 WHERE id = ?3",
+                params![status, now_timestamp(), id, REVIEW_STATUS],
+            )?;
+            if changes > 0 {
+                record_history(connection, id as i64, "status", status)?;
+            }
+            changes
+        }
+        MacroAction::Priority(priority) => connection.execute(
+            "UPDATE todos SET priority = ?1, updated_at = ?2 WHERE id = ?3",
+            params![priority, now_timestamp(), id],
+        )?,
+        MacroAction::Owner(owner) => connection.execute(
+            "UPDATE todos SET owner = ?1, updated_at = ?2 WHERE id = ?3",
+            params![register_owner(connection, owner)?, now_timestamp(), id],
+        )?,
+        MacroAction::Topic(topic) => connection.execute(
+            "UPDATE todos SET topic = ?1, updated_at = ?2 WHERE id = ?3",
+            params![topic, now_timestamp(), id],
+        )?,
+        MacroAction::Pin(pinned) => connection.execute(
+            "UPDATE todos SET pinned = ?1 WHERE id = ?2",
+            params![pinned, id],
+        )?,
+    };
+    Ok(changes)
+}
+
+// Put a todo in the milestone called `name`, creating it when there is none
+fn link_milestone(connection: &Connection, todo_id: i64, name: &str) -> Result<(), Box<dyn Error>> {
+    connection.execute(
+        "INSERT INTO milestones (name) SELECT ?1
+         WHERE NOT EXISTS (SELECT 1 FROM milestones WHERE name = ?1 COLLATE NOCASE)",
+        params![name],
+    )?;
+    connection.execute(
+        "UPDATE todos SET milestone_id =
+             (SELECT id FROM milestones WHERE name = ?1 COLLATE NOCASE)
+         WHERE id = ?2",
+        params![name, todo_id],
+    )?;
+    Ok(())
+}
+
+// The ids of the unarchived todos a `voido apply` selector picks
+fn select_todos(connection: &Connection, selector: &Selector) -> Result<Vec<i32>, Box<dyn Error>> {
+    let mut stmt = connection.prepare(
+        "SELECT id FROM todos WHERE archived = 0
+           AND (?1 IS NULL OR id = ?1)
+           AND (?2 IS NULL OR topic = ?2 COLLATE NOCASE)
+           AND (?3 IS NULL OR owner = ?3 COLLATE NOCASE)
+           AND (?4 IS NULL OR status = ?4 COLLATE NOCASE)
+           AND (?5 IS NULL OR priority = ?5 COLLATE NOCASE)
+           AND (?6 IS NULL OR context = ?6)
+           AND (?7 IS NULL OR instr(lower(text), lower(?7)) > 0)
+         ORDER BY id",
+    )?;
+    let ids = stmt
+        .query_map(
+            params![
+                selector.id.map(|id| id as i64),
+                selector.topic,
+                selector.owner,
+                selector.status,
+                selector.priority,
+                selector.context,
+                selector.text
+            ],
+            |row| row.get(0),
+        )?
+        .collect::<Result<Vec<i32>, _>>()?;
+    if ids.is_empty()
+        && let Some(id) = selector.id
+    {
+        return Err(format!("no unarchived todo with id {}", id).into());
+    }
+    Ok(ids)
+}
+
+fn apply_operation(
+    connection: &Connection,
+    operation: &Operation,
+    workflow: &WorkflowConfig,
+) -> Result<usize, Box<dyn Error>> {
+    let (selector, actions, changes) = match operation {
+        Operation::Add(todo, milestone) => {
+            let id = insert_todo(connection, todo)?;
+            if let Some(name) = milestone {
+                link_milestone(connection, id, name)?;
+            }
+            return Ok(1);
+        }
+        Operation::Delete(selector) => {
+            let ids = select_todos(connection, selector)?;
+            for id in &ids {
+                // Its subtasks and dependencies are deleted with it
+                connection.execute("DELETE FROM todos WHERE id = ?", params![id])?;
+            }
+            return Ok(ids.len());
+        }
+        Operation::Complete(selector) => (
+            selector,
+            vec![MacroAction::Status("Done".to_string())],
+            None,
+        ),
+        Operation::Update(selector, changes) => (selector, changes.actions.clone(), Some(changes)),
+    };
+
+    let ids = select_todos(connection, selector)?;
+    for &id in &ids {
+        for action in &actions {
+            apply_action(connection, id, action, workflow)?;
+        }
+        let Some(changes) = changes else {
+            continue;
+        };
+        if let Some((due, due_at)) = changes.due {
+            connection.execute(
+                "UPDATE todos SET due = ?1, due_at = ?2, updated_at = ?3 WHERE id = ?4",
+                params![
+                    due_to_sql(Some(due)),
+                    due_at.map(|due_at| due_at.to_rfc3339()),
+                    now_timestamp(),
+                    id
+                ],
+            )?;
         }
+        if let Some(context) = &changes.context {
+            connection.execute(
+                "UPDATE todos SET context = ?1, updated_at = ?2 WHERE id = ?3",
+                params![context, now_timestamp(), id],
+            )?;
+        }
+    }
+    Ok(ids.len())
+}
+
+// Record an entry in the history table
+fn record_history(
+    connection: &Connection,
+    todo_id: i64,
+    action: &str,
+    detail: &str,
+) -> Result<(), Box<dyn Error>> {
+    connection.execute(
+        "INSERT INTO history (todo_id, action, detail, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![todo_id, action, detail, now_timestamp()],
+    )?;
+    Ok(())
+}
+
+// Join two text fields, skipping empty values and placeholders
+fn join_text(first: &str, second: &str, placeholder: &str) -> String {
+    let is_empty = |text: &str| text.trim().is_empty() || text == placeholder;
+    match (is_empty(first), is_empty(second)) {
+        (_, true) => first.to_string(),
+        (true, false) => second.to_string(),
+        (false, false) => format!("{}\n\n{}", first, second),
+    }
+}
+
+// A random (version 4) UUID, made by SQLite so the triggers can use it
+const SQL_UUID: &str = "lower(hex(randomblob(4)) || '-' || hex(randomblob(2)) || '-4' ||
+    substr(hex(randomblob(2)), 2) || '-' || substr('89ab', 1 + abs(random()) % 4, 1) ||
+    substr(hex(randomblob(2)), 2) || '-' || hex(randomblob(6)))";
+
+// Every write to a todo or its subtasks lands in the sync journal, wherever it comes from.
+// Nothing is journaled until a backend has synced once.
+fn create_journal_triggers(connection: &Connection) -> Result<(), Box<dyn Error>> {
+    let journal = |todo_id: &str, action: &str, topic: &str| {
+        format!(
+            "INSERT INTO sync_journal (uuid, todo_id, action, topic, changed_at)
+             SELECT {}, {}, '{}', {}, strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+             WHERE EXISTS (SELECT 1 FROM sync_state)",
+            SQL_UUID, todo_id, action, topic
+        )
+    };
+    let mut sql = String::new();
+    // The topic is the one before the change, to know when a todo left a synced topic
+    for (event, row, action, topic) in [
+        ("INSERT", "NEW", "insert", "NEW.topic"),
+        ("UPDATE", "NEW", "update", "OLD.topic"),
+        ("DELETE", "OLD", "delete", "OLD.topic"),
+    ] {
+        // Recreated every time, so older versions of them are replaced
+        sql.push_str(&format!(
+            "DROP TRIGGER IF EXISTS journal_todo_{0};
+             CREATE TRIGGER journal_todo_{0} AFTER {1} ON todos BEGIN {2}; END;\n",
+            action,
+            event,
+            journal(&format!("{}.id", row), action, topic)
+        ));
+        // A subtask change is a change to its todo, unless the todo itself is being deleted
+        sql.push_str(&format!(
+            "DROP TRIGGER IF EXISTS journal_subtask_{0};
+             CREATE TRIGGER journal_subtask_{0} AFTER {1} ON subtasks
+             WHEN EXISTS (SELECT 1 FROM todos WHERE id = {2}.todo_id) BEGIN {3}; END;\n",
+            action,
+            event,
+            row,
+            journal(
+                &format!("{}.todo_id", row),
+                "update",
+                &format!("(SELECT topic FROM todos WHERE id = {}.todo_id)", row)
+            )
+        ));
     }
+    connection.execute_batch(&sql)?;
+    Ok(())
 }
 
 impl DBtodo {
     pub fn new() -> Result<DBtodo, Box<dyn Error>> {
-        let config_dir = ConfigDir::new();
-        let folder = config_dir.config_dir;
+        if DEMO.load(Ordering::Relaxed) {
+            return Self::open(Path::new(DEMO_DATABASE));
+        }
+        if DRY_RUN.load(Ordering::Relaxed) {
+            return Self::open(Path::new(DRY_RUN_DATABASE));
+        }
+
+        // The database of the active profile, todos.db in the config folder by default
+        let db_path = configs::db_path()?;
+        if SCHEMA_CHECKED
+            .lock()
+            .is_ok_and(|checked| checked.as_ref() == Some(&db_path))
+        {
+            return Self::connect(&db_path);
+        }
+        let folder = db_path.parent().ok_or("Invalid database path")?;
 
         // Check if the folder path exists and is a file
-        if std::path::Path::new(&folder).is_file() {
-            return Err(format!("Error: Expected a directory at '{}', but found a file. Please remove or rename the file.", folder).into());
+        if folder.is_file() {
+            return Err(format!("Error: Expected a directory at '{}', but found a file. Please remove or rename the file.", folder.display()).into());
         }
 
         // Create directory if it doesn't exist
-        std::fs::create_dir_all(&folder)?;
-
-        // Create the path to the database file
-        let db_path = std::path::Path::new(&folder).join("todos.db");
-        // println!("Database path: {}", db_path.display());
+        std::fs::create_dir_all(folder)?;
 
         // Check if db_path exists and is a directory
         if db_path.exists() && db_path.is_dir() {
             return Err(format!("Error: Expected a file at '{}', but found a directory. Please remove or rename the directory.", db_path.display()).into());
         }
 
-        // Open or create the database file
-        let connection = Connection::open(&db_path)?;
+        let db = Self::open(&db_path)?;
+        if let Ok(mut checked) = SCHEMA_CHECKED.lock() {
+            *checked = Some(db_path);
+        }
+        Ok(db)
+    }
+
+    // After something replaced the tables under VoiDo, as `voido load` does, the next
+    // DBtodo::new() checks them again
+    pub fn schema_changed() {
+        if let Ok(mut checked) = SCHEMA_CHECKED.lock() {
+            *checked = None;
+        }
+    }
+
+    // A connection to a database `open` already brought up to date
+    fn connect(db_path: &Path) -> Result<DBtodo, Box<dyn Error>> {
+        let connection = Connection::open(db_path)?;
+        connection.pragma_update(None, "foreign_keys", true)?;
+        Ok(DBtodo { connection })
+    }
+
+    // Point every DBtodo::new() at the demo database. It lives as long as a connection to it
+    // is open, so keep the returned one until VoiDo exits.
+    pub fn open_demo() -> Result<DBtodo, Box<dyn Error>> {
+        DEMO.store(true, Ordering::Relaxed);
+        Self::open(Path::new(DEMO_DATABASE))
+    }
+
+    // Point every DBtodo::new() at a copy of the real database, the API key included, so a
+    // command runs as usual and the real one is never written. Keep the returned connection
+    // until VoiDo exits, as with the demo.
+    pub fn open_dry_run() -> Result<DBtodo, Box<dyn Error>> {
+        let real = Self::new()?;
+        let copy = Self::open(Path::new(DRY_RUN_DATABASE))?;
+        crate::import_export::sql::load_sql(
+            &copy.connection,
+            &crate::import_export::sql::dump_sql(&real.connection)?,
+        )?;
+        if let Ok(apikey) = real.get_api_credentials() {
+            copy.connection.execute(
+                "INSERT INTO model (name, apikey) VALUES (?, ?)",
+                params!["gemini", apikey],
+            )?;
+        }
+        DRY_RUN.store(true, Ordering::Relaxed);
+        // Opening it again puts back the journal triggers the dump dropped with its tables
+        Self::open(Path::new(DRY_RUN_DATABASE))
+    }
+
+    pub fn is_dry_run() -> bool {
+        DRY_RUN.load(Ordering::Relaxed)
+    }
+
+    // Open or create a database file and bring it up to the current schema
+    pub fn open(db_path: &Path) -> Result<DBtodo, Box<dyn Error>> {
+        let mut connection = Connection::open(db_path)?;
 
         // Initialise the MODEL TABLE
         connection.execute(
@@ -70,41 +715,197 @@ impl DBtodo {
                 due TEXT,
                 status TEXT NOT NULL,
                 owner TEXT NOT NULL,
-                notes TEXT DEFAULT ''
+                notes TEXT DEFAULT '',
+                pinned INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL DEFAULT '',
+                archived INTEGER NOT NULL DEFAULT 0
             )",
             [],
         )?;
 
         // INITIALISE THE SUBTASKS TABLE
         connection.execute(
-            "CREATE TABLE IF NOT EXISTS subtasks (
+            &format!("CREATE TABLE IF NOT EXISTS subtasks ({})", SUBTASKS_COLUMNS),
+            [],
+        )?;
+
+        // HISTORY OF CHANGES MADE TO TODOS (merges, ...)
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS history (
+               id INTEGER PRIMARY KEY AUTOINCREMENT,
+               todo_id INTEGER NOT NULL,
+               action TEXT NOT NULL,
+               detail TEXT NOT NULL DEFAULT '',
+               created_at TEXT NOT NULL
+)",
+            [],
+        )?;
+
+        // MILESTONES, todos point at one through `todos.milestone_id`
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS milestones (
+               id INTEGER PRIMARY KEY AUTOINCREMENT,
+               name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+               target TEXT
+)",
+            [],
+        )?;
+
+        // COMMENTS, a thread per todo next to the notes
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS comments (
                id INTEGER PRIMARY KEY AUTOINCREMENT,
                todo_id INTEGER NOT NULL,
+               author TEXT NOT NULL,
                text TEXT NOT NULL,
-               status TEXT NOT NULL,
-               FOREIGN KEY (todo_id) REFERENCES todos(id)
+               created_at TEXT NOT NULL,
+               FOREIGN KEY (todo_id) REFERENCES todos(id) ON DELETE CASCADE
 )",
             [],
         )?;
 
-        // Check if notes column exists and add it if it doesn't
-        {
-            let mut stmt = connection.prepare("PRAGMA table_info(todos)").unwrap();
-            let column_info: Vec<String> = stmt
-                .query_map([], |row| {
-                    let column_name: String = row.get(1)?;
-                    Ok(column_name)
-                })
-                .unwrap()
-                .collect::<Result<Vec<_>, _>>()
-                .unwrap();
+        // HABITS, todos that come back every day or week
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS habits (
+               todo_id INTEGER PRIMARY KEY,
+               every TEXT NOT NULL,
+               FOREIGN KEY (todo_id) REFERENCES todos(id) ON DELETE CASCADE
+)",
+            [],
+        )?;
 
-            if !column_info.contains(&"notes".to_string()) {
-                connection
-                    .execute("ALTER TABLE todos ADD COLUMN notes TEXT DEFAULT ''", [])
-                    .unwrap();
-            }
-        }
+        // SYNC LINKS, which remote item a todo is on each sync backend. No foreign key: the link
+        // a deleted todo leaves behind is how the next sync knows to delete the remote item too.
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS sync_links (
+               backend TEXT NOT NULL,
+               todo_id INTEGER NOT NULL,
+               remote_id TEXT NOT NULL,
+               remote_list TEXT NOT NULL DEFAULT '',
+               synced_at TEXT NOT NULL,
+               PRIMARY KEY (backend, todo_id)
+)",
+            [],
+        )?;
+
+        // SYNC JOURNAL, a row per change to a todo (or its subtasks) once a backend has synced.
+        // Each backend keeps the last entry it has seen in `sync_state`, the rows after it are
+        // the changes still queued for it.
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS sync_journal (
+               id INTEGER PRIMARY KEY AUTOINCREMENT,
+               uuid TEXT NOT NULL DEFAULT '',
+               todo_id INTEGER NOT NULL,
+               action TEXT NOT NULL,
+               topic TEXT NOT NULL DEFAULT '',
+               changed_at TEXT NOT NULL
+)",
+            [],
+        )?;
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS sync_state (
+               backend TEXT PRIMARY KEY,
+               journal_id INTEGER NOT NULL DEFAULT 0,
+               synced_at TEXT,
+               error TEXT,
+               next_run TEXT
+)",
+            [],
+        )?;
+
+        // OWNERS, todos keep the name, this adds how to show it
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS owners (
+               id INTEGER PRIMARY KEY AUTOINCREMENT,
+               name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+               display_name TEXT NOT NULL DEFAULT '',
+               color TEXT NOT NULL DEFAULT ''
+)",
+            [],
+        )?;
+
+        // REUSABLE CHECKLISTS, their items become subtasks when applied
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS checklists (
+               id INTEGER PRIMARY KEY AUTOINCREMENT,
+               name TEXT NOT NULL UNIQUE COLLATE NOCASE
+)",
+            [],
+        )?;
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS checklist_items (
+               id INTEGER PRIMARY KEY AUTOINCREMENT,
+               checklist_id INTEGER NOT NULL,
+               position INTEGER NOT NULL,
+               text TEXT NOT NULL,
+               FOREIGN KEY (checklist_id) REFERENCES checklists(id)
+)",
+            [],
+        )?;
+
+        // EMBEDDINGS for semantic search, one vector per todo with the text it was made from, so
+        // only changed todos are embedded again. Rebuilt from the todos, never dumped or synced
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS embeddings (
+               todo_id INTEGER PRIMARY KEY,
+               model TEXT NOT NULL,
+               source TEXT NOT NULL,
+               vector BLOB NOT NULL
+)",
+            [],
+        )?;
+
+        // DEPENDENCIES, `todo_id` can't start before `blocked_by` is done
+        connection.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS dependencies ({})",
+                DEPENDENCIES_COLUMNS
+            ),
+            [],
+        )?;
+
+        // Add columns introduced after the table was first created
+        ensure_column(&connection, "todos", "notes", "TEXT DEFAULT ''")?;
+        ensure_column(&connection, "todos", "pinned", "INTEGER NOT NULL DEFAULT 0")?;
+        ensure_column(
+            &connection,
+            "todos",
+            "updated_at",
+            "TEXT NOT NULL DEFAULT ''",
+        )?;
+        ensure_column(
+            &connection,
+            "todos",
+            "archived",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        ensure_column(&connection, "todos", "milestone_id", "INTEGER")?;
+        ensure_column(&connection, "todos", "waiting_on", "TEXT")?;
+        ensure_column(&connection, "todos", "context", "TEXT")?;
+        ensure_column(&connection, "todos", "due_at", "TEXT")?;
+        ensure_column(&connection, "todos", "external_url", "TEXT")?;
+        ensure_column(&connection, "subtasks", "due", "TEXT")?;
+        ensure_column(&connection, "subtasks", "owner", "TEXT")?;
+        ensure_column(&connection, "subtasks", "note", "TEXT NOT NULL DEFAULT ''")?;
+        ensure_column(
+            &connection,
+            "sync_journal",
+            "uuid",
+            "TEXT NOT NULL DEFAULT ''",
+        )?;
+        ensure_column(
+            &connection,
+            "sync_journal",
+            "topic",
+            "TEXT NOT NULL DEFAULT ''",
+        )?;
+        ensure_column(&connection, "sync_state", "next_run", "TEXT")?;
+
+        migrate_data(&mut connection)?;
+        // After the migrations, which rebuild the subtasks table and drop its triggers
+        create_journal_triggers(&connection)?;
+        // SQLite leaves foreign keys off unless each connection asks for them
+        connection.pragma_update(None, "foreign_keys", true)?;
 
         Ok(DBtodo { connection })
     }
@@ -119,43 +920,40 @@ impl DBtodo {
     }
 
     /// Adds a new todo to the database (better than standalone function)
-    pub fn add_todo(&self, todo: &Todo) -> Result<(), Box<dyn Error>> {
-        // First insert the todo and get its ID
-        self.connection.execute(
-            "INSERT INTO todos (priority, topic, text, desc, date_added, due, status, owner, notes)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
-            params![
-                &todo.priority,
-                &todo.topic,
-                &todo.text,
-                &todo.desc,
-                &todo.date_added,
-                &todo.due,
-                &todo.status,
-                &todo.owner,
-                &todo.notes
-            ],
-        )?;
+    pub fn add_todo(&self, todo: &Todo) -> Result<i64, Box<dyn Error>> {
+        insert_todo(&self.connection, todo)
+    }
 
-        // Get the last inserted row ID (the todo's ID)
-        let todo_id = self.connection.last_insert_rowid();
+    /// Adds a todo without the table checks of `open` when the database is already up to date.
+    /// A new file, a pending migration or a missing column take the usual way instead.
+    pub fn capture(todo: &Todo) -> Result<i64, Box<dyn Error>> {
+        if !DEMO.load(Ordering::Relaxed)
+            && !DRY_RUN.load(Ordering::Relaxed)
+            && let Ok(connection) =
+                Connection::open_with_flags(configs::db_path()?, OpenFlags::SQLITE_OPEN_READ_WRITE)
+        {
+            let version: i64 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+            if version == SCHEMA_VERSION
+                && let Ok(id) = insert_todo(&connection, todo)
+            {
+                return Ok(id);
+            }
+        }
+        Self::new()?.add_todo(todo)
+    }
 
-        // Now insert subtasks with the correct todo_id
-        for subtask in &todo.subtasks {
-            self.connection.execute(
-                "INSERT INTO subtasks (todo_id, text, status) VALUES (?1, ?2, ?3)",
-                params![todo_id, &subtask.text, &subtask.status],
-            )?;
+    /// Adds several todos in a single transaction, all or nothing
+    pub fn add_todos(&mut self, todos: &[Todo]) -> Result<usize, Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        for todo in todos {
+            insert_todo(&tx, todo)?;
         }
-        Ok(())
+        tx.commit()?;
+        Ok(todos.len())
     }
     // DELETE TODO BASED ON ID
     pub fn delete_todo(&self, id: i32) -> Result<(), Box<dyn Error>> {
-        // First delete all subtasks associated with this todo
-        self.connection
-            .execute("DELETE FROM subtasks WHERE todo_id = ?", params![id])?;
-
-        // Then delete the todo itself
+        // Its subtasks and dependencies are deleted with it
         let changes = self
             .connection
             .execute("DELETE FROM todos WHERE id = ?", params![id])?;
@@ -171,39 +969,72 @@ impl DBtodo {
 
     // SHOW ALL THE TODOS
     pub fn get_todos(&self) -> Result<Vec<Todo>, Box<dyn Error>> {
-        let mut stmt = self.connection.prepare(
-            "SELECT id, priority, topic, text, desc, date_added, due, status, owner, notes FROM todos",
+        self.query_todos(false)
+    }
+
+    // SHOW THE ARCHIVED TODOS
+    pub fn get_archived_todos(&self) -> Result<Vec<Todo>, Box<dyn Error>> {
+        self.query_todos(true)
+    }
+
+    fn query_todos(&self, archived: bool) -> Result<Vec<Todo>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare_cached(
+            "SELECT id, priority, topic, text, desc, date_added, due, status, owner, notes, pinned, updated_at, archived, waiting_on, context, due_at, external_url
+             FROM todos WHERE archived = ?1 ORDER BY pinned DESC, id",
         )?;
 
-        let todos_iter = stmt.query_map(params![], |row| {
+        let todos_iter = stmt.query_map(params![archived], |row| {
             Ok(Todo {
                 id: row.get(0)?,
                 priority: row.get(1)?,
                 topic: row.get(2)?,
                 text: row.get(3)?,
                 desc: row.get(4)?,
-                date_added: row.get(5)?,
-                due: row.get(6)?,
+                date_added: dates::parse_timestamp(&row.get::<_, String>(5)?).unwrap_or_default(),
+                due: row
+                    .get::<_, Option<String>>(6)?
+                    .as_deref()
+                    .and_then(dates::parse_date),
                 status: row.get(7)?,
                 owner: row.get(8)?,
                 notes: row.get(9).unwrap_or_default(),
+                pinned: row.get(10)?,
+                updated_at: row
+                    .get::<_, Option<String>>(11)?
+                    .as_deref()
+                    .and_then(dates::parse_timestamp),
+                archived: row.get(12)?,
+                waiting_on: row.get(13)?,
+                context: row.get(14)?,
+                due_at: row
+                    .get::<_, Option<String>>(15)?
+                    .as_deref()
+                    .and_then(dates::parse_timestamp),
+                external_url: row.get(16)?,
                 subtasks: Vec::new(),
             })
         })?;
 
+        // Prepared once, run for every todo
+        let mut subtasks_stmt = self.connection.prepare_cached(
+            "SELECT id, text, status, due, owner, note FROM subtasks WHERE todo_id = ?",
+        )?;
         let mut todos: Vec<Todo> = Vec::new();
         for todo_result in todos_iter {
             let mut todo = todo_result?;
 
-            let mut subtasks_stmt = self
-                .connection
-                .prepare("SELECT id, text, status FROM subtasks WHERE todo_id = ?")?;
             let subtasks_iter = subtasks_stmt.query_map(params![todo.id], |row| {
                 Ok(Subtask {
                     todo_id: todo.id,
                     subtask_id: row.get(0)?,
                     text: row.get(1)?,
                     status: row.get(2)?,
+                    due: row
+                        .get::<_, Option<String>>(3)?
+                        .as_deref()
+                        .and_then(dates::parse_date),
+                    owner: row.get(4)?,
+                    note: row.get(5)?,
                 })
             })?;
 
@@ -217,25 +1048,73 @@ impl DBtodo {
         Ok(todos)
     }
 
-    // UPDATE TODO STATUS
+    // UPDATE TODO STATUS, within the [WORKFLOW] transitions when they are enabled
     pub fn update_todo(&self, id: i32, status: Option<String>) -> Result<(), Box<dyn Error>> {
-        let changes = self.connection.execute(
-            "UPDATE todos SET status = ? WHERE id = ?",
-            params![status, id],
-        )?;
-        if changes > 0 {
+        let Some(status) = status else {
             return Ok(());
-        } else {
+        };
+        if !self.set_status(id, &status, None, &WorkflowConfig::load())? {
             println!("❌ No todo found with id: {}", id);
         }
         Ok(())
     }
 
+    // Move a todo to `status`, `waiting_on` is who a Review waits for. Leaving Review forgets
+    // who it was waiting on. Returns false when there is no such todo.
+    pub fn set_status(
+        &self,
+        id: i32,
+        status: &str,
+        waiting_on: Option<&str>,
+        workflow: &WorkflowConfig,
+    ) -> Result<bool, Box<dyn Error>> {
+        check_transition(&self.connection, id, status, workflow)?;
+        let waiting_on = waiting_on
+            .filter(|_| status == REVIEW_STATUS)
+            .map(|owner| register_owner(&self.connection, owner))
+            .transpose()?;
+        let changes = self.connection.execute(
+            "UPDATE todos SET status = ?1, updated_at = ?2,
+                waiting_on = CASE WHEN ?1 = ?4 THEN coalesce(?3, waiting_on) END
+             WHERE id = ?5",
+            params![status, now_timestamp(), waiting_on, REVIEW_STATUS, id],
+        )?;
+        if changes == 0 {
+            return Ok(false);
+        }
+        // Completion dates for the burndown report come from these entries
+        record_history(&self.connection, id as i64, "status", status)?;
+        Ok(true)
+    }
+
+    // Every recorded status change as (todo id, new status, when), oldest first
+    pub fn status_history(&self) -> Result<Vec<StatusChange>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT todo_id, detail, created_at FROM history WHERE action = 'status' ORDER BY created_at, id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut history = Vec::new();
+        for row in rows {
+            let (todo_id, status, created_at) = row?;
+            if let Some(created_at) = dates::parse_timestamp(&created_at) {
+                history.push((todo_id, status, created_at));
+            }
+        }
+        Ok(history)
+    }
+
     // UPDATE TODO PRIORITY
     pub fn update_priority(&self, id: i32, priority: String) -> Result<(), Box<dyn Error>> {
         let changes = self.connection.execute(
-            "UPDATE todos SET priority = ? WHERE id = ?",
-            params![priority, id],
+            "UPDATE todos SET priority = ?, updated_at = ? WHERE id = ?",
+            params![priority, now_timestamp(), id],
         )?;
         if changes > 0 {
             return Ok(());
@@ -245,24 +1124,813 @@ impl DBtodo {
         Ok(())
     }
 
-    // CLEAR ALL TODOS FROM DB
-    pub fn clear_all_todos(&self) -> Result<(), Box<dyn Error>> {
-        let changes = self.connection.execute("DELETE FROM todos", params![])?;
-        let changes_sub = self.connection.execute("DELETE FROM subtasks", params![])?;
-        if changes > 0 && changes_sub > 0 {
-            println!("✅ All todos cleared successfully!");
-        } else {
-            println!("❌ No todos found.");
+    // SET OR CLEAR THE CONTEXT OF A TODO
+    pub fn set_context(&self, id: i32, context: Option<&str>) -> Result<bool, Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "UPDATE todos SET context = ?1, updated_at = ?2 WHERE id = ?3",
+            params![context, now_timestamp(), id],
+        )?;
+        Ok(changes > 0)
+    }
+
+    // SET OR CLEAR THE LINK OF A TODO
+    pub fn set_external_url(&self, id: i32, url: Option<&str>) -> Result<bool, Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "UPDATE todos SET external_url = ?1, updated_at = ?2 WHERE id = ?3",
+            params![url, now_timestamp(), id],
+        )?;
+        Ok(changes > 0)
+    }
+
+    // PIN OR UNPIN A TODO
+    pub fn set_pinned(&self, id: i32, pinned: bool) -> Result<(), Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "UPDATE todos SET pinned = ? WHERE id = ?",
+            params![pinned, id],
+        )?;
+        if changes == 0 {
+            println!("❌ No todo found with id: {}", id);
         }
         Ok(())
     }
 
-    pub fn flush_db(&self) -> Result<(), Box<dyn Error>> {
-        let changes = self.connection.execute("DELETE FROM todos", params![])?;
-        // clear subtasks
-        let changes_sub = self.connection.execute("DELETE FROM subtasks", params![])?;
-        if changes > 0 {
-            println!("");
+    // RENAME A TOPIC ON ALL MATCHING TODOS
+    pub fn rename_topic(&self, from: &str, to: &str) -> Result<usize, Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "UPDATE todos SET topic = ?1, updated_at = ?2 WHERE topic = ?3 COLLATE NOCASE",
+            params![to, now_timestamp(), from],
+        )?;
+        Ok(changes)
+    }
+
+    // REASSIGN TODOS FROM ONE OWNER TO ANOTHER
+    pub fn reassign_owner(
+        &self,
+        from: &str,
+        to: &str,
+        topic: Option<&str>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let to = register_owner(&self.connection, to)?;
+        let changes = self.connection.execute(
+            "UPDATE todos SET owner = ?1, updated_at = ?2
+             WHERE owner = ?3 COLLATE NOCASE
+             AND (?4 IS NULL OR topic = ?4 COLLATE NOCASE)",
+            params![to, now_timestamp(), from, topic],
+        )?;
+        Ok(changes)
+    }
+
+    // MERGE ONE TODO INTO ANOTHER
+    // Keeps `keep`, appends the description and notes of `other`, moves its
+    // subtasks, comments and dependencies over and deletes it. Everything happens in one
+    // transaction.
+    pub fn merge_todos(&mut self, keep: i32, other: i32) -> Result<(), Box<dyn Error>> {
+        if keep == other {
+            return Err("Cannot merge a todo into itself".into());
+        }
+
+        let tx = self.connection.transaction()?;
+
+        let fetch = |id: i32| -> Result<(String, String, String, bool), Box<dyn Error>> {
+            let row = tx
+                .query_row(
+                    "SELECT text, desc, notes, pinned FROM todos WHERE id = ?",
+                    params![id],
+                    |row| {
+                        Ok((
+                            row.get::<_, String>(0)?,
+                            row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                            row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                            row.get::<_, bool>(3)?,
+                        ))
+                    },
+                )
+                .optional()?;
+            row.ok_or_else(|| format!("No todo found with id: {}", id).into())
+        };
+        let (_, keep_desc, keep_notes, keep_pinned) = fetch(keep)?;
+        let (other_text, other_desc, other_notes, other_pinned) = fetch(other)?;
+
+        tx.execute(
+            "UPDATE todos SET desc = ?1, notes = ?2, pinned = ?3, updated_at = ?4 WHERE id = ?5",
+            params![
+                join_text(&keep_desc, &other_desc, "No description provided"),
+                join_text(&keep_notes, &other_notes, ""),
+                keep_pinned || other_pinned,
+                now_timestamp(),
+                keep
+            ],
+        )?;
+        tx.execute(
+            "UPDATE subtasks SET todo_id = ?1 WHERE todo_id = ?2",
+            params![keep, other],
+        )?;
+        tx.execute(
+            "UPDATE comments SET todo_id = ?1 WHERE todo_id = ?2",
+            params![keep, other],
+        )?;
+        // A link between the two would block the kept todo on itself, one it already has is
+        // left to go with `other`
+        tx.execute(
+            "DELETE FROM dependencies
+             WHERE (todo_id = ?1 AND blocked_by = ?2) OR (todo_id = ?2 AND blocked_by = ?1)",
+            params![keep, other],
+        )?;
+        tx.execute(
+            "UPDATE OR IGNORE dependencies SET todo_id = ?1 WHERE todo_id = ?2",
+            params![keep, other],
+        )?;
+        tx.execute(
+            "UPDATE OR IGNORE dependencies SET blocked_by = ?1 WHERE blocked_by = ?2",
+            params![keep, other],
+        )?;
+        tx.execute("DELETE FROM todos WHERE id = ?", params![other])?;
+        record_history(
+            &tx,
+            keep as i64,
+            "merge",
+            &format!("Merged #{} \"{}\"", other, other_text),
+        )?;
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    // ARCHIVE TODOS, they stay in the DB but are hidden from the list
+    pub fn archive_todos(&mut self, ids: &[i32]) -> Result<usize, Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        let mut changes = 0;
+        for id in ids {
+            // Missing and already archived todos get no history entry
+            let changed = tx.execute(
+                "UPDATE todos SET archived = 1, updated_at = ?1 WHERE id = ?2 AND archived = 0",
+                params![now_timestamp(), id],
+            )?;
+            if changed > 0 {
+                record_history(&tx, *id as i64, "archive", "")?;
+            }
+            changes += changed;
+        }
+        tx.commit()?;
+        Ok(changes)
+    }
+
+    // CHANGE THE PRIORITY OF SEVERAL TODOS, recording each change in the history
+    pub fn escalate_priorities(
+        &mut self,
+        changes: &[(i32, String, String)],
+    ) -> Result<(), Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        for (id, from, to) in changes {
+            tx.execute(
+                "UPDATE todos SET priority = ?1, updated_at = ?2 WHERE id = ?3",
+                params![to, now_timestamp(), id],
+            )?;
+            record_history(&tx, *id as i64, "escalate", &format!("{} → {}", from, to))?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    // RUN A KEY MACRO ON ONE TODO
+    // All actions land in one transaction, a failing step leaves the todo untouched
+    pub fn apply_macro(&mut self, id: i32, actions: &[MacroAction]) -> Result<(), Box<dyn Error>> {
+        let workflow = WorkflowConfig::load();
+        let tx = self.connection.transaction()?;
+        for action in actions {
+            if apply_action(&tx, id, action, &workflow)? == 0 {
+                return Err(format!("No todo found with id: {}", id).into());
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    // RUN A `voido apply` BATCH
+    // Operations run in order, each one sees what the ones before did, and the first one that
+    // fails rolls the whole batch back. Returns how many todos each operation touched.
+    pub fn apply_batch(&mut self, operations: &[Operation]) -> Result<Vec<usize>, Box<dyn Error>> {
+        let workflow = WorkflowConfig::load();
+        let tx = self.connection.transaction()?;
+        let mut counts = Vec::new();
+        for (index, operation) in operations.iter().enumerate() {
+            let count = apply_operation(&tx, operation, &workflow)
+                .map_err(|e| format!("operation {} ({}): {}", index + 1, operation.label(), e))?;
+            counts.push(count);
+        }
+        tx.commit()?;
+        Ok(counts)
+    }
+
+    // BRING AN ARCHIVED TODO BACK INTO THE LIST
+    // Touching `updated_at` keeps the auto-archive rules from moving it straight back
+    pub fn unarchive_todo(&self, id: i32) -> Result<usize, Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "UPDATE todos SET archived = 0, updated_at = ?1 WHERE id = ?2 AND archived = 1",
+            params![now_timestamp(), id],
+        )?;
+        if changes > 0 {
+            record_history(&self.connection, id as i64, "restore", "")?;
+        }
+        Ok(changes)
+    }
+
+    // SET A NEW DUE DATE ON SEVERAL TODOS
+    pub fn reschedule_todos(
+        &mut self,
+        ids: &[i32],
+        due: NaiveDate,
+        due_at: Option<DateTime<Utc>>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let due = due_to_sql(Some(due));
+        // A new date without a time drops the old time of day
+        let due_at = due_at.map(|due_at| due_at.to_rfc3339());
+        let tx = self.connection.transaction()?;
+        let mut changes = 0;
+        for id in ids {
+            changes += tx.execute(
+                "UPDATE todos SET due = ?1, due_at = ?2, updated_at = ?3 WHERE id = ?4",
+                params![due, due_at, now_timestamp(), id],
+            )?;
+            record_history(
+                &tx,
+                *id as i64,
+                "reschedule",
+                &format!(
+                    "Due {}",
+                    due_at.as_deref().or(due.as_deref()).unwrap_or("-")
+                ),
+            )?;
+        }
+        tx.commit()?;
+        Ok(changes)
+    }
+
+    // CREATE A MILESTONE, or change the target date of an existing one
+    pub fn save_milestone(
+        &self,
+        name: &str,
+        target: Option<NaiveDate>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "INSERT INTO milestones (name, target) VALUES (?1, ?2)
+             ON CONFLICT(name) DO UPDATE SET target = excluded.target",
+            params![name, due_to_sql(target)],
+        )?;
+        Ok(())
+    }
+
+    // Milestones by target date, the ones without a date last
+    pub fn get_milestones(&self) -> Result<Vec<Milestone>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare_cached(
+            "SELECT id, name, target FROM milestones ORDER BY target IS NULL, target, name",
+        )?;
+        let milestones = stmt
+            .query_map([], |row| {
+                Ok(Milestone {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    target: row
+                        .get::<_, Option<String>>(2)?
+                        .as_deref()
+                        .and_then(dates::parse_date),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(milestones)
+    }
+
+    pub fn find_milestone(&self, name: &str) -> Result<Option<Milestone>, Box<dyn Error>> {
+        Ok(self
+            .get_milestones()?
+            .into_iter()
+            .find(|milestone| milestone.name.eq_ignore_ascii_case(name)))
+    }
+
+    // DELETE A MILESTONE, its todos stay but are unlinked
+    pub fn delete_milestone(&mut self, id: i64) -> Result<(), Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        tx.execute(
+            "UPDATE todos SET milestone_id = NULL WHERE milestone_id = ?",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM milestones WHERE id = ?", params![id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // LINK TODOS TO A MILESTONE, or unlink them with `None`
+    pub fn set_milestone(
+        &mut self,
+        ids: &[i32],
+        milestone_id: Option<i64>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        let mut changes = 0;
+        for id in ids {
+            changes += tx.execute(
+                "UPDATE todos SET milestone_id = ?1 WHERE id = ?2",
+                params![milestone_id, id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(changes)
+    }
+
+    // (todo id, milestone id) of every linked todo, archived ones included
+    pub fn milestone_links(&self) -> Result<Vec<(i64, i64)>, Box<dyn Error>> {
+        let mut stmt = self
+            .connection
+            .prepare_cached("SELECT id, milestone_id FROM todos WHERE milestone_id IS NOT NULL")?;
+        let links = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    // ADD A COMMENT to a todo, returns its ID
+    pub fn add_comment(
+        &self,
+        todo_id: i32,
+        author: &str,
+        text: &str,
+    ) -> Result<i64, Box<dyn Error>> {
+        let exists = self
+            .connection
+            .query_row("SELECT 1 FROM todos WHERE id = ?", params![todo_id], |_| {
+                Ok(())
+            })
+            .optional()?
+            .is_some();
+        if !exists {
+            return Err(format!("No todo found with id: {}", todo_id).into());
+        }
+        insert_comment(&self.connection, todo_id as i64, author, text, Utc::now())
+    }
+
+    // Make a todo a habit, or a plain todo again with `None`. Returns false when nothing changed.
+    pub fn set_habit(&self, todo_id: i32, every: Option<Cadence>) -> Result<bool, Box<dyn Error>> {
+        let Some(every) = every else {
+            let changes = self
+                .connection
+                .execute("DELETE FROM habits WHERE todo_id = ?", params![todo_id])?;
+            return Ok(changes > 0);
+        };
+        let exists = self
+            .connection
+            .query_row("SELECT 1 FROM todos WHERE id = ?", params![todo_id], |_| {
+                Ok(())
+            })
+            .optional()?
+            .is_some();
+        if !exists {
+            return Err(format!("No todo found with id: {}", todo_id).into());
+        }
+        self.connection.execute(
+            "INSERT INTO habits (todo_id, every) VALUES (?1, ?2)
+             ON CONFLICT(todo_id) DO UPDATE SET every = ?2",
+            params![todo_id, every.name()],
+        )?;
+        Ok(true)
+    }
+
+    // (todo id, cadence) of every habit, unknown cadences are skipped
+    pub fn get_habits(&self) -> Result<Vec<(i64, Cadence)>, Box<dyn Error>> {
+        let mut stmt = self
+            .connection
+            .prepare_cached("SELECT todo_id, every FROM habits ORDER BY todo_id")?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|(todo_id, every)| Some((todo_id, Cadence::parse(&every)?)))
+            .collect())
+    }
+
+    // Comments of one todo, or of every todo with `None`, oldest first
+    pub fn get_comments(&self, todo_id: Option<i64>) -> Result<Vec<Comment>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT id, todo_id, author, text, created_at FROM comments
+             WHERE ?1 IS NULL OR todo_id = ?1 ORDER BY created_at, id",
+        )?;
+        let comments = stmt
+            .query_map(params![todo_id], |row| {
+                Ok(Comment {
+                    id: row.get(0)?,
+                    todo_id: row.get(1)?,
+                    author: row.get(2)?,
+                    text: row.get(3)?,
+                    created_at: dates::parse_timestamp(&row.get::<_, String>(4)?)
+                        .unwrap_or_default(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(comments)
+    }
+
+    // CREATE AN OWNER, or change the display name and color of an existing one. `None` keeps
+    // what is there, an empty string clears it.
+    pub fn save_owner(
+        &self,
+        name: &str,
+        display_name: Option<&str>,
+        color: Option<&str>,
+    ) -> Result<Owner, Box<dyn Error>> {
+        let name = register_owner(&self.connection, name)?;
+        self.connection.execute(
+            "UPDATE owners SET display_name = coalesce(?1, display_name), color = coalesce(?2, color)
+             WHERE name = ?3",
+            params![display_name, color, name],
+        )?;
+        self.find_owner(&name)?
+            .ok_or_else(|| format!("Owner {} was not saved", name).into())
+    }
+
+    // Owners by name
+    pub fn get_owners(&self) -> Result<Vec<Owner>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare_cached(
+            "SELECT id, name, display_name, color FROM owners ORDER BY name COLLATE NOCASE",
+        )?;
+        let owners = stmt
+            .query_map([], |row| {
+                Ok(Owner {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    display_name: row.get(2)?,
+                    color: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(owners)
+    }
+
+    pub fn find_owner(&self, name: &str) -> Result<Option<Owner>, Box<dyn Error>> {
+        Ok(self
+            .get_owners()?
+            .into_iter()
+            .find(|owner| owner.name.eq_ignore_ascii_case(name.trim())))
+    }
+
+    // Todos and subtasks assigned to an owner, archived todos included
+    pub fn owner_usage(&self, name: &str) -> Result<i64, Box<dyn Error>> {
+        let count = self.connection.query_row(
+            "SELECT (SELECT COUNT(*) FROM todos WHERE owner = ?1 COLLATE NOCASE OR waiting_on = ?1 COLLATE NOCASE)
+                  + (SELECT COUNT(*) FROM subtasks WHERE owner = ?1 COLLATE NOCASE)",
+            params![name],
+            |row| row.get(0),
+        )?;
+        Ok(count)
+    }
+
+    pub fn delete_owner(&self, id: i64) -> Result<(), Box<dyn Error>> {
+        self.connection
+            .execute("DELETE FROM owners WHERE id = ?", params![id])?;
+        Ok(())
+    }
+
+    // MERGE ONE OWNER INTO ANOTHER: their todos and subtasks move over and `from` is removed.
+    // Returns how many todos and subtasks changed.
+    pub fn merge_owners(&mut self, from: &Owner, into: &Owner) -> Result<usize, Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        let mut changes = tx.execute(
+            "UPDATE todos SET owner = ?1, updated_at = ?2 WHERE owner = ?3 COLLATE NOCASE",
+            params![into.name, now_timestamp(), from.name],
+        )?;
+        changes += tx.execute(
+            "UPDATE subtasks SET owner = ?1 WHERE owner = ?2 COLLATE NOCASE",
+            params![into.name, from.name],
+        )?;
+        tx.execute(
+            "UPDATE todos SET waiting_on = ?1 WHERE waiting_on = ?2 COLLATE NOCASE",
+            params![into.name, from.name],
+        )?;
+        tx.execute("DELETE FROM owners WHERE id = ?", params![from.id])?;
+        tx.commit()?;
+        Ok(changes)
+    }
+
+    // MERGE ANOTHER DATABASE: copy its todos (archived too) with new ids. A todo with the same
+    // topic and text as one already here is skipped, links to it point at the existing one.
+    // Returns (added, skipped).
+    pub fn merge_from(
+        &mut self,
+        other: &DBtodo,
+        source: &str,
+    ) -> Result<(usize, usize), Box<dyn Error>> {
+        let mut existing = self.get_todos()?;
+        existing.extend(self.get_archived_todos()?);
+        let mut incoming = other.get_todos()?;
+        incoming.extend(other.get_archived_todos()?);
+        let milestones = other.get_milestones()?;
+        let milestone_links = other.milestone_links()?;
+        let dependency_links = other.dependency_links()?;
+        let comments = other.get_comments(None)?;
+
+        let tx = self.connection.transaction()?;
+        let mut ids = std::collections::HashMap::new();
+        let mut added = Vec::new();
+        for todo in &incoming {
+            let duplicate = existing.iter().find(|known| {
+                known.topic.eq_ignore_ascii_case(&todo.topic)
+                    && known.text.eq_ignore_ascii_case(&todo.text)
+            });
+            let id = match duplicate {
+                Some(known) => known.id as i64,
+                None => {
+                    let id = insert_todo(&tx, todo)?;
+                    record_history(
+                        &tx,
+                        id,
+                        "merge-db",
+                        &format!("From #{} in {}", todo.id, source),
+                    )?;
+                    added.push(id);
+                    id
+                }
+            };
+            ids.insert(todo.id as i64, id);
+        }
+
+        // Milestones are matched by name, the new todos keep theirs
+        for (todo_id, milestone_id) in &milestone_links {
+            let (Some(id), Some(milestone)) = (
+                ids.get(todo_id).filter(|id| added.contains(id)),
+                milestones
+                    .iter()
+                    .find(|milestone| milestone.id == *milestone_id),
+            ) else {
+                continue;
+            };
+            tx.execute(
+                "INSERT OR IGNORE INTO milestones (name, target) VALUES (?1, ?2)",
+                params![milestone.name, due_to_sql(milestone.target)],
+            )?;
+            tx.execute(
+                "UPDATE todos SET milestone_id = (SELECT id FROM milestones WHERE name = ?1)
+                 WHERE id = ?2",
+                params![milestone.name, id],
+            )?;
+        }
+
+        for (todo_id, blocked_by) in &dependency_links {
+            if let (Some(todo_id), Some(blocked_by)) = (ids.get(todo_id), ids.get(blocked_by))
+                && todo_id != blocked_by
+            {
+                tx.execute(
+                    "INSERT OR IGNORE INTO dependencies (todo_id, blocked_by) VALUES (?1, ?2)",
+                    params![todo_id, blocked_by],
+                )?;
+            }
+        }
+
+        // The comments of the new todos come along, the ones of skipped duplicates don't
+        for comment in &comments {
+            if let Some(id) = ids.get(&comment.todo_id).filter(|id| added.contains(id)) {
+                insert_comment(&tx, *id, &comment.author, &comment.text, comment.created_at)?;
+            }
+        }
+
+        tx.commit()?;
+        Ok((added.len(), incoming.len() - added.len()))
+    }
+
+    // BLOCK A TODO ON ANOTHER ONE, false when the link already existed
+    pub fn add_dependency(&self, todo_id: i32, blocked_by: i32) -> Result<bool, Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "INSERT OR IGNORE INTO dependencies (todo_id, blocked_by) VALUES (?1, ?2)",
+            params![todo_id, blocked_by],
+        )?;
+        Ok(changes > 0)
+    }
+
+    pub fn remove_dependency(
+        &self,
+        todo_id: i32,
+        blocked_by: i32,
+    ) -> Result<usize, Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "DELETE FROM dependencies WHERE todo_id = ?1 AND blocked_by = ?2",
+            params![todo_id, blocked_by],
+        )?;
+        Ok(changes)
+    }
+
+    // (todo id, blocked by) of every dependency
+    pub fn dependency_links(&self) -> Result<Vec<(i64, i64)>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare_cached(
+            "SELECT todo_id, blocked_by FROM dependencies ORDER BY todo_id, blocked_by",
+        )?;
+        let links = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    // SAVE A CHECKLIST, replacing the items of an existing one with the same name
+    pub fn save_checklist(&mut self, name: &str, items: &[String]) -> Result<(), Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        write_checklist(&tx, name, items)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // Checklists by name with their items in order
+    pub fn get_checklists(&self) -> Result<Vec<Checklist>, Box<dyn Error>> {
+        let mut stmt = self
+            .connection
+            .prepare("SELECT id, name FROM checklists ORDER BY name")?;
+        let mut checklists = stmt
+            .query_map([], |row| {
+                Ok(Checklist {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    items: Vec::new(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut stmt = self
+            .connection
+            .prepare("SELECT text FROM checklist_items WHERE checklist_id = ? ORDER BY position")?;
+        for checklist in &mut checklists {
+            checklist.items = stmt
+                .query_map(params![checklist.id], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+        }
+        Ok(checklists)
+    }
+
+    pub fn find_checklist(&self, name: &str) -> Result<Option<Checklist>, Box<dyn Error>> {
+        Ok(self
+            .get_checklists()?
+            .into_iter()
+            .find(|checklist| checklist.name.eq_ignore_ascii_case(name)))
+    }
+
+    pub fn delete_checklist(&mut self, id: i64) -> Result<(), Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        tx.execute(
+            "DELETE FROM checklist_items WHERE checklist_id = ?",
+            params![id],
+        )?;
+        tx.execute("DELETE FROM checklists WHERE id = ?", params![id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // ADD SEVERAL PENDING SUBTASKS TO A TODO, returns 0 when the todo doesn't exist
+    pub fn add_subtasks(
+        &mut self,
+        todo_id: i32,
+        items: &[String],
+    ) -> Result<usize, Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        let exists = tx
+            .query_row("SELECT 1 FROM todos WHERE id = ?", params![todo_id], |_| {
+                Ok(())
+            })
+            .optional()?
+            .is_some();
+        if !exists {
+            return Ok(0);
+        }
+        for text in items {
+            tx.execute(
+                "INSERT INTO subtasks (todo_id, text, status) VALUES (?1, ?2, 'Pending')",
+                params![todo_id, text],
+            )?;
+        }
+        tx.execute(
+            "UPDATE todos SET updated_at = ?1 WHERE id = ?2",
+            params![now_timestamp(), todo_id],
+        )?;
+        tx.commit()?;
+        Ok(items.len())
+    }
+
+    // SET THE SUBTASK WITH THIS TEXT Done or Pending, adding it when the todo has none.
+    // Returns true when it was added
+    pub fn sync_subtask(
+        &self,
+        todo_id: i32,
+        text: &str,
+        done: bool,
+    ) -> Result<bool, Box<dyn Error>> {
+        let status = if done { "Done" } else { "Pending" };
+        let changes = self.connection.execute(
+            "UPDATE subtasks SET status = ?1 WHERE todo_id = ?2 AND lower(trim(text)) = lower(?3)",
+            params![status, todo_id, text.trim()],
+        )?;
+        if changes == 0 {
+            self.connection.execute(
+                "INSERT INTO subtasks (todo_id, text, status) VALUES (?1, ?2, ?3)",
+                params![todo_id, text.trim(), status],
+            )?;
+        }
+        Ok(changes == 0)
+    }
+
+    // EXPLODE A TODO: save the shortened description and notes and add the list items as subtasks
+    pub fn explode_todo(
+        &mut self,
+        todo_id: i32,
+        desc: &str,
+        notes: &str,
+        items: &[ListItem],
+    ) -> Result<(), Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        tx.execute(
+            "UPDATE todos SET desc = ?1, notes = ?2, updated_at = ?3 WHERE id = ?4",
+            params![desc, notes, now_timestamp(), todo_id],
+        )?;
+        for item in items {
+            tx.execute(
+                "INSERT INTO subtasks (todo_id, text, status) VALUES (?1, ?2, ?3)",
+                params![
+                    todo_id,
+                    item.text,
+                    if item.done { "Done" } else { "Pending" }
+                ],
+            )?;
+        }
+        record_history(
+            &tx,
+            todo_id as i64,
+            "explode",
+            &format!("{} subtask(s) from the text", items.len()),
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // SEED FROM A FIXTURE, all or nothing. `wipe` empties the todos, history, milestones and
+    // checklists first and restarts the ids, so the same fixture always gives the same database.
+    // Each todo comes with the name of its milestone, created without a target if it is new.
+    pub fn seed(
+        &mut self,
+        todos: &[(Todo, Option<String>)],
+        milestones: &[Milestone],
+        checklists: &[Checklist],
+        wipe: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        if wipe {
+            tx.execute_batch(
+                "DELETE FROM todos;
+                 DELETE FROM history;
+                 DELETE FROM milestones;
+                 DELETE FROM checklist_items;
+                 DELETE FROM checklists;
+                 DELETE FROM sqlite_sequence;",
+            )?;
+        }
+
+        for milestone in milestones {
+            tx.execute(
+                "INSERT INTO milestones (name, target) VALUES (?1, ?2)
+                 ON CONFLICT(name) DO UPDATE SET target = excluded.target",
+                params![milestone.name, due_to_sql(milestone.target)],
+            )?;
+        }
+        for checklist in checklists {
+            write_checklist(&tx, &checklist.name, &checklist.items)?;
+        }
+        for (todo, milestone) in todos {
+            let id = insert_todo(&tx, todo)?;
+            if let Some(name) = milestone {
+                link_milestone(&tx, id, name)?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    // CLEAR ALL TODOS FROM DB
+    pub fn clear_all_todos(&self) -> Result<(), Box<dyn Error>> {
+        // Subtasks and dependencies go with their todos
+        let changes = self.connection.execute("DELETE FROM todos", params![])?;
+        if changes > 0 {
+            println!("✅ All todos cleared successfully!");
+        } else {
+            println!("❌ No todos found.");
+        }
+        Ok(())
+    }
+
+    pub fn flush_db(&self) -> Result<(), Box<dyn Error>> {
+        // Subtasks and dependencies go with their todos
+        let changes = self.connection.execute("DELETE FROM todos", params![])?;
+        self.connection.execute("DELETE FROM history", params![])?;
+        // A fresh start pulls the synced todos back instead of deleting them remotely
+        self.connection
+            .execute("DELETE FROM sync_links", params![])?;
+        self.connection
+            .execute("DELETE FROM sync_journal", params![])?;
+        if changes > 0 {
+            println!("");
             println!("✅ All todos cleared successfully!");
             println!("");
         } else {
@@ -273,6 +1941,327 @@ impl DBtodo {
         Ok(())
     }
 
+    // SYNC LINKS of one backend
+    #[cfg(feature = "sync")]
+    pub fn sync_links(&self, backend: &str) -> Result<Vec<SyncLink>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT todo_id, remote_id, remote_list, synced_at FROM sync_links WHERE backend = ?",
+        )?;
+        let links = stmt
+            .query_map(params![backend], |row| {
+                Ok(SyncLink {
+                    todo_id: row.get(0)?,
+                    remote_id: row.get(1)?,
+                    remote_list: row.get(2)?,
+                    synced_at: dates::parse_timestamp(&row.get::<_, String>(3)?)
+                        .unwrap_or_default(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    #[cfg(feature = "sync")]
+    pub fn save_sync_link(&self, backend: &str, link: &SyncLink) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO sync_links (backend, todo_id, remote_id, remote_list, synced_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                backend,
+                link.todo_id,
+                link.remote_id,
+                link.remote_list,
+                link.synced_at.to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    #[cfg(feature = "sync")]
+    pub fn delete_sync_link(&self, backend: &str, todo_id: i64) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "DELETE FROM sync_links WHERE backend = ?1 AND todo_id = ?2",
+            params![backend, todo_id],
+        )?;
+        Ok(())
+    }
+
+    // SYNC STATE of every backend that has synced, with how many todos changed since
+    #[cfg(feature = "sync")]
+    pub fn sync_states(&self) -> Result<Vec<SyncState>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT backend, error,
+                (SELECT COUNT(DISTINCT todo_id) FROM sync_journal WHERE id > journal_id),
+                next_run
+             FROM sync_state ORDER BY backend",
+        )?;
+        let states = stmt
+            .query_map([], |row| {
+                Ok(SyncState {
+                    backend: row.get(0)?,
+                    error: row.get(1)?,
+                    pending: row.get::<_, i64>(2)? as usize,
+                    next_run: row
+                        .get::<_, Option<String>>(3)?
+                        .and_then(|next_run| dates::parse_timestamp(&next_run)),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(states)
+    }
+
+    // None before the first successful sync
+    #[cfg(feature = "sync")]
+    pub fn sync_cursor(&self, backend: &str) -> Result<Option<SyncCursor>, Box<dyn Error>> {
+        let cursor = self
+            .connection
+            .query_row(
+                "SELECT journal_id, synced_at FROM sync_state
+                 WHERE backend = ? AND synced_at IS NOT NULL",
+                params![backend],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )
+            .optional()?;
+        Ok(cursor.and_then(|(journal_id, synced_at)| {
+            Some(SyncCursor {
+                journal_id,
+                synced_at: dates::parse_timestamp(&synced_at)?,
+            })
+        }))
+    }
+
+    // The journal entries after `journal_id`, oldest first
+    #[cfg(feature = "sync")]
+    pub fn journal_since(&self, journal_id: i64) -> Result<Vec<JournalEntry>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT uuid, todo_id, action, topic, changed_at FROM sync_journal
+             WHERE id > ? ORDER BY id",
+        )?;
+        let entries = stmt
+            .query_map(params![journal_id], |row| {
+                Ok(JournalEntry {
+                    uuid: row.get(0)?,
+                    todo_id: row.get(1)?,
+                    action: row.get(2)?,
+                    topic: row.get(3)?,
+                    changed_at: dates::parse_timestamp(&row.get::<_, String>(4)?)
+                        .unwrap_or_default(),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    // The last journal entry so far, a sync records it as seen once it went through
+    #[cfg(feature = "sync")]
+    pub fn journal_head(&self) -> Result<i64, Box<dyn Error>> {
+        Ok(self
+            .connection
+            .query_row("SELECT IFNULL(MAX(id), 0) FROM sync_journal", [], |row| {
+                row.get(0)
+            })?)
+    }
+
+    // A successful sync that started at `started` has seen the journal up to `journal_id`, the
+    // head when it started. Edits made while it ran stay for the next one.
+    #[cfg(feature = "sync")]
+    pub fn record_sync(
+        &self,
+        backend: &str,
+        journal_id: i64,
+        started: DateTime<Utc>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "INSERT INTO sync_state (backend, journal_id, synced_at, error)
+             VALUES (?1, ?2, ?3, NULL)
+             ON CONFLICT (backend) DO UPDATE SET
+                journal_id = excluded.journal_id, synced_at = excluded.synced_at, error = NULL",
+            params![backend, journal_id, started.to_rfc3339()],
+        )?;
+        // Entries every backend has seen are done with
+        self.connection.execute(
+            "DELETE FROM sync_journal WHERE id <= (SELECT MIN(journal_id) FROM sync_state)",
+            [],
+        )?;
+        Ok(())
+    }
+
+    // A failed sync leaves the changes queued for the next try
+    #[cfg(feature = "sync")]
+    pub fn record_sync_error(&self, backend: &str, error: &str) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "INSERT INTO sync_state (backend, error) VALUES (?1, ?2)
+             ON CONFLICT (backend) DO UPDATE SET error = excluded.error",
+            params![backend, error],
+        )?;
+        Ok(())
+    }
+
+    // The daemon's next run of a backend that has a sync state, none once it stops
+    #[cfg(feature = "sync")]
+    pub fn record_next_run(
+        &self,
+        backend: &str,
+        next_run: Option<DateTime<Utc>>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.connection.execute(
+            "UPDATE sync_state SET next_run = ? WHERE backend = ?",
+            params![next_run.map(|next_run| next_run.to_rfc3339()), backend],
+        )?;
+        Ok(())
+    }
+
+    // Write what a sync brought in: text, description, status and due date
+    #[cfg(feature = "sync")]
+    pub fn apply_synced(&self, todo: &Todo) -> Result<(), Box<dyn Error>> {
+        let old_status: Option<String> = self
+            .connection
+            .query_row(
+                "SELECT status FROM todos WHERE id = ?",
+                params![todo.id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        self.connection.execute(
+            "UPDATE todos SET text = ?1, desc = ?2, status = ?3, due = ?4, due_at = ?5, updated_at = ?6
+             WHERE id = ?7",
+            params![
+                todo.text,
+                todo.desc,
+                todo.status,
+                due_to_sql(todo.due),
+                todo.due_at.map(|due_at| due_at.to_rfc3339()),
+                now_timestamp(),
+                todo.id
+            ],
+        )?;
+        if old_status.is_some_and(|status| status != todo.status) {
+            record_history(&self.connection, todo.id as i64, "status", &todo.status)?;
+        }
+        Ok(())
+    }
+
+    // MAINTENANCE: SQLite's own consistency check, "ok" when nothing is wrong
+    pub fn integrity_check(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare("PRAGMA integrity_check")?;
+        let problems = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(problems)
+    }
+
+    // Subtasks whose todo is gone, as (subtask id, todo id, text)
+    pub fn orphaned_subtasks(&self) -> Result<Vec<(i64, i64, String)>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT id, todo_id, text FROM subtasks
+             WHERE todo_id NOT IN (SELECT id FROM todos) ORDER BY id",
+        )?;
+        let subtasks = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(subtasks)
+    }
+
+    // Dependencies where either todo is gone
+    pub fn orphaned_dependencies(&self) -> Result<Vec<(i64, i64)>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT todo_id, blocked_by FROM dependencies
+             WHERE todo_id NOT IN (SELECT id FROM todos) OR blocked_by NOT IN (SELECT id FROM todos)",
+        )?;
+        let links = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    // Delete the orphaned subtasks and dependencies, returns how many of each
+    pub fn delete_orphans(&mut self) -> Result<(usize, usize), Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        let subtasks = tx.execute(
+            "DELETE FROM subtasks WHERE todo_id NOT IN (SELECT id FROM todos)",
+            [],
+        )?;
+        let dependencies = tx.execute(
+            "DELETE FROM dependencies
+             WHERE todo_id NOT IN (SELECT id FROM todos) OR blocked_by NOT IN (SELECT id FROM todos)",
+            [],
+        )?;
+        tx.commit()?;
+        Ok((subtasks, dependencies))
+    }
+
+    pub fn vacuum(&self) -> Result<(), Box<dyn Error>> {
+        self.connection.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    // The todos embedded with `model`
+    pub fn embeddings(&self, model: &str) -> Result<Vec<Embedding>, Box<dyn Error>> {
+        let mut stmt = self
+            .connection
+            .prepare("SELECT todo_id, source, vector FROM embeddings WHERE model = ?1")?;
+        let rows = stmt
+            .query_map(params![model], |row| {
+                let vector = row
+                    .get::<_, Vec<u8>>(2)?
+                    .chunks_exact(4)
+                    .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                    .collect();
+                Ok((row.get::<_, i64>(0)? as usize, row.get(1)?, vector))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    // Store new vectors and drop the ones of deleted todos or of another model
+    pub fn save_embeddings(
+        &mut self,
+        model: &str,
+        embeddings: &[Embedding],
+    ) -> Result<(), Box<dyn Error>> {
+        let tx = self.connection.transaction()?;
+        for (todo_id, source, vector) in embeddings {
+            let bytes = vector
+                .iter()
+                .flat_map(|value| value.to_le_bytes())
+                .collect::<Vec<u8>>();
+            tx.execute(
+                "INSERT OR REPLACE INTO embeddings (todo_id, model, source, vector)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![*todo_id as i64, model, source, bytes],
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM embeddings
+             WHERE model != ?1 OR todo_id NOT IN (SELECT id FROM todos)",
+            params![model],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // (table, rows) for every table, by name
+    pub fn table_counts(&self) -> Result<Vec<(String, i64)>, Box<dyn Error>> {
+        let mut stmt = self.connection.prepare(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )?;
+        let tables = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        tables
+            .into_iter()
+            .map(|table| {
+                let count = self.connection.query_row(
+                    &format!("SELECT COUNT(*) FROM \"{}\"", table),
+                    [],
+                    |row| row.get(0),
+                )?;
+                Ok((table, count))
+            })
+            .collect()
+    }
+
     // SET THE API KEY CREDENTRIALS
     pub fn set_api_credentials(&self, apikey: Option<String>) -> Result<(), Box<dyn Error>> {
         // Always clear the table first
@@ -304,13 +2293,53 @@ impl DBtodo {
     pub fn get_subtasks(&self, todo_id: usize) -> Result<Vec<String>, Box<dyn Error>> {
         let mut stmt = self
             .connection
-            .prepare("SELECT text FROM subtasks WHERE todo_id = ?")?;
+            .prepare_cached("SELECT text FROM subtasks WHERE todo_id = ?")?;
         let subtasks = stmt
             .query_map(params![todo_id], |row| row.get(0))?
             .collect::<Result<Vec<String>, _>>()?;
         Ok(subtasks)
     }
 
+    // SET OR CLEAR THE DUE DATE AND OWNER OF A SUBTASK
+    pub fn update_subtask_details(
+        &self,
+        subtask_id: i32,
+        due: Option<NaiveDate>,
+        owner: Option<&str>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let owner = owner
+            .map(|owner| register_owner(&self.connection, owner))
+            .transpose()?;
+        let changes = self.connection.execute(
+            "UPDATE subtasks SET due = ?1, owner = ?2 WHERE id = ?3",
+            params![due_to_sql(due), owner, subtask_id],
+        )?;
+        Ok(changes)
+    }
+
+    pub fn update_subtask_note(
+        &self,
+        subtask_id: i32,
+        note: &str,
+    ) -> Result<usize, Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "UPDATE subtasks SET note = ?1 WHERE id = ?2",
+            params![note, subtask_id],
+        )?;
+        Ok(changes)
+    }
+
+    // MOVE A SUBTASK TO ANOTHER TODO, keeping its status
+    // Returns 0 when either the subtask or the target todo doesn't exist
+    pub fn move_subtask(&self, subtask_id: i32, todo_id: i32) -> Result<usize, Box<dyn Error>> {
+        let changes = self.connection.execute(
+            "UPDATE subtasks SET todo_id = ?1
+             WHERE id = ?2 AND EXISTS (SELECT 1 FROM todos WHERE id = ?1)",
+            params![todo_id, subtask_id],
+        )?;
+        Ok(changes)
+    }
+
     // Change the subtast state
     pub fn change_subtask_status(
         &self,
@@ -350,8 +2379,8 @@ impl DBtodo {
     // UPDATE TODO NOTES
     pub fn update_notes(&self, id: i32, notes: String) -> Result<(), Box<dyn Error>> {
         let changes = self.connection.execute(
-            "UPDATE todos SET notes = ? WHERE id = ?",
-            params![notes, id],
+            "UPDATE todos SET notes = ?, updated_at = ? WHERE id = ?",
+            params![notes, now_timestamp(), id],
         )?;
         if changes > 0 {
             return Ok(());
@@ -361,3 +2390,277 @@ impl DBtodo {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subtasks_cascade() {
+        let path = std::env::temp_dir().join(format!("voido-cascade-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut db = DBtodo::open(&path).unwrap();
+        db.connection
+            .execute_batch(
+                "INSERT INTO todos (id, priority, topic, text, desc, date_added, status, owner)
+                     VALUES (1, 'Low', 'Work', 'Keep', '', '2025-01-01T00:00:00Z', 'Pending', 'Joe'),
+                            (2, 'Low', 'Work', 'Drop', '', '2025-01-01T00:00:00Z', 'Pending', 'Joe');",
+            )
+            .unwrap();
+        db.add_subtasks(1, &["kept".to_string()]).unwrap();
+        db.add_subtasks(2, &["dropped".to_string()]).unwrap();
+
+        // Rows left behind by deletes before version 2
+        db.connection
+            .execute_batch(
+                "PRAGMA foreign_keys = OFF;
+                 INSERT INTO subtasks (todo_id, text, status) VALUES (99, 'orphan', 'Pending');
+                 INSERT INTO dependencies (todo_id, blocked_by) VALUES (1, 99);
+                 PRAGMA user_version = 1;",
+            )
+            .unwrap();
+        drop(db);
+
+        let db = DBtodo::open(&path).unwrap();
+        let count = |table: &str| -> i64 {
+            db.connection
+                .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+                    row.get(0)
+                })
+                .unwrap()
+        };
+        assert_eq!(count("subtasks"), 2);
+        assert_eq!(count("dependencies"), 0);
+
+        db.add_dependency(1, 2).unwrap();
+        db.delete_todo(2).unwrap();
+        assert_eq!(db.get_subtasks(1).unwrap(), vec!["kept".to_string()]);
+        assert_eq!(count("subtasks"), 1);
+        assert_eq!(count("dependencies"), 0);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_comments() {
+        let mut db = DBtodo::open(Path::new(":memory:")).unwrap();
+        db.add_todos(&crate::data::demo_todos()[..3]).unwrap();
+        db.add_comment(1, "Ana", "Blocked on legal").unwrap();
+        db.add_comment(2, "Joe", "Sent the draft").unwrap();
+        assert!(db.add_comment(99, "Joe", "Lost").is_err());
+
+        // Merging keeps the thread of the duplicate, deleting a todo drops its thread
+        db.merge_todos(1, 2).unwrap();
+        let thread = db.get_comments(Some(1)).unwrap();
+        assert_eq!(
+            thread
+                .iter()
+                .map(|comment| comment.text.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Blocked on legal", "Sent the draft"]
+        );
+        db.delete_todo(1).unwrap();
+        assert!(db.get_comments(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_archive_records_only_real_changes() {
+        let mut db = DBtodo::open(Path::new(":memory:")).unwrap();
+        db.add_todos(&crate::data::demo_todos()[..2]).unwrap();
+        let archived = |db: &DBtodo| -> i64 {
+            db.connection
+                .query_row(
+                    "SELECT COUNT(*) FROM history WHERE action = 'archive'",
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+
+        assert_eq!(db.archive_todos(&[1, 99]).unwrap(), 1);
+        assert_eq!(db.archive_todos(&[1]).unwrap(), 0);
+        assert_eq!(archived(&db), 1);
+    }
+
+    #[test]
+    fn test_merge_moves_dependencies() {
+        let mut db = DBtodo::open(Path::new(":memory:")).unwrap();
+        db.add_todos(&crate::data::demo_todos()[..5]).unwrap();
+        db.connection
+            .execute("UPDATE todos SET updated_at = '2025-01-01T00:00:00Z'", [])
+            .unwrap();
+        db.add_dependency(2, 3).unwrap();
+        db.add_dependency(4, 2).unwrap();
+        db.add_dependency(1, 2).unwrap();
+        db.add_dependency(1, 3).unwrap();
+        db.add_dependency(5, 1).unwrap();
+
+        db.merge_todos(1, 2).unwrap();
+        assert_eq!(db.dependency_links().unwrap(), vec![(1, 3), (4, 1), (5, 1)]);
+        let kept = db.get_todos().unwrap().remove(0);
+        assert_eq!(kept.id, 1);
+        assert_ne!(
+            kept.updated_at.unwrap().date_naive().to_string(),
+            "2025-01-01"
+        );
+    }
+
+    #[test]
+    fn test_review_workflow() {
+        let mut db = DBtodo::open(Path::new(":memory:")).unwrap();
+        db.add_todos(&crate::data::demo_todos()[..2]).unwrap();
+        let config = toml::from_str::<toml::Value>(
+            "[WORKFLOW]\nenabled = true\n[WORKFLOW.transitions]\n\
+             Ongoing = [\"Review\"]\nReview = [\"Done\", \"Ongoing\"]\n",
+        )
+        .unwrap();
+        let workflow = WorkflowConfig::from_toml(&config);
+        let todo = |db: &DBtodo| db.get_todos().unwrap().remove(0);
+
+        // Ongoing can't skip the review
+        let error = db.set_status(1, "Done", None, &workflow).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Todo 1 can't go from Ongoing to Done, next: Review"
+        );
+        assert_eq!(todo(&db).status, "Ongoing");
+
+        assert!(db.set_status(1, "Review", Some("ana"), &workflow).unwrap());
+        assert_eq!(todo(&db).waiting_on.as_deref(), Some("Ana"));
+        // Ana owns todo 2 and todo 1 waits on Ana
+        assert_eq!(db.owner_usage("Ana").unwrap(), 2);
+
+        // Leaving the review forgets who it waited on
+        assert!(db.set_status(1, "Done", None, &workflow).unwrap());
+        let done = todo(&db);
+        assert_eq!(done.status, "Done");
+        assert_eq!(done.waiting_on, None);
+        assert!(!db.set_status(9, "Done", None, &workflow).unwrap());
+    }
+
+    #[test]
+    fn test_owners() {
+        let path = std::env::temp_dir().join(format!("voido-owners-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = DBtodo::open(&path).unwrap();
+        db.connection
+            .execute_batch(
+                "DELETE FROM owners;
+                 INSERT INTO todos (priority, topic, text, desc, date_added, status, owner)
+                     VALUES ('Low', 'Work', 'One', '', '2025-01-01T00:00:00Z', 'Pending', 'joe'),
+                            ('Low', 'Work', 'Two', '', '2025-01-01T00:00:00Z', 'Pending', 'Joe'),
+                            ('Low', 'Work', 'Three', '', '2025-01-01T00:00:00Z', 'Pending', 'Joe'),
+                            ('Low', 'Work', 'Four', '', '2025-01-01T00:00:00Z', 'Pending', 'Joseph');
+                 PRAGMA user_version = 2;",
+            )
+            .unwrap();
+        drop(db);
+
+        // The most used spelling wins
+        let mut db = DBtodo::open(&path).unwrap();
+        let names = |db: &DBtodo| {
+            db.get_owners()
+                .unwrap()
+                .into_iter()
+                .map(|owner| owner.name)
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(names(&db), vec!["Joe", "Joseph"]);
+        assert_eq!(db.get_todos().unwrap()[0].owner, "Joe");
+
+        // New todos take the known spelling
+        let todo = Todo {
+            owner: "JOE".to_string(),
+            ..db.get_todos().unwrap()[0].clone()
+        };
+        let id = db.add_todo(&todo).unwrap() as usize;
+        let todos = db.get_todos().unwrap();
+        assert_eq!(
+            todos.iter().find(|todo| todo.id == id).unwrap().owner,
+            "Joe"
+        );
+
+        let joe = db
+            .save_owner("joe", Some("Joe Bloggs"), Some("#e07a5f"))
+            .unwrap();
+        assert_eq!(joe.label(), "Joe Bloggs");
+        let joseph = db.find_owner("joseph").unwrap().unwrap();
+        assert_eq!(db.merge_owners(&joseph, &joe).unwrap(), 1);
+        assert_eq!(names(&db), vec!["Joe"]);
+        assert_eq!(db.owner_usage("Joe").unwrap(), 5);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_bulk_edits_touch_updated_at() {
+        let path = std::env::temp_dir().join(format!("voido-bulk-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut db = DBtodo::open(&path).unwrap();
+        db.connection
+            .execute_batch(
+                "INSERT INTO todos (priority, topic, text, desc, date_added, status, owner, updated_at)
+                     VALUES ('Low', 'work', 'One', '', '2025-01-01T00:00:00Z', 'Pending', 'Joe', '2025-01-01T00:00:00Z'),
+                            ('Low', 'Home', 'Two', '', '2025-01-01T00:00:00Z', 'Pending', 'Ana', '2025-01-01T00:00:00Z');",
+            )
+            .unwrap();
+        let updated = |db: &DBtodo, text: &str| {
+            db.get_todos()
+                .unwrap()
+                .into_iter()
+                .find(|todo| todo.text == text)
+                .unwrap()
+                .updated_at
+                .unwrap()
+                .date_naive()
+                .to_string()
+        };
+
+        assert_eq!(db.rename_topic("Work", "Office").unwrap(), 1);
+        assert_ne!(updated(&db, "One"), "2025-01-01");
+        assert_eq!(updated(&db, "Two"), "2025-01-01");
+
+        assert_eq!(db.reassign_owner("ana", "Joe", None).unwrap(), 1);
+        assert_ne!(updated(&db, "Two"), "2025-01-01");
+
+        db.connection
+            .execute("UPDATE todos SET updated_at = '2025-01-01T00:00:00Z'", [])
+            .unwrap();
+        let id = db.get_todos().unwrap()[0].id as i32;
+        db.escalate_priorities(&[(id, "Low".to_string(), "High".to_string())])
+            .unwrap();
+        assert_ne!(updated(&db, "One"), "2025-01-01");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_edits_during_a_sync_stay_in_the_journal() {
+        let db = DBtodo::open(Path::new(":memory:")).unwrap();
+        // Nothing is journaled before the first sync
+        db.record_sync("github", 0, Utc::now()).unwrap();
+        db.add_todo(&Todo::sample("Before")).unwrap();
+        let started = Utc::now();
+        let head = db.journal_head().unwrap();
+        // Saved while the backend was still syncing
+        let during = db.add_todo(&Todo::sample("During")).unwrap();
+        db.record_sync("github", head, started).unwrap();
+
+        let delta = crate::sync::journal::delta(&db, "github").unwrap().unwrap();
+        assert_eq!(
+            delta
+                .changes
+                .iter()
+                .map(|change| change.todo_id)
+                .collect::<Vec<_>>(),
+            vec![during]
+        );
+    }
+
+    #[test]
+    fn test_demo_database_is_shared_in_memory() {
+        let mut keeper = DBtodo::open(Path::new(DEMO_DATABASE)).unwrap();
+        keeper.add_todos(&crate::data::demo_todos()[..2]).unwrap();
+        let other = DBtodo::open(Path::new(DEMO_DATABASE)).unwrap();
+        assert_eq!(other.get_todos().unwrap().len(), 2);
+        assert!(!Path::new(DEMO_DATABASE).exists());
+    }
+}
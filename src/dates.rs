@@ -0,0 +1,407 @@
+// DATES: storage, parsing and display
+//
+// Timestamps (`date_added`, `updated_at`, history) are stored as RFC 3339 in UTC,
+// due dates as plain `YYYY-MM-DD` with the optional time of day as a UTC timestamp beside them. Older databases and exports used "%d-%m-%y",
+// which is still accepted everywhere a date is read. Timestamps and due times are shown in
+// the `[DISPLAY] timezone`, the system's own unless it or `--tz` says otherwise.
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime,
+    TimeZone, Utc, Weekday,
+};
+
+use crate::configs::display_config;
+
+pub const ISO_DATE_FORMAT: &str = "%Y-%m-%d";
+pub const LEGACY_DATE_FORMAT: &str = "%d-%m-%y";
+const LEGACY_TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+pub fn week_starts_on() -> Weekday {
+    display_config().week_starts_on
+}
+
+// Where times are read and shown: the system's timezone, or one offset from UTC that a team
+// spread over several regions agrees on ("UTC", "+05:30", "UTC-3")
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Timezone {
+    #[default]
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl Timezone {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        if value == "local" {
+            return Some(Self::Local);
+        }
+        let offset = value
+            .strip_prefix("utc")
+            .or_else(|| value.strip_prefix("gmt"))
+            .unwrap_or(&value)
+            .trim();
+        if offset.is_empty() || offset == "z" {
+            return FixedOffset::east_opt(0).map(Self::Fixed);
+        }
+
+        let (sign, offset) = match (offset.strip_prefix('+'), offset.strip_prefix('-')) {
+            (Some(offset), _) => (1, offset),
+            (_, Some(offset)) => (-1, offset),
+            _ => return None,
+        };
+        let (hours, minutes) = match offset.split_once(':') {
+            Some((hours, minutes)) => (hours, minutes),
+            None if offset.len() == 4 => offset.split_at(2),
+            None => (offset, "0"),
+        };
+        let (hours, minutes) = (hours.parse::<i32>().ok()?, minutes.parse::<i32>().ok()?);
+        if hours > 14 || minutes > 59 {
+            return None;
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(Self::Fixed)
+    }
+
+    // The wall clock in this timezone at `at`
+    pub fn clock(&self, at: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Local => at.with_timezone(&Local).naive_local(),
+            Self::Fixed(offset) => at.with_timezone(offset).naive_local(),
+        }
+    }
+
+    // The moment the wall clock here reads `datetime`, the earlier one when it reads it twice
+    pub fn utc_at(&self, datetime: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Local => local_to_utc(datetime),
+            Self::Fixed(offset) => offset
+                .from_local_datetime(&datetime)
+                .earliest()
+                .map(|datetime| datetime.with_timezone(&Utc)),
+        }
+    }
+}
+
+pub fn timezone() -> Timezone {
+    display_config().timezone
+}
+
+// Parse a date in any of the formats VoiDo has stored over time
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    // The legacy format goes first: "%Y" would happily read "03-01-25" as year 3
+    NaiveDate::parse_from_str(value, LEGACY_DATE_FORMAT)
+        .or_else(|_| NaiveDate::parse_from_str(value, ISO_DATE_FORMAT))
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(value)
+                .ok()
+                .map(|dt| dt.date_naive())
+        })
+}
+
+// Parse a stored timestamp, plain dates are taken as local midnight
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.with_timezone(&Utc));
+    }
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, LEGACY_TIMESTAMP_FORMAT) {
+        return local_to_utc(datetime);
+    }
+    parse_date(value).and_then(|date| local_to_utc(date.and_time(NaiveTime::MIN)))
+}
+
+fn local_to_utc(datetime: NaiveDateTime) -> Option<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&datetime)
+        .earliest()
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+// Turn `today`, `tomorrow`, weekday names, `eow`/`nextweek` and full dates into a due date
+pub fn parse_due(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    parse_due_with(value, today, week_starts_on())
+}
+
+pub fn parse_due_with(value: &str, today: NaiveDate, week_start: Weekday) -> Option<NaiveDate> {
+    // Days since the start of the current week
+    let into_week = (today.weekday().num_days_from_monday() as i64
+        - week_start.num_days_from_monday() as i64)
+        .rem_euclid(7);
+
+    let weekday = match value.to_lowercase().as_str() {
+        "today" => return Some(today),
+        "tomorrow" | "tmr" => return Some(today + Duration::days(1)),
+        "eow" | "endofweek" => return Some(today + Duration::days(6 - into_week)),
+        "nextweek" => return Some(today + Duration::days(7 - into_week)),
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    };
+
+    if let Some(weekday) = weekday {
+        // Next occurrence of that weekday, today included
+        let days_ahead = (weekday.num_days_from_monday() as i64
+            - today.weekday().num_days_from_monday() as i64)
+            .rem_euclid(7);
+        return Some(today + Duration::days(days_ahead));
+    }
+
+    parse_date(value)
+}
+
+// A time of day: "09:30", "9:30am", "2pm"
+pub fn parse_time(value: &str) -> Option<NaiveTime> {
+    let value = value.trim().to_lowercase();
+    let (clock, pm) = match value
+        .strip_suffix("am")
+        .or_else(|| value.strip_suffix("a.m."))
+    {
+        Some(clock) => (clock, Some(false)),
+        None => match value
+            .strip_suffix("pm")
+            .or_else(|| value.strip_suffix("p.m."))
+        {
+            Some(clock) => (clock, Some(true)),
+            None => (value.as_str(), None),
+        },
+    };
+    let (hour, minute) = match clock.trim().split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => {
+            (hour.parse::<u32>().ok()?, minute.parse().ok()?)
+        }
+        // A bare hour needs am/pm, "9" alone is more likely a day of the month
+        None if pm.is_some() => (clock.trim().parse::<u32>().ok()?, 0),
+        _ => return None,
+    };
+    let hour = match pm {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(true) => hour % 12 + 12,
+        Some(false) => hour % 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+// A due date with an optional time of day: "fri 09:30", "tomorrow@9am", "2025-03-14 14:00", or
+// a time alone for today. The time is read in the display timezone and comes back in UTC.
+pub fn parse_due_at(value: &str, today: NaiveDate) -> Option<(NaiveDate, Option<DateTime<Utc>>)> {
+    let value = value.trim();
+    if let Some(date) = parse_due(value, today) {
+        return Some((date, None));
+    }
+    let (date, time) = match value.rsplit_once(['@', ' ']) {
+        Some((day, time)) => (parse_due(day.trim(), today)?, parse_time(time)?),
+        None => (today, parse_time(value)?),
+    };
+    Some((date, Some(timezone().utc_at(date.and_time(time))?)))
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format(&display_config().date_format).to_string()
+}
+
+// Timestamps are shown as a date in the display timezone
+pub fn format_timestamp(timestamp: &DateTime<Utc>) -> String {
+    format_date(timezone().clock(timestamp).date())
+}
+
+pub fn format_due(due: Option<NaiveDate>) -> String {
+    due.map(format_date).unwrap_or_else(|| "-".to_string())
+}
+
+// The due date, with the time of day in the display timezone when it has one
+pub fn format_due_at(due: Option<NaiveDate>, due_at: Option<DateTime<Utc>>) -> String {
+    match due_at {
+        Some(due_at) => {
+            let clock = timezone().clock(&due_at);
+            format!("{} {}", format_date(clock.date()), clock.format("%H:%M"))
+        }
+        None => format_due(due),
+    }
+}
+
+// SERDE HELPERS: write ISO-8601, read both ISO and the legacy format
+pub mod timestamp_serde {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(
+        value: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_rfc3339())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        super::parse_timestamp(&value)
+            .ok_or_else(|| D::Error::custom(format!("invalid date: {}", value)))
+    }
+}
+
+pub mod optional_timestamp_serde {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_str(&value.to_rfc3339()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        let value = Option::<String>::deserialize(deserializer)?;
+        Ok(value.as_deref().and_then(super::parse_timestamp))
+    }
+}
+
+// Due dates: `None` is written as null, "-" and "" are read as no due date
+pub mod due_serde {
+    use chrono::NaiveDate;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<NaiveDate>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(date) => {
+                serializer.serialize_str(&date.format(super::ISO_DATE_FORMAT).to_string())
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<NaiveDate>, D::Error> {
+        match Option::<String>::deserialize(deserializer)?
+            .as_deref()
+            .map(str::trim)
+        {
+            None | Some("") | Some("-") => Ok(None),
+            Some(value) => super::parse_date(value)
+                .map(Some)
+                .ok_or_else(|| D::Error::custom(format!("invalid due date: {}", value))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_legacy_and_iso_dates() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 3);
+        assert_eq!(parse_date("03-01-25"), date);
+        assert_eq!(parse_date("2025-01-03"), date);
+        assert_eq!(parse_date("2025-01-03T10:00:00+00:00"), date);
+        assert_eq!(parse_date("-"), None);
+    }
+
+    #[test]
+    fn test_parse_due_relative() {
+        // 2025-01-01 is a Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert_eq!(parse_due("fri", today), NaiveDate::from_ymd_opt(2025, 1, 3));
+        assert_eq!(parse_due("wed", today), Some(today));
+        assert_eq!(
+            parse_due("tomorrow", today),
+            NaiveDate::from_ymd_opt(2025, 1, 2)
+        );
+        assert_eq!(parse_due("soon", today), None);
+    }
+
+    #[test]
+    fn test_parse_due_with_a_time() {
+        // 2025-01-01 is a Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let at = |value: &str| {
+            parse_due_at(value, today).map(|(date, due_at)| {
+                (
+                    date,
+                    due_at.map(|due_at| due_at.with_timezone(&Local).naive_local()),
+                )
+            })
+        };
+        let friday = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+        let half_nine = NaiveTime::from_hms_opt(9, 30, 0).unwrap();
+
+        assert_eq!(at("fri"), Some((friday, None)));
+        assert_eq!(
+            at("fri 09:30"),
+            Some((friday, Some(friday.and_time(half_nine))))
+        );
+        assert_eq!(at("fri@9:30am"), at("fri 09:30"));
+        assert_eq!(
+            at("2025-01-03 14:00"),
+            Some((friday, friday.and_hms_opt(14, 0, 0)))
+        );
+        assert_eq!(at("2pm"), Some((today, today.and_hms_opt(14, 0, 0))));
+        assert_eq!(at("fri 25:00"), None);
+        assert_eq!(at("soon 09:30"), None);
+
+        assert_eq!(parse_time("12am"), NaiveTime::from_hms_opt(0, 0, 0));
+        assert_eq!(parse_time("12:15pm"), NaiveTime::from_hms_opt(12, 15, 0));
+        assert_eq!(parse_time("9"), None);
+        assert_eq!(parse_time("13pm"), None);
+    }
+
+    #[test]
+    fn test_timezones() {
+        let due_at = parse_timestamp("2025-03-14T09:30:00Z").unwrap();
+        let india = Timezone::parse("UTC+05:30").unwrap();
+        assert_eq!(Timezone::parse("+0530"), Some(india));
+        assert_eq!(
+            india.clock(&due_at),
+            NaiveDate::from_ymd_opt(2025, 3, 14)
+                .and_then(|date| date.and_hms_opt(15, 0, 0))
+                .unwrap()
+        );
+        assert_eq!(india.utc_at(india.clock(&due_at)), Some(due_at));
+
+        // Early on the 14th in UTC is still the 13th on the west coast
+        let west = Timezone::parse("utc-8").unwrap();
+        let early = parse_timestamp("2025-03-14T05:00:00Z").unwrap();
+        assert_eq!(
+            west.clock(&early).date(),
+            NaiveDate::from_ymd_opt(2025, 3, 13).unwrap()
+        );
+        assert_eq!(Timezone::parse("UTC"), Timezone::parse("+00:00"));
+        assert_eq!(Timezone::parse("Local"), Some(Timezone::Local));
+        assert_eq!(Timezone::parse("Europe/Lisbon"), None);
+        assert_eq!(Timezone::parse("+15"), None);
+    }
+
+    #[test]
+    fn test_parse_due_follows_week_start() {
+        // 2025-01-01 is a Wednesday
+        let today = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert_eq!(
+            parse_due_with("eow", today, Weekday::Mon),
+            NaiveDate::from_ymd_opt(2025, 1, 5)
+        );
+        assert_eq!(
+            parse_due_with("eow", today, Weekday::Sun),
+            NaiveDate::from_ymd_opt(2025, 1, 4)
+        );
+        assert_eq!(
+            parse_due_with("nextweek", today, Weekday::Sun),
+            NaiveDate::from_ymd_opt(2025, 1, 5)
+        );
+    }
+}
@@ -0,0 +1,184 @@
+// DETAIL SEARCH: `/` in the todo details finds text in the description, the notes and the
+// subtasks. Matches are whole lines, `n`/`N` step through them and the panes scroll to each one.
+use std::ops::Range;
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Details,
+    Notes,
+    Subtasks,
+}
+
+#[derive(Debug, Default)]
+pub struct DetailSearch {
+    pub query: String,
+    // The match shown, counting across the panes
+    pub current: usize,
+    // How many matches the modal found when it was last drawn
+    pub count: usize,
+    // Scroll to the current match on the next draw
+    pub jump: bool,
+}
+
+impl DetailSearch {
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    pub fn start(&mut self, query: &str) {
+        *self = Self {
+            query: query.trim().to_string(),
+            jump: true,
+            ..Self::default()
+        };
+    }
+
+    pub fn step(&mut self, forward: bool) {
+        if self.count == 0 {
+            return;
+        }
+        self.current = if forward {
+            (self.current + 1) % self.count
+        } else {
+            (self.current + self.count - 1) % self.count
+        };
+        self.jump = true;
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    // `2/7` for the pane title, or `no match`
+    pub fn position(&self) -> String {
+        if self.count == 0 {
+            "no match".to_string()
+        } else {
+            format!("{}/{}", self.current + 1, self.count)
+        }
+    }
+}
+
+// Byte ranges of `query` in `text`, ignoring case
+pub fn find(text: &str, query: &str) -> Vec<Range<usize>> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    // Lowercasing can change the length of some characters, those are matched as typed
+    let (haystack, needle) = match (text.to_lowercase(), query.to_lowercase()) {
+        (haystack, needle) if haystack.len() == text.len() => (haystack, needle),
+        _ => (text.to_string(), query.to_string()),
+    };
+    haystack
+        .match_indices(&needle)
+        .map(|(start, found)| start..start + found.len())
+        .collect()
+}
+
+pub fn line_text(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+pub fn matches(line: &Line, query: &str) -> bool {
+    !find(&line_text(line), query).is_empty()
+}
+
+// The line with every match of `query` marked, brighter on the current match
+pub fn highlight(line: Line<'_>, query: &str, current: bool) -> Line<'static> {
+    let marked = if current {
+        Style::default()
+            .bg(Color::Rgb(220, 180, 100))
+            .fg(Color::Black)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().bg(Color::Rgb(90, 80, 50)).fg(Color::White)
+    };
+    let spans = line
+        .spans
+        .into_iter()
+        .flat_map(|span| {
+            let text = span.content.to_string();
+            let mut parts = Vec::new();
+            let mut end = 0;
+            for range in find(&text, query) {
+                if range.start > end {
+                    parts.push(Span::styled(text[end..range.start].to_string(), span.style));
+                }
+                parts.push(Span::styled(
+                    text[range.clone()].to_string(),
+                    span.style.patch(marked),
+                ));
+                end = range.end;
+            }
+            if end < text.len() || parts.is_empty() {
+                parts.push(Span::styled(text[end..].to_string(), span.style));
+            }
+            parts
+        })
+        .collect::<Vec<_>>();
+    let mut marked = Line::from(spans).style(line.style);
+    marked.alignment = line.alignment;
+    marked
+}
+
+// Highlight `line` when it is one of the `found` matches, `at` being where it is
+pub fn mark<'a>(
+    line: Line<'a>,
+    query: &str,
+    found: &[(Pane, usize)],
+    current: Option<(Pane, usize)>,
+    at: (Pane, usize),
+) -> Line<'a> {
+    if found.contains(&at) {
+        highlight(line, query, current == Some(at))
+    } else {
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_and_highlight_ignore_case() {
+        assert_eq!(find("Call Bob, then bob again", "BOB"), vec![5..8, 15..18]);
+        assert_eq!(find("nothing here", "bob"), Vec::<Range<usize>>::new());
+        assert_eq!(find("anything", ""), Vec::<Range<usize>>::new());
+
+        let line = Line::from(vec![Span::raw("NOTES: "), Span::raw("ask Bob")]);
+        assert!(matches(&line, "bob"));
+        let marked = highlight(line, "bob", true);
+        assert_eq!(
+            marked
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<Vec<_>>(),
+            vec!["NOTES: ", "ask ", "Bob"]
+        );
+        assert_eq!(marked.spans[2].style.fg, Some(Color::Black));
+    }
+
+    #[test]
+    fn test_step_wraps_around_the_matches() {
+        let mut search = DetailSearch::default();
+        search.start(" bob ");
+        assert_eq!(search.query, "bob");
+        search.count = 3;
+        search.step(false);
+        assert_eq!((search.current, search.position()), (2, "3/3".to_string()));
+        search.step(true);
+        assert_eq!(search.current, 0);
+        search.clear();
+        assert!(!search.is_active());
+    }
+}
@@ -0,0 +1,212 @@
+// SEMANTIC SEARCH: every todo is embedded once, a vector of what it is about, and a query finds
+// the todos whose vectors point the same way. The vectors live in the `embeddings` table next to
+// the text they were made from, a todo is embedded again only after that text changed.
+use std::collections::HashSet;
+use std::error::Error;
+
+use crate::arguments::models::Todo;
+use crate::configs::SearchConfig;
+use crate::database::{DBtodo, Embedding};
+
+// How many related todos the details modal lists
+pub const RELATED_TODOS: usize = 5;
+
+// What a todo is embedded from
+pub fn source(todo: &Todo) -> String {
+    [todo.text.as_str(), todo.desc.as_str(), todo.notes.as_str()]
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot = a.iter().zip(b).map(|(a, b)| a * b).sum::<f32>();
+    let norms =
+        a.iter().map(|a| a * a).sum::<f32>().sqrt() * b.iter().map(|b| b * b).sum::<f32>().sqrt();
+    if norms == 0.0 { 0.0 } else { dot / norms }
+}
+
+// The `limit` todos closest to `vector`, closest first
+pub fn nearest(vector: &[f32], index: &[Embedding], limit: usize) -> Vec<(usize, f32)> {
+    let mut scored = index
+        .iter()
+        .map(|(id, _, other)| (*id, cosine(vector, other)))
+        .collect::<Vec<(usize, f32)>>();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(limit);
+    scored
+}
+
+// The embedding APIs, left out of builds without the `ai` feature
+#[cfg(feature = "ai")]
+mod providers;
+#[cfg(feature = "ai")]
+use providers::embed;
+
+#[cfg(not(feature = "ai"))]
+async fn embed(
+    _config: &SearchConfig,
+    _db: &DBtodo,
+    _texts: &[String],
+) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    Err(crate::features::missing("ai"))
+}
+
+// Embed the todos that are new or changed since they were last embedded, returns how many
+pub async fn update_index(
+    db: &mut DBtodo,
+    todos: &[Todo],
+    config: &SearchConfig,
+) -> Result<usize, Box<dyn Error>> {
+    let index = db.embeddings(&config.model)?;
+    let stale = todos
+        .iter()
+        .map(|todo| (todo.id, source(todo)))
+        .filter(|(id, source)| {
+            !index
+                .iter()
+                .any(|(indexed, indexed_source, _)| indexed == id && indexed_source == source)
+        })
+        .collect::<Vec<(usize, String)>>();
+    let texts = stale
+        .iter()
+        .map(|(_, source)| source.clone())
+        .collect::<Vec<String>>();
+    let vectors = if texts.is_empty() {
+        Vec::new()
+    } else {
+        embed(config, db, &texts).await?
+    };
+    let embedded = stale
+        .into_iter()
+        .zip(vectors)
+        .map(|((id, source), vector)| (id, source, vector))
+        .collect::<Vec<_>>();
+    db.save_embeddings(&config.model, &embedded)?;
+    Ok(embedded.len())
+}
+
+// The todos closest in meaning to `query`, with how close they are from 0 to 1
+pub async fn semantic_search(
+    db: &mut DBtodo,
+    todos: &[Todo],
+    query: &str,
+    limit: usize,
+) -> Result<Vec<(usize, f32)>, Box<dyn Error>> {
+    let config = SearchConfig::load();
+    let embedded = update_index(db, todos, &config).await?;
+    if embedded > 0 {
+        println!("🧭 Embedded {} new or changed todo(s)", embedded);
+    }
+    let query = embed(&config, db, &[query.to_string()])
+        .await?
+        .pop()
+        .ok_or("No embedding for the query")?;
+    // Archived todos keep their vectors but aren't searched
+    let mut index = db.embeddings(&config.model)?;
+    index.retain(|(id, _, _)| todos.iter().any(|todo| todo.id == *id));
+    Ok(nearest(&query, &index, limit))
+}
+
+// Lowercased words of four letters or more, what the word fallback compares
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 4)
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+// The open todos sharing the most words with `todo`, for when there are no vectors to compare
+pub fn related_by_words(todo: &Todo, todos: &[Todo], limit: usize) -> Vec<usize> {
+    let own = words(&source(todo));
+    let mut scored = todos
+        .iter()
+        .filter(|other| other.id != todo.id && !other.is_done())
+        .filter_map(|other| {
+            let theirs = words(&source(other));
+            let shared = own.intersection(&theirs).count();
+            (shared > 0).then(|| {
+                let all = own.union(&theirs).count();
+                (other.id, shared as f32 / all as f32)
+            })
+        })
+        .collect::<Vec<(usize, f32)>>();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.truncate(limit);
+    scored.into_iter().map(|(id, _)| id).collect()
+}
+
+// RELATED TODOS for the details modal: the open todos closest to `todo`, by the vectors already
+// in the index with [SEARCH] semantic on, by shared words otherwise. Nothing is embedded here,
+// opening a todo never waits on the network
+pub fn related(todo: &Todo, todos: &[Todo], limit: usize) -> Vec<usize> {
+    let config = SearchConfig::load();
+    if config.semantic {
+        let index = DBtodo::new()
+            .and_then(|db| db.embeddings(&config.model))
+            .unwrap_or_default();
+        if let Some((_, _, vector)) = index.iter().find(|(id, _, _)| *id == todo.id) {
+            let open = index
+                .iter()
+                .filter(|(id, _, _)| {
+                    *id != todo.id
+                        && todos
+                            .iter()
+                            .any(|other| other.id == *id && !other.is_done())
+                })
+                .cloned()
+                .collect::<Vec<Embedding>>();
+            return nearest(vector, &open, limit)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+        }
+    }
+    related_by_words(todo, todos, limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: usize, text: &str, status: &str) -> Todo {
+        Todo {
+            id,
+            status: status.to_string(),
+            ..Todo::sample(text)
+        }
+    }
+
+    #[test]
+    fn test_nearest_ranks_by_direction() {
+        assert!((cosine(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert_eq!(cosine(&[1.0, 0.0], &[0.0, 3.0]), 0.0);
+        assert_eq!(cosine(&[0.0, 0.0], &[1.0, 1.0]), 0.0);
+
+        let index = vec![
+            (1, "invoices".to_string(), vec![0.9, 0.1]),
+            (2, "garden".to_string(), vec![0.0, 1.0]),
+            (3, "billing".to_string(), vec![1.0, 0.0]),
+        ];
+        let found = nearest(&[1.0, 0.05], &index, 2);
+        assert_eq!(
+            found.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![3, 1]
+        );
+    }
+
+    #[test]
+    fn test_related_by_words_skips_done_and_unrelated() {
+        let todos = vec![
+            todo(1, "Send the March invoices", "Pending"),
+            todo(2, "Chase unpaid invoices from March", "Pending"),
+            todo(3, "Water the garden", "Pending"),
+            todo(4, "Invoices for February", "Done"),
+            todo(5, "Check invoices total", "Ongoing"),
+        ];
+        assert_eq!(related_by_words(&todos[0], &todos, 5), vec![2, 5]);
+        assert_eq!(related_by_words(&todos[0], &todos, 1), vec![2]);
+    }
+}
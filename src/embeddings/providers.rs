@@ -0,0 +1,89 @@
+// EMBEDDING PROVIDERS: Gemini's batchEmbedContents, or Ollama's /api/embed for [SEARCH]
+// provider = "ollama"
+use serde::Deserialize;
+use serde_json::json;
+use std::error::Error;
+
+use crate::ai;
+use crate::configs::SearchConfig;
+use crate::database::DBtodo;
+
+// Texts per request, Gemini takes up to 100
+const BATCH: usize = 100;
+
+#[derive(Deserialize)]
+struct GeminiEmbedding {
+    values: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+struct GeminiEmbeddings {
+    embeddings: Vec<GeminiEmbedding>,
+}
+
+#[derive(Deserialize)]
+struct OllamaEmbeddings {
+    embeddings: Vec<Vec<f32>>,
+}
+
+pub async fn embed(
+    config: &SearchConfig,
+    db: &DBtodo,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, Box<dyn Error>> {
+    let client = reqwest::Client::new();
+    let mut vectors = Vec::new();
+    for batch in texts.chunks(BATCH) {
+        let embedded = match config.provider.as_str() {
+            "ollama" => {
+                client
+                    .post(format!("{}/api/embed", config.url.trim_end_matches('/')))
+                    .json(&json!({ "model": config.model, "input": batch }))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<OllamaEmbeddings>()
+                    .await?
+                    .embeddings
+            }
+            _ => {
+                let requests = batch
+                    .iter()
+                    .map(|text| {
+                        json!({
+                            "model": format!("models/{}", config.model),
+                            "content": { "parts": [{ "text": text }] },
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                client
+                    .post(format!(
+                        "https://generativelanguage.googleapis.com/v1beta/models/{}:batchEmbedContents?key={}",
+                        config.model,
+                        ai::api_key(db)?
+                    ))
+                    .json(&json!({ "requests": requests }))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<GeminiEmbeddings>()
+                    .await?
+                    .embeddings
+                    .into_iter()
+                    .map(|embedding| embedding.values)
+                    .collect()
+            }
+        };
+        if embedded.len() != batch.len() {
+            return Err(format!(
+                "{} sent back {} embeddings for {} texts",
+                config.provider,
+                embedded.len(),
+                batch.len()
+            )
+            .into());
+        }
+        vectors.extend(embedded);
+    }
+    Ok(vectors)
+}
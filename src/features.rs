@@ -0,0 +1,30 @@
+// CARGO FEATURES: the integrations with big dependencies can be left out of the build, all of
+// them are on by default. `cargo build --no-default-features` gives a small offline VoiDo, the
+// TUI and the local commands included.
+//
+//   xls        Excel export and import (xlsxwriter, calamine)
+//   ai         Gemini, Ollama and Whisper: summaries, due dates, triage, voice memos, search by meaning
+//   sync       GitHub and Google Tasks sync, Microsoft To Do and Google Sheets imports
+//   serve      `voido serve`
+//   web        the HTTP client (reqwest) the ones above use, `--from-url` and `voido publish`
+//   clipboard  copy and paste with the system clipboard (arboard)
+//   yaml       YAML files for `voido seed` and `voido apply`, JSON ones work without it
+//
+// Only the code that needs a left out dependency goes, a command that gets to it answers with
+// `missing` instead.
+#[cfg(not(all(
+    feature = "xls",
+    feature = "ai",
+    feature = "sync",
+    feature = "serve",
+    feature = "web",
+    feature = "clipboard",
+    feature = "yaml"
+)))]
+pub fn missing(feature: &str) -> Box<dyn std::error::Error> {
+    format!(
+        "this VoiDo was built without the `{}` feature, see `cargo build --features {}`",
+        feature, feature
+    )
+    .into()
+}
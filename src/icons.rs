@@ -0,0 +1,59 @@
+// ICONS: priority and status glyphs for the icon column, picked with `icons` in [DISPLAY].
+// The nerd-font set needs a patched font in the terminal, emoji work everywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconSet {
+    Off,
+    Emoji,
+    Nerd,
+}
+
+impl IconSet {
+    pub const NAMES: [&'static str; 3] = ["off", "emoji", "nerd"];
+
+    pub fn by_name(name: &str) -> Option<IconSet> {
+        match name.trim().to_lowercase().as_str() {
+            "off" => Some(IconSet::Off),
+            "emoji" => Some(IconSet::Emoji),
+            "nerd" => Some(IconSet::Nerd),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            IconSet::Off => "off",
+            IconSet::Emoji => "emoji",
+            IconSet::Nerd => "nerd",
+        }
+    }
+
+    pub fn priority(self, priority: &str) -> &'static str {
+        match (self, priority.to_lowercase().as_str()) {
+            (IconSet::Off, _) => "",
+            (IconSet::Emoji, "high") => "🔴",
+            (IconSet::Emoji, "medium") => "🟠",
+            (IconSet::Emoji, "low") => "🟢",
+            (IconSet::Emoji, _) => "⚪",
+            (IconSet::Nerd, "high") => "\u{f102}", // angle-double-up
+            (IconSet::Nerd, "medium") => "\u{f106}", // angle-up
+            (IconSet::Nerd, "low") => "\u{f107}",  // angle-down
+            (IconSet::Nerd, _) => "\u{f068}",      // minus
+        }
+    }
+
+    pub fn status(self, status: &str) -> &'static str {
+        match (self, status) {
+            (IconSet::Off, _) => "",
+            (IconSet::Emoji, "Done" | "Completed") => "✅",
+            (IconSet::Emoji, "Ongoing") => "🔄",
+            (IconSet::Emoji, "Planned") => "📅",
+            (IconSet::Emoji, "Pending") => "⏳",
+            (IconSet::Emoji, _) => "❔",
+            (IconSet::Nerd, "Done" | "Completed") => "\u{f058}", // check-circle
+            (IconSet::Nerd, "Ongoing") => "\u{f110}",            // spinner
+            (IconSet::Nerd, "Planned") => "\u{f073}",            // calendar
+            (IconSet::Nerd, "Pending") => "\u{f10c}",            // circle-o
+            (IconSet::Nerd, _) => "\u{f059}",                    // question-circle
+        }
+    }
+}
@@ -0,0 +1,272 @@
+// SETTINGS BUNDLE: config.toml in one file to carry to a new machine or hand to a team. The keys
+// are fixed, so the key macros are what there is to share of them, and the theme is the [DISPLAY]
+// look plus the [STYLING] rules. Tokens and client secrets are left out unless asked for.
+use std::{error::Error, fs};
+
+use chrono::Utc;
+use toml_edit::{DocumentMut, Item};
+
+use crate::{
+    arguments::models::ConfigPart,
+    configs::{self, AppConfigs},
+    database::DBtodo,
+};
+
+const BUNDLE_TABLE: &str = "VOIDO_BUNDLE";
+const BUNDLE_VERSION: i64 = 1;
+
+// Values that sign in somewhere
+const SECRETS: [(&str, &str); 3] = [
+    ("PUBLISH", "token"),
+    ("GOOGLE_TASKS", "client_secret"),
+    ("AUDIO", "token"),
+];
+
+// Which part of the config a key belongs to
+fn part_of(section: &str, key: &str) -> ConfigPart {
+    match (section, key) {
+        ("DISPLAY", "theme" | "density" | "icons" | "topic_colors") | ("STYLING", _) => {
+            ConfigPart::Theme
+        }
+        ("MACROS", _) => ConfigPart::Keys,
+        _ => ConfigPart::Settings,
+    }
+}
+
+fn is_secret(section: &str, key: &str) -> bool {
+    SECRETS.contains(&(section, key))
+}
+
+// Every (section, key, value) of a config, in file order
+fn entries(document: &DocumentMut) -> Vec<(String, String, Item)> {
+    document
+        .iter()
+        .filter_map(|(section, item)| Some((section, item.as_table_like()?)))
+        .flat_map(|(section, table)| {
+            table
+                .iter()
+                .map(move |(key, item)| (section.to_string(), key.to_string(), item.clone()))
+        })
+        .collect()
+}
+
+pub fn export_bundle(
+    config: &str,
+    parts: &[ConfigPart],
+    secrets: bool,
+) -> Result<String, Box<dyn Error>> {
+    let document = config.parse::<DocumentMut>()?;
+    let mut bundle = DocumentMut::new();
+    bundle[BUNDLE_TABLE] = toml_edit::table();
+    bundle[BUNDLE_TABLE]["version"] = toml_edit::value(BUNDLE_VERSION);
+    bundle[BUNDLE_TABLE]["exported"] = toml_edit::value(Utc::now().to_rfc3339());
+    bundle[BUNDLE_TABLE]["parts"] = toml_edit::value(
+        parts
+            .iter()
+            .map(|part| part.name())
+            .collect::<toml_edit::Array>(),
+    );
+
+    for (section, key, item) in entries(&document) {
+        if !parts.contains(&part_of(&section, &key)) {
+            continue;
+        }
+        if !bundle.contains_table(&section) {
+            bundle[&section] = toml_edit::table();
+        }
+        bundle[&section][&key] = if is_secret(&section, &key) && !secrets {
+            toml_edit::value("")
+        } else {
+            item
+        };
+    }
+    Ok(format!(
+        "# VoiDo settings, apply them with `voido config import <this file>`\n{}",
+        bundle
+    ))
+}
+
+// The config with the bundle's values of `parts` in it, and how many of them changed. A secret
+// left blank in the bundle keeps the one already set.
+pub fn import_bundle(
+    config: &str,
+    bundle: &str,
+    parts: &[ConfigPart],
+) -> Result<(String, usize), Box<dyn Error>> {
+    let bundle = bundle
+        .parse::<DocumentMut>()
+        .map_err(|e| format!("Not a settings file: {}", e))?;
+    let version = bundle
+        .get(BUNDLE_TABLE)
+        .and_then(|header| header.get("version"))
+        .and_then(|version| version.as_integer())
+        .ok_or("Not a VoiDo settings file, export one with `voido config export`")?;
+    if version > BUNDLE_VERSION {
+        return Err(format!(
+            "The file is from a newer VoiDo (version {} settings), update VoiDo first",
+            version
+        )
+        .into());
+    }
+
+    let mut document = config.parse::<DocumentMut>()?;
+    let mut changed = 0;
+    for (section, key, item) in entries(&bundle) {
+        if section == BUNDLE_TABLE || !parts.contains(&part_of(&section, &key)) {
+            continue;
+        }
+        if is_secret(&section, &key) && item.as_str().is_some_and(str::is_empty) {
+            continue;
+        }
+        if !document.contains_table(&section) {
+            document[&section] = toml_edit::table();
+        }
+        let before = document[&section]
+            .get(&key)
+            .map(|item| item.to_string().trim().to_string());
+        if before.as_deref() != Some(item.to_string().trim()) {
+            changed += 1;
+        }
+        document[&section][&key] = item;
+    }
+    Ok((document.to_string(), changed))
+}
+
+pub fn export(file: &str, parts: &[ConfigPart], secrets: bool) -> Result<(), Box<dyn Error>> {
+    let parts = if parts.is_empty() {
+        &ConfigPart::ALL[..]
+    } else {
+        parts
+    };
+    if DBtodo::is_dry_run() {
+        println!(
+            "🧪 Dry run: the settings would be exported to {}, no file was written",
+            file
+        );
+        return Ok(());
+    }
+    AppConfigs::create_default_config()?;
+    let config = fs::read_to_string(AppConfigs::get_config_path()?)?;
+    fs::write(file, export_bundle(&config, parts, secrets)?)?;
+    println!(
+        "✅ Exported the {} to {}",
+        parts
+            .iter()
+            .map(|part| part.name())
+            .collect::<Vec<&str>>()
+            .join(", "),
+        file
+    );
+    if !secrets {
+        println!("   Tokens and client secrets were left out, --secrets keeps them");
+    }
+    Ok(())
+}
+
+pub fn import(file: &str, parts: &[ConfigPart]) -> Result<(), Box<dyn Error>> {
+    let parts = if parts.is_empty() {
+        &ConfigPart::ALL[..]
+    } else {
+        parts
+    };
+    let bundle = fs::read_to_string(file)?;
+    let config_file = AppConfigs::get_config_path()?;
+    // A dry run compares with the file as it is, an empty one before the first run
+    let dry_run = DBtodo::is_dry_run();
+    if !dry_run {
+        AppConfigs::create_default_config()?;
+    }
+    let config = match fs::read_to_string(&config_file) {
+        Err(_) if dry_run => String::new(),
+        config => config?,
+    };
+
+    let (imported, changed) = import_bundle(&config, &bundle, parts)?;
+    let report = configs::check_config(&imported);
+    if !report.errors.is_empty() {
+        return Err(format!(
+            "The file would break config.toml, nothing was changed:\n{}",
+            report.errors.join("\n")
+        )
+        .into());
+    }
+    if changed == 0 {
+        println!("✓ config.toml already has these settings");
+        return Ok(());
+    }
+    if dry_run {
+        println!(
+            "🧪 Dry run: {} setting(s) from {} would be imported, config.toml was not changed",
+            changed, file
+        );
+        return Ok(());
+    }
+
+    let backup = config_file.with_extension("toml.bak");
+    fs::copy(&config_file, &backup)?;
+    fs::write(&config_file, imported)?;
+    println!(
+        "✅ Imported {} setting(s) from {}, the old config is in {}",
+        changed,
+        file,
+        backup.display()
+    );
+    for warning in report.warnings {
+        println!("⚠️  {}", warning);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "[DISPLAY]\n# the look\ntheme = \"ocean\"\ndate_format = \"iso\"\n\n\
+        [DISPLAY.topic_colors]\nWork = \"red\"\n\n[PUBLISH]\nurl = \"https://example.com\"\n\
+        token = \"abc\"\n\n[MACROS]\n1 = [\"pin\"]\n";
+
+    #[test]
+    fn test_export_parts_without_secrets() {
+        let bundle = export_bundle(CONFIG, &[ConfigPart::Theme, ConfigPart::Keys], false).unwrap();
+        let document = bundle.parse::<DocumentMut>().unwrap();
+        assert_eq!(document["DISPLAY"]["theme"].as_str(), Some("ocean"));
+        assert_eq!(
+            document["DISPLAY"]["topic_colors"]["Work"].as_str(),
+            Some("red")
+        );
+        assert!(document["DISPLAY"].get("date_format").is_none());
+        assert!(document.get("PUBLISH").is_none());
+
+        let bundle = export_bundle(CONFIG, &ConfigPart::ALL, false).unwrap();
+        let document = bundle.parse::<DocumentMut>().unwrap();
+        assert_eq!(document["PUBLISH"]["token"].as_str(), Some(""));
+        assert_eq!(
+            document["PUBLISH"]["url"].as_str(),
+            Some("https://example.com")
+        );
+    }
+
+    #[test]
+    fn test_import_merges_into_config() {
+        let bundle = "[VOIDO_BUNDLE]\nversion = 1\n\n[DISPLAY]\ntheme = \"forest\"\n\
+            date_format = \"us\"\n\n[PUBLISH]\ntoken = \"\"\n\n[MACROS]\n2 = [\"unpin\"]\n";
+        let (config, changed) = import_bundle(CONFIG, bundle, &[ConfigPart::Theme]).unwrap();
+        assert_eq!(changed, 1);
+        let document = config.parse::<DocumentMut>().unwrap();
+        assert_eq!(document["DISPLAY"]["theme"].as_str(), Some("forest"));
+        assert_eq!(document["DISPLAY"]["date_format"].as_str(), Some("iso"));
+        // Comments stay, and so does the token the bundle left blank
+        assert!(config.contains("# the look"));
+
+        let (config, changed) = import_bundle(CONFIG, bundle, &ConfigPart::ALL).unwrap();
+        assert_eq!(changed, 3);
+        let document = config.parse::<DocumentMut>().unwrap();
+        assert_eq!(document["PUBLISH"]["token"].as_str(), Some("abc"));
+        assert!(document["MACROS"].get("1").is_some());
+        assert!(document["MACROS"].get("2").is_some());
+
+        assert!(
+            import_bundle(CONFIG, "[DISPLAY]\ntheme = \"forest\"\n", &ConfigPart::ALL).is_err()
+        );
+    }
+}
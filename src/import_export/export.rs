@@ -0,0 +1,327 @@
+// EXPORT: write a list of todos to JSON, Excel, CSV or Markdown. The CLI narrows the list with
+// --filter/--status/--topic, the TUI passes the rows it is showing.
+use std::{error::Error, fs};
+
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+use super::{json, xls};
+use crate::arguments::models::{ExportFormat, SplitBy, Todo};
+use crate::clipboard::{self, CopyFormat};
+use crate::database::DBtodo;
+use crate::search;
+
+impl ExportFormat {
+    pub fn file_name(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "Voido - Todos.json",
+            ExportFormat::Excel => "VoiDo - Todos Export.xlsx",
+            ExportFormat::Csv => "VoiDo - Todos Export.csv",
+            ExportFormat::Markdown => "VoiDo - Todos Export.md",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ExportFilter {
+    pub query: Option<String>,
+    pub status: Option<String>,
+    pub topic: Option<String>,
+}
+
+impl ExportFilter {
+    pub fn apply<'a>(&self, todos: &'a [Todo]) -> Vec<&'a Todo> {
+        let matcher = SkimMatcherV2::default();
+        todos
+            .iter()
+            .filter(|todo| {
+                self.query.as_ref().is_none_or(|query| {
+                    matcher
+                        .fuzzy_match(&search::searchable_text(todo), query)
+                        .is_some()
+                })
+            })
+            .filter(|todo| {
+                self.status
+                    .as_ref()
+                    .is_none_or(|status| status.eq_ignore_ascii_case(&todo.status))
+            })
+            .filter(|todo| {
+                self.topic
+                    .as_ref()
+                    .is_none_or(|topic| topic.eq_ignore_ascii_case(&todo.topic))
+            })
+            .collect()
+    }
+}
+
+// Quote a CSV field when it holds a separator, a quote or a line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Same columns as the Excel export, the subtasks share one cell
+pub fn todos_csv(todos: &[&Todo]) -> String {
+    let mut out = String::from(
+        "ID,PRIORITY,TOPIC,TODO,DESCRIPTION,CREATED,DUE DATE,STATUS,OWNER,NOTES,SUBTASKS\n",
+    );
+    for todo in todos {
+        let subtasks = todo
+            .subtasks
+            .iter()
+            .map(|subtask| format!("{} ({})", subtask.text, subtask.status))
+            .collect::<Vec<String>>()
+            .join("; ");
+        let fields = [
+            todo.id.to_string(),
+            todo.priority.clone(),
+            todo.topic.clone(),
+            todo.text.clone(),
+            todo.desc.clone(),
+            todo.created_display(),
+            todo.due_display(),
+            todo.status.clone(),
+            todo.owner.clone(),
+            todo.notes.clone(),
+            subtasks,
+        ];
+        out.push_str(
+            &fields
+                .iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+        out.push('\n');
+    }
+    out
+}
+
+// Write the todos to the default file of the format and return its name
+pub fn write_todos(todos: &[&Todo], format: ExportFormat) -> Result<&'static str, Box<dyn Error>> {
+    let path = format.file_name();
+    match format {
+        ExportFormat::Json => json::export_to_json(todos, path)?,
+        ExportFormat::Excel => xls::export_todos_xls(todos, path)?,
+        ExportFormat::Csv => fs::write(path, todos_csv(todos))?,
+        ExportFormat::Markdown => fs::write(
+            path,
+            format!(
+                "# VoiDo todos\n\n{}",
+                clipboard::format_todos(todos, CopyFormat::Markdown)
+            ),
+        )?,
+    }
+    Ok(path)
+}
+
+// SPLIT EXPORTS: one group per topic or owner, by name. Names that only differ in case are one
+// group, under the spelling seen first.
+pub fn split_todos<'a>(todos: &[&'a Todo], by: SplitBy) -> Vec<(String, Vec<&'a Todo>)> {
+    let mut groups: Vec<(String, Vec<&'a Todo>)> = Vec::new();
+    for todo in todos {
+        let name = match by {
+            SplitBy::Topic => &todo.topic,
+            SplitBy::Owner => &todo.owner,
+        };
+        match groups
+            .iter_mut()
+            .find(|(group, _)| group.eq_ignore_ascii_case(name))
+        {
+            Some((_, group)) => group.push(todo),
+            None => groups.push((name.clone(), vec![todo])),
+        }
+    }
+    groups.sort_by_key(|(name, _)| name.to_lowercase());
+    groups
+}
+
+// "VoiDo - Todos Export.csv" → "VoiDo - Todos Export - Joe.csv"
+fn split_file_name(format: ExportFormat, name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || " -_".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let name = match name.trim() {
+        "" => "-",
+        name => name,
+    };
+    let (stem, extension) = format
+        .file_name()
+        .rsplit_once('.')
+        .unwrap_or((format.file_name(), ""));
+    format!("{} - {}.{}", stem, name, extension)
+}
+
+// Excel gets a sheet per group in one workbook, the other formats a file per group
+pub fn write_split(
+    todos: &[&Todo],
+    format: ExportFormat,
+    by: SplitBy,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let groups = split_todos(todos, by);
+    if format == ExportFormat::Excel {
+        xls::export_sheets_xls(&groups, format.file_name())?;
+        return Ok(vec![format.file_name().to_string()]);
+    }
+
+    let mut paths: Vec<String> = Vec::new();
+    for (name, todos) in &groups {
+        let mut path = split_file_name(format, name);
+        // Names that only differ in a character the file name can't hold
+        let mut number = 2;
+        while paths.contains(&path) {
+            path = split_file_name(format, &format!("{} {}", name, number));
+            number += 1;
+        }
+        match format {
+            ExportFormat::Json => json::export_to_json(todos, &path)?,
+            ExportFormat::Csv => fs::write(&path, todos_csv(todos))?,
+            ExportFormat::Markdown => fs::write(
+                &path,
+                format!(
+                    "# VoiDo todos: {}\n\n{}",
+                    name,
+                    clipboard::format_todos(todos, CopyFormat::Markdown)
+                ),
+            )?,
+            ExportFormat::Excel => unreachable!(),
+        }
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+pub fn export_todos(
+    format: ExportFormat,
+    filter: &ExportFilter,
+    split_by: Option<SplitBy>,
+) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let todos = db.get_todos()?;
+    let todos = filter.apply(&todos);
+    if todos.is_empty() {
+        println!("❌ No todos match, nothing exported");
+        return Ok(());
+    }
+    if DBtodo::is_dry_run() {
+        println!(
+            "🧪 Dry run: {} todo(s) would be exported, no file was written",
+            todos.len()
+        );
+        return Ok(());
+    }
+
+    let Some(by) = split_by else {
+        let path = write_todos(&todos, format)?;
+        println!("\n🤖 {} todo(s) exported to {}\n", todos.len(), path);
+        return Ok(());
+    };
+    let groups = split_todos(&todos, by).len();
+    let paths = write_split(&todos, format, by)?;
+    if format == ExportFormat::Excel {
+        println!(
+            "\n🤖 {} todo(s) exported to {}, one sheet for each of the {} {}s\n",
+            todos.len(),
+            paths[0],
+            groups,
+            by_name(by)
+        );
+    } else {
+        println!(
+            "\n🤖 {} todo(s) exported to {} file(s), one per {}:",
+            todos.len(),
+            paths.len(),
+            by_name(by)
+        );
+        for path in &paths {
+            println!("   {}", path);
+        }
+        println!();
+    }
+    Ok(())
+}
+
+fn by_name(by: SplitBy) -> &'static str {
+    match by {
+        SplitBy::Topic => "topic",
+        SplitBy::Owner => "owner",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: usize, text: &str, topic: &str, status: &str) -> Todo {
+        Todo {
+            id,
+            topic: topic.to_string(),
+            status: status.to_string(),
+            ..Todo::sample(text)
+        }
+    }
+
+    #[test]
+    fn test_filter_and_csv() {
+        let todos = vec![
+            todo(1, "Write the report", "Work", "Pending"),
+            todo(2, "Buy milk, eggs", "Home", "Pending"),
+            todo(3, "Send the report", "work", "Done"),
+        ];
+
+        let ids = |filter: ExportFilter| {
+            filter
+                .apply(&todos)
+                .iter()
+                .map(|todo| todo.id)
+                .collect::<Vec<usize>>()
+        };
+        assert_eq!(ids(ExportFilter::default()), vec![1, 2, 3]);
+        assert_eq!(
+            ids(ExportFilter {
+                topic: Some("WORK".to_string()),
+                status: Some("pending".to_string()),
+                ..Default::default()
+            }),
+            vec![1]
+        );
+        assert_eq!(
+            ids(ExportFilter {
+                query: Some("report".to_string()),
+                ..Default::default()
+            }),
+            vec![1, 3]
+        );
+
+        let csv = todos_csv(&[&todos[1]]);
+        let row = csv.lines().nth(1).unwrap();
+        assert!(row.starts_with("2,Low,Home,\"Buy milk, eggs\","));
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+
+        // Split per topic, "Work" and "work" are one group
+        let all = todos.iter().collect::<Vec<&Todo>>();
+        let groups = split_todos(&all, SplitBy::Topic)
+            .into_iter()
+            .map(|(name, todos)| (name, todos.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![("Home".to_string(), 1), ("Work".to_string(), 2)]
+        );
+        assert_eq!(
+            split_file_name(ExportFormat::Csv, "R&D / Ops"),
+            "VoiDo - Todos Export - R_D _ Ops.csv"
+        );
+    }
+}
@@ -0,0 +1,76 @@
+// GOOGLE SHEETS IMPORT: download a shared or published sheet as XLSX and run it through the
+// Excel importer, so a team sheet can be pulled without saving it by hand first.
+use std::{error::Error, fs};
+
+use super::xls;
+
+const SHEETS_PREFIX: &str = "https://docs.google.com/spreadsheets/d/";
+
+// Turn the link from the Share or Publish to web dialog into a download link for the workbook
+pub fn download_url(url: &str) -> Result<String, String> {
+    let not_a_sheet = || format!("\"{}\" is not a Google Sheets link", url);
+    let path = url
+        .trim()
+        .strip_prefix(SHEETS_PREFIX)
+        .ok_or_else(not_a_sheet)?;
+
+    // Published to the web: /d/e/<id>/pubhtml, /d/e/<id>/pub?output=csv...
+    if let Some(published) = path.strip_prefix("e/") {
+        let id = published.split(['/', '?', '#']).next().unwrap_or_default();
+        if id.is_empty() {
+            return Err(not_a_sheet());
+        }
+        return Ok(format!("{}e/{}/pub?output=xlsx", SHEETS_PREFIX, id));
+    }
+
+    // Shared with a link: /d/<id>/edit?usp=sharing, /d/<id>/edit#gid=0...
+    let id = path.split(['/', '?', '#']).next().unwrap_or_default();
+    if id.is_empty() {
+        return Err(not_a_sheet());
+    }
+    Ok(format!("{}{}/export?format=xlsx", SHEETS_PREFIX, id))
+}
+
+pub async fn import_gsheet(url: &str) -> Result<(), Box<dyn Error>> {
+    let download = download_url(url)?;
+    println!("⬇️ Downloading {}", download);
+
+    let bytes = reqwest::get(&download)
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    // A private sheet answers with the Google sign-in page instead of a workbook
+    if !bytes.starts_with(b"PK") {
+        return Err("Google did not send a spreadsheet. Share the sheet with \"Anyone with the link\" or publish it to the web".into());
+    }
+
+    let path = std::env::temp_dir().join("voido-gsheet.xlsx");
+    fs::write(&path, &bytes)?;
+    let result = xls::import_todos(&path.to_string_lossy());
+    let _ = fs::remove_file(&path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_download_url() {
+        assert_eq!(
+            download_url("https://docs.google.com/spreadsheets/d/1AbC-xyz/edit?usp=sharing"),
+            Ok("https://docs.google.com/spreadsheets/d/1AbC-xyz/export?format=xlsx".to_string())
+        );
+        assert_eq!(
+            download_url("https://docs.google.com/spreadsheets/d/1AbC-xyz/edit#gid=0"),
+            Ok("https://docs.google.com/spreadsheets/d/1AbC-xyz/export?format=xlsx".to_string())
+        );
+        assert_eq!(
+            download_url("https://docs.google.com/spreadsheets/d/e/2PACX-1vQ/pubhtml"),
+            Ok("https://docs.google.com/spreadsheets/d/e/2PACX-1vQ/pub?output=xlsx".to_string())
+        );
+        assert!(download_url("https://example.com/sheet.xlsx").is_err());
+        assert!(download_url("https://docs.google.com/spreadsheets/d/").is_err());
+    }
+}
@@ -0,0 +1,168 @@
+// HTML REPORT: one self-contained page with the stats, topic progress, owner workload and
+// every todo in a table that sorts by clicking a header. No external assets, so it can be
+// attached to an email as is.
+use std::{error::Error, fs};
+
+use chrono::Local;
+
+use crate::arguments::models::Todo;
+use crate::arguments::report;
+use crate::database::DBtodo;
+use crate::dates;
+
+const STYLE: &str = r#"body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem; color: #222; }
+h1 { margin-bottom: 0; }
+.subtitle { color: #777; margin-top: 0.25rem; }
+.cards { display: flex; gap: 1rem; margin: 1.5rem 0; }
+.card { border: 1px solid #ddd; border-radius: 6px; padding: 0.75rem 1.25rem; min-width: 7rem; }
+.card .value { font-size: 1.6rem; font-weight: bold; }
+.card .label { color: #777; font-size: 0.85rem; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 2rem; }
+th, td { text-align: left; padding: 0.4rem 0.6rem; border-bottom: 1px solid #eee; vertical-align: top; }
+th { background: #f5f5f5; }
+table.sortable th { cursor: pointer; user-select: none; }
+table.sortable th.asc::after { content: " ▲"; }
+table.sortable th.desc::after { content: " ▼"; }
+.bar { background: #eee; border-radius: 3px; height: 0.8rem; width: 12rem; }
+.bar div { background: #4caf50; border-radius: 3px; height: 100%; }
+.done { color: #999; }"#;
+
+// Numbers sort numerically, everything else as text
+const SCRIPT: &str = r#"document.querySelectorAll("table.sortable th").forEach((th, column) => {
+  th.addEventListener("click", () => {
+    const table = th.closest("table");
+    const body = table.tBodies[0];
+    const ascending = !th.classList.contains("asc");
+    table.querySelectorAll("th").forEach((other) => other.classList.remove("asc", "desc"));
+    th.classList.add(ascending ? "asc" : "desc");
+    const value = (row) => {
+      const cell = row.cells[column];
+      return cell.dataset.sort ?? cell.textContent.trim();
+    };
+    const rows = Array.from(body.rows).sort((a, b) => {
+      const [left, right] = [value(a), value(b)];
+      const order = left !== "" && right !== "" && !isNaN(left) && !isNaN(right)
+        ? Number(left) - Number(right)
+        : left.localeCompare(right);
+      return ascending ? order : -order;
+    });
+    rows.forEach((row) => body.appendChild(row));
+  });
+});"#;
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn card(value: usize, label: &str) -> String {
+    format!(
+        "<div class=\"card\"><div class=\"value\">{}</div><div class=\"label\">{}</div></div>",
+        value, label
+    )
+}
+
+pub fn render_report(todos: &[Todo]) -> String {
+    let today = Local::now().date_naive();
+    let done = todos.iter().filter(|todo| todo.is_done()).count();
+    let overdue = todos
+        .iter()
+        .filter(|todo| !todo.is_done() && todo.next_due().is_some_and(|due| due < today))
+        .count();
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>VoiDo report</title>\n");
+    html.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE));
+    html.push_str("<h1>VoiDo report</h1>\n");
+    html.push_str(&format!(
+        "<p class=\"subtitle\">{}</p>\n",
+        escape(&dates::format_date(today))
+    ));
+
+    // STATS
+    html.push_str("<div class=\"cards\">");
+    html.push_str(&card(todos.len(), "Todos"));
+    html.push_str(&card(todos.len() - done, "Open"));
+    html.push_str(&card(done, "Done"));
+    html.push_str(&card(overdue, "Overdue"));
+    html.push_str("</div>\n");
+
+    // PROGRESS PER TOPIC
+    html.push_str("<h2>Progress by topic</h2>\n<table class=\"sortable\">\n");
+    html.push_str("<thead><tr><th>Topic</th><th>Done</th><th>Total</th><th>Progress</th></tr></thead>\n<tbody>\n");
+    for entry in report::topic_progress(todos) {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td data-sort=\"{}\"><div class=\"bar\"><div style=\"width: {}%\"></div></div> {}%</td></tr>\n",
+            escape(&entry.topic),
+            entry.done,
+            entry.total,
+            entry.percent(),
+            entry.percent(),
+            entry.percent()
+        ));
+    }
+    html.push_str("</tbody>\n</table>\n");
+
+    // OPEN WORK PER OWNER
+    html.push_str("<h2>Open todos by owner</h2>\n<table class=\"sortable\">\n");
+    html.push_str("<thead><tr><th>Owner</th><th>High</th><th>Medium</th><th>Normal</th><th>Low</th><th>Open</th><th>Open subtasks</th></tr></thead>\n<tbody>\n");
+    for entry in report::owner_workload(todos) {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            escape(&entry.owner),
+            entry.high,
+            entry.medium,
+            entry.normal,
+            entry.low,
+            entry.open,
+            entry.open_subtasks
+        ));
+    }
+    html.push_str("</tbody>\n</table>\n");
+
+    // EVERY TODO, dates sort by their ISO value
+    html.push_str("<h2>Todos</h2>\n<table class=\"sortable\">\n");
+    html.push_str("<thead><tr><th>ID</th><th>Todo</th><th>Topic</th><th>Priority</th><th>Status</th><th>Owner</th><th>Created</th><th>Due</th></tr></thead>\n<tbody>\n");
+    for todo in todos {
+        html.push_str(&format!(
+            "<tr{}><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td data-sort=\"{}\">{}</td><td data-sort=\"{}\">{}</td></tr>\n",
+            if todo.is_done() { " class=\"done\"" } else { "" },
+            todo.id,
+            escape(&todo.text),
+            escape(&todo.topic),
+            escape(&todo.priority),
+            escape(&todo.status),
+            escape(&todo.owner),
+            todo.date_added.to_rfc3339(),
+            escape(&todo.created_display()),
+            todo.due.map(|due| due.to_string()).unwrap_or_default(),
+            escape(&todo.due_display())
+        ));
+    }
+    html.push_str("</tbody>\n</table>\n");
+
+    html.push_str(&format!(
+        "<script>\n{}\n</script>\n</body>\n</html>\n",
+        SCRIPT
+    ));
+    html
+}
+
+// WRITE THE REPORT OF ALL ACTIVE TODOS TO AN HTML FILE
+pub fn export_report_html(path: &str) -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let report = render_report(&db.get_todos()?);
+    if DBtodo::is_dry_run() {
+        println!(
+            "🧪 Dry run: the report would be written to {}, no file was written",
+            path
+        );
+        return Ok(());
+    }
+    fs::write(path, report)?;
+    println!(" \n🤖 Report written to {}", path);
+    Ok(())
+}
@@ -5,18 +5,15 @@ use std::{
 
 use rusqlite::params;
 
-use crate::{arguments::models::Todo, data, database};
+use crate::{arguments::models::Todo, database};
 
-pub fn export_to_json() -> Result<(), Box<dyn std::error::Error>> {
-    let todos = data::sample_todos();
-    let json = serde_json::to_string(&todos);
+pub fn export_to_json(todos: &[&Todo], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string(todos);
 
     // write the file to HD
-    let mut file = std::fs::File::create("Voido - Todos.json")?;
+    let mut file = std::fs::File::create(path)?;
     file.write_all(json?.as_bytes())?;
 
-    println!(" \n🤖 Todos exported successfully!");
-
     Ok(())
 }
 
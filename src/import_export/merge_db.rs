@@ -0,0 +1,77 @@
+// MERGE DATABASES: pull the todos of another VoiDo database (another machine's todos.db) into
+// this one, for consolidating work and personal setups.
+use std::{error::Error, fs, path::Path};
+
+use crate::configs;
+use crate::database::DBtodo;
+
+pub fn merge_db(file_path: &str) -> Result<(), Box<dyn Error>> {
+    let source = Path::new(file_path);
+    if !source.is_file() {
+        return Err(format!("No database file at {}", file_path).into());
+    }
+    let own = configs::db_path()?;
+    if own.canonicalize().ok() == source.canonicalize().ok() {
+        return Err("That is the database VoiDo already uses".into());
+    }
+
+    // Work on a copy, opening a database upgrades its schema and the other file should stay as is
+    let copy = std::env::temp_dir().join("voido-merge.db");
+    fs::copy(source, &copy)?;
+    let result = DBtodo::open(&copy).and_then(|other| DBtodo::new()?.merge_from(&other, file_path));
+    let _ = fs::remove_file(&copy);
+
+    let (added, skipped) = result?;
+    println!(
+        "✅ Merged {}: {} todo(s) added, {} already here were skipped",
+        file_path, added, skipped
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arguments::models::Todo;
+
+    fn todo(text: &str) -> Todo {
+        Todo::sample(text)
+    }
+
+    #[test]
+    fn test_merge_from() {
+        let memory = Path::new(":memory:");
+        let mut db = DBtodo::open(memory).unwrap();
+        db.add_todo(&todo("Write report")).unwrap();
+
+        let mut other = DBtodo::open(memory).unwrap();
+        for text in ["Filler", "write REPORT", "Send report"] {
+            other.add_todo(&todo(text)).unwrap();
+        }
+        // Send report (3) waits on the report (2), which already exists here as 1
+        other.add_dependency(3, 2).unwrap();
+        other.save_milestone("Q3", None).unwrap();
+        let q3 = other.find_milestone("Q3").unwrap().unwrap().id;
+        other.set_milestone(&[3], Some(q3)).unwrap();
+        other
+            .add_comment(2, "Ana", "Skipped with its todo")
+            .unwrap();
+        other.add_comment(3, "Ana", "Copied").unwrap();
+
+        assert_eq!(db.merge_from(&other, "other.db").unwrap(), (2, 1));
+        let todos = db.get_todos().unwrap();
+        let send = todos
+            .iter()
+            .find(|todo| todo.text == "Send report")
+            .unwrap();
+        assert_eq!(db.dependency_links().unwrap(), vec![(send.id as i64, 1)]);
+        let milestone = db.find_milestone("q3").unwrap().unwrap();
+        assert_eq!(
+            db.milestone_links().unwrap(),
+            vec![(send.id as i64, milestone.id)]
+        );
+        let comments = db.get_comments(None).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].todo_id, send.id as i64);
+    }
+}
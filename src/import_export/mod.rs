@@ -1,2 +1,46 @@
+pub mod config_bundle;
+pub mod export;
+#[cfg(feature = "sync")]
+pub mod gsheet;
+pub mod html;
 pub mod json;
+pub mod merge_db;
+#[cfg(feature = "sync")]
+pub mod mstodo;
+pub mod notion;
+pub mod seed;
+pub mod sql;
+#[cfg(feature = "xls")]
 pub mod xls;
+
+// Without the `xls` feature Excel files are refused
+#[cfg(not(feature = "xls"))]
+pub mod xls {
+    use crate::arguments::models::Todo;
+    use crate::features::missing;
+    use std::error::Error;
+
+    pub fn export_todos_xls(_todos: &[&Todo], _path: &str) -> Result<(), Box<dyn Error>> {
+        Err(missing("xls"))
+    }
+
+    pub fn export_sheets_xls(
+        _groups: &[(String, Vec<&Todo>)],
+        _path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        Err(missing("xls"))
+    }
+
+    pub fn import_todos(_file_path: &str) -> Result<(), Box<dyn Error>> {
+        Err(missing("xls"))
+    }
+}
+
+// Import a file, Excel workbooks by extension and JSON otherwise
+pub fn import_file(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if file_path.ends_with(".xlsx") {
+        xls::import_todos(file_path)
+    } else {
+        json::import_from_json(file_path)
+    }
+}
@@ -0,0 +1,289 @@
+// MICROSOFT TO DO IMPORT: sign in with a device code, then pull every task list through the
+// Graph API. Lists become topics, steps become subtasks. Tasks already in VoiDo (same topic and
+// text) are skipped, so the import can be run again to pick up new ones.
+use std::{error::Error, time::Duration};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::arguments::models::{Subtask, Todo};
+use crate::configs::{AppConfigs, DefaultsConfig};
+use crate::database::DBtodo;
+use crate::dates;
+
+const LOGIN_URL: &str = "https://login.microsoftonline.com/common/oauth2/v2.0";
+const GRAPH_URL: &str = "https://graph.microsoft.com/v1.0";
+const SCOPE: &str = "Tasks.Read";
+
+#[derive(Debug, Deserialize)]
+struct DeviceCode {
+    device_code: String,
+    message: String,
+    interval: u64,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+// One page of a Graph collection
+#[derive(Debug, Deserialize)]
+struct Page<T> {
+    value: Vec<T>,
+    #[serde(rename = "@odata.nextLink")]
+    next_link: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskList {
+    id: String,
+    display_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Task {
+    title: String,
+    status: String,
+    importance: String,
+    body: Option<TaskBody>,
+    due_date_time: Option<GraphDate>,
+    #[serde(default, with = "dates::optional_timestamp_serde")]
+    created_date_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    checklist_items: Vec<ChecklistItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskBody {
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphDate {
+    // "2025-03-14T00:00:00.0000000", in the time zone next to it
+    date_time: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChecklistItem {
+    display_name: String,
+    is_checked: bool,
+}
+
+fn priority(importance: &str) -> &'static str {
+    match importance {
+        "high" => "High",
+        "low" => "Low",
+        _ => "Medium",
+    }
+}
+
+fn status(status: &str) -> &'static str {
+    match status {
+        "completed" => "Done",
+        "inProgress" => "Ongoing",
+        _ => "Pending",
+    }
+}
+
+fn task_to_todo(list: &str, task: Task, owner: &str) -> Todo {
+    Todo {
+        id: 0,
+        priority: priority(&task.importance).to_string(),
+        topic: list.to_string(),
+        text: task.title.trim().to_string(),
+        desc: task
+            .body
+            .map(|body| body.content.trim().to_string())
+            .unwrap_or_default(),
+        date_added: task.created_date_time.unwrap_or_else(Utc::now),
+        status: status(&task.status).to_string(),
+        owner: owner.to_string(),
+        due: task
+            .due_date_time
+            .and_then(|due| NaiveDate::parse_from_str(due.date_time.get(..10)?, "%Y-%m-%d").ok()),
+        subtasks: task
+            .checklist_items
+            .into_iter()
+            .map(|item| Subtask {
+                todo_id: 0,
+                subtask_id: 0,
+                text: item.display_name,
+                status: if item.is_checked { "Done" } else { "Pending" }.to_string(),
+                due: None,
+                owner: None,
+                note: String::new(),
+            })
+            .collect(),
+        notes: String::new(),
+        pinned: false,
+        updated_at: None,
+        archived: false,
+        waiting_on: None,
+        context: None,
+        external_url: None,
+        due_at: None,
+    }
+}
+
+// DEVICE CODE SIGN-IN: print the code, then wait until it is entered in the browser
+async fn sign_in(client: &Client, client_id: &str) -> Result<String, Box<dyn Error>> {
+    let code = client
+        .post(format!("{}/devicecode", LOGIN_URL))
+        .form(&[("client_id", client_id), ("scope", SCOPE)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<DeviceCode>()
+        .await?;
+    println!("\n🔑 {}\n", code.message);
+
+    let mut interval = code.interval.max(1);
+    let mut waited = 0;
+    while waited < code.expires_in {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        waited += interval;
+
+        let token = client
+            .post(format!("{}/token", LOGIN_URL))
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("client_id", client_id),
+                ("device_code", code.device_code.as_str()),
+            ])
+            .send()
+            .await?
+            .json::<TokenResponse>()
+            .await?;
+        match (token.access_token, token.error.as_deref()) {
+            (Some(access_token), _) => return Ok(access_token),
+            (None, Some("authorization_pending")) => {}
+            (None, Some("slow_down")) => interval += 5,
+            (None, _) => {
+                return Err(token
+                    .error_description
+                    .or(token.error)
+                    .unwrap_or("sign-in failed".to_string())
+                    .into());
+            }
+        }
+    }
+    Err("The sign-in code expired, run the import again".into())
+}
+
+// Every item of a Graph collection, following the next links
+async fn get_all<T: for<'de> Deserialize<'de>>(
+    client: &Client,
+    token: &str,
+    url: String,
+) -> Result<Vec<T>, Box<dyn Error>> {
+    let mut items = Vec::new();
+    let mut next = Some(url);
+    while let Some(url) = next {
+        let page = client
+            .get(&url)
+            .bearer_auth(token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Page<T>>()
+            .await?;
+        items.extend(page.value);
+        next = page.next_link;
+    }
+    Ok(items)
+}
+
+pub async fn import_mstodo() -> Result<(), Box<dyn Error>> {
+    let client_id = AppConfigs::new()?.ms_client_id;
+    if client_id.trim().is_empty() {
+        return Err("Set client_id in the [MICROSOFT] section of config.toml first".into());
+    }
+
+    let client = Client::new();
+    let token = sign_in(&client, client_id.trim()).await?;
+    let owner = DefaultsConfig::load().owner;
+
+    let mut todos = Vec::new();
+    let lists =
+        get_all::<TaskList>(&client, &token, format!("{}/me/todo/lists", GRAPH_URL)).await?;
+    for list in &lists {
+        let tasks = get_all::<Task>(
+            &client,
+            &token,
+            format!(
+                "{}/me/todo/lists/{}/tasks?$expand=checklistItems",
+                GRAPH_URL, list.id
+            ),
+        )
+        .await?;
+        println!("📋 {}: {} task(s)", list.display_name, tasks.len());
+        todos.extend(
+            tasks
+                .into_iter()
+                .map(|task| task_to_todo(&list.display_name, task, &owner)),
+        );
+    }
+
+    let mut db = DBtodo::new()?;
+    let existing = db.get_todos()?;
+    let total = todos.len();
+    todos.retain(|todo| {
+        !existing.iter().any(|known| {
+            known.topic.eq_ignore_ascii_case(&todo.topic)
+                && known.text.eq_ignore_ascii_case(&todo.text)
+        })
+    });
+
+    let added = db.add_todos(&todos)?;
+    println!(
+        "\n✅ Imported {} task(s) from Microsoft To Do, {} already in VoiDo were skipped",
+        added,
+        total - added
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_to_todo() {
+        let task = serde_json::from_str::<Task>(
+            r#"{
+                "title": "Renew passport ",
+                "status": "inProgress",
+                "importance": "high",
+                "body": { "content": "Photos first", "contentType": "text" },
+                "dueDateTime": { "dateTime": "2025-03-14T00:00:00.0000000", "timeZone": "UTC" },
+                "createdDateTime": "2025-03-01T09:30:00Z",
+                "checklistItems": [
+                    { "displayName": "Get photos", "isChecked": true },
+                    { "displayName": "Fill the form", "isChecked": false }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let todo = task_to_todo("Errands", task, "You");
+        assert_eq!(todo.text, "Renew passport");
+        assert_eq!(todo.topic, "Errands");
+        assert_eq!(todo.priority, "High");
+        assert_eq!(todo.status, "Ongoing");
+        assert_eq!(todo.desc, "Photos first");
+        assert_eq!(todo.due, NaiveDate::from_ymd_opt(2025, 3, 14));
+        assert_eq!(todo.subtasks.len(), 2);
+        assert_eq!(todo.subtasks[0].status, "Done");
+        assert_eq!(todo.subtasks[1].status, "Pending");
+    }
+}
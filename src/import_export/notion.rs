@@ -0,0 +1,341 @@
+// NOTION IMPORT: read the CSV a Notion database exports ("Export" → "Markdown & CSV"), ask which
+// column feeds which VoiDo field and add the rows as todos. Checkbox properties become subtasks
+// named after their column, ticked ones Done.
+use std::{
+    error::Error,
+    fs,
+    io::{self, Write},
+};
+
+use chrono::{NaiveDate, Utc};
+
+use crate::arguments::models::{Subtask, Todo};
+use crate::configs::DefaultsConfig;
+use crate::database::DBtodo;
+use crate::dates;
+
+// VoiDo fields and the Notion property names guessed for them
+const FIELDS: [(&str, &[&str]); 8] = [
+    ("todo", &["name", "title", "task", "todo"]),
+    ("description", &["description", "desc", "summary"]),
+    ("status", &["status", "state"]),
+    ("priority", &["priority", "importance"]),
+    ("topic", &["topic", "tags", "tag", "category", "project"]),
+    (
+        "owner",
+        &["owner", "assignee", "assign", "assigned to", "person"],
+    ),
+    ("due", &["due", "due date", "deadline", "date"]),
+    ("notes", &["notes", "note", "comments"]),
+];
+
+// Column index for each of FIELDS, and the checkbox columns that become subtasks
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Mapping {
+    fields: [Option<usize>; 8],
+    checkboxes: Vec<usize>,
+}
+
+// Split CSV text into rows, quoted fields may hold commas, quotes and line breaks
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+fn is_checkbox(value: &str) -> bool {
+    matches!(value.trim(), "Yes" | "No")
+}
+
+fn guess_mapping(headers: &[String], rows: &[Vec<String>]) -> Mapping {
+    let mut fields = [None; 8];
+    for (field, (_, names)) in FIELDS.iter().enumerate() {
+        fields[field] = headers.iter().enumerate().position(|(index, header)| {
+            names.contains(&header.trim().to_lowercase().as_str()) && !fields.contains(&Some(index))
+        });
+    }
+
+    let checkboxes = (0..headers.len())
+        .filter(|index| !fields.contains(&Some(*index)))
+        .filter(|&index| {
+            let mut values = rows
+                .iter()
+                .filter_map(|row| row.get(index))
+                .filter(|value| !value.trim().is_empty())
+                .peekable();
+            values.peek().is_some() && values.all(|value| is_checkbox(value))
+        })
+        .collect();
+    Mapping { fields, checkboxes }
+}
+
+fn status(value: &str) -> String {
+    let value = value.to_lowercase();
+    if value.contains("done") || value.contains("complete") {
+        "Done"
+    } else if value.contains("progress") || value.contains("doing") {
+        "Ongoing"
+    } else if value.contains("plan") {
+        "Planned"
+    } else {
+        "Pending"
+    }
+    .to_string()
+}
+
+fn priority(value: &str) -> String {
+    let value = value.to_lowercase();
+    if value.contains("high") || value.contains("urgent") {
+        "High"
+    } else if value.contains("low") {
+        "Low"
+    } else {
+        "Medium"
+    }
+    .to_string()
+}
+
+// Notion writes "March 14, 2025", and "start → end" for ranges, the start is used
+fn parse_due(value: &str) -> Option<NaiveDate> {
+    let start = value.split('→').next()?.trim();
+    dates::parse_date(start)
+        .or_else(|| {
+            // "March 14, 2025", with a time after it when the property has one
+            let date = start
+                .splitn(4, ' ')
+                .take(3)
+                .collect::<Vec<&str>>()
+                .join(" ");
+            NaiveDate::parse_from_str(&date, "%B %d, %Y").ok()
+        })
+        .or_else(|| NaiveDate::parse_from_str(start, "%Y/%m/%d").ok())
+}
+
+fn row_to_todo(
+    headers: &[String],
+    row: &[String],
+    mapping: &Mapping,
+    defaults: &DefaultsConfig,
+) -> Option<Todo> {
+    let cell = |field: usize| {
+        mapping.fields[field]
+            .and_then(|index| row.get(index))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let text = cell(0)?;
+
+    Some(Todo {
+        id: 0,
+        priority: priority(&cell(3).unwrap_or(defaults.priority.clone())),
+        // Multi-select properties list every tag, the first one is the topic
+        topic: cell(4)
+            .and_then(|tags| tags.split(',').next().map(|tag| tag.trim().to_string()))
+            .unwrap_or(defaults.topic.clone()),
+        text,
+        desc: cell(1).unwrap_or_default(),
+        date_added: Utc::now(),
+        status: status(&cell(2).unwrap_or_default()),
+        owner: cell(5).unwrap_or(defaults.owner.clone()),
+        due: cell(6).and_then(|due| parse_due(&due)),
+        subtasks: mapping
+            .checkboxes
+            .iter()
+            .map(|&index| Subtask {
+                todo_id: 0,
+                subtask_id: 0,
+                text: headers[index].trim().to_string(),
+                status: if row.get(index).is_some_and(|value| value.trim() == "Yes") {
+                    "Done"
+                } else {
+                    "Pending"
+                }
+                .to_string(),
+                due: None,
+                owner: None,
+                note: String::new(),
+            })
+            .collect(),
+        notes: cell(7).unwrap_or_default(),
+        pinned: false,
+        updated_at: None,
+        archived: false,
+        waiting_on: None,
+        context: None,
+        external_url: None,
+        due_at: None,
+    })
+}
+
+fn ask(prompt: &str) -> Result<String, Box<dyn Error>> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+// A column by number or by name
+fn find_column(headers: &[String], answer: &str) -> Option<usize> {
+    match answer.parse::<usize>() {
+        Ok(number) => (1..=headers.len()).contains(&number).then(|| number - 1),
+        Err(_) => headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(answer)),
+    }
+}
+
+// COLUMN MAPPING PROMPT: Enter keeps the guess, `-` leaves the field empty
+fn confirm_mapping(headers: &[String], mut mapping: Mapping) -> Result<Mapping, Box<dyn Error>> {
+    let name =
+        |index: Option<usize>| index.map_or("none".to_string(), |index| headers[index].clone());
+
+    println!("\nColumns in the export:");
+    for (index, header) in headers.iter().enumerate() {
+        println!("  {}. {}", index + 1, header);
+    }
+    println!("\nPick the column for each field: Enter keeps [the guess], - for none\n");
+
+    for (field, (label, _)) in FIELDS.iter().enumerate() {
+        loop {
+            let answer = ask(&format!(
+                "{:<12} [{}]: ",
+                label,
+                name(mapping.fields[field])
+            ))?;
+            if answer.is_empty() {
+                break;
+            }
+            if answer == "-" {
+                mapping.fields[field] = None;
+                break;
+            }
+            match find_column(headers, &answer) {
+                Some(index) => {
+                    mapping.fields[field] = Some(index);
+                    break;
+                }
+                None => println!("❌ No column \"{}\"", answer),
+            }
+        }
+    }
+    if mapping.fields[0].is_none() {
+        return Err("The todo text needs a column".into());
+    }
+
+    let guessed = mapping
+        .checkboxes
+        .iter()
+        .map(|&index| headers[index].clone())
+        .collect::<Vec<String>>()
+        .join(", ");
+    let answer = ask(&format!(
+        "Checkbox columns to turn into subtasks, comma separated [{}]: ",
+        guessed
+    ))?;
+    if answer == "-" {
+        mapping.checkboxes.clear();
+    } else if !answer.is_empty() {
+        mapping.checkboxes = answer
+            .split(',')
+            .map(|column| {
+                find_column(headers, column.trim())
+                    .ok_or_else(|| format!("No column \"{}\"", column.trim()))
+            })
+            .collect::<Result<Vec<usize>, String>>()?;
+    }
+    Ok(mapping)
+}
+
+pub fn import_notion(file_path: &str) -> Result<(), Box<dyn Error>> {
+    let mut rows = parse_csv(&fs::read_to_string(file_path)?);
+    if rows.is_empty() {
+        return Err("The CSV file is empty".into());
+    }
+    let headers = rows.remove(0);
+
+    let mapping = confirm_mapping(&headers, guess_mapping(&headers, &rows))?;
+    let defaults = DefaultsConfig::load();
+    let todos = rows
+        .iter()
+        .filter_map(|row| row_to_todo(&headers, row, &mapping, &defaults))
+        .collect::<Vec<Todo>>();
+    if todos.is_empty() {
+        println!("❌ No rows with a todo text, nothing imported");
+        return Ok(());
+    }
+
+    let answer = ask(&format!(
+        "\nAdd {} todo(s) from Notion? [y/N] ",
+        todos.len()
+    ))?;
+    if !answer.eq_ignore_ascii_case("y") {
+        println!("Import cancelled");
+        return Ok(());
+    }
+    let added = DBtodo::new()?.add_todos(&todos)?;
+    println!("✅ Imported {} todo(s) from {}", added, file_path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notion_csv_import() {
+        let content = "\u{feff}Name,Status,Tags,Due,Reviewed,Notes\r\n\
+            \"Plan launch, v2\",In progress,\"Work, Q3\",\"March 14, 2025\",Yes,\"Say \"\"hi\"\"\nthen go\"\r\n\
+            Tidy desk,Not started,,,No,\r\n";
+        let mut rows = parse_csv(content);
+        let headers = rows.remove(0);
+        assert_eq!(headers[0], "Name");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][5], "Say \"hi\"\nthen go");
+
+        let mapping = guess_mapping(&headers, &rows);
+        assert_eq!(mapping.fields[0], Some(0));
+        assert_eq!(mapping.fields[4], Some(2));
+        assert_eq!(mapping.fields[6], Some(3));
+        assert_eq!(mapping.checkboxes, vec![4]);
+
+        let defaults = DefaultsConfig::default();
+        let todo = row_to_todo(&headers, &rows[0], &mapping, &defaults).unwrap();
+        assert_eq!(todo.text, "Plan launch, v2");
+        assert_eq!(todo.status, "Ongoing");
+        assert_eq!(todo.topic, "Work");
+        assert_eq!(todo.due, NaiveDate::from_ymd_opt(2025, 3, 14));
+        assert_eq!(todo.subtasks[0].text, "Reviewed");
+        assert_eq!(todo.subtasks[0].status, "Done");
+
+        let todo = row_to_todo(&headers, &rows[1], &mapping, &defaults).unwrap();
+        assert_eq!(todo.topic, defaults.topic);
+        assert_eq!(todo.status, "Pending");
+        assert_eq!(todo.subtasks[0].status, "Pending");
+    }
+}
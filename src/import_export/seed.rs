@@ -0,0 +1,333 @@
+// SEED FIXTURES: a declarative list of todos, milestones and checklists in YAML or JSON, for
+// demos, tests and setting up a new machine with a standard checklist. Everything is checked
+// before anything is written, and the whole fixture goes in as one transaction.
+use std::{error::Error, fs, io};
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use crate::arguments::add_todo::build_todo;
+use crate::arguments::models::{Checklist, Milestone, Subtask, Todo, normalize_context};
+use crate::database::DBtodo;
+use crate::dates;
+
+const STATUSES: [&str; 5] = ["Pending", "Ongoing", "Planned", "Review", "Done"];
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Fixture {
+    #[serde(default)]
+    todos: Vec<TodoFixture>,
+    #[serde(default)]
+    milestones: Vec<MilestoneFixture>,
+    #[serde(default)]
+    checklists: Vec<ChecklistFixture>,
+}
+
+// A todo as a fixture writes it, `voido apply` adds them the same way
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TodoFixture {
+    pub text: String,
+    desc: Option<String>,
+    topic: Option<String>,
+    priority: Option<String>,
+    status: Option<String>,
+    owner: Option<String>,
+    context: Option<String>,
+    // Anything `--due` takes: 2025-03-14, today, fri 09:30...
+    due: Option<String>,
+    // RFC 3339 or a date, now when left out
+    created: Option<String>,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    pinned: bool,
+    milestone: Option<String>,
+    #[serde(default)]
+    subtasks: Vec<SubtaskFixture>,
+}
+
+// A subtask is its text, or a map when it needs more than that
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SubtaskFixture {
+    Text(String),
+    Full {
+        text: String,
+        status: Option<String>,
+        due: Option<String>,
+        owner: Option<String>,
+        #[serde(default)]
+        note: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MilestoneFixture {
+    name: String,
+    target: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChecklistFixture {
+    name: String,
+    items: Vec<String>,
+}
+
+// YAML, or JSON when the file says so
+pub fn parse_fixture<T: DeserializeOwned>(
+    file_path: &str,
+    content: &str,
+) -> Result<T, Box<dyn Error>> {
+    if file_path.ends_with(".json") {
+        Ok(serde_json::from_str(content)?)
+    } else {
+        parse_yaml(content)
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn parse_yaml<T: DeserializeOwned>(content: &str) -> Result<T, Box<dyn Error>> {
+    Ok(serde_yaml::from_str(content)?)
+}
+
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml<T: DeserializeOwned>(_content: &str) -> Result<T, Box<dyn Error>> {
+    Err(crate::features::missing("yaml"))
+}
+
+pub fn status(value: Option<String>) -> Result<String, String> {
+    let Some(value) = value else {
+        return Ok("Pending".to_string());
+    };
+    STATUSES
+        .iter()
+        .find(|status| status.eq_ignore_ascii_case(value.trim()))
+        .map(|status| status.to_string())
+        .ok_or_else(|| {
+            format!(
+                "status \"{}\" should be one of {}",
+                value,
+                STATUSES.join(", ")
+            )
+        })
+}
+
+fn due(value: Option<String>, today: NaiveDate) -> Result<Option<NaiveDate>, String> {
+    value
+        .map(|value| {
+            dates::parse_due(value.trim(), today)
+                .ok_or_else(|| format!("\"{}\" is not a date", value))
+        })
+        .transpose()
+}
+
+// Todos can be due at a time of day too
+pub fn due_at(
+    value: Option<String>,
+    today: NaiveDate,
+) -> Result<(Option<NaiveDate>, Option<DateTime<Utc>>), String> {
+    match value {
+        Some(value) => dates::parse_due_at(value.trim(), today)
+            .map(|(date, due_at)| (Some(date), due_at))
+            .ok_or_else(|| format!("\"{}\" is not a date", value)),
+        None => Ok((None, None)),
+    }
+}
+
+fn subtask(fixture: SubtaskFixture, today: NaiveDate) -> Result<Subtask, String> {
+    let (text, status_value, due_value, owner, note) = match fixture {
+        SubtaskFixture::Text(text) => (text, None, None, None, String::new()),
+        SubtaskFixture::Full {
+            text,
+            status,
+            due,
+            owner,
+            note,
+        } => (text, status, due, owner, note),
+    };
+    Ok(Subtask {
+        todo_id: 0,
+        subtask_id: 0,
+        text,
+        status: status(status_value)?,
+        due: due(due_value, today)?,
+        owner,
+        note,
+    })
+}
+
+pub fn todo(fixture: TodoFixture, today: NaiveDate) -> Result<(Todo, Option<String>), String> {
+    let mut todo = build_todo(
+        fixture.text,
+        fixture.topic,
+        fixture.priority,
+        fixture.owner,
+        None,
+        fixture.desc,
+        Vec::new(),
+    );
+    // Only the due date the fixture sets, not the configured default offset
+    (todo.due, todo.due_at) = due_at(fixture.due, today)?;
+    todo.status = status(fixture.status)?;
+    todo.notes = fixture.notes;
+    todo.pinned = fixture.pinned;
+    todo.context = fixture.context.as_deref().and_then(normalize_context);
+    if let Some(created) = fixture.created {
+        todo.date_added = dates::parse_timestamp(&created)
+            .ok_or_else(|| format!("created \"{}\" is not a date", created))?;
+    }
+    todo.subtasks = fixture
+        .subtasks
+        .into_iter()
+        .map(|fixture| subtask(fixture, today))
+        .collect::<Result<Vec<Subtask>, String>>()?;
+    Ok((todo, fixture.milestone))
+}
+
+// What DBtodo::seed takes: todos with the name of their milestone, milestones, checklists
+type Rows = (Vec<(Todo, Option<String>)>, Vec<Milestone>, Vec<Checklist>);
+
+// Turn the fixture into rows, naming the entry that is wrong
+fn build(fixture: Fixture, today: NaiveDate) -> Result<Rows, String> {
+    let todos = fixture
+        .todos
+        .into_iter()
+        .enumerate()
+        .map(|(index, fixture)| {
+            let text = fixture.text.clone();
+            todo(fixture, today).map_err(|e| format!("todo {} (\"{}\"): {}", index + 1, text, e))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let milestones = fixture
+        .milestones
+        .into_iter()
+        .map(|milestone| {
+            Ok(Milestone {
+                id: 0,
+                target: due(milestone.target, today)
+                    .map_err(|e| format!("milestone \"{}\": target {}", milestone.name, e))?,
+                name: milestone.name,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let checklists = fixture
+        .checklists
+        .into_iter()
+        .map(|checklist| Checklist {
+            id: 0,
+            name: checklist.name,
+            items: checklist.items,
+        })
+        .collect();
+    Ok((todos, milestones, checklists))
+}
+
+pub fn seed(file_path: &str, wipe: bool, yes: bool) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(file_path)?;
+    let fixture: Fixture = parse_fixture(file_path, &content)
+        .map_err(|e| format!("{} is not a valid fixture: {}", file_path, e))?;
+    let (todos, milestones, checklists) = build(fixture, Local::now().date_naive())?;
+
+    let mut db = DBtodo::new()?;
+    if wipe && !yes && !db.get_todos()?.is_empty() {
+        println!(
+            "‼️ This deletes every todo, milestone and checklist before seeding. Continue? [y/N]"
+        );
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if input.trim().to_lowercase() != "y" {
+            println!("Seeding cancelled");
+            return Ok(());
+        }
+    }
+
+    db.seed(&todos, &milestones, &checklists, wipe)?;
+    println!(
+        "✅ Seeded {} todo(s), {} milestone(s) and {} checklist(s) from {}",
+        todos.len(),
+        milestones.len(),
+        checklists.len(),
+        file_path
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const FIXTURE: &str = r#"
+milestones:
+  - name: Onboarding
+    target: 2025-03-31
+checklists:
+  - name: New laptop
+    items: [Install git, Add SSH key]
+todos:
+  - text: Set up the dev machine
+    topic: setup
+    priority: high
+    context: "@Office"
+    status: ongoing
+    due: 2025-03-14
+    created: 2025-03-01T09:00:00Z
+    milestone: onboarding
+    subtasks:
+      - Install git
+      - text: Add SSH key
+        status: done
+  - text: Read the handbook
+    milestone: Week one
+"#;
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_seed_fixture() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let fixture = parse_fixture("fixtures.yaml", FIXTURE).unwrap();
+        let (todos, milestones, checklists) = build(fixture, today).unwrap();
+        let (todo, milestone) = &todos[0];
+        assert_eq!(todo.priority, "High");
+        assert_eq!(todo.status, "Ongoing");
+        assert_eq!(todo.context.as_deref(), Some("office"));
+        assert_eq!(todo.due, NaiveDate::from_ymd_opt(2025, 3, 14));
+        assert_eq!(todo.subtasks[1].status, "Done");
+        assert_eq!(milestone.as_deref(), Some("onboarding"));
+        assert_eq!(todos[1].0.due, None);
+
+        // Seeding twice with a wipe gives the same ids
+        let mut db = DBtodo::open(Path::new(":memory:")).unwrap();
+        for _ in 0..2 {
+            db.seed(&todos, &milestones, &checklists, true).unwrap();
+        }
+        let ids = db
+            .get_todos()
+            .unwrap()
+            .iter()
+            .map(|todo| todo.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(db.get_checklists().unwrap()[0].items.len(), 2);
+        let milestones = db.get_milestones().unwrap();
+        assert_eq!(milestones.len(), 2);
+        assert_eq!(db.milestone_links().unwrap().len(), 2);
+
+        // Mistakes name the entry
+        let fixture = parse_fixture(
+            "fixtures.json",
+            r#"{"todos": [{"text": "Ship", "status": "Later"}]}"#,
+        )
+        .unwrap();
+        let error = build(fixture, today).unwrap_err();
+        assert!(error.starts_with("todo 1 (\"Ship\"): status \"Later\""));
+        assert!(parse_fixture::<Fixture>("fixtures.yaml", "todos:\n  - txt: Ship\n").is_err());
+    }
+}
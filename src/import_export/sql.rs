@@ -0,0 +1,177 @@
+// SQL DUMP: every VoiDo table as plain SQL, in a stable order so the file diffs well in git.
+// `voido load` replays it, and so does `sqlite3 todos.db < todos.sql`. The Gemini API key in
+// the `model` table is left out, a dump is meant to be shared and committed, and so are the
+// search embeddings, which are rebuilt from the todos.
+use std::{error::Error, fs, io};
+
+use rusqlite::{Connection, types::ValueRef};
+
+use crate::database::DBtodo;
+
+const HEADER: &str = "-- VoiDo database dump";
+const SKIPPED_TABLES: [&str; 2] = ["model", "embeddings"];
+
+fn sql_value(value: ValueRef) -> String {
+    match value {
+        ValueRef::Null => "NULL".to_string(),
+        ValueRef::Integer(number) => number.to_string(),
+        ValueRef::Real(number) => format!("{:?}", number),
+        ValueRef::Text(text) => format!("'{}'", String::from_utf8_lossy(text).replace('\'', "''")),
+        ValueRef::Blob(bytes) => format!(
+            "X'{}'",
+            bytes
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect::<String>()
+        ),
+    }
+}
+
+pub fn dump_sql(connection: &Connection) -> Result<String, Box<dyn Error>> {
+    let version: i64 = connection.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    // No timestamp in the header, dumping unchanged data gives the same file
+    let mut out = format!("{}\nPRAGMA foreign_keys=OFF;\nBEGIN TRANSACTION;\n", HEADER);
+
+    let mut stmt = connection.prepare(
+        "SELECT name, sql FROM sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )?;
+    let tables = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (table, create) in &tables {
+        if SKIPPED_TABLES.contains(&table.as_str()) {
+            continue;
+        }
+        out.push_str(&format!(
+            "\nDROP TABLE IF EXISTS \"{}\";\n{};\n",
+            table, create
+        ));
+
+        let mut rows_stmt =
+            connection.prepare(&format!("SELECT * FROM \"{}\" ORDER BY rowid", table))?;
+        let columns = rows_stmt
+            .column_names()
+            .iter()
+            .map(|column| format!("\"{}\"", column))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let count = rows_stmt.column_count();
+        let mut rows = rows_stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let values = (0..count)
+                .map(|index| row.get_ref(index).map(sql_value))
+                .collect::<Result<Vec<String>, _>>()?
+                .join(", ");
+            out.push_str(&format!(
+                "INSERT INTO \"{}\" ({}) VALUES ({});\n",
+                table, columns, values
+            ));
+        }
+    }
+
+    out.push_str(&format!("\nPRAGMA user_version = {};\nCOMMIT;\n", version));
+    Ok(out)
+}
+
+// Replace the tables in the dump with its contents, all or nothing
+pub fn load_sql(connection: &Connection, sql: &str) -> Result<(), Box<dyn Error>> {
+    if let Err(e) = connection.execute_batch(sql) {
+        // The dump opens its own transaction, don't leave it half applied
+        let _ = connection.execute_batch("ROLLBACK");
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+// PRINT THE DUMP, meant for `voido dump > todos.sql`
+pub fn dump() -> Result<(), Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    print!("{}", dump_sql(&db.connection)?);
+    Ok(())
+}
+
+pub fn load(file_path: &str) -> Result<(), Box<dyn Error>> {
+    let sql = fs::read_to_string(file_path)?;
+    if !sql.starts_with(HEADER) {
+        println!("⚠️ {} was not written by `voido dump`", file_path);
+    }
+
+    println!(
+        "‼️ This will replace all todos, subtasks, history and lists with the dump. Continue? [y/N]"
+    );
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim().to_lowercase() != "y" {
+        println!("Load cancelled");
+        return Ok(());
+    }
+
+    load_sql(&DBtodo::new()?.connection, &sql)?;
+    // Opening the database again brings an older dump up to the current schema
+    DBtodo::schema_changed();
+    let todos = DBtodo::new()?.get_todos()?;
+    println!("✅ Loaded {} ({} todos)", file_path, todos.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dump_and_load() {
+        let source = Connection::open_in_memory().unwrap();
+        source
+            .execute_batch(
+                "CREATE TABLE todos (id INTEGER PRIMARY KEY, text TEXT, notes TEXT, score REAL);
+                 CREATE TABLE model (id INTEGER PRIMARY KEY, apikey TEXT NOT NULL);
+                 INSERT INTO todos VALUES (1, 'Don''t panic', NULL, 1.0);
+                 INSERT INTO todos VALUES (2, 'Two
+lines', '', 2.5);
+                 INSERT INTO model VALUES (1, 'secret');
+                 PRAGMA user_version = 1;",
+            )
+            .unwrap();
+
+        let dump = dump_sql(&source).unwrap();
+        assert!(dump.starts_with(HEADER));
+        assert!(dump.contains("INSERT INTO \"todos\" (\"id\", \"text\", \"notes\", \"score\") VALUES (1, 'Don''t panic', NULL, 1.0);"));
+        assert!(!dump.contains("secret"));
+
+        // Loading replaces what was there
+        let target = Connection::open_in_memory().unwrap();
+        target
+            .execute_batch(
+                "CREATE TABLE todos (id INTEGER PRIMARY KEY, text TEXT);
+                 INSERT INTO todos VALUES (9, 'old');",
+            )
+            .unwrap();
+        load_sql(&target, &dump).unwrap();
+        let texts = target
+            .prepare("SELECT text FROM todos ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get::<_, String>(0))
+            .unwrap()
+            .collect::<Result<Vec<String>, _>>()
+            .unwrap();
+        assert_eq!(texts, vec!["Don't panic", "Two\nlines"]);
+        assert_eq!(dump_sql(&target).unwrap(), dump);
+
+        // A broken dump leaves the database as it was
+        assert!(
+            load_sql(
+                &target,
+                "BEGIN; DELETE FROM todos; INSERT INTO nope VALUES (1); COMMIT;"
+            )
+            .is_err()
+        );
+        let count: i64 = target
+            .query_row("SELECT COUNT(*) FROM todos", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+}
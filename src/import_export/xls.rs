@@ -1,17 +1,114 @@
-use std::{io, path::Path};
+use std::{collections::HashSet, io, path::Path};
 
 use crate::{
     arguments::models::{Subtask, Todo},
     database::DBtodo,
+    dates,
 };
-use calamine::{open_workbook, Data, DataType, Reader, Xlsx};
+use calamine::{Data, DataType, Reader, Xlsx, open_workbook};
+use chrono::{DateTime, Local, Utc};
 use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use xlsxwriter::worksheet::{LXW_DEF_COL_WIDTH, RowColOptions};
 use xlsxwriter::*;
 
-pub fn export_todos_xls() -> Result<(), Box<dyn std::error::Error>> {
-    let db = DBtodo::new().expect("Failed to initialize database");
-    let todos = db.get_todos().expect("Failed to get todos");
+// Hidden columns that make export → edit in Excel → import a lossless round trip. The ID keeps
+// dependencies and milestones pointing at the right todos, the data holds what the visible
+// cells only show in part.
+const ID_HEADER: &str = "VOIDO ID";
+const DATA_HEADER: &str = "VOIDO DATA";
 
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RowData {
+    #[serde(default, with = "dates::optional_timestamp_serde")]
+    created: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pinned: bool,
+    #[serde(default, with = "dates::optional_timestamp_serde")]
+    updated_at: Option<DateTime<Utc>>,
+    // Status, owner, due date and note of every subtask
+    #[serde(default)]
+    subtasks: Vec<Subtask>,
+}
+
+// Subtask cell text, with its owner and due date when set
+fn subtask_cell(subtask: &Subtask) -> String {
+    match subtask.details() {
+        details if details.is_empty() => subtask.text.clone(),
+        details => format!("{} ({})", subtask.text, details),
+    }
+}
+
+fn row_data(todo: &Todo) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&RowData {
+        created: Some(todo.date_added),
+        pinned: todo.pinned,
+        updated_at: todo.updated_at,
+        subtasks: todo.subtasks.clone(),
+    })
+}
+
+pub fn export_todos_xls(todos: &[&Todo], path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let workbook = Workbook::new(path)?;
+    write_sheet(&mut workbook.add_worksheet(None)?, todos)?;
+    workbook.close()?;
+    Ok(())
+}
+
+// One sheet per group, named after it. Excel allows 31 characters and none of []:*?/\ in a
+// sheet name, and no two sheets with the same name.
+pub fn export_sheets_xls(
+    groups: &[(String, Vec<&Todo>)],
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let workbook = Workbook::new(path)?;
+    let mut names: Vec<String> = Vec::new();
+    for (name, todos) in groups {
+        let name = sheet_name(name, &names);
+        write_sheet(&mut workbook.add_worksheet(Some(&name))?, todos)?;
+        names.push(name);
+    }
+    workbook.close()?;
+    Ok(())
+}
+
+fn sheet_name(name: &str, taken: &[String]) -> String {
+    let base = name
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+        .collect::<String>();
+    let base = match base.trim().trim_matches('\'') {
+        "" => "-".to_string(),
+        base => base.to_string(),
+    };
+    let mut number = 1;
+    loop {
+        let suffix = if number == 1 {
+            String::new()
+        } else {
+            format!(" ({})", number)
+        };
+        let candidate = format!(
+            "{}{}",
+            base.chars()
+                .take(31 - suffix.chars().count())
+                .collect::<String>(),
+            suffix
+        );
+        if !taken
+            .iter()
+            .any(|name| name.to_lowercase() == candidate.to_lowercase())
+        {
+            return candidate;
+        }
+        number += 1;
+    }
+}
+
+fn write_sheet(
+    worksheet: &mut Worksheet,
+    todos: &[&Todo],
+) -> Result<(), Box<dyn std::error::Error>> {
     // Determine maximum number of subtasks
     let max_subtasks = todos
         .iter()
@@ -19,10 +116,6 @@ pub fn export_todos_xls() -> Result<(), Box<dyn std::error::Error>> {
         .max()
         .unwrap_or(0);
 
-    // Create workbook
-    let mut workbook = Workbook::new("VoiDo - Todos Export.xlsx")?;
-    let mut worksheet = workbook.add_worksheet(None)?;
-
     // Write headers - using owned Strings
     let mut headers = vec![
         "ID".to_string(),
@@ -35,6 +128,8 @@ pub fn export_todos_xls() -> Result<(), Box<dyn std::error::Error>> {
         "STATUS".to_string(),
         "OWNER".to_string(),
         "NOTES".to_string(),
+        ID_HEADER.to_string(),
+        DATA_HEADER.to_string(),
     ];
 
     // Add generic subtask headers - using owned Strings
@@ -47,6 +142,15 @@ pub fn export_todos_xls() -> Result<(), Box<dyn std::error::Error>> {
         worksheet.write_string(0, col_num as u16, header, None)?;
     }
 
+    // Keep the round-trip columns out of the way
+    worksheet.set_column_opt(
+        10,
+        11,
+        LXW_DEF_COL_WIDTH,
+        None,
+        &RowColOptions::new(true, 0, false),
+    )?;
+
     // Helper functions that return owned Strings
     fn get_value(value: impl AsRef<str>) -> String {
         let s = value.as_ref();
@@ -75,23 +179,122 @@ pub fn export_todos_xls() -> Result<(), Box<dyn std::error::Error>> {
         worksheet.write_string(row, 2, &get_value(&todo.topic), None)?;
         worksheet.write_string(row, 3, &get_value(&todo.text), None)?;
         worksheet.write_string(row, 4, &get_value(&todo.desc), None)?;
-        worksheet.write_string(row, 5, &get_value(&todo.date_added), None)?;
-        worksheet.write_string(row, 6, &get_due_date(&todo.due), None)?;
+        worksheet.write_string(row, 5, &todo.created_display(), None)?;
+        worksheet.write_string(row, 6, &get_due_date(todo.due_display()), None)?;
         worksheet.write_string(row, 7, &get_value(&todo.status), None)?;
         worksheet.write_string(row, 8, &get_value(&todo.owner), None)?;
         worksheet.write_string(row, 9, &get_value(&todo.notes), None)?;
+        worksheet.write_number(row, 10, todo.id as f64, None)?;
+        worksheet.write_string(row, 11, &row_data(todo)?, None)?;
 
-        // Write subtasks
+        // Write subtasks, with their owner and due date when set
         for (col_offset, subtask) in todo.subtasks.iter().enumerate() {
-            worksheet.write_string(row, 10 + col_offset as u16, &get_value(&subtask.text), None)?;
+            worksheet.write_string(row, 12 + col_offset as u16, &subtask_cell(subtask), None)?;
         }
     }
-
-    workbook.close()?;
-    println!("\n🤖 Todos exported to VoiDo - Todos Export.xlsx\n");
     Ok(())
 }
 
+// Helper function to parse cell values
+fn parse_cell(cell: &Data) -> String {
+    match cell {
+        Data::String(s) => s.trim().to_string(),
+        Data::Float(f) => f.to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::DateTime(d) => d.to_string(),
+        _ => String::new(),
+    }
+}
+
+// Read one sheet row, the columns are found by header so sheets from older exports still load.
+// The id is 0 when the row has none, the importer gives it a new one.
+fn row_to_todo(headers: &[String], row: &[Data]) -> Todo {
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let cell = |name: &str| {
+        column(name)
+            .and_then(|index| row.get(index))
+            .map(parse_cell)
+            .unwrap_or_default()
+    };
+
+    let id = cell(ID_HEADER).parse::<f64>().map_or(0, |id| id as usize);
+    let data = serde_json::from_str::<RowData>(&cell(DATA_HEADER)).unwrap_or_default();
+
+    // Store dates as ISO-8601, whatever format the sheet used. An untouched CREATED cell
+    // keeps the exact time from the hidden data.
+    let created = cell("CREATED");
+    let date_added = data
+        .created
+        .filter(|exact| dates::format_timestamp(exact) == created)
+        .or_else(|| dates::parse_timestamp(&created))
+        .unwrap_or_else(Utc::now);
+
+    // The due cell reads like `--due`, so "2025-03-14 09:30" keeps its time of day
+    let (due, due_at) = dates::parse_due_at(&cell("DUE DATE"), Local::now().date_naive())
+        .map_or((None, None), |(due, due_at)| (Some(due), due_at));
+
+    // Subtask cells that were not edited get their status, owner, due date and note back
+    let mut known = data.subtasks;
+    let subtasks = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| header.starts_with("Subtask"))
+        .filter_map(|(index, _)| row.get(index).map(parse_cell))
+        .filter(|text| !text.is_empty())
+        .map(|text| {
+            match known
+                .iter()
+                .position(|subtask| subtask_cell(subtask) == text)
+            {
+                Some(position) => known.remove(position),
+                None => Subtask {
+                    todo_id: id,
+                    subtask_id: 0,
+                    text,
+                    status: "Pending".to_string(), // Default status
+                    due: None,
+                    owner: None,
+                    note: String::new(),
+                },
+            }
+        })
+        .collect();
+
+    Todo {
+        id,
+        priority: cell("PRIORITY"),
+        topic: cell("TOPIC"),
+        text: cell("TODO"),
+        desc: cell("DESCRIPTION"),
+        date_added,
+        status: cell("STATUS"),
+        owner: cell("OWNER"),
+        due,
+        subtasks,
+        notes: cell("NOTES"),
+        pinned: data.pinned,
+        updated_at: data.updated_at,
+        archived: false,
+        waiting_on: None,
+        context: None,
+        external_url: None,
+        due_at,
+    }
+}
+
+// Keep the ids from the sheet, rows without one or with a copied one get the next free id
+fn assign_ids(todos: &mut [Todo]) {
+    let mut next_id = todos.iter().map(|todo| todo.id).max().unwrap_or(0) + 1;
+    let mut used = HashSet::new();
+    for todo in todos.iter_mut() {
+        if todo.id == 0 || !used.insert(todo.id) {
+            todo.id = next_id;
+            used.insert(next_id);
+            next_id += 1;
+        }
+    }
+}
+
 // TODO: Add support for Appending TODOS to the existing ones in the DB
 // IMPORT TODOs
 pub fn import_todos(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
@@ -104,6 +307,20 @@ pub fn import_todos(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
         .worksheet_range_at(0)
         .ok_or("No worksheet found")??;
 
+    let mut rows = range.rows();
+    let headers = rows
+        .next()
+        .ok_or("The worksheet is empty")?
+        .iter()
+        .map(parse_cell)
+        .collect::<Vec<String>>();
+    let mut todos = rows
+        // Skip empty rows
+        .filter(|row| row.iter().any(|cell| !cell.is_empty()))
+        .map(|row| row_to_todo(&headers, row))
+        .collect::<Vec<Todo>>();
+    assign_ids(&mut todos);
+
     // Connect to the database (make mutable)
     let mut db = DBtodo::new()?;
 
@@ -116,6 +333,9 @@ pub fn import_todos(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    // Milestone links live on the todo rows, put them back on the ids that survive
+    let milestone_links = db.milestone_links()?;
+
     // Start transaction for bulk import
     let tx = db.connection.transaction()?;
 
@@ -123,63 +343,166 @@ pub fn import_todos(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
     tx.execute("DELETE FROM subtasks", params![])?;
     tx.execute("DELETE FROM todos", params![])?;
 
-    // Process each row (skip header row)
-    for (row_num, row) in range.rows().skip(1).enumerate() {
-        // Skip empty rows
-        if row.is_empty() {
-            continue;
-        }
-
-        // Helper function to parse cell values
-        fn parse_cell(cell: &Data) -> String {
-            match cell {
-                Data::String(s) => s.trim().to_string(),
-                Data::Float(f) => f.to_string(),
-                Data::Int(i) => i.to_string(),
-                Data::DateTime(d) => d.to_string(),
-                _ => String::new(),
-            }
-        }
-
-        // Parse main todo fields
-        let id = (row_num + 1) as i32; // Generate sequential IDs
-        let priority = parse_cell(&row[1]);
-        let topic = parse_cell(&row[2]);
-        let text = parse_cell(&row[3]);
-        let desc = parse_cell(&row[4]);
-        let date_added = parse_cell(&row[5]);
-        let due = parse_cell(&row[6]);
-        let status = parse_cell(&row[7]);
-        let owner = parse_cell(&row[8]);
-        let notes = parse_cell(&row[9]);
-
-        // Insert todo
+    for todo in &todos {
+        // Insert todo, keeping its id
         tx.execute(
-            "INSERT INTO todos (id, priority, topic, text, desc, date_added, due, status, owner, notes)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO todos (id, priority, topic, text, desc, date_added, due, status, owner, notes, pinned, updated_at, due_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
             params![
-                id, priority, topic, text, desc, date_added, due, status, owner, notes
+                todo.id,
+                todo.priority,
+                todo.topic,
+                todo.text,
+                todo.desc,
+                todo.date_added.to_rfc3339(),
+                todo.due
+                    .map(|due| due.format(dates::ISO_DATE_FORMAT).to_string()),
+                todo.status,
+                todo.owner,
+                todo.notes,
+                todo.pinned,
+                todo.updated_at
+                    .map(|updated_at| updated_at.to_rfc3339())
+                    .unwrap_or_default(),
+                todo.due_at.map(|due_at| due_at.to_rfc3339())
             ],
         )?;
 
-        // Parse and insert subtasks (columns 10+)
-        for (subtask_num, cell) in row.iter().skip(10).enumerate() {
-            let text = parse_cell(cell);
-            if !text.is_empty() {
-                tx.execute(
-                    "INSERT INTO subtasks (todo_id, text, status)
-                     VALUES (?1, ?2, ?3)",
-                    params![id, text, "Pending"], // Default status
-                )?;
-            }
+        for subtask in &todo.subtasks {
+            tx.execute(
+                "INSERT INTO subtasks (todo_id, text, status, due, owner, note)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    todo.id,
+                    subtask.text,
+                    subtask.status,
+                    subtask
+                        .due
+                        .map(|due| due.format(dates::ISO_DATE_FORMAT).to_string()),
+                    subtask.owner,
+                    subtask.note
+                ],
+            )?;
         }
     }
 
+    for (todo_id, milestone_id) in &milestone_links {
+        tx.execute(
+            "UPDATE todos SET milestone_id = ?1 WHERE id = ?2",
+            params![milestone_id, todo_id],
+        )?;
+    }
+
     // Commit the transaction
     tx.commit()?;
 
     println!("\n✅ Todos imported successfully from {}", file_path);
-    println!("   Total todos imported: {}", range.rows().count() - 1); // Subtract header row
+    println!("   Total todos imported: {}", todos.len());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_row_round_trip() {
+        let subtask = |text: &str, status: &str, owner: Option<&str>| Subtask {
+            todo_id: 7,
+            subtask_id: 1,
+            text: text.to_string(),
+            status: status.to_string(),
+            due: None,
+            owner: owner.map(str::to_string),
+            note: "checked twice".to_string(),
+        };
+        let todo = Todo {
+            id: 7,
+            priority: "High".to_string(),
+            desc: "Release notes too".to_string(),
+            date_added: dates::parse_timestamp("2025-03-01T09:30:15+00:00").unwrap(),
+            status: "Ongoing".to_string(),
+            owner: "Ana".to_string(),
+            due: NaiveDate::from_ymd_opt(2025, 3, 14),
+            subtasks: vec![
+                subtask("Tag", "Done", Some("Joe")),
+                subtask("Announce", "Pending", None),
+            ],
+            notes: "- call Joe".to_string(),
+            pinned: true,
+            due_at: dates::parse_due_at("2025-03-14 17:30", NaiveDate::MIN)
+                .and_then(|(_, due_at)| due_at),
+            ..Todo::sample("Ship it")
+        };
+
+        // The row as the export writes it, with the second subtask edited in Excel
+        let headers = [
+            "ID",
+            "PRIORITY",
+            "TOPIC",
+            "TODO",
+            "DESCRIPTION",
+            "CREATED",
+            "DUE DATE",
+            "STATUS",
+            "OWNER",
+            "NOTES",
+            ID_HEADER,
+            DATA_HEADER,
+            "Subtask 1",
+            "Subtask 2",
+        ]
+        .map(str::to_string);
+        let text = |value: &str| Data::String(value.to_string());
+        let row = vec![
+            Data::Float(7.0),
+            text(&todo.priority),
+            text(&todo.topic),
+            text(&todo.text),
+            text(&todo.desc),
+            text(&todo.created_display()),
+            text(&todo.due_display()),
+            text(&todo.status),
+            text(&todo.owner),
+            text(&todo.notes),
+            Data::Float(7.0),
+            text(&row_data(&todo).unwrap()),
+            text(&subtask_cell(&todo.subtasks[0])),
+            text("Announce on the blog"),
+        ];
+
+        let imported = row_to_todo(&headers, &row);
+        assert_eq!(imported.id, 7);
+        assert_eq!(imported.date_added, todo.date_added);
+        assert_eq!(imported.due, todo.due);
+        assert_eq!(imported.due_at, todo.due_at);
+        assert!(imported.due_at.is_some());
+        assert_eq!(imported.notes, "- call Joe");
+        assert!(imported.pinned);
+        assert_eq!(imported.subtasks[0].status, "Done");
+        assert_eq!(imported.subtasks[0].owner.as_deref(), Some("Joe"));
+        assert_eq!(imported.subtasks[0].note, "checked twice");
+        assert_eq!(imported.subtasks[1].text, "Announce on the blog");
+        assert_eq!(imported.subtasks[1].status, "Pending");
+
+        // Sheets from older exports have no id, copied rows repeat one
+        let mut todos = vec![
+            imported.clone(),
+            row_to_todo(&headers[..10], &row[..10]),
+            imported,
+        ];
+        assign_ids(&mut todos);
+        assert_eq!(
+            todos.iter().map(|todo| todo.id).collect::<Vec<usize>>(),
+            vec![7, 8, 9]
+        );
+
+        // Split exports name a sheet after its topic or owner
+        let taken = vec!["Work".to_string()];
+        assert_eq!(sheet_name("Q1/Q2 [draft]", &taken), "Q1_Q2 _draft_");
+        assert_eq!(sheet_name("work", &taken), "work (2)");
+        assert_eq!(sheet_name(&"x".repeat(40), &taken).chars().count(), 31);
+    }
+}
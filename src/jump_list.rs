@@ -0,0 +1,87 @@
+// JUMP LIST: todos opened in the details modal this session, walked with Ctrl-o / Ctrl-i like vim
+const MAX_JUMPS: usize = 100;
+
+#[derive(Debug, Default)]
+pub struct JumpList {
+    ids: Vec<usize>,
+    // Entry we are on, moves when jumping back and forth
+    position: usize,
+}
+
+impl JumpList {
+    // Opening a todo drops the entries after the current one, like a browser history
+    pub fn visit(&mut self, id: usize) {
+        if self.ids.get(self.position) == Some(&id) {
+            return;
+        }
+        self.ids.truncate(self.position + 1);
+        self.ids.push(id);
+        if self.ids.len() > MAX_JUMPS {
+            self.ids.remove(0);
+        }
+        self.position = self.ids.len() - 1;
+    }
+
+    // The todo before the one being viewed. With no todo open, the last one viewed comes back first.
+    pub fn back(&mut self, viewing: Option<usize>) -> Option<usize> {
+        let current = *self.ids.get(self.position)?;
+        if viewing != Some(current) {
+            return Some(current);
+        }
+        self.position = self.position.checked_sub(1)?;
+        Some(self.ids[self.position])
+    }
+
+    pub fn forward(&mut self) -> Option<usize> {
+        if self.position + 1 >= self.ids.len() {
+            return None;
+        }
+        self.position += 1;
+        Some(self.ids[self.position])
+    }
+
+    // Forget a deleted todo
+    pub fn remove(&mut self, id: usize) {
+        let before = self.ids[..self.position.min(self.ids.len())]
+            .iter()
+            .filter(|&&entry| entry == id)
+            .count();
+        self.ids.retain(|&entry| entry != id);
+        self.ids.dedup();
+        self.position = self
+            .position
+            .saturating_sub(before)
+            .min(self.ids.len().saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jump_back_and_forward() {
+        let mut jumps = JumpList::default();
+        assert_eq!(jumps.back(None), None);
+        for id in [1, 2, 3] {
+            jumps.visit(id);
+        }
+        assert_eq!(jumps.back(Some(3)), Some(2));
+        assert_eq!(jumps.back(Some(2)), Some(1));
+        assert_eq!(jumps.back(Some(1)), None);
+        assert_eq!(jumps.forward(), Some(2));
+
+        // Opening something new in the middle drops the forward entries
+        jumps.visit(4);
+        assert_eq!(jumps.forward(), None);
+        assert_eq!(jumps.back(Some(4)), Some(2));
+
+        // With the modal closed, Ctrl-o first reopens the current entry
+        assert_eq!(jumps.back(None), Some(2));
+
+        jumps.remove(2);
+        assert_eq!(jumps.back(Some(4)), Some(1));
+        assert_eq!(jumps.back(Some(1)), None);
+        assert_eq!(jumps.forward(), Some(4));
+    }
+}
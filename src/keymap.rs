@@ -0,0 +1,452 @@
+// KEYBINDINGS: every key the TUI reacts to, grouped by where it works.
+// The help screen is generated from this list, update it together with the key handling in main.rs.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Table,
+    Details,
+    Focus,
+    Triage,
+    Notes,
+    Search,
+    QuickAdd,
+    Input,
+    Copy,
+    Export,
+    Checklists,
+    Links,
+    NoteTasks,
+    Settings,
+    Help,
+}
+
+impl KeyContext {
+    pub const ALL: [KeyContext; 15] = [
+        KeyContext::Table,
+        KeyContext::Details,
+        KeyContext::Focus,
+        KeyContext::Triage,
+        KeyContext::Notes,
+        KeyContext::Search,
+        KeyContext::QuickAdd,
+        KeyContext::Input,
+        KeyContext::Copy,
+        KeyContext::Export,
+        KeyContext::Checklists,
+        KeyContext::Links,
+        KeyContext::NoteTasks,
+        KeyContext::Settings,
+        KeyContext::Help,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            KeyContext::Table => "Todo table",
+            KeyContext::Details => "Details modal",
+            KeyContext::Focus => "Focus mode",
+            KeyContext::Triage => "Inbox triage",
+            KeyContext::Notes => "Editing notes",
+            KeyContext::Search => "Search bar",
+            KeyContext::QuickAdd => "Quick add bar",
+            KeyContext::Input => "Text fields",
+            KeyContext::Copy => "Copy menu",
+            KeyContext::Export => "Export menu",
+            KeyContext::Checklists => "Checklist picker",
+            KeyContext::Links => "Links in the notes",
+            KeyContext::NoteTasks => "Checkboxes in the notes",
+            KeyContext::Settings => "Settings",
+            KeyContext::Help => "This help",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct KeyBinding {
+    pub context: KeyContext,
+    pub keys: &'static str,
+    pub action: &'static str,
+}
+
+const fn bind(context: KeyContext, keys: &'static str, action: &'static str) -> KeyBinding {
+    KeyBinding {
+        context,
+        keys,
+        action,
+    }
+}
+
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    // Table
+    bind(KeyContext::Table, "↑/↓, j/k", "Move through the todos"),
+    bind(
+        KeyContext::Table,
+        "Enter, l",
+        "Open the details of the selected todo",
+    ),
+    bind(KeyContext::Table, "i", "Search the todos"),
+    bind(
+        KeyContext::Table,
+        "Ctrl-o / Ctrl-i, Tab",
+        "Jump back / forward through the todos opened this session",
+    ),
+    bind(
+        KeyContext::Table,
+        "a",
+        "Quick add: text #topic !priority @owner due:date ctx:place",
+    ),
+    bind(KeyContext::Table, "d", "Mark the selected todo as Done"),
+    bind(KeyContext::Table, "o", "Mark the selected todo as Ongoing"),
+    bind(KeyContext::Table, "p", "Mark the selected todo as Pending"),
+    bind(
+        KeyContext::Table,
+        "r",
+        "Send the selected todo to Review, asks who it waits on (Tab completes)",
+    ),
+    bind(KeyContext::Table, "P", "Open the priority menu"),
+    bind(
+        KeyContext::Table,
+        "L / M / H",
+        "Set the priority to Low / Medium / High",
+    ),
+    bind(KeyContext::Table, "f", "Pin or unpin the selected todo"),
+    bind(
+        KeyContext::Table,
+        "b",
+        "Open the link of the selected todo (🔗) in the browser",
+    ),
+    bind(
+        KeyContext::Table,
+        "G",
+        "Show what the selected todo is blocked by, as a tree",
+    ),
+    bind(
+        KeyContext::Table,
+        "O",
+        "Sort by days left until due, or by ID again",
+    ),
+    bind(
+        KeyContext::Table,
+        "T",
+        "Today view: due today or overdue, and Ongoing",
+    ),
+    bind(
+        KeyContext::Table,
+        "@",
+        "Show one owner's todos, the next owner on each press, then everyone",
+    ),
+    bind(
+        KeyContext::Table,
+        "u",
+        "Show only my todos (the default owner), or everyone again",
+    ),
+    bind(
+        KeyContext::Table,
+        "g",
+        "Context switcher: only what can be done in the next context, then anywhere again",
+    ),
+    bind(
+        KeyContext::Table,
+        "F",
+        "Focus mode: the selected todo alone, full-screen",
+    ),
+    bind(
+        KeyContext::Table,
+        "I",
+        "Inbox triage: the todos captured with `voido q`, one at a time",
+    ),
+    bind(
+        KeyContext::Table,
+        "x, Delete",
+        "Delete the selected todo (y/n to confirm)",
+    ),
+    bind(
+        KeyContext::Table,
+        "m",
+        "Merge a duplicate into another todo",
+    ),
+    bind(
+        KeyContext::Table,
+        "v",
+        "Move the picked subtask to the selected todo",
+    ),
+    bind(KeyContext::Table, "c", "Copy the selected todo or the list"),
+    bind(KeyContext::Table, "E", "Export the visible list to a file"),
+    bind(
+        KeyContext::Table,
+        "s",
+        "Stats: topic and milestone progress, owner workload",
+    ),
+    bind(
+        KeyContext::Table,
+        "w",
+        "Show the full text of the selected todo",
+    ),
+    bind(
+        KeyContext::Table,
+        "D",
+        "Switch between compact and comfortable rows",
+    ),
+    bind(
+        KeyContext::Table,
+        "S",
+        "Settings: theme, columns and date format",
+    ),
+    bind(
+        KeyContext::Table,
+        "0-9, other free keys",
+        "Run the macro bound in [MACROS] of config.toml",
+    ),
+    bind(
+        KeyContext::Table,
+        "X",
+        "Add the demo todos (only while the list is empty)",
+    ),
+    bind(KeyContext::Table, "\\", "Open this help"),
+    bind(
+        KeyContext::Table,
+        "Esc, h",
+        "Cancel a merge or move, or close a modal",
+    ),
+    bind(KeyContext::Table, "q", "Quit"),
+    // Details modal
+    bind(KeyContext::Details, "↑/↓, j/k", "Select a subtask"),
+    bind(
+        KeyContext::Details,
+        "d",
+        "Mark the selected subtask Done or Pending",
+    ),
+    bind(
+        KeyContext::Details,
+        "x, Delete",
+        "Delete the selected subtask",
+    ),
+    bind(
+        KeyContext::Details,
+        "v",
+        "Move the selected subtask: pick a todo, then press v",
+    ),
+    bind(
+        KeyContext::Details,
+        "n",
+        "Add or edit a note on the selected subtask",
+    ),
+    bind(
+        KeyContext::Details,
+        "e",
+        "Turn list lines in the description and notes into subtasks",
+    ),
+    bind(KeyContext::Details, "N", "Edit the notes (Markdown)"),
+    bind(
+        KeyContext::Details,
+        "[ / ]",
+        "Scroll the comments, added with `voido comment`",
+    ),
+    bind(
+        KeyContext::Details,
+        "C",
+        "Add a saved checklist as subtasks",
+    ),
+    bind(
+        KeyContext::Details,
+        "/",
+        "Find text in the description, notes and subtasks",
+    ),
+    bind(
+        KeyContext::Details,
+        "n / N",
+        "Next / previous match while searching, Esc ends the search",
+    ),
+    bind(
+        KeyContext::Details,
+        "U",
+        "List the links in the notes and open one in the browser",
+    ),
+    bind(
+        KeyContext::Details,
+        "X",
+        "List the checkboxes in the notes and tick them",
+    ),
+    bind(
+        KeyContext::Details,
+        "b",
+        "Open the link set with --url in the browser",
+    ),
+    bind(
+        KeyContext::Details,
+        "A",
+        "Summarize the notes with Gemini, on top of them",
+    ),
+    bind(
+        KeyContext::Details,
+        "R, Enter",
+        "Pick one of the related todos, then open it",
+    ),
+    bind(KeyContext::Details, "J / K", "Scroll the description"),
+    bind(KeyContext::Details, "PgUp / PgDn", "Scroll the notes"),
+    bind(KeyContext::Details, "Esc, Enter", "Close the details"),
+    // Focus mode
+    bind(KeyContext::Focus, "↑/↓, j/k", "Select a subtask"),
+    bind(
+        KeyContext::Focus,
+        "d, Space",
+        "Mark the selected subtask Done or Pending",
+    ),
+    bind(KeyContext::Focus, "J / K, PgUp / PgDn", "Scroll"),
+    bind(KeyContext::Focus, "Esc, q, F", "Back to the table"),
+    // Inbox triage
+    bind(
+        KeyContext::Triage,
+        "1-9",
+        "Move the todo to that topic, on to the next one",
+    ),
+    bind(KeyContext::Triage, "t", "Type a topic for the todo"),
+    bind(
+        KeyContext::Triage,
+        "h / m / l / n",
+        "Set the priority to High / Medium / Low / Normal",
+    ),
+    bind(
+        KeyContext::Triage,
+        "d",
+        "Type a due date (today, fri 09:30, 2025-03-14...)",
+    ),
+    bind(KeyContext::Triage, "x, Delete", "Delete the todo, no undo"),
+    bind(
+        KeyContext::Triage,
+        "s, Space",
+        "Leave it in the inbox for now",
+    ),
+    bind(KeyContext::Triage, "Esc, q, I", "Back to the table"),
+    // Notes editor
+    bind(
+        KeyContext::Notes,
+        "Tab",
+        "Switch between raw Markdown and preview",
+    ),
+    bind(KeyContext::Notes, "PgUp / PgDn", "Scroll the notes"),
+    bind(KeyContext::Notes, "Esc", "Save the notes and stop editing"),
+    // Search bar
+    bind(KeyContext::Search, "type", "Filter the todos as you type"),
+    bind(KeyContext::Search, "Enter", "Open the first match"),
+    bind(KeyContext::Search, "Esc", "Clear the search"),
+    // Quick add bar
+    bind(KeyContext::QuickAdd, "Enter", "Add the todo"),
+    bind(
+        KeyContext::QuickAdd,
+        "Tab",
+        "Complete the @owner being typed from the known owners",
+    ),
+    bind(KeyContext::QuickAdd, "Esc", "Cancel"),
+    // Every text field: search, quick add, notes, subtask notes
+    bind(
+        KeyContext::Input,
+        "Ctrl-←/→",
+        "Jump to the previous / next word",
+    ),
+    bind(
+        KeyContext::Input,
+        "Shift-←/→, Shift-Home/End",
+        "Select text (add Ctrl to select by word)",
+    ),
+    bind(KeyContext::Input, "Ctrl-A", "Select everything"),
+    bind(
+        KeyContext::Input,
+        "Ctrl-W",
+        "Delete the word before the cursor",
+    ),
+    bind(
+        KeyContext::Input,
+        "Ctrl-U",
+        "Delete to the start of the line",
+    ),
+    bind(
+        KeyContext::Input,
+        "Ctrl-C / Ctrl-X / Ctrl-V",
+        "Copy / cut the selection, paste",
+    ),
+    // Copy menu
+    bind(
+        KeyContext::Copy,
+        "m / t",
+        "Copy the selected todo as Markdown / text",
+    ),
+    bind(
+        KeyContext::Copy,
+        "M / T",
+        "Copy the visible list as Markdown / text",
+    ),
+    // Export menu
+    bind(
+        KeyContext::Export,
+        "j / e / c / m",
+        "Export the visible list as JSON / Excel / CSV / Markdown",
+    ),
+    // Checklist picker
+    bind(KeyContext::Checklists, "↑/↓, j/k", "Select a checklist"),
+    bind(
+        KeyContext::Checklists,
+        "Enter, l",
+        "Add its items to the todo as Pending subtasks",
+    ),
+    bind(KeyContext::Checklists, "Esc, q", "Close the picker"),
+    // Links in the notes
+    bind(KeyContext::Links, "↑/↓, j/k", "Select a link"),
+    bind(KeyContext::Links, "Enter, l", "Open it in the browser"),
+    bind(KeyContext::Links, "Esc, q", "Close the list"),
+    // Checkboxes in the notes
+    bind(KeyContext::NoteTasks, "↑/↓, j/k", "Select a checkbox"),
+    bind(
+        KeyContext::NoteTasks,
+        "Space, Enter, x",
+        "Tick or clear it in the notes",
+    ),
+    bind(KeyContext::NoteTasks, "Esc, q", "Close the list"),
+    // Settings
+    bind(KeyContext::Settings, "↑/↓, j/k", "Select a setting"),
+    bind(
+        KeyContext::Settings,
+        "Space, Enter, l",
+        "Change the selected setting",
+    ),
+    bind(KeyContext::Settings, "Esc, q, S", "Close the settings"),
+    // Help
+    bind(KeyContext::Help, "type", "Search the keybindings"),
+    bind(KeyContext::Help, "↑/↓, PgUp/PgDn", "Scroll"),
+    bind(
+        KeyContext::Help,
+        "Esc",
+        "Clear the search, or close the help",
+    ),
+];
+
+// Bindings whose keys, action or context contain the query (case-insensitive)
+pub fn search_bindings(query: &str) -> Vec<&'static KeyBinding> {
+    let query = query.trim().to_lowercase();
+    KEYBINDINGS
+        .iter()
+        .filter(|binding| {
+            query.is_empty()
+                || binding.keys.to_lowercase().contains(&query)
+                || binding.action.to_lowercase().contains(&query)
+                || binding.context.title().to_lowercase().contains(&query)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_bindings() {
+        assert_eq!(search_bindings("").len(), KEYBINDINGS.len());
+        let pin = search_bindings("PIN");
+        assert_eq!(pin.len(), 1);
+        assert_eq!(pin[0].keys, "f");
+        assert!(
+            search_bindings("settings")
+                .iter()
+                .any(|binding| binding.context == KeyContext::Settings)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+// KEY MACROS, from the [MACROS] config section. A single key runs several actions
+// on the selected todo in one go:
+//
+//   [MACROS]
+//   1 = ["priority High", "status Ongoing", "pin"]
+//   2 = ["status Done", "unpin"]
+//
+// Keys the TUI already uses keep their built-in meaning, the digits are free.
+
+const STATUSES: [&str; 5] = ["Pending", "Ongoing", "Planned", "Review", "Done"];
+const PRIORITIES: [&str; 3] = ["Low", "Medium", "High"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroAction {
+    Status(String),
+    Priority(String),
+    Owner(String),
+    Topic(String),
+    Pin(bool),
+}
+
+impl MacroAction {
+    pub fn parse(action: &str) -> Result<Self, String> {
+        let action = action.trim();
+        let (name, value) = action.split_once(' ').unwrap_or((action, ""));
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+
+        let required = |kind: &str| {
+            if value.is_empty() {
+                Err(format!("`{}` needs a value", kind))
+            } else {
+                Ok(value.to_string())
+            }
+        };
+        // Match the spelling the rest of the app uses, whatever case the config has
+        let one_of = |kind: &str, options: &[&str]| {
+            options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(value))
+                .map(|option| option.to_string())
+                .ok_or(format!(
+                    "{} \"{}\" should be one of {}",
+                    kind,
+                    value,
+                    options.join(", ")
+                ))
+        };
+
+        match name.to_lowercase().as_str() {
+            "status" => Ok(MacroAction::Status(one_of("status", &STATUSES)?)),
+            "priority" => Ok(MacroAction::Priority(one_of("priority", &PRIORITIES)?)),
+            "owner" => Ok(MacroAction::Owner(required("owner")?)),
+            "topic" => Ok(MacroAction::Topic(required("topic")?)),
+            "pin" if value.is_empty() => Ok(MacroAction::Pin(true)),
+            "unpin" if value.is_empty() => Ok(MacroAction::Pin(false)),
+            "pin" | "unpin" => Err(format!("`{}` takes no value", name)),
+            "" => Err("empty action".to_string()),
+            other => Err(format!(
+                "unknown action `{}` (expected status, priority, owner, topic, pin or unpin)",
+                other
+            )),
+        }
+    }
+
+    // Short description for the status bar
+    pub fn label(&self) -> String {
+        match self {
+            MacroAction::Status(status) => format!("status {}", status),
+            MacroAction::Priority(priority) => format!("priority {}", priority),
+            MacroAction::Owner(owner) => format!("owner {}", owner),
+            MacroAction::Topic(topic) => format!("topic {}", topic),
+            MacroAction::Pin(true) => "pin".to_string(),
+            MacroAction::Pin(false) => "unpin".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyMacro {
+    pub key: char,
+    pub actions: Vec<MacroAction>,
+}
+
+impl KeyMacro {
+    // A macro with a bad key or action is rejected as a whole, half a macro would surprise
+    pub fn parse(key: &str, actions: &[&str]) -> Result<Self, String> {
+        let mut chars = key.chars();
+        let (Some(key), None) = (chars.next(), chars.next()) else {
+            return Err("the key must be a single character".to_string());
+        };
+        if actions.is_empty() {
+            return Err("no actions".to_string());
+        }
+        let actions = actions
+            .iter()
+            .map(|action| MacroAction::parse(action))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { key, actions })
+    }
+
+    pub fn label(&self) -> String {
+        self.actions
+            .iter()
+            .map(MacroAction::label)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_macro_actions() {
+        let triage = KeyMacro::parse("1", &["priority high", "status ongoing", "pin"]).unwrap();
+        assert_eq!(triage.key, '1');
+        assert_eq!(
+            triage.actions,
+            vec![
+                MacroAction::Priority("High".to_string()),
+                MacroAction::Status("Ongoing".to_string()),
+                MacroAction::Pin(true),
+            ]
+        );
+        assert_eq!(triage.label(), "priority High, status Ongoing, pin");
+        assert_eq!(
+            MacroAction::parse("owner \"Ana Lopes\""),
+            Ok(MacroAction::Owner("Ana Lopes".to_string()))
+        );
+
+        assert!(KeyMacro::parse("12", &["pin"]).is_err());
+        assert!(KeyMacro::parse("1", &[]).is_err());
+        assert!(KeyMacro::parse("1", &["pin", "status Someday"]).is_err());
+        assert!(MacroAction::parse("archive").is_err());
+        assert!(MacroAction::parse("topic").is_err());
+    }
+}
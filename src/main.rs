@@ -1,14 +1,25 @@
+#[cfg(feature = "sync")]
+use arguments::models::SyncTarget;
 use arguments::{
     delete_todo,
-    models::{self, Cli, Todo},
+    models::{
+        self, AiCommand, ChecklistCommand, Cli, Commands, ConfigCommand, DbCommand, DependCommand,
+        ExportFormat, MilestoneCommand, OwnerCommand, ReportCommand, SubtaskCommand, Todo,
+        TopicCommand,
+    },
 };
 use clap::Parser;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        Event, KeyCode, KeyModifiers,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use data::sample_todos;
+use detail_search::DetailSearch;
+use markdown::{MarkdownRenderer, NoteLink, NoteTask};
 use ratatui::widgets::{ListState, TableState};
 use ratatui::{
     Frame, Terminal,
@@ -20,19 +31,39 @@ use ratatui::{
 };
 use search::{FuzzySearch, InputField};
 use std::io;
+use std::time::Duration;
 use ui::{calculate_stats, draw_ui};
 
 mod ai; // LLMS stuff
 mod args; // Print all the args available in the App so it does not clutter the main.rs
 mod arguments;
+mod browser;
+mod clipboard;
 mod colors;
 mod configs;
 mod data; // DATABASE STUFF;
 mod database;
+mod dates;
+mod detail_search;
+mod embeddings;
+mod features;
+mod icons;
+mod jump_list;
+mod keymap;
+mod macros;
 mod markdown;
 mod modals; // All the modals logic
+mod picker;
 mod search;
+mod selection;
+#[cfg(feature = "serve")]
+mod serve; // HTTP endpoints for dashboards
+mod settings;
+mod styling;
+#[cfg(feature = "sync")]
 mod sync;
+mod text_width;
+mod triage;
 mod ui; // ALL THE UI STUFF
 
 // Import Export TODOS
@@ -53,6 +84,12 @@ pub struct App {
     pub show_delete_confirmation: bool,
     pub show_priority_modal: bool,
     pub show_main_menu_modal: bool,
+    pub help_query: String,
+    pub help_scroll: u16,
+    pub show_copy_modal: bool,
+    pub show_export_modal: bool,
+    pub show_stats_modal: bool,
+    pub milestones: Vec<arguments::report::MilestoneProgress>,
     pub subtask_state: ListState,
     pub selected_subtask: Option<String>,
     pub show_search_input: bool,
@@ -63,7 +100,75 @@ pub struct App {
     pub notes_input: InputField,
     pub editing_notes: bool,
     pub notes_scroll_offset: u16,
+    pub details_scroll_offset: u16,
     pub notes_preview_mode: bool,
+    pub status_message: Option<String>,
+    pub quick_add_input: InputField,
+    pub merge_source: Option<usize>,
+    // Note of the selected subtask being edited in the details modal, with the subtask ID
+    pub subtask_note_input: InputField,
+    // `/` in the details: the prompt, then the search it started
+    pub detail_search_input: InputField,
+    pub detail_search: DetailSearch,
+    pub editing_subtask_note: Option<usize>,
+    // Subtask picked in the details modal to move to another todo, with its text
+    pub moving_subtask: Option<(usize, String)>,
+    // Show the whole text of the selected todo in the table
+    pub expand_selected: bool,
+    pub show_settings_modal: bool,
+    pub settings_state: ListState,
+    // Checklist picker opened from the details modal
+    pub show_checklist_modal: bool,
+    pub checklists: Vec<models::Checklist>,
+    pub checklist_state: ListState,
+    // Links in the notes of the todo in the details modal, to open one
+    pub show_links_modal: bool,
+    pub note_links: Vec<NoteLink>,
+    pub links_state: ListState,
+    // Checkboxes in the notes of the todo in the details modal, to tick them
+    pub show_note_tasks_modal: bool,
+    pub note_tasks: Vec<NoteTask>,
+    pub note_tasks_state: ListState,
+    pub config_watcher: configs::ConfigWatcher,
+    // Todos opened in the details modal, for Ctrl-o / Ctrl-i
+    pub jump_list: jump_list::JumpList,
+    // Only the todos due today or overdue and the Ongoing ones, from `voido today` or `T`
+    pub today_view: bool,
+    // Order the table by days left instead of ID
+    pub sort_by_countdown: bool,
+    // Blocked-by tree of the selected todo
+    pub show_graph_modal: bool,
+    pub graph_lines: Vec<arguments::dependencies::GraphLine>,
+    // Full-screen view of the selected todo
+    pub focus_mode: bool,
+    pub focus_scroll: u16,
+    // Known owners for the table and quick-add completion, and the one the table is narrowed to
+    pub owners: Vec<models::Owner>,
+    pub owner_filter: Option<String>,
+    // Where you are: only the todos doable there and the ones without a context
+    pub context_filter: Option<String>,
+    // Comment thread of the todo open in the details modal
+    pub comments: Vec<models::Comment>,
+    pub comments_scroll: u16,
+    // Open todos most like the one in the details modal, and the one picked with R
+    pub related: Vec<usize>,
+    pub related_selected: Option<usize>,
+    // Who the selected todo waits on once it goes to Review
+    pub review_input: InputField,
+    // Streaks of the recurring todos, for the table and the stats panel
+    pub habits: Vec<arguments::habits::Habit>,
+    // Going through the inbox one todo at a time, with the prompt for a new topic or a due date
+    pub triage: Option<triage::Triage>,
+    pub triage_input: InputField,
+    // Sync state for the stats bar, flushing the queued changes once a backend is back
+    #[cfg(feature = "sync")]
+    pub sync_watcher: sync::queue::SyncWatcher,
+    // Notes being summarized in the background, for the todo with this id
+    pub summarizing: Option<(usize, ai::Worker)>,
+    // Gemini picking a due date for the quick-add text, and the last text it was asked about:
+    // Enter adds that one as it is
+    pub due_suggestion: Option<(String, ai::Worker)>,
+    pub due_asked_for: Option<String>,
 }
 
 impl App {
@@ -79,6 +184,12 @@ impl App {
             show_delete_confirmation: false,
             show_priority_modal: false,
             show_main_menu_modal: false,
+            help_query: String::new(),
+            help_scroll: 0,
+            show_copy_modal: false,
+            show_export_modal: false,
+            show_stats_modal: false,
+            milestones: Vec::new(),
             subtask_state: ListState::default(),
             selected_subtask: None,
             show_search_input: true,
@@ -89,20 +200,1070 @@ impl App {
             notes_input: InputField::new_multiline("Notes"),
             editing_notes: false,
             notes_scroll_offset: 0,
+            details_scroll_offset: 0,
             notes_preview_mode: false,
+            status_message: None,
+            quick_add_input: InputField::new(
+                "Quick add: text #topic !priority @owner due:date ctx:place",
+            ),
+            merge_source: None,
+            moving_subtask: None,
+            subtask_note_input: InputField::new("Subtask note (Enter: save, Esc: cancel)"),
+            detail_search_input: InputField::new(
+                "Find in the description, notes and subtasks (Enter: search, Esc: cancel)",
+            ),
+            detail_search: DetailSearch::default(),
+            review_input: InputField::new(
+                "Review waiting on (Tab: complete, Enter: send to review, Esc: cancel)",
+            ),
+            editing_subtask_note: None,
+            expand_selected: false,
+            show_settings_modal: false,
+            settings_state: ListState::default(),
+            show_checklist_modal: false,
+            checklists: Vec::new(),
+            checklist_state: ListState::default(),
+            show_links_modal: false,
+            note_links: Vec::new(),
+            links_state: ListState::default(),
+            show_note_tasks_modal: false,
+            note_tasks: Vec::new(),
+            note_tasks_state: ListState::default(),
+            config_watcher: configs::ConfigWatcher::new(),
+            jump_list: jump_list::JumpList::default(),
+            today_view: false,
+            sort_by_countdown: false,
+            show_graph_modal: false,
+            graph_lines: Vec::new(),
+            focus_mode: false,
+            focus_scroll: 0,
+            owners: database::DBtodo::new()
+                .and_then(|db| db.get_owners())
+                .unwrap_or_default(),
+            owner_filter: None,
+            context_filter: None,
+            comments: Vec::new(),
+            comments_scroll: 0,
+            related: Vec::new(),
+            related_selected: None,
+            habits: database::DBtodo::new()
+                .and_then(|db| arguments::habits::load_habits(&db))
+                .unwrap_or_default(),
+            triage: None,
+            triage_input: InputField::new("Triage (Enter: save, Esc: cancel)"),
+            #[cfg(feature = "sync")]
+            sync_watcher: sync::queue::SyncWatcher::new(),
+            summarizing: None,
+            due_suggestion: None,
+            due_asked_for: None,
+        }
+    }
+
+    // Streaks change with every status change of a habit
+    fn load_habits(&mut self) {
+        if let Ok(habits) =
+            database::DBtodo::new().and_then(|db| arguments::habits::load_habits(&db))
+        {
+            self.habits = habits;
+        }
+    }
+
+    // Is the table showing search results, the today view, one owner or one context instead of
+    // every todo
+    fn is_filtered(&self) -> bool {
+        self.today_view
+            || self.owner_filter.is_some()
+            || self.context_filter.is_some()
+            || self.fuzzy_search.input.active
+            || !self.fuzzy_search.input.value.is_empty()
+    }
+
+    // TODAY VIEW: switch between today's todos and the full list
+    fn toggle_today_view(&mut self) {
+        self.today_view = !self.today_view;
+        self.fuzzy_search.update_matches(&self.todos);
+        self.update_filtered_todos();
+        if !self.today_view && self.state.selected().is_none() && !self.todos.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    // OWNER FILTER: `@` steps through the owners with todos, then back to everyone
+    fn cycle_owner_filter(&mut self) {
+        let mut owners: Vec<&str> = Vec::new();
+        for todo in &self.todos {
+            if !owners
+                .iter()
+                .any(|owner| owner.eq_ignore_ascii_case(&todo.owner))
+            {
+                owners.push(&todo.owner);
+            }
+        }
+        owners.sort_by_key(|owner| owner.to_lowercase());
+        let next = match &self.owner_filter {
+            None => owners.first(),
+            Some(current) => owners
+                .iter()
+                .skip_while(|owner| !owner.eq_ignore_ascii_case(current))
+                .nth(1),
+        }
+        .map(|owner| owner.to_string());
+        self.set_owner_filter(next);
+    }
+
+    // `u`: only the todos of the default owner, or everyone again
+    fn toggle_my_todos(&mut self) {
+        let me = configs::defaults_config().owner.clone();
+        let mine = self
+            .owner_filter
+            .as_ref()
+            .is_some_and(|owner| owner.eq_ignore_ascii_case(&me));
+        self.set_owner_filter((!mine).then_some(me));
+    }
+
+    fn set_owner_filter(&mut self, owner: Option<String>) {
+        self.owner_filter = owner;
+        self.fuzzy_search.update_matches(&self.todos);
+        self.update_filtered_todos();
+        if !self.is_filtered() && self.state.selected().is_none() && !self.todos.is_empty() {
+            self.state.select(Some(0));
+        }
+        self.status_message = Some(match &self.owner_filter {
+            Some(owner) => format!("👤 Todos of {}", self.owner_label(owner)),
+            None => "👥 Todos of everyone".to_string(),
+        });
+    }
+
+    // CONTEXT SWITCHER: `g` steps through the contexts of the open todos, then back to anywhere
+    fn cycle_context_filter(&mut self) {
+        let mut contexts = self
+            .todos
+            .iter()
+            .filter(|todo| !todo.is_done())
+            .filter_map(|todo| todo.context.as_deref())
+            .collect::<Vec<&str>>();
+        contexts.sort();
+        contexts.dedup();
+        let next = match &self.context_filter {
+            None => contexts.first(),
+            Some(current) => contexts
+                .iter()
+                .skip_while(|context| **context != current.as_str())
+                .nth(1),
+        }
+        .map(|context| context.to_string());
+        if next.is_none() && self.context_filter.is_none() {
+            self.status_message =
+                Some("📍 No contexts yet, add one with ctx:home or `voido context`".to_string());
+            return;
+        }
+        self.set_context_filter(next);
+    }
+
+    fn set_context_filter(&mut self, context: Option<String>) {
+        self.context_filter = context;
+        self.fuzzy_search.update_matches(&self.todos);
+        self.update_filtered_todos();
+        if !self.is_filtered() && self.state.selected().is_none() && !self.todos.is_empty() {
+            self.state.select(Some(0));
+        }
+        self.status_message = Some(match &self.context_filter {
+            Some(context) => format!("📍 What can be done @{}", context),
+            None => "📍 Every context".to_string(),
+        });
+    }
+
+    // The display name of an owner when one is set
+    pub fn owner_label<'a>(&'a self, name: &'a str) -> &'a str {
+        self.owners
+            .iter()
+            .find(|owner| owner.name.eq_ignore_ascii_case(name))
+            .map_or(name, |owner| owner.label())
+    }
+
+    // Tab in the quick-add bar completes the `@owner` being typed
+    fn complete_quick_add_owner(&mut self) {
+        let input = &mut self.quick_add_input;
+        if input.cursor_position != input.value.len() {
+            return;
+        }
+        let names = self
+            .owners
+            .iter()
+            .map(|owner| owner.name.clone())
+            .collect::<Vec<String>>();
+        if let Some(value) = arguments::quick_add::complete_owner(&input.value, &names) {
+            input.value = value;
+            input.focus();
+        }
+    }
+
+    // Todos currently shown in the table, in display order
+    pub fn visible_todos(&self) -> Vec<&Todo> {
+        if self.is_filtered() {
+            self.filtered_indices
+                .iter()
+                .filter_map(|&i| self.todos.get(i))
+                .collect()
+        } else {
+            self.todos.iter().collect()
+        }
+    }
+
+    // Map the table selection to an index into `todos`
+    fn selected_index(&self) -> Option<usize> {
+        let shown = self
+            .is_filtered()
+            .then_some(self.filtered_indices.as_slice());
+        selection::todo_index(self.state.selected(), shown, self.todos.len())
+    }
+
+    // Re-run the search after the todo list changed so indices stay valid
+    fn refresh_filter(&mut self) {
+        if self.is_filtered() {
+            self.fuzzy_search.update_matches(&self.todos);
+            self.update_filtered_todos();
+        }
+    }
+
+    // Reload every todo from the DB, keeping the cursor on the given todo
+    fn reload_todos(&mut self, select_id: Option<usize>) {
+        self.todos = sample_todos();
+        // A new todo may have brought a new owner
+        if let Ok(owners) = database::DBtodo::new().and_then(|db| db.get_owners()) {
+            self.owners = owners;
+        }
+        self.load_habits();
+        self.sort_todos();
+
+        let row = select_id.and_then(|id| self.visible_todos().iter().position(|t| t.id == id));
+        if let Some(row) = row {
+            self.state.select(Some(row));
+        } else if self.state.selected().is_none() && !self.todos.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    // Back in the quick-add bar once Gemini answered, with the suggested `due:` added
+    fn check_due_suggestion(&mut self) {
+        let Some((input, worker)) = self
+            .due_suggestion
+            .take_if(|(_, worker)| worker.is_finished())
+        else {
+            return;
+        };
+        let output = worker.join().ok().and_then(Result::ok);
+        let suggestion = output.as_ref().and_then(|output| {
+            let line = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .next()?
+                .to_string();
+            let (due, reason) = line.split_once(char::is_whitespace)?;
+            Some((due.to_string(), reason.trim().to_string()))
+        });
+
+        self.quick_add_input.value = match &suggestion {
+            Some((due, _)) => format!("{} due:{}", input, due),
+            None => input,
+        };
+        self.quick_add_input.focus();
+        self.status_message = Some(match suggestion {
+            Some((due, reason)) => format!(
+                "🤖 Due {} ({}): Enter adds the todo, or change the due: first",
+                due, reason
+            ),
+            None => {
+                let error = output
+                    .and_then(|output| {
+                        String::from_utf8_lossy(&output.stderr)
+                            .lines()
+                            .last()
+                            .map(|line| line.trim_start_matches("❌ ").to_string())
+                    })
+                    .unwrap_or_else(|| "Gemini did not answer".to_string());
+                format!("❌ {}, Enter adds the todo without a due date", error)
+            }
+        });
+    }
+
+    // QUICK ADD A TODO FROM THE INLINE BAR
+    fn submit_quick_add(&mut self) {
+        let input = self.quick_add_input.value.trim().to_string();
+        self.quick_add_input.clear();
+        self.quick_add_input.unfocus();
+
+        if input.is_empty() {
+            return;
+        }
+
+        // Without a due date Gemini may suggest one first, it comes back in the bar to confirm
+        let parsed = arguments::quick_add::parse_quick_add(&input);
+        if parsed.due.is_none()
+            && !parsed.text.is_empty()
+            && self.due_suggestion.is_none()
+            && self.due_asked_for.as_ref() != Some(&input)
+            && configs::defaults_config().due_offset.is_none()
+            && configs::GeminiConfig::load().suggest_due
+        {
+            self.due_asked_for = Some(input.clone());
+            self.due_suggestion = Some((
+                input,
+                ai::in_background(vec!["ai".to_string(), "due".to_string(), parsed.text]),
+            ));
+            self.status_message = Some("🤖 Asking Gemini for a due date...".to_string());
+            return;
+        }
+        self.due_asked_for = None;
+
+        match arguments::quick_add::quick_add(&input) {
+            Ok(id) => {
+                self.reload_todos(Some(id as usize));
+                self.status_message = Some(format!("✅ Todo {} added", id));
+            }
+            Err(e) => self.status_message = Some(format!("❌ Could not add todo: {}", e)),
+        }
+    }
+
+    // PASTE into whichever text field is open, the whole text arrives as one event
+    fn paste(&mut self, text: &str) {
+        let event = Event::Paste(text.to_string());
+        if self.editing_notes {
+            self.notes_input.handle_event(&event);
+            let visible_height = self.calculate_notes_visible_height();
+            self.auto_scroll_to_cursor(visible_height);
+        } else if self.subtask_note_input.active {
+            self.subtask_note_input.handle_event(&event);
+        } else if self.quick_add_input.active {
+            self.quick_add_input.handle_event(&event);
+        } else if self.review_input.active {
+            self.review_input.handle_event(&event);
+        } else if self.triage_input.active {
+            self.triage_input.handle_event(&event);
+        } else if self.fuzzy_search.input.active {
+            self.handle_fuzzy_search(&event);
+        } else if self.show_main_menu_modal {
+            self.help_query.push_str(&text.replace(['\n', '\r'], " "));
+            self.help_scroll = 0;
+        }
+    }
+
+    // COPY TODOS TO THE CLIPBOARD
+    fn copy_todos(&mut self, whole_list: bool, format: clipboard::CopyFormat) {
+        let todos: Vec<&Todo> = if whole_list {
+            self.visible_todos()
+        } else {
+            self.selected_index()
+                .and_then(|index| self.todos.get(index))
+                .into_iter()
+                .collect()
+        };
+
+        if todos.is_empty() {
+            self.status_message = Some("Nothing to copy".to_string());
+            return;
+        }
+
+        let text = clipboard::format_todos(&todos, format);
+        let count = todos.len();
+        self.status_message = Some(match clipboard::copy_to_clipboard(&text) {
+            Ok(_) => format!("📋 Copied {} todo(s) to the clipboard", count),
+            Err(e) => format!("❌ Could not copy to clipboard: {}", e),
+        });
+    }
+
+    // EXPORT WHAT I'M SEEING: the visible rows, with the search and today view applied
+    fn export_visible(&mut self, format: ExportFormat) {
+        let todos = self.visible_todos();
+        if todos.is_empty() {
+            self.status_message = Some("Nothing to export".to_string());
+            return;
+        }
+
+        let count = todos.len();
+        self.status_message = Some(match import_export::export::write_todos(&todos, format) {
+            Ok(path) => format!("💾 Exported {} todo(s) to {}", count, path),
+            Err(e) => format!("❌ Export failed: {}", e),
+        });
+    }
+
+    // MERGE TODOS: first press marks the duplicate, second press merges it into the selection
+    fn merge_selected(&mut self) {
+        let Some(target) = self.selected_index().map(|index| self.todos[index].id) else {
+            return;
+        };
+
+        let Some(source) = self.merge_source.take() else {
+            self.merge_source = Some(target);
+            return;
+        };
+
+        if source == target {
+            self.status_message = Some("Merge cancelled".to_string());
+            return;
+        }
+
+        match arguments::merge_todos::merge_todos(target as i32, source as i32) {
+            Ok(_) => {
+                self.reload_todos(Some(target));
+                self.status_message =
+                    Some(format!("✅ Todo {} merged into todo {}", source, target));
+            }
+            Err(e) => self.status_message = Some(format!("❌ Could not merge todos: {}", e)),
+        }
+    }
+
+    // SUBTASK NOTES: edit the note of the selected subtask in the details modal
+    fn edit_subtask_note(&mut self) {
+        let Some(subtask) = self.selected_todo.as_ref().and_then(|todo| {
+            self.subtask_state
+                .selected()
+                .and_then(|index| todo.subtasks.get(index))
+        }) else {
+            return;
+        };
+        self.editing_subtask_note = Some(subtask.subtask_id);
+        self.subtask_note_input.value = subtask.note.clone();
+        self.subtask_note_input.focus();
+    }
+
+    fn save_subtask_note(&mut self) {
+        let note = self.subtask_note_input.value.trim().to_string();
+        self.subtask_note_input.clear();
+        self.subtask_note_input.unfocus();
+        let Some(subtask_id) = self.editing_subtask_note.take() else {
+            return;
+        };
+        if let Err(e) =
+            database::DBtodo::new().and_then(|db| db.update_subtask_note(subtask_id as i32, &note))
+        {
+            self.status_message = Some(format!("❌ Could not save the note: {}", e));
+        }
+        if let Some(todo_id) = self.selected_todo.as_ref().map(|todo| todo.id) {
+            self.load_todo(todo_id);
+        }
+    }
+
+    // EXPLODE the todo open in the details modal, its list lines become subtasks
+    fn explode_selected(&mut self) {
+        let Some(todo_id) = self.selected_todo.as_ref().map(|todo| todo.id) else {
+            return;
+        };
+        self.status_message = Some(match arguments::explode::explode_todo(todo_id as i32) {
+            Ok(0) => "No list items to turn into subtasks".to_string(),
+            Ok(count) => format!("✅ {} subtask(s) added from the text", count),
+            Err(e) => format!("❌ Could not explode the todo: {}", e),
+        });
+        self.load_todo(todo_id);
+    }
+
+    // MOVE A SUBTASK: `v` in the details modal picks it, `v` in the table moves it to the selection
+    fn pick_subtask_to_move(&mut self) {
+        let Some(subtask) = self.selected_todo.as_ref().and_then(|todo| {
+            self.subtask_state
+                .selected()
+                .and_then(|index| todo.subtasks.get(index))
+        }) else {
+            return;
+        };
+        self.moving_subtask = Some((subtask.subtask_id, subtask.text.clone()));
+        self.close_modal();
+    }
+
+    fn move_subtask_to_selected(&mut self) {
+        let Some((subtask_id, text)) = self.moving_subtask.take() else {
+            return;
+        };
+        let Some(target) = self.selected_index().map(|index| self.todos[index].id) else {
+            return;
+        };
+
+        match database::DBtodo::new()
+            .and_then(|db| db.move_subtask(subtask_id as i32, target as i32))
+        {
+            Ok(0) => self.status_message = Some(format!("❌ Could not move '{}'", text)),
+            Ok(_) => {
+                self.reload_todos(Some(target));
+                self.status_message = Some(format!("✅ '{}' moved to todo {}", text, target));
+            }
+            Err(e) => self.status_message = Some(format!("❌ Could not move the subtask: {}", e)),
+        }
+    }
+
+    // SETTINGS SCREEN
+    // Milestone progress is read when the panel opens, todos may have changed since
+    fn open_stats(&mut self) {
+        self.milestones = database::DBtodo::new()
+            .and_then(|db| arguments::report::load_milestone_progress(&db))
+            .unwrap_or_default();
+        self.show_stats_modal = true;
+    }
+
+    fn open_settings(&mut self) {
+        self.show_settings_modal = true;
+        self.settings_state.select(Some(0));
+    }
+
+    fn open_checklists(&mut self) {
+        match database::DBtodo::new().and_then(|db| db.get_checklists()) {
+            Ok(checklists) => {
+                self.checklist_state
+                    .select((!checklists.is_empty()).then_some(0));
+                self.checklists = checklists;
+                self.show_checklist_modal = true;
+            }
+            Err(e) => self.status_message = Some(format!("❌ Could not load checklists: {}", e)),
+        }
+    }
+
+    fn open_links(&mut self) {
+        let Some(todo) = &self.selected_todo else {
+            return;
+        };
+        self.note_links = MarkdownRenderer::new().links(&todo.notes);
+        if self.note_links.is_empty() {
+            self.status_message = Some("No links in the notes".to_string());
+            return;
+        }
+        self.links_state.select(Some(0));
+        self.show_links_modal = true;
+    }
+
+    fn move_links_selection(&mut self, forward: bool) {
+        let len = self.note_links.len();
+        if let Some(selected) = self.links_state.selected() {
+            let next = if forward {
+                (selected + 1) % len
+            } else {
+                (selected + len - 1) % len
+            };
+            self.links_state.select(Some(next));
+        }
+    }
+
+    fn open_selected_link(&mut self) {
+        let Some(link) = self
+            .links_state
+            .selected()
+            .and_then(|index| self.note_links.get(index))
+        else {
+            return;
+        };
+        self.status_message = Some(match browser::open(&link.url) {
+            Ok(()) => format!("🌐 Opened {}", link.url),
+            Err(e) => format!("❌ {}", e),
+        });
+        self.show_links_modal = false;
+    }
+
+    // Open the link of the todo in the details modal, or of the selected row
+    fn open_external_url(&mut self) {
+        let todo = match &self.selected_todo {
+            Some(todo) => Some(todo),
+            None => self.selected_index().map(|index| &self.todos[index]),
+        };
+        let Some(todo) = todo else {
+            return;
+        };
+        self.status_message = Some(match &todo.external_url {
+            Some(url) => match browser::open(url) {
+                Ok(()) => format!("🌐 Opened {}", url),
+                Err(e) => format!("❌ {}", e),
+            },
+            None => format!(
+                "Todo {} has no link, set one with `voido -u {} --url`",
+                todo.id, todo.id
+            ),
+        });
+    }
+
+    fn open_note_tasks(&mut self) {
+        let Some(todo) = &self.selected_todo else {
+            return;
+        };
+        self.note_tasks = markdown::note_tasks(&todo.notes);
+        if self.note_tasks.is_empty() {
+            self.status_message = Some("No checkboxes in the notes".to_string());
+            return;
+        }
+        self.note_tasks_state.select(Some(0));
+        self.show_note_tasks_modal = true;
+    }
+
+    fn move_note_tasks_selection(&mut self, forward: bool) {
+        let len = self.note_tasks.len();
+        if let Some(selected) = self.note_tasks_state.selected() {
+            let next = if forward {
+                (selected + 1) % len
+            } else {
+                (selected + len - 1) % len
+            };
+            self.note_tasks_state.select(Some(next));
+        }
+    }
+
+    // SUMMARIZE THE NOTES of the todo in the details modal, Gemini answers in the background
+    fn summarize_selected(&mut self) {
+        let Some(todo) = &self.selected_todo else {
+            return;
+        };
+        if self.summarizing.is_some() {
+            self.status_message = Some("🤖 Still summarizing, one at a time".to_string());
+            return;
+        }
+        if ai::strip_summary(&todo.notes).trim().is_empty() {
+            self.status_message = Some("No notes to summarize".to_string());
+            return;
+        }
+        self.summarizing = Some((
+            todo.id,
+            ai::in_background(vec![
+                "ai".to_string(),
+                "summarize".to_string(),
+                todo.id.to_string(),
+            ]),
+        ));
+        self.status_message = Some("🤖 Summarizing the notes...".to_string());
+    }
+
+    // Picks up the summary once Gemini answered, the notes have it on top by then
+    fn check_summary(&mut self) {
+        let Some((todo_id, worker)) = self.summarizing.take_if(|(_, worker)| worker.is_finished())
+        else {
+            return;
+        };
+        let error = match worker.join() {
+            Ok(Ok(output)) => String::from_utf8_lossy(&output.stderr)
+                .lines()
+                .last()
+                .map(|line| line.trim_start_matches("❌ ").to_string()),
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some("The summary stopped unexpectedly".to_string()),
+        };
+        self.status_message = Some(match error {
+            Some(e) => format!("❌ {}", e),
+            None => format!("✅ Notes of todo {} summarized", todo_id),
+        });
+        if self
+            .selected_todo
+            .as_ref()
+            .is_some_and(|todo| todo.id == todo_id)
+        {
+            self.load_todo(todo_id);
+        } else {
+            let selected = self
+                .selected_index()
+                .and_then(|index| self.todos.get(index))
+                .map(|todo| todo.id);
+            self.reload_todos(selected);
+        }
+    }
+
+    // Tick or clear the picked checkbox in the notes, and its subtask when [NOTES] asks for it
+    fn toggle_selected_note_task(&mut self) {
+        let Some(todo) = &self.selected_todo else {
+            return;
+        };
+        let Some(task) = self
+            .note_tasks_state
+            .selected()
+            .and_then(|index| self.note_tasks.get(index))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(notes) = markdown::toggle_note_task(&todo.notes, task.line) else {
+            return;
+        };
+        let todo_id = todo.id;
+        let sync = configs::notes_config().checkbox_subtasks;
+
+        match database::DBtodo::new().and_then(|db| {
+            db.update_notes(todo_id as i32, notes)?;
+            if sync {
+                db.sync_subtask(todo_id as i32, &task.text, !task.done)
+            } else {
+                Ok(false)
+            }
+        }) {
+            Ok(true) => {
+                self.status_message = Some(format!("✅ '{}' added as a subtask", task.text))
+            }
+            Ok(false) => {}
+            Err(e) => self.status_message = Some(format!("❌ Could not save the notes: {}", e)),
+        }
+        self.load_todo(todo_id);
+        if let Some(todo) = &self.selected_todo {
+            self.note_tasks = markdown::note_tasks(&todo.notes);
+        }
+    }
+
+    fn move_checklist_selection(&mut self, forward: bool) {
+        let len = self.checklists.len();
+        if let Some(selected) = self.checklist_state.selected() {
+            let next = if forward {
+                (selected + 1) % len
+            } else {
+                (selected + len - 1) % len
+            };
+            self.checklist_state.select(Some(next));
+        }
+    }
+
+    // Add the picked checklist to the todo open in the details modal
+    fn apply_checklist(&mut self) {
+        let Some(todo_id) = self.selected_todo.as_ref().map(|todo| todo.id) else {
+            return;
+        };
+        let Some(checklist) = self
+            .checklist_state
+            .selected()
+            .and_then(|index| self.checklists.get(index))
+        else {
+            return;
+        };
+        match database::DBtodo::new()
+            .and_then(|mut db| db.add_subtasks(todo_id as i32, &checklist.items))
+        {
+            Ok(count) => {
+                self.status_message = Some(format!(
+                    "✅ {} subtask(s) from '{}' added",
+                    count, checklist.name
+                ))
+            }
+            Err(e) => self.status_message = Some(format!("❌ Could not apply checklist: {}", e)),
+        }
+        self.show_checklist_modal = false;
+        self.load_todo(todo_id);
+    }
+
+    fn move_settings_selection(&mut self, forward: bool) {
+        let len = settings::settings_items().len();
+        let selected = self.settings_state.selected().unwrap_or(0);
+        let next = if forward {
+            (selected + 1) % len
+        } else {
+            (selected + len - 1) % len
+        };
+        self.settings_state.select(Some(next));
+    }
+
+    // Change the selected setting, apply it right away and save it to config.toml
+    fn change_setting(&mut self) {
+        if let Some(item) = self
+            .settings_state
+            .selected()
+            .and_then(|index| settings::settings_items().get(index).copied())
+        {
+            self.apply_setting(item);
+        }
+    }
+
+    fn apply_setting(&mut self, item: settings::SettingsItem) {
+        let mut config = configs::display_config().clone();
+        let mut defaults = configs::defaults_config().clone();
+        item.change(&mut config, &mut defaults);
+        if database::DBtodo::is_dry_run() {
+            self.status_message = Some(
+                "🧪 Dry run: the settings apply to this session, config.toml was not changed"
+                    .to_string(),
+            );
+        } else if let Err(e) = config.save().and_then(|_| defaults.save()) {
+            self.status_message = Some(format!("❌ Could not save settings: {}", e));
+        }
+        configs::set_display_config(config);
+        configs::set_defaults_config(defaults);
+        self.config_watcher.sync();
+    }
+
+    // Change subtask status
+    fn change_subtask_status(
+        &mut self,
+        todo_id: i32,
+        subtask_id: i32,
+        status: String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let db = database::DBtodo::new()?;
+        db.change_subtask_status(todo_id, subtask_id, status)?;
+        Ok(())
+    }
+
+    // MARK THE SELECTED SUBTASK Done, or back to Pending
+    fn toggle_selected_subtask(&mut self) {
+        // Early return if no selection or no todo
+        let Some(selected) = self.subtask_state.selected() else {
+            return;
+        };
+        let Some(todo) = &self.selected_todo else {
+            return;
+        };
+        let Some(subtask) = todo.subtasks.get(selected) else {
+            return;
+        };
+
+        // Prepare update parameters
+        let todo_id = todo.id;
+        let subtask_id = subtask.subtask_id;
+
+        // Determine new status
+        let new_status = if subtask.status == "Done" {
+            "Pending".to_string()
+        } else {
+            "Done".to_string()
+        };
+
+        // Update database
+        if let Err(e) =
+            self.change_subtask_status(todo_id as i32, subtask_id as i32, new_status.clone())
+        {
+            eprintln!("Error updating subtask: {}", e);
+            return;
+        }
+
+        // Update both in-memory states
+        if let Some(todo) = &mut self.selected_todo
+            && let Some(subtask) = todo.subtasks.get_mut(selected)
+        {
+            subtask.status = new_status.clone();
+        }
+
+        // Update the main todos list
+        if let Some(todo) = self.todos.iter_mut().find(|t| t.id == todo_id)
+            && let Some(subtask) = todo
+                .subtasks
+                .iter_mut()
+                .find(|s| s.subtask_id == subtask_id)
+        {
+            subtask.status = new_status;
+        }
+
+        // Force a full refresh from DB to ensure consistency
+        self.load_todo(todo_id);
+    }
+
+    // DEPENDENCY GRAPH of the selected todo
+    fn open_graph(&mut self) {
+        let Some(selected) = self.selected_index() else {
+            return;
+        };
+        let id = self.todos[selected].id;
+        match database::DBtodo::new().and_then(|db| db.dependency_links()) {
+            Ok(links) => {
+                self.graph_lines =
+                    arguments::dependencies::blocked_by_tree(&self.todos, &links, id);
+                self.show_graph_modal = true;
+            }
+            Err(e) => self.status_message = Some(format!("❌ Could not load dependencies: {}", e)),
+        }
+    }
+
+    // FOCUS MODE: the selected todo alone on the screen
+    fn open_focus(&mut self) {
+        self.select_current();
+        if self.selected_todo.is_some() {
+            self.show_modal = false;
+            self.focus_mode = true;
+            self.focus_scroll = 0;
+            self.subtask_state.select(None);
+        }
+    }
+
+    fn close_focus(&mut self) {
+        self.focus_mode = false;
+        self.selected_todo = None;
+        self.subtask_state.select(None);
+    }
+
+    // INBOX TRIAGE: the captured todos one at a time, see triage.rs
+    fn open_triage(&mut self) {
+        let triage = triage::Triage::new(&self.todos, &configs::defaults_config().inbox_topic);
+        if triage.current().is_none() {
+            self.status_message = Some("📭 The inbox is empty".to_string());
+            return;
+        }
+        self.close_modal();
+        self.triage = Some(triage);
+    }
+
+    // The inbox todo on screen
+    fn triage_todo(&self) -> Option<&Todo> {
+        let id = self.triage.as_ref()?.current()?;
+        self.todos.iter().find(|todo| todo.id == id)
+    }
+
+    fn close_triage(&mut self) {
+        self.triage_input.clear();
+        self.triage_input.unfocus();
+        if let Some(triage) = self.triage.take()
+            && triage.sorted + triage.deleted + triage.skipped > 0
+        {
+            self.status_message = Some(triage.summary());
+        }
+    }
+
+    // On to the next inbox todo, or back to the table after the last one
+    fn next_triage(&mut self) {
+        self.triage_input.clear();
+        self.triage_input.unfocus();
+        let Some(triage) = &mut self.triage else {
+            return;
+        };
+        triage.advance();
+        // Skip what was deleted or sorted out since the triage started
+        while let Some(id) = triage.current() {
+            if self.todos.iter().any(|todo| todo.id == id) {
+                return;
+            }
+            triage.advance();
+        }
+        self.close_triage();
+    }
+
+    fn run_triage_actions(&mut self, actions: &[macros::MacroAction]) -> bool {
+        let Some(id) = self.triage_todo().map(|todo| todo.id) else {
+            return false;
+        };
+        match database::DBtodo::new().and_then(|mut db| db.apply_macro(id as i32, actions)) {
+            Ok(()) => {
+                self.reload_todos(Some(id));
+                true
+            }
+            Err(e) => {
+                self.status_message = Some(format!("❌ {}", e));
+                false
+            }
         }
     }
 
-    // Change subtask status
-    fn change_subtask_status(
-        &mut self,
-        todo_id: i32,
-        subtask_id: i32,
-        status: String,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let db = database::DBtodo::new()?;
-        db.change_subtask_status(todo_id, subtask_id, status)?;
-        Ok(())
+    // A topic takes the todo out of the inbox
+    fn triage_topic(&mut self, topic: &str) {
+        let topic = arguments::add_todo::format_topic(topic.trim());
+        if topic.is_empty() {
+            return;
+        }
+        if self.run_triage_actions(&[macros::MacroAction::Topic(topic)])
+            && let Some(triage) = &mut self.triage
+        {
+            triage.sorted += 1;
+            self.next_triage();
+        }
+    }
+
+    fn triage_due(&mut self, value: &str) {
+        let Some(id) = self.triage_todo().map(|todo| todo.id) else {
+            return;
+        };
+        let Some((due, due_at)) =
+            dates::parse_due_at(value.trim(), chrono::Local::now().date_naive())
+        else {
+            self.status_message = Some(format!("❌ \"{}\" is not a date", value.trim()));
+            return;
+        };
+        match database::DBtodo::new()
+            .and_then(|mut db| db.reschedule_todos(&[id as i32], due, due_at))
+        {
+            Ok(_) => self.reload_todos(Some(id)),
+            Err(e) => self.status_message = Some(format!("❌ {}", e)),
+        }
+    }
+
+    fn triage_delete(&mut self) {
+        let Some(id) = self.triage_todo().map(|todo| todo.id) else {
+            return;
+        };
+        match database::DBtodo::new().and_then(|db| db.delete_todo(id as i32)) {
+            Ok(()) => {
+                self.todos.retain(|todo| todo.id != id);
+                self.jump_list.remove(id);
+                self.refresh_filter();
+                if let Some(triage) = &mut self.triage {
+                    triage.deleted += 1;
+                }
+                self.next_triage();
+            }
+            Err(e) => self.status_message = Some(format!("❌ {}", e)),
+        }
+    }
+
+    fn triage_key(&mut self, key: KeyCode) {
+        let Some(triage) = &mut self.triage else {
+            return;
+        };
+        match key {
+            KeyCode::Char(c @ '1'..='9') => {
+                if let Some(topic) = triage.topic_for(c).map(str::to_string) {
+                    self.triage_topic(&topic);
+                }
+            }
+            KeyCode::Char('t') => {
+                triage.field = Some(triage::TriageField::Topic);
+                self.triage_input.focus();
+            }
+            KeyCode::Char('d') => {
+                triage.field = Some(triage::TriageField::Due);
+                self.triage_input.focus();
+            }
+            KeyCode::Char('h') => {
+                self.run_triage_actions(&[macros::MacroAction::Priority("High".to_string())]);
+            }
+            KeyCode::Char('m') => {
+                self.run_triage_actions(&[macros::MacroAction::Priority("Medium".to_string())]);
+            }
+            KeyCode::Char('l') => {
+                self.run_triage_actions(&[macros::MacroAction::Priority("Low".to_string())]);
+            }
+            KeyCode::Char('n') => {
+                self.run_triage_actions(&[macros::MacroAction::Priority("Normal".to_string())]);
+            }
+            KeyCode::Char('x') | KeyCode::Delete => self.triage_delete(),
+            KeyCode::Char('s') | KeyCode::Char(' ') => {
+                triage.skipped += 1;
+                self.next_triage();
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('I') => self.close_triage(),
+            _ => {}
+        }
+    }
+
+    fn submit_triage_input(&mut self) {
+        let value = self.triage_input.value.clone();
+        self.triage_input.clear();
+        self.triage_input.unfocus();
+        match self.triage.as_mut().and_then(|triage| triage.field.take()) {
+            Some(triage::TriageField::Topic) => self.triage_topic(&value),
+            Some(triage::TriageField::Due) => self.triage_due(&value),
+            None => {}
+        }
+    }
+
+    // Move the subtask selection in focus mode, wrapping neither way
+    fn move_focus_selection(&mut self, down: bool) {
+        let count = self
+            .selected_todo
+            .as_ref()
+            .map_or(0, |todo| todo.subtasks.len());
+        if count == 0 {
+            return;
+        }
+        let next = match (self.subtask_state.selected(), down) {
+            (None, _) => 0,
+            (Some(index), true) => (index + 1).min(count - 1),
+            (Some(index), false) => index.saturating_sub(1),
+        };
+        self.subtask_state.select(Some(next));
+    }
+
+    // COMMENTS of the todo open in the details modal, the thread opens at the newest one
+    fn load_comments(&mut self, scroll_to_newest: bool) {
+        let Some(todo_id) = self.selected_todo.as_ref().map(|todo| todo.id) else {
+            return;
+        };
+        self.comments = database::DBtodo::new()
+            .and_then(|db| db.get_comments(Some(todo_id as i64)))
+            .unwrap_or_default();
+        if scroll_to_newest {
+            // Clamped to the end of the thread when it is drawn
+            self.comments_scroll = u16::MAX;
+        }
     }
 
     // Update TODOS to ensure SYNC with DB
@@ -116,6 +1277,7 @@ impl App {
 
                     // Update selected todo
                     self.selected_todo = Some(updated_todo.clone());
+                    self.load_comments(false);
 
                     // Update the main todos list
                     if let Some(todo) = self.todos.iter_mut().find(|t| t.id == todo_id) {
@@ -147,8 +1309,8 @@ impl App {
     }
 
     fn handle_priority_change(&mut self, priority: &str) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(selected) = self.state.selected() {
-            if selected < self.todos.len() {
+        if self.state.selected().is_some() {
+            if let Some(selected) = self.selected_index() {
                 let id = self.todos[selected].id;
                 self.show_priority_modal = false;
                 self.change_priority(id as i32, priority.to_string())?;
@@ -159,6 +1321,94 @@ impl App {
         Ok(())
     }
 
+    // PIN / UNPIN the selected todo
+    fn toggle_pin(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let selected = self.selected_index().ok_or("No todo selected")?;
+        let todo = &mut self.todos[selected];
+
+        let db = database::DBtodo::new()?;
+        db.set_pinned(todo.id as i32, !todo.pinned)?;
+        todo.pinned = !todo.pinned;
+        let id = todo.id;
+
+        self.sort_todos();
+
+        // Keep the cursor on the todo that was just (un)pinned
+        if let Some(row) = self.visible_todos().iter().position(|t| t.id == id) {
+            self.state.select(Some(row));
+        }
+        Ok(())
+    }
+
+    // EMPTY STATE: fill an empty list with the demo todos, only when asked for
+    fn load_demo_todos(&mut self) {
+        if !self.todos.is_empty() {
+            return;
+        }
+        let result = database::DBtodo::new().and_then(|mut db| db.add_todos(&data::demo_todos()));
+        self.status_message = Some(match result {
+            Ok(count) => {
+                self.reload_todos(None);
+                format!(
+                    "✅ Added {} demo todos, remove them with x or `voido --clear`",
+                    count
+                )
+            }
+            Err(e) => format!("❌ Could not add the demo todos: {}", e),
+        });
+    }
+
+    // RUN THE [MACROS] ENTRY bound to `key` on the selected todo, if there is one
+    fn run_macro(&mut self, key: char) {
+        let macros = configs::MacrosConfig::load();
+        let (Some(key_macro), Some(selected)) = (macros.find(key), self.selected_index()) else {
+            return;
+        };
+        let id = self.todos[selected].id;
+
+        let result = database::DBtodo::new()
+            .and_then(|mut db| db.apply_macro(id as i32, &key_macro.actions));
+        self.status_message = Some(match result {
+            Ok(()) => {
+                self.reload_todos(Some(id));
+                format!("✅ {}: {}", key, key_macro.label())
+            }
+            Err(e) => format!("❌ Macro {} failed: {}", key, e),
+        });
+    }
+
+    // Pinned todos first, everything else in ID order (same as the DB query),
+    // or by days left with the todos without a due date last
+    fn sort_todos(&mut self) {
+        let today = chrono::Local::now().date_naive();
+        if self.sort_by_countdown {
+            self.todos.sort_by_key(|todo| {
+                let days_left = todo.days_left(today);
+                (!todo.pinned, days_left.is_none(), days_left, todo.id)
+            });
+        } else {
+            self.todos.sort_by_key(|todo| (!todo.pinned, todo.id));
+        }
+        self.refresh_filter();
+    }
+
+    // SORT BY DAYS LEFT, or back to ID order, keeping the cursor on the same todo
+    fn toggle_countdown_sort(&mut self) {
+        let selected_id = self.selected_index().map(|index| self.todos[index].id);
+        self.sort_by_countdown = !self.sort_by_countdown;
+        self.sort_todos();
+        if let Some(row) =
+            selected_id.and_then(|id| self.visible_todos().iter().position(|t| t.id == id))
+        {
+            self.state.select(Some(row));
+        }
+        self.status_message = Some(if self.sort_by_countdown {
+            "Sorted by days left".to_string()
+        } else {
+            "Sorted by ID".to_string()
+        });
+    }
+
     // SCROLL NOTES FUNCTIONALITY
     fn scroll_notes_up(&mut self) {
         if self.notes_scroll_offset > 0 {
@@ -205,6 +1455,7 @@ impl App {
         // Update local state
         if let Some(todo) = self.todos.iter_mut().find(|t| t.id == id as usize) {
             todo.notes = notes.clone();
+            todo.updated_at = Some(chrono::Utc::now());
         }
 
         // Update selected todo if it matches
@@ -217,54 +1468,130 @@ impl App {
         Ok(())
     }
 
-    // CHANGE TODO STATUS
+    // CHANGE TODO STATUS, the workflow may refuse it
     fn change_todo_status(
         &mut self,
         id: i32,
         status: String,
+        waiting_on: Option<String>,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Validate selection exists
-        let selected = self.state.selected().ok_or("No todo selected")?;
-
-        // Validate selection is within bounds
-        if selected >= self.todos.len() {
-            return Err("Invalid selection".into());
-        }
+        let selected = self.selected_index().ok_or("No todo selected")?;
 
         // Update database
         let db = database::DBtodo::new()?;
-        db.update_todo(id, Some(status.clone()))?;
-
-        // Update local state
-        self.todos[selected].status = status;
+        db.set_status(
+            id,
+            &status,
+            waiting_on.as_deref(),
+            &configs::WorkflowConfig::load(),
+        )?;
 
-        // Maintain selection position
-        if !self.todos.is_empty() {
-            let new_selection = selected.min(self.todos.len().saturating_sub(1));
-            self.state.select(Some(new_selection));
+        // Update local state, with the known spelling of the owner
+        let waiting_on = waiting_on.map(|name| {
+            self.owners
+                .iter()
+                .find(|owner| owner.name.eq_ignore_ascii_case(&name))
+                .map_or(name, |owner| owner.name.clone())
+        });
+        let todo = &mut self.todos[selected];
+        if status == models::REVIEW_STATUS {
+            todo.waiting_on = waiting_on.or(todo.waiting_on.take());
+        } else {
+            todo.waiting_on = None;
+        }
+        todo.status = status;
+        todo.updated_at = Some(chrono::Utc::now());
+        if let Some(selected_todo) = &mut self.selected_todo
+            && selected_todo.id == todo.id
+        {
+            selected_todo.status = todo.status.clone();
+            selected_todo.waiting_on = todo.waiting_on.clone();
+        }
+        if self.habits.iter().any(|habit| habit.todo_id == id as usize) {
+            self.load_habits();
         }
 
         Ok(())
     }
 
-    // Delete current selected TODO
-    fn delete_current_todo(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        if let Some(selected) = self.state.selected() {
-            if selected < self.todos.len() {
-                let id = self.todos[selected].id;
-                let db = database::DBtodo::new()?;
-                db.delete_todo(id as i32)?;
+    // Status keys: refused moves show the ones the workflow allows instead
+    fn set_selected_status(&mut self, status: &str) {
+        let Some(selected) = self.selected_index() else {
+            return;
+        };
+        let id = self.todos[selected].id;
+        if let Err(e) = self.change_todo_status(id as i32, status.to_string(), None) {
+            self.status_message = Some(format!("⛔ {}", e));
+        }
+    }
 
-                // Update local state
-                self.todos.remove(selected);
+    // Ask who the review waits on, unless the workflow doesn't allow a review now
+    fn start_review(&mut self) {
+        let Some(selected) = self.selected_index() else {
+            return;
+        };
+        let current = &self.todos[selected].status;
+        if let Err(e) = configs::WorkflowConfig::load().check(current, models::REVIEW_STATUS) {
+            self.status_message = Some(format!("⛔ Todo {} {}", self.todos[selected].id, e));
+            return;
+        }
+        self.review_input.value = self.todos[selected].waiting_on.clone().unwrap_or_default();
+        self.review_input.focus();
+    }
 
-                // Adjust selection
-                if !self.todos.is_empty() {
-                    self.state.select(Some(selected.min(self.todos.len() - 1)));
-                } else {
-                    self.state.select(None);
+    fn submit_review(&mut self) {
+        let waiting_on = self.review_input.value.trim().to_string();
+        self.review_input.clear();
+        self.review_input.unfocus();
+        let Some(selected) = self.selected_index() else {
+            return;
+        };
+        let id = self.todos[selected].id;
+        let waiting_on = (!waiting_on.is_empty()).then_some(waiting_on);
+        match self.change_todo_status(id as i32, models::REVIEW_STATUS.to_string(), waiting_on) {
+            Ok(()) => {
+                self.status_message = Some(match &self.todos[selected].waiting_on {
+                    Some(owner) => format!("🔍 In review, waiting on {}", self.owner_label(owner)),
+                    None => "🔍 In review".to_string(),
+                });
+                // A new reviewer is a new owner
+                if let Ok(owners) = database::DBtodo::new().and_then(|db| db.get_owners()) {
+                    self.owners = owners;
                 }
             }
+            Err(e) => self.status_message = Some(format!("⛔ {}", e)),
+        }
+    }
+
+    // Tab in the review prompt completes the owner name
+    fn complete_review_owner(&mut self) {
+        let prefix = self.review_input.value.trim().to_lowercase();
+        if let Some(owner) = self
+            .owners
+            .iter()
+            .find(|owner| owner.name.to_lowercase().starts_with(&prefix))
+        {
+            self.review_input.value = owner.name.clone();
+            self.review_input.focus();
+        }
+    }
+
+    // Delete current selected TODO
+    fn delete_current_todo(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if let (Some(row), Some(selected)) = (self.state.selected(), self.selected_index()) {
+            let id = self.todos[selected].id;
+            let db = database::DBtodo::new()?;
+            db.delete_todo(id as i32)?;
+
+            // Update local state
+            self.todos.remove(selected);
+            self.jump_list.remove(id);
+            self.refresh_filter();
+
+            // Adjust selection
+            let visible = self.visible_todos().len();
+            self.state.select(selection::row_after_delete(row, visible));
         }
         Ok(())
     }
@@ -298,64 +1625,132 @@ impl App {
     }
 
     fn next(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i >= self.todos.len() - 1 {
-                    0
-                } else {
-                    i + 1
-                }
-            }
-            None => 0,
-        };
-        self.state.select(Some(i));
+        let rows = self.visible_todos().len();
+        if let Some(row) = selection::next_row(self.state.selected(), rows) {
+            self.state.select(Some(row));
+        }
     }
 
     fn previous(&mut self) {
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    self.todos.len() - 1
-                } else {
-                    i - 1
-                }
-            }
-            None => 0,
-        };
-        self.state.select(Some(i));
+        let rows = self.visible_todos().len();
+        if let Some(row) = selection::previous_row(self.state.selected(), rows) {
+            self.state.select(Some(row));
+        }
     }
 
     fn select_current(&mut self) {
-        if let Some(index) = self.state.selected() {
-            // If we have filtered indices, map the selection index through filtered_indices
-            let actual_index = if !self.filtered_indices.is_empty() {
-                if index < self.filtered_indices.len() {
-                    self.filtered_indices[index]
-                } else {
-                    return; // Invalid index
-                }
+        // Map the table selection through the search results if a filter is active
+        if let Some(actual_index) = self.selected_index() {
+            self.selected_todo = Some(self.todos[actual_index].clone());
+            self.show_modal = true;
+            self.jump_list.visit(self.todos[actual_index].id);
+            self.load_comments(true);
+            self.load_related();
+        }
+    }
+
+    // JUMP BACK OR FORWARD through the todos opened this session, skipping deleted ones
+    fn jump(&mut self, back: bool) {
+        loop {
+            let viewing = self.selected_todo.as_ref().map(|todo| todo.id);
+            let target = if back {
+                self.jump_list.back(viewing)
             } else {
-                index
+                self.jump_list.forward()
             };
-
-            if actual_index < self.todos.len() {
-                self.selected_todo = Some(self.todos[actual_index].clone());
-                self.show_modal = true;
+            let Some(id) = target else {
+                self.status_message = Some(if back {
+                    "Already at the oldest jump".to_string()
+                } else {
+                    "Already at the newest jump".to_string()
+                });
+                return;
+            };
+            if !self.todos.iter().any(|todo| todo.id == id) {
+                self.jump_list.remove(id);
+                continue;
             }
+            // Open it without recording the jump itself
+            self.show_todo(id);
+            return;
+        }
+    }
+
+    // Select `id` in the table and open its details
+    fn show_todo(&mut self, id: usize) {
+        // A todo hidden by the search, the today view or the owner and context filters can
+        // only be selected once they are cleared
+        if !self.visible_todos().iter().any(|todo| todo.id == id) {
+            self.fuzzy_search.input.unfocus();
+            self.fuzzy_search.input.value.clear();
+            self.input_mode = InputMode::Normal;
+            self.today_view = false;
+            self.owner_filter = None;
+            self.context_filter = None;
+        }
+        self.close_modal();
+        if let Some(row) = self.visible_todos().iter().position(|todo| todo.id == id) {
+            self.state.select(Some(row));
+        }
+        if let Some(index) = self.selected_index() {
+            self.selected_todo = Some(self.todos[index].clone());
+            self.show_modal = true;
+            self.load_comments(true);
+            self.load_related();
+        }
+    }
+
+    fn load_related(&mut self) {
+        self.related_selected = None;
+        self.related = match &self.selected_todo {
+            Some(todo) => embeddings::related(todo, &self.todos, embeddings::RELATED_TODOS),
+            None => Vec::new(),
+        };
+    }
+
+    // R steps through the related todos, back to none after the last one
+    fn move_related_selection(&mut self) {
+        if self.related.is_empty() {
+            self.status_message = Some("No related todos".to_string());
+            return;
         }
+        self.related_selected = match self.related_selected {
+            Some(selected) if selected + 1 < self.related.len() => Some(selected + 1),
+            Some(_) => None,
+            None => Some(0),
+        };
+    }
+
+    // Open the related todo picked with R, Ctrl-o comes back
+    fn open_related(&mut self) {
+        let Some(id) = self
+            .related_selected
+            .and_then(|selected| self.related.get(selected))
+            .copied()
+        else {
+            return;
+        };
+        self.show_todo(id);
+        self.jump_list.visit(id);
     }
 
     fn close_modal(&mut self) {
         self.show_modal = false;
         self.selected_todo = None;
+        self.related.clear();
+        self.related_selected = None;
         self.show_priority_modal = false;
         self.show_main_menu_modal = false;
         self.show_delete_confirmation = false;
+        self.show_copy_modal = false;
+        self.show_export_modal = false;
         self.editing_notes = false;
         self.notes_input.unfocus();
         self.notes_input.value.clear();
         self.notes_scroll_offset = 0;
+        self.details_scroll_offset = 0;
         self.notes_preview_mode = false;
+        self.detail_search.clear();
 
         // Re-apply filter if there's text in the search input
         if !self.fuzzy_search.input.value.is_empty() {
@@ -376,8 +1771,27 @@ impl App {
     }
 
     fn update_filtered_todos(&mut self) {
-        // Update the filtered indices
-        self.filtered_indices = self.fuzzy_search.matched_indices().to_vec();
+        // Update the filtered indices, the today view and owner filter narrow the search results
+        let today = chrono::Local::now().date_naive();
+        self.filtered_indices = self
+            .fuzzy_search
+            .matched_indices()
+            .iter()
+            .copied()
+            .filter(|&index| {
+                self.todos.get(index).is_some_and(|todo| {
+                    (!self.today_view || todo.is_for_today(today))
+                        && self
+                            .owner_filter
+                            .as_ref()
+                            .is_none_or(|owner| todo.owner.eq_ignore_ascii_case(owner))
+                        && self
+                            .context_filter
+                            .as_deref()
+                            .is_none_or(|context| todo.fits_context(context))
+                })
+            })
+            .collect();
 
         // Update table selection to match the fuzzy search selection
         if !self.filtered_indices.is_empty() {
@@ -390,33 +1804,659 @@ impl App {
             self.state.select(None);
         }
     }
-}
-
-#[tokio::main]
-async fn main() -> Result<(), io::Error> {
-    // Create the configs
-    let _ = configs::AppConfigs::create_default_config();
-
-    // Backup the existing TODOS
-
-    let cli = Cli::parse();
-
-    // Check if no arguments were provided
-    let no_args_provided = std::env::args().count() == 1;
-
+}
+
+// Commands that print or export the todos without changing them, `voido config` doesn't open
+// the database at all. Serve, publish, diff, planner and habits show the todos as they stand
+// after the rules, so those still run first.
+fn read_only(cli: &Cli) -> bool {
+    match &cli.command {
+        Some(
+            Commands::Search { .. }
+            | Commands::Report { .. }
+            | Commands::Export { .. }
+            | Commands::Dump
+            | Commands::Config { .. }
+            | Commands::Show { .. },
+        ) => true,
+        Some(Commands::List { plain, .. }) => *plain,
+        Some(Commands::Stale {
+            archive,
+            reschedule,
+            ..
+        }) => !archive && reschedule.is_none(),
+        Some(Commands::Archive { restore }) => restore.is_none(),
+        Some(Commands::Owners { action }) => action.is_none(),
+        Some(Commands::Comment { text, .. }) => text.is_none(),
+        Some(_) => false,
+        None => cli.print || cli.show || cli.export,
+    }
+}
+
+// The auto-archive and priority escalation rules from the config, and the habits done in an
+// earlier day or week back to Pending. What they changed is reported.
+fn startup_rules() -> Vec<String> {
+    let mut reports = Vec::new();
+    match arguments::archive::auto_archive() {
+        Ok(archived) if !archived.is_empty() => {
+            reports.push(arguments::archive::archive_report(&archived))
+        }
+        Ok(_) => {}
+        Err(e) => reports.push(format!("❌ Auto-archive failed: {}", e)),
+    }
+    match arguments::escalate::escalate_priorities() {
+        Ok(changes) if !changes.is_empty() => {
+            reports.push(arguments::escalate::escalation_report(&changes))
+        }
+        Ok(_) => {}
+        Err(e) => reports.push(format!("❌ Priority escalation failed: {}", e)),
+    }
+    match arguments::habits::reopen_habits() {
+        Ok(ids) if !ids.is_empty() => reports.push(format!(
+            "🔁 {} habit(s) back to Pending: {}",
+            ids.len(),
+            ids.iter()
+                .map(|id| format!("#{}", id))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )),
+        Ok(_) => {}
+        Err(e) => reports.push(format!("❌ Reopening habits failed: {}", e)),
+    }
+    reports
+}
+
+#[tokio::main]
+async fn main() -> Result<(), io::Error> {
+    let cli = Cli::parse();
+
+    // Print version, before anything reads or creates a file
+    if cli.release {
+        println!("voido {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+
+    // The profile decides which database and settings everything below uses
+    configs::set_profile(cli.profile.clone());
+    if let Err(e) = configs::check_profile() {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+    if let Some(tz) = &cli.tz
+        && let Err(e) = configs::set_timezone_override(tz)
+    {
+        eprintln!("❌ {}", e);
+        std::process::exit(1);
+    }
+
+    // Demo mode: nothing below touches the real database, and nothing is saved
+    let _demo_db = if cli.demo {
+        match database::DBtodo::open_demo()
+            .and_then(|mut db| db.add_todos(&data::demo_todos()).map(|_| db))
+        {
+            Ok(db) => Some(db),
+            Err(e) => {
+                eprintln!("❌ Could not set up the demo database: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+    // Dry run: the same, on a copy of the real database, and what changed is printed at the end
+    let dry_run = if cli.dry_run && !cli.demo {
+        match arguments::diff::DryRun::start() {
+            Ok(dry_run) => Some(dry_run),
+            Err(e) => {
+                eprintln!("❌ Could not set up the dry run: {}", e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    // Quick capture returns before the config checks and startup rules below
+    if let Some(Commands::Q { text }) = &cli.command {
+        match arguments::quick_add::capture(&text.join(" ")) {
+            Ok(id) => println!("📥 #{} in the inbox", id),
+            Err(e) => {
+                eprintln!("❌ Could not capture the todo: {}", e);
+                // exit() skips the dry run's report, it goes out first
+                drop(dry_run);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    // Check if no arguments were provided
+    let no_args_provided = std::env::args().count() == 1;
+
+    // Report problems in config.toml, broken values fall back to their defaults
+    let mut startup_reports = configs::check_config_file().messages();
+
+    // Commands that only read leave the todos as they are, the rules run on the next one that
+    // works on them. A dry run shows what the command does, not the rules.
+    if !read_only(&cli) && dry_run.is_none() {
+        startup_reports.extend(startup_rules());
+    }
+    let run_tui = match &cli.command {
+        None => cli.list || cli.demo || no_args_provided,
+        Some(Commands::List { plain, .. }) => !plain,
+        Some(Commands::Today | Commands::Triage) => true,
+        Some(_) => false,
+    };
+    let today_view = matches!(cli.command, Some(Commands::Today));
+    let triage_view = matches!(cli.command, Some(Commands::Triage));
+    // `voido list --context home` opens the TUI on that context
+    let list_context = match &cli.command {
+        Some(Commands::List { context, .. }) => context.clone(),
+        _ => None,
+    };
+    // Plain listings and dumps go to other programs, keep stdout for the data only
+    let plain_output = matches!(
+        cli.command,
+        Some(Commands::List { plain: true, .. } | Commands::Dump)
+    );
+    if !run_tui {
+        for report in &startup_reports {
+            if plain_output {
+                eprintln!("{}", report);
+            } else {
+                println!("{}", report);
+            }
+        }
+    }
+
+    // Subcommands
+    if let Some(command) = cli.command.filter(|_| !run_tui) {
+        match command {
+            Commands::Topic {
+                action: TopicCommand::Rename { from, to },
+            } => match arguments::bulk_update::rename_topic(&from, &to) {
+                Ok(0) => println!("❌ No todos found with topic: {}", from),
+                Ok(count) => println!("✅ Topic '{}' renamed on {} todo(s)", from, count),
+                Err(e) => eprintln!("Error renaming topic: {}", e),
+            },
+            Commands::Assign { from, to, topic } => {
+                match arguments::bulk_update::reassign_owner(&from, &to, topic.as_deref()) {
+                    Ok(0) => println!("❌ No todos found for owner: {}", from),
+                    Ok(count) => {
+                        println!("✅ {} todo(s) reassigned from {} to {}", count, from, to)
+                    }
+                    Err(e) => eprintln!("Error reassigning todos: {}", e),
+                }
+            }
+            Commands::Search {
+                query,
+                limit,
+                words,
+            } => {
+                if let Err(e) = arguments::search::search(&query.join(" "), limit, words).await {
+                    eprintln!("❌ Could not search: {}", e);
+                }
+            }
+            Commands::Stale {
+                days,
+                archive,
+                reschedule,
+            } => {
+                if let Err(e) = arguments::stale::stale(days, archive, reschedule.as_deref()) {
+                    eprintln!("Error checking stale todos: {}", e);
+                }
+            }
+            Commands::Archive { restore } => {
+                let result = match restore {
+                    Some(id) => arguments::archive::restore(id),
+                    None => arguments::archive::list_archived(),
+                };
+                if let Err(e) = result {
+                    eprintln!("Error reading archived todos: {}", e);
+                }
+            }
+            Commands::Subtask { action } => {
+                let result = match action {
+                    SubtaskCommand::Move {
+                        subtask_id,
+                        todo_id,
+                    } => arguments::subtasks::move_subtask(subtask_id, todo_id),
+                    SubtaskCommand::Done { todo_id, subtask } => {
+                        arguments::subtasks::complete_subtask(todo_id, &subtask)
+                    }
+                    SubtaskCommand::Set {
+                        todo_id,
+                        subtask,
+                        due,
+                        owner,
+                    } => arguments::subtasks::set_subtask_details(
+                        todo_id,
+                        &subtask,
+                        due.as_deref(),
+                        owner.as_deref(),
+                    ),
+                    SubtaskCommand::Note {
+                        todo_id,
+                        subtask,
+                        note,
+                    } => arguments::subtasks::set_subtask_note(todo_id, &subtask, &note),
+                    SubtaskCommand::Rm { todo_id, subtask } => {
+                        arguments::subtasks::remove_subtask(todo_id, &subtask)
+                    }
+                };
+                if let Err(e) = result {
+                    eprintln!("Error updating the subtask: {}", e);
+                }
+            }
+            Commands::Checklist { action } => {
+                let result = match action {
+                    ChecklistCommand::Save { name, from } => {
+                        arguments::checklists::save_checklist(&name, from)
+                    }
+                    ChecklistCommand::Apply { name, id } => {
+                        arguments::checklists::apply_checklist(&name, id)
+                    }
+                    ChecklistCommand::List => arguments::checklists::list_checklists(),
+                    ChecklistCommand::Remove { name } => {
+                        arguments::checklists::remove_checklist(&name)
+                    }
+                };
+                if let Err(e) = result {
+                    eprintln!("Error updating checklists: {}", e);
+                }
+            }
+            Commands::Depend { action } => {
+                let result = match action {
+                    DependCommand::Add { id, on } => {
+                        arguments::dependencies::add_dependency(id, on)
+                    }
+                    DependCommand::Remove { id, on } => {
+                        arguments::dependencies::remove_dependency(id, on)
+                    }
+                    DependCommand::Graph { id } => picker::resolve_id(id, "Graph")
+                        .and_then(|id| id.map_or(Ok(()), arguments::dependencies::print_graph)),
+                };
+                if let Err(e) = result {
+                    eprintln!("Error updating dependencies: {}", e);
+                }
+            }
+            Commands::Owners { action } => {
+                let result = match action.unwrap_or(OwnerCommand::List) {
+                    OwnerCommand::List => arguments::owners::list_owners(),
+                    OwnerCommand::Add {
+                        name,
+                        display,
+                        color,
+                    } => arguments::owners::add_owner(&name, display.as_deref(), color.as_deref()),
+                    OwnerCommand::Merge { from, into } => {
+                        arguments::owners::merge_owners(&from, &into)
+                    }
+                    OwnerCommand::Remove { name } => arguments::owners::remove_owner(&name),
+                };
+                if let Err(e) = result {
+                    eprintln!("Error updating owners: {}", e);
+                }
+            }
+            Commands::Milestone { action } => {
+                let result = match action {
+                    MilestoneCommand::Add { name, target } => {
+                        arguments::milestones::add_milestone(&name, target.as_deref())
+                    }
+                    MilestoneCommand::Link { name, ids } => {
+                        arguments::milestones::link_todos(Some(&name), &ids)
+                    }
+                    MilestoneCommand::Unlink { ids } => {
+                        arguments::milestones::link_todos(None, &ids)
+                    }
+                    MilestoneCommand::List => arguments::milestones::list_milestones(),
+                    MilestoneCommand::Show { name } => arguments::milestones::show_milestone(&name),
+                    MilestoneCommand::Remove { name } => {
+                        arguments::milestones::remove_milestone(&name)
+                    }
+                };
+                if let Err(e) = result {
+                    eprintln!("Error updating milestones: {}", e);
+                }
+            }
+            Commands::Report { action, html } => {
+                let result = match (action, html) {
+                    (Some(ReportCommand::Owners { format }), _) => {
+                        arguments::report::report_owners(format)
+                    }
+                    (Some(ReportCommand::Burndown { due_by, from, csv }), _) => {
+                        arguments::report::report_burndown(&due_by, from.as_deref(), csv)
+                    }
+                    (None, Some(path)) => import_export::html::export_report_html(&path),
+                    // Topic progress is the default report
+                    (Some(ReportCommand::Topics), _) | (None, None) => {
+                        arguments::report::report_topics()
+                    }
+                };
+                if let Err(e) = result {
+                    eprintln!("Error building the report: {}", e);
+                }
+            }
+            Commands::Export {
+                format,
+                filter,
+                status,
+                topic,
+                split_by,
+            } => {
+                let filter = import_export::export::ExportFilter {
+                    query: filter,
+                    status,
+                    topic,
+                };
+                if let Err(e) = import_export::export::export_todos(format, &filter, split_by) {
+                    eprintln!("Error exporting todos: {}", e);
+                }
+            }
+            Commands::Import {
+                file,
+                gsheet,
+                mstodo,
+                notion,
+            } => {
+                let result = match (file, gsheet, notion) {
+                    #[cfg(feature = "sync")]
+                    _ if mstodo => import_export::mstodo::import_mstodo().await,
+                    #[cfg(feature = "sync")]
+                    (_, Some(url), _) => import_export::gsheet::import_gsheet(&url).await,
+                    #[cfg(not(feature = "sync"))]
+                    (_, Some(_), _) => Err(features::missing("sync")),
+                    #[cfg(not(feature = "sync"))]
+                    _ if mstodo => Err(features::missing("sync")),
+                    (_, _, Some(csv)) => import_export::notion::import_notion(&csv),
+                    (Some(file), None, None) => import_export::import_file(&file),
+                    (None, None, None) => Ok(()),
+                };
+                if let Err(e) = result {
+                    eprintln!("Error importing todos: {}", e);
+                }
+            }
+            Commands::Dump => {
+                if let Err(e) = import_export::sql::dump() {
+                    eprintln!("Error dumping the database: {}", e);
+                }
+            }
+            Commands::Load { file } => {
+                if let Err(e) = import_export::sql::load(&file) {
+                    eprintln!("Error loading {}: {}", file, e);
+                }
+            }
+            Commands::Diff { file } => {
+                if let Err(e) = arguments::diff::diff(&file) {
+                    eprintln!("❌ Could not compare with {}: {}", file, e);
+                }
+            }
+            #[cfg(feature = "web")]
+            Commands::Publish { url, every } => {
+                if let Err(e) = arguments::publish::publish(url, every).await {
+                    eprintln!("Error publishing the stats: {}", e);
+                }
+            }
+            #[cfg(not(feature = "web"))]
+            Commands::Publish { .. } => eprintln!("❌ {}", features::missing("web")),
+            #[cfg(feature = "serve")]
+            Commands::Serve {
+                host,
+                port,
+                board,
+                calendar,
+            } => {
+                if let Err(e) = serve::serve(&host, port, serve::Pages { board, calendar }).await {
+                    eprintln!("Error serving on {}:{}: {}", host, port, e);
+                }
+            }
+            #[cfg(not(feature = "serve"))]
+            Commands::Serve { .. } => eprintln!("❌ {}", features::missing("serve")),
+            #[cfg(feature = "sync")]
+            Commands::Sync {
+                backend,
+                logout,
+                full,
+                token,
+                background,
+            } => {
+                let result = match (backend, logout) {
+                    (SyncTarget::Github, _) if token => sync::forge::save_token(),
+                    (SyncTarget::Google, _) if token => Err(
+                        "Google Tasks signs in with a code on the first sync, no token needed"
+                            .into(),
+                    ),
+                    (SyncTarget::Github, true) => sync::forge::logout(),
+                    (SyncTarget::Google, true) => sync::google_tasks::logout(),
+                    (backend, false) => sync::run_backend(backend.key(), full, background)
+                        .await
+                        .map(|_| ()),
+                };
+                if let Err(e) = result {
+                    eprintln!("❌ Error syncing with {:?}: {}", backend, e);
+                }
+            }
+            #[cfg(not(feature = "sync"))]
+            Commands::Sync { .. } => eprintln!("❌ {}", features::missing("sync")),
+            #[cfg(feature = "sync")]
+            Commands::Daemon => {
+                if let Err(e) = sync::daemon::run().await {
+                    eprintln!("❌ Sync daemon stopped: {}", e);
+                }
+            }
+            #[cfg(not(feature = "sync"))]
+            Commands::Daemon => eprintln!("❌ {}", features::missing("sync")),
+            Commands::Seed { file, wipe, yes } => {
+                if let Err(e) = import_export::seed::seed(&file, wipe, yes) {
+                    eprintln!("Error seeding from {}: {}", file, e);
+                }
+            }
+            Commands::Apply { file } => {
+                if let Err(e) = arguments::apply::apply(&file) {
+                    eprintln!("❌ Nothing applied from {}: {}", file, e);
+                }
+            }
+            Commands::MergeDb { file } => {
+                if let Err(e) = import_export::merge_db::merge_db(&file) {
+                    eprintln!("Error merging {}: {}", file, e);
+                }
+            }
+            Commands::Config { action } => {
+                let result = match action {
+                    ConfigCommand::Export {
+                        file,
+                        only,
+                        secrets,
+                    } => import_export::config_bundle::export(&file, &only, secrets),
+                    ConfigCommand::Import { file, only } => {
+                        import_export::config_bundle::import(&file, &only)
+                    }
+                };
+                if let Err(e) = result {
+                    eprintln!("❌ {}", e);
+                }
+            }
+            Commands::Ai { action } => match action {
+                AiCommand::Summarize { id } => match picker::resolve_id(id, "Summarize") {
+                    Ok(Some(id)) => match ai::summarize(id).await {
+                        Ok(summary) => {
+                            println!("✅ Summary added on top of the notes of todo {}", id);
+                            println!("\n🤖 {}", summary);
+                        }
+                        Err(e) => eprintln!("❌ Could not summarize the notes: {}", e),
+                    },
+                    Ok(None) => {}
+                    Err(e) => eprintln!("❌ Could not summarize the notes: {}", e),
+                },
+                AiCommand::Triage => {
+                    if let Err(e) = arguments::ai_triage::triage().await {
+                        eprintln!("❌ Could not triage the inbox: {}", e);
+                    }
+                }
+                AiCommand::Due { text } => match ai::suggest_due(&text.join(" ")).await {
+                    Ok((due, reason)) => println!("{}  {}", due.format("%Y-%m-%d"), reason),
+                    Err(e) => eprintln!("❌ Could not suggest a due date: {}", e),
+                },
+            },
+            Commands::Db { action } => {
+                let result = match action {
+                    DbCommand::Vacuum => arguments::maintenance::vacuum(),
+                    DbCommand::Check { repair } => arguments::maintenance::check(repair),
+                    DbCommand::Stats => arguments::maintenance::stats(),
+                };
+                if let Err(e) = result {
+                    eprintln!("Error maintaining the database: {}", e);
+                }
+            }
+            // Exit codes for scripts: 0 todos printed, 1 nothing to list, 2 error
+            Commands::List { open, context, .. } => {
+                match arguments::print::print_plain(open, context.as_deref()) {
+                    Ok(0) => {
+                        drop(dry_run);
+                        std::process::exit(1)
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        eprintln!("Error listing todos: {}", e);
+                        drop(dry_run);
+                        std::process::exit(2);
+                    }
+                }
+            }
+            // Opens the TUI, see below
+            Commands::Today | Commands::Triage => {}
+            Commands::Show { id } => {
+                let result = picker::resolve_id(id, "Show")
+                    .and_then(|id| id.map_or(Ok(()), arguments::print::show_todo));
+                if let Err(e) = result {
+                    eprintln!("Error showing todo: {}", e);
+                }
+            }
+            Commands::Review { id, waiting_on } => {
+                if let Err(e) = arguments::update_todo::send_to_review(id, waiting_on.as_deref()) {
+                    eprintln!("Error updating todo: {}", e);
+                }
+            }
+            Commands::Habit { id, every } => {
+                if let Err(e) = arguments::habits::set_habit(id, &every) {
+                    eprintln!("Error setting the habit: {}", e);
+                }
+            }
+            Commands::Context { id, context } => {
+                if let Err(e) = arguments::add_todo::set_context(id, &context) {
+                    eprintln!("Error setting the context: {}", e);
+                }
+            }
+            // Captured before the startup checks
+            Commands::Q { .. } => {}
+            Commands::Planner { week, html } => {
+                if let Err(e) = arguments::planner::planner(week.as_deref(), html.as_deref()) {
+                    eprintln!("Error printing the planner: {}", e);
+                }
+            }
+            Commands::Habits => {
+                if let Err(e) = arguments::habits::print_habits() {
+                    eprintln!("Error listing habits: {}", e);
+                }
+            }
+            Commands::Comment { id, text, author } => {
+                let result = match text {
+                    Some(text) => arguments::comments::add_comment(id, &text, author.as_deref()),
+                    None => arguments::comments::print_comments(id),
+                };
+                if let Err(e) = result {
+                    eprintln!("Error with comments: {}", e);
+                }
+            }
+            Commands::Explode { id } => match picker::resolve_id(id, "Explode") {
+                Ok(Some(id)) => match arguments::explode::explode_todo(id) {
+                    Ok(0) => println!("❌ Todo {} has no list items to turn into subtasks", id),
+                    Ok(count) => println!("✅ {} subtask(s) added to todo {}", count, id),
+                    Err(e) => eprintln!("Error exploding todo: {}", e),
+                },
+                Ok(None) => {}
+                Err(e) => eprintln!("Error exploding todo: {}", e),
+            },
+            Commands::Merge { keep, other } => {
+                match arguments::merge_todos::merge_todos(keep, other) {
+                    Ok(_) => println!("✅ Todo {} merged into todo {}", other, keep),
+                    Err(e) => eprintln!("Error merging todos: {}", e),
+                }
+            }
+        }
+    }
     // Terminal UI mode (default when no args provided or when --list is explicitly set)
-    if cli.list || no_args_provided {
+    else if run_tui {
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
         let todos = sample_todos();
         let mut app = App::new(todos);
+        if triage_view {
+            app.open_triage();
+        }
+        if today_view {
+            app.toggle_today_view();
+        }
+        if let Some(context) = list_context.as_deref().and_then(models::normalize_context) {
+            app.set_context_filter(Some(context));
+        }
+        if cli.demo {
+            startup_reports.insert(
+                0,
+                "🎬 Demo mode: changes are thrown away on exit".to_string(),
+            );
+        }
+        if !startup_reports.is_empty() {
+            app.status_message = Some(startup_reports.join(" | "));
+        }
 
         loop {
             terminal.draw(|f| draw_ui(f, &mut app))?;
-            if let Event::Key(key) = event::read()? {
+
+            // Pick up edits to config.toml while waiting for input
+            if !event::poll(Duration::from_millis(500))? {
+                if app.config_watcher.changed() {
+                    configs::reload_configs();
+                    let report = configs::check_config_file();
+                    app.status_message = Some(if report.is_clean() {
+                        "⚙️ Config reloaded".to_string()
+                    } else {
+                        report.messages().join(" | ")
+                    });
+                }
+                app.check_summary();
+                app.check_due_suggestion();
+                #[cfg(feature = "sync")]
+                if app.sync_watcher.tick() {
+                    let selected = app
+                        .selected_index()
+                        .and_then(|index| app.todos.get(index))
+                        .map(|todo| todo.id);
+                    app.reload_todos(selected);
+                    if app.sync_watcher.status == Some(sync::queue::SyncStatus::Synced) {
+                        app.status_message = Some("🔄 Queued changes synced".to_string());
+                    }
+                }
+                continue;
+            }
+
+            let event = event::read()?;
+            if let Event::Paste(text) = &event {
+                app.paste(text);
+                continue;
+            }
+
+            if let Event::Key(key) = event {
+                // Status messages only stay up until the next key press
+                app.status_message = None;
+
                 // Handle notes editing input
                 if app.editing_notes {
                     match key.code {
@@ -469,7 +2509,244 @@ async fn main() -> Result<(), io::Error> {
                     }
                 }
 
+                // Subtask note input captures all input while it is open
+                if app.subtask_note_input.active {
+                    match key.code {
+                        KeyCode::Enter => app.save_subtask_note(),
+                        KeyCode::Esc => {
+                            app.subtask_note_input.clear();
+                            app.subtask_note_input.unfocus();
+                            app.editing_subtask_note = None;
+                        }
+                        _ => {
+                            app.subtask_note_input.handle_event(&Event::Key(key));
+                        }
+                    }
+                    continue;
+                }
+
+                // Detail search prompt captures all input while it is open
+                if app.detail_search_input.active {
+                    match key.code {
+                        KeyCode::Enter => {
+                            app.detail_search.start(&app.detail_search_input.value);
+                            app.detail_search_input.unfocus();
+                        }
+                        KeyCode::Esc => app.detail_search_input.unfocus(),
+                        _ => {
+                            app.detail_search_input.handle_event(&Event::Key(key));
+                        }
+                    }
+                    continue;
+                }
+
+                // Review prompt captures all input while it is open
+                if app.review_input.active {
+                    match key.code {
+                        KeyCode::Enter => app.submit_review(),
+                        KeyCode::Tab => app.complete_review_owner(),
+                        KeyCode::Esc => {
+                            app.review_input.clear();
+                            app.review_input.unfocus();
+                        }
+                        _ => {
+                            app.review_input.handle_event(&Event::Key(key));
+                        }
+                    }
+                    continue;
+                }
+
+                // Inbox triage: its prompt, then a key per decision
+                if app.triage_input.active {
+                    match key.code {
+                        KeyCode::Enter => app.submit_triage_input(),
+                        KeyCode::Esc => {
+                            app.triage_input.clear();
+                            app.triage_input.unfocus();
+                        }
+                        _ => {
+                            app.triage_input.handle_event(&Event::Key(key));
+                        }
+                    }
+                    continue;
+                }
+                if app.triage.is_some() {
+                    app.triage_key(key.code);
+                    continue;
+                }
+
+                // Quick-add bar captures all input while it is open
+                if app.quick_add_input.active {
+                    match key.code {
+                        KeyCode::Enter => app.submit_quick_add(),
+                        KeyCode::Tab => app.complete_quick_add_owner(),
+                        KeyCode::Esc => {
+                            app.quick_add_input.clear();
+                            app.quick_add_input.unfocus();
+                        }
+                        _ => {
+                            app.quick_add_input.handle_event(&Event::Key(key));
+                        }
+                    }
+                    continue;
+                }
+
+                // Help screen: typing searches the keybindings
+                if app.show_main_menu_modal {
+                    match key.code {
+                        KeyCode::Esc if !app.help_query.is_empty() => app.help_query.clear(),
+                        KeyCode::Esc | KeyCode::Enter => app.show_main_menu_modal = false,
+                        KeyCode::Down => app.help_scroll = app.help_scroll.saturating_add(1),
+                        KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+                        KeyCode::PageDown => app.help_scroll = app.help_scroll.saturating_add(10),
+                        KeyCode::PageUp => app.help_scroll = app.help_scroll.saturating_sub(10),
+                        KeyCode::Backspace => {
+                            app.help_query.pop();
+                            app.help_scroll = 0;
+                        }
+                        KeyCode::Char(c) => {
+                            app.help_query.push(c);
+                            app.help_scroll = 0;
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Stats panel only needs closing
+                if app.show_stats_modal {
+                    if matches!(
+                        key.code,
+                        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('s')
+                    ) {
+                        app.show_stats_modal = false;
+                    }
+                    continue;
+                }
+
+                // Checklist picker
+                if app.show_checklist_modal {
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => app.move_checklist_selection(true),
+                        KeyCode::Char('k') | KeyCode::Up => app.move_checklist_selection(false),
+                        KeyCode::Enter | KeyCode::Char('l') => app.apply_checklist(),
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('C') => {
+                            app.show_checklist_modal = false
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Links in the notes
+                if app.show_links_modal {
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => app.move_links_selection(true),
+                        KeyCode::Char('k') | KeyCode::Up => app.move_links_selection(false),
+                        KeyCode::Enter | KeyCode::Char('l') => app.open_selected_link(),
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('U') => {
+                            app.show_links_modal = false
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Checkboxes in the notes
+                if app.show_note_tasks_modal {
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => app.move_note_tasks_selection(true),
+                        KeyCode::Char('k') | KeyCode::Up => app.move_note_tasks_selection(false),
+                        KeyCode::Char(' ') | KeyCode::Enter | KeyCode::Char('x') => {
+                            app.toggle_selected_note_task()
+                        }
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('X') => {
+                            app.show_note_tasks_modal = false
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Settings screen
+                if app.show_settings_modal {
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => app.move_settings_selection(true),
+                        KeyCode::Char('k') | KeyCode::Up => app.move_settings_selection(false),
+                        KeyCode::Char(' ') | KeyCode::Enter | KeyCode::Char('l') => {
+                            app.change_setting()
+                        }
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('S') => {
+                            app.show_settings_modal = false
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Dependency graph only needs closing
+                if app.show_graph_modal {
+                    if matches!(
+                        key.code,
+                        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('G')
+                    ) {
+                        app.show_graph_modal = false;
+                    }
+                    continue;
+                }
+
+                // Focus mode: only the todo on screen and its subtasks
+                if app.focus_mode {
+                    match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => app.move_focus_selection(true),
+                        KeyCode::Char('k') | KeyCode::Up => app.move_focus_selection(false),
+                        KeyCode::Char('d') | KeyCode::Char(' ') => app.toggle_selected_subtask(),
+                        KeyCode::Char('J') | KeyCode::PageDown => {
+                            app.focus_scroll = app.focus_scroll.saturating_add(3)
+                        }
+                        KeyCode::Char('K') | KeyCode::PageUp => {
+                            app.focus_scroll = app.focus_scroll.saturating_sub(3)
+                        }
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('F') => app.close_focus(),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Copy menu: pick what to copy and in which format
+                if app.show_copy_modal {
+                    match key.code {
+                        KeyCode::Char('m') => {
+                            app.copy_todos(false, clipboard::CopyFormat::Markdown)
+                        }
+                        KeyCode::Char('t') => app.copy_todos(false, clipboard::CopyFormat::Plain),
+                        KeyCode::Char('M') => app.copy_todos(true, clipboard::CopyFormat::Markdown),
+                        KeyCode::Char('T') => app.copy_todos(true, clipboard::CopyFormat::Plain),
+                        _ => {}
+                    }
+                    app.show_copy_modal = false;
+                    continue;
+                }
+
+                // Export menu: write the visible list to a file
+                if app.show_export_modal {
+                    match key.code {
+                        KeyCode::Char('j') => app.export_visible(ExportFormat::Json),
+                        KeyCode::Char('e') => app.export_visible(ExportFormat::Excel),
+                        KeyCode::Char('c') => app.export_visible(ExportFormat::Csv),
+                        KeyCode::Char('m') => app.export_visible(ExportFormat::Markdown),
+                        _ => {}
+                    }
+                    app.show_export_modal = false;
+                    continue;
+                }
+
+                let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
                 match key.code {
+                    // Jump list: terminals send Ctrl-i as Tab
+                    KeyCode::Char('o') if ctrl => app.jump(true),
+                    KeyCode::Char('i') if ctrl => app.jump(false),
+                    KeyCode::Tab => app.jump(false),
                     KeyCode::Char('i') if !app.fuzzy_search.input.active => {
                         app.fuzzy_search.input.focus();
                         app.input_mode = InputMode::Search;
@@ -511,59 +2788,61 @@ async fn main() -> Result<(), io::Error> {
                     }
 
                     // CHANGE SUBTASK STATUS
-                    KeyCode::Char('d') if app.show_modal => {
-                        // Early return if no selection or no todo
-                        let Some(selected) = app.subtask_state.selected() else {
-                            continue;
-                        };
-                        let Some(todo) = &app.selected_todo else {
-                            continue;
-                        };
-                        let Some(subtask) = todo.subtasks.get(selected) else {
-                            continue;
-                        };
-
-                        // Prepare update parameters
-                        let todo_id = todo.id;
-                        let subtask_id = subtask.subtask_id;
-
-                        // Determine new status
-                        let new_status = if subtask.status == "Done" {
-                            "Pending".to_string()
-                        } else {
-                            "Done".to_string()
-                        };
-
-                        // Update database
-                        if let Err(e) = app.change_subtask_status(
-                            todo_id as i32,
-                            subtask_id as i32,
-                            new_status.clone(),
-                        ) {
-                            eprintln!("Error updating subtask: {}", e);
-                            continue;
-                        }
-
-                        // Update both in-memory states
-                        if let Some(todo) = &mut app.selected_todo {
-                            if let Some(subtask) = todo.subtasks.get_mut(selected) {
-                                subtask.status = new_status.clone();
-                            }
-                        }
+                    KeyCode::Char('d') if app.show_modal => app.toggle_selected_subtask(),
 
-                        // Update the main todos list
-                        if let Some(todo) = app.todos.iter_mut().find(|t| t.id == todo_id) {
-                            if let Some(subtask) = todo
-                                .subtasks
-                                .iter_mut()
-                                .find(|s| s.subtask_id == subtask_id)
-                            {
-                                subtask.status = new_status;
-                            }
-                        }
+                    // Search the details, n / N step through the matches until Esc
+                    KeyCode::Char('/') if app.show_modal && !app.editing_notes => {
+                        app.detail_search_input.focus();
+                    }
+                    KeyCode::Char('n') if app.show_modal && app.detail_search.is_active() => {
+                        app.detail_search.step(true);
+                    }
+                    KeyCode::Char('N') if app.show_modal && app.detail_search.is_active() => {
+                        app.detail_search.step(false);
+                    }
+                    KeyCode::Esc if app.show_modal && app.detail_search.is_active() => {
+                        app.detail_search.clear();
+                    }
+
+                    // Add or change the note of the selected subtask
+                    KeyCode::Char('n') if app.show_modal && !app.editing_notes => {
+                        app.edit_subtask_note();
+                    }
+
+                    // Turn the list lines of the description and notes into subtasks
+                    KeyCode::Char('e') if app.show_modal && !app.editing_notes => {
+                        app.explode_selected();
+                    }
+
+                    // Add a saved checklist as subtasks
+                    KeyCode::Char('C') if app.show_modal && !app.editing_notes => {
+                        app.open_checklists();
+                    }
+
+                    // Pick a link from the notes to open in the browser
+                    KeyCode::Char('U') if app.show_modal && !app.editing_notes => {
+                        app.open_links();
+                    }
+
+                    // Tick the checkboxes of the notes
+                    KeyCode::Char('X') if app.show_modal && !app.editing_notes => {
+                        app.open_note_tasks();
+                    }
+
+                    // Open the ticket or PR the todo links to, from the details or the table
+                    KeyCode::Char('b') if !app.editing_notes => app.open_external_url(),
+
+                    // Summarize the notes with Gemini
+                    KeyCode::Char('A') if app.show_modal && !app.editing_notes => {
+                        app.summarize_selected();
+                    }
 
-                        // Force a full refresh from DB to ensure consistency
-                        app.load_todo(todo_id);
+                    // Pick a related todo, Enter opens it
+                    KeyCode::Char('R') if app.show_modal && !app.editing_notes => {
+                        app.move_related_selection();
+                    }
+                    KeyCode::Enter if app.show_modal && app.related_selected.is_some() => {
+                        app.open_related();
                     }
 
                     // Start editing notes
@@ -576,6 +2855,22 @@ async fn main() -> Result<(), io::Error> {
                         }
                     }
 
+                    // Scroll the comment thread
+                    KeyCode::Char('[') if app.show_modal && !app.editing_notes => {
+                        app.comments_scroll = app.comments_scroll.saturating_sub(1);
+                    }
+                    KeyCode::Char(']') if app.show_modal && !app.editing_notes => {
+                        app.comments_scroll = app.comments_scroll.saturating_add(1);
+                    }
+
+                    // Scroll the description pane
+                    KeyCode::Char('J') if app.show_modal => {
+                        app.details_scroll_offset = app.details_scroll_offset.saturating_add(1);
+                    }
+                    KeyCode::Char('K') if app.show_modal => {
+                        app.details_scroll_offset = app.details_scroll_offset.saturating_sub(1);
+                    }
+
                     // Scroll notes in read-only mode
                     KeyCode::PageUp if app.show_modal && !app.editing_notes => {
                         app.scroll_notes_up();
@@ -588,54 +2883,104 @@ async fn main() -> Result<(), io::Error> {
                         }
                     }
 
-                    KeyCode::Char('d') => {
-                        if let Some(selected) = app.state.selected() {
-                            if selected < app.todos.len() {
-                                let id = app.todos[selected].id;
-                                let status = "Done".to_string();
-                                if let Err(e) = app.change_todo_status(id as i32, status) {
-                                    eprintln!("Error updating todo status: {}", e);
-                                }
-                            }
-                        }
+                    KeyCode::Char('d') => app.set_selected_status("Done"),
+                    KeyCode::Char('o') => app.set_selected_status("Ongoing"),
+                    KeyCode::Char('p') => app.set_selected_status("Pending"),
+                    // Review asks who it waits on first
+                    KeyCode::Char('r') => app.start_review(),
+
+                    // Open the quick-add bar
+                    KeyCode::Char('a') if !app.show_modal => {
+                        app.quick_add_input.focus();
                     }
 
-                    KeyCode::Char('o') => {
-                        if let Some(selected) = app.state.selected() {
-                            if selected < app.todos.len() {
-                                let id = app.todos[selected].id;
-                                let status = "Ongoing".to_string();
-                                if let Err(e) = app.change_todo_status(id as i32, status) {
-                                    eprintln!("Error updating todo status: {}", e);
-                                }
-                            }
-                        }
+                    // Open the settings screen
+                    KeyCode::Char('S') if !app.show_modal => {
+                        app.open_settings();
                     }
 
-                    KeyCode::Char('p') => {
-                        if let Some(selected) = app.state.selected() {
-                            if selected < app.todos.len() {
-                                let id = app.todos[selected].id;
-                                let status = "Pending".to_string();
-                                if let Err(e) = app.change_todo_status(id as i32, status) {
-                                    eprintln!("Error updating todo status: {}", e);
-                                }
-                            }
+                    // Progress per topic and milestone
+                    KeyCode::Char('s') if !app.show_modal => {
+                        app.open_stats();
+                    }
+
+                    // Open the copy-to-clipboard menu
+                    KeyCode::Char('c') if !app.show_modal => {
+                        app.show_copy_modal = true;
+                    }
+
+                    // Open the export menu
+                    KeyCode::Char('E') if !app.show_modal => {
+                        app.show_export_modal = true;
+                    }
+
+                    // Pick a subtask in the details modal, then move it to the selected todo
+                    KeyCode::Char('v') if app.show_modal && !app.editing_notes => {
+                        app.pick_subtask_to_move();
+                    }
+                    KeyCode::Char('v') if !app.show_modal => {
+                        app.move_subtask_to_selected();
+                    }
+
+                    // Mark a duplicate, then merge it into the selected todo
+                    KeyCode::Char('m') if !app.show_modal => {
+                        app.merge_selected();
+                    }
+
+                    // Show the full text of the selected row, follows the selection
+                    KeyCode::Char('w') if !app.show_modal => {
+                        app.expand_selected = !app.expand_selected;
+                    }
+
+                    // Switch between the compact and comfortable table
+                    KeyCode::Char('D') if !app.show_modal => {
+                        app.apply_setting(settings::SettingsItem::Density);
+                    }
+
+                    // Demo todos, offered by the empty-state screen
+                    KeyCode::Char('X') if !app.show_modal && app.todos.is_empty() => {
+                        app.load_demo_todos();
+                    }
+
+                    // Order by days left until due, or by ID again
+                    KeyCode::Char('O') if !app.show_modal => app.toggle_countdown_sort(),
+
+                    // Only today's todos, or everything again
+                    KeyCode::Char('T') if !app.show_modal => app.toggle_today_view(),
+
+                    // One owner's todos: the next owner on each press, or only mine
+                    KeyCode::Char('@') if !app.show_modal => app.cycle_owner_filter(),
+                    KeyCode::Char('u') if !app.show_modal => app.toggle_my_todos(),
+                    KeyCode::Char('g') if !app.show_modal => app.cycle_context_filter(),
+
+                    // What the selected todo is waiting on
+                    KeyCode::Char('G') if !app.show_modal => app.open_graph(),
+
+                    // Work on the selected todo alone, full-screen
+                    KeyCode::Char('F') if !app.show_modal => app.open_focus(),
+
+                    // Go through the todos captured with `voido q`
+                    KeyCode::Char('I') if !app.show_modal => app.open_triage(),
+
+                    // Pin / unpin the selected todo
+                    KeyCode::Char('f') if !app.show_modal => {
+                        if let Err(e) = app.toggle_pin() {
+                            eprintln!("Error pinning todo: {}", e);
                         }
                     }
 
-                    // Show main menu modal
+                    // Show the help screen
                     KeyCode::Char('\\') => {
-                        app.show_main_menu_modal = !app.show_main_menu_modal;
+                        app.help_query.clear();
+                        app.help_scroll = 0;
+                        app.show_main_menu_modal = true;
                     }
 
                     // SHOW PRIORITY MODAL
-                    KeyCode::Char('P') => {
-                        if let Some(selected) = app.state.selected() {
-                            app.close_modal();
-                            if selected < app.todos.len() {
-                                app.show_priority_modal = true;
-                            }
+                    KeyCode::Char('P') if app.state.selected().is_some() => {
+                        app.close_modal();
+                        if app.selected_index().is_some() {
+                            app.show_priority_modal = true;
                         }
                     }
 
@@ -699,6 +3044,8 @@ async fn main() -> Result<(), io::Error> {
                         }
                     }
                     KeyCode::Esc | KeyCode::Char('h') => {
+                        app.merge_source = None;
+                        app.moving_subtask = None;
                         if app.show_modal
                             || app.show_priority_modal
                             || app.show_main_menu_modal
@@ -707,6 +3054,11 @@ async fn main() -> Result<(), io::Error> {
                             app.close_modal();
                         }
                     }
+
+                    // Anything else may be bound to a macro in config.toml
+                    KeyCode::Char(c) if !app.show_modal && !app.show_delete_confirmation => {
+                        app.run_macro(c);
+                    }
                     _ => {}
                 }
             }
@@ -716,15 +3068,21 @@ async fn main() -> Result<(), io::Error> {
         execute!(
             terminal.backend_mut(),
             LeaveAlternateScreen,
-            DisableMouseCapture
+            DisableMouseCapture,
+            DisableBracketedPaste
         )?;
         terminal.show_cursor()?;
     }
     //
     // Sync with Github
     else if cli.github {
-        println!("Syncing with Github...");
-        sync::handle_github_sync();
+        #[cfg(feature = "sync")]
+        {
+            println!("Syncing with Github...");
+            sync::github::handle_github_sync();
+        }
+        #[cfg(not(feature = "sync"))]
+        eprintln!("❌ {}", features::missing("sync"));
     }
     // Append subtask to already existing TODO
     else if !cli.subtasks.is_empty() {
@@ -737,11 +3095,8 @@ async fn main() -> Result<(), io::Error> {
     }
     // Import todos from excel file
     else if let Some(file_path) = cli.import {
-        // Check the file path and extension
-        if file_path.ends_with(".xlsx") {
-            let _workbook = import_export::xls::import_todos(&file_path);
-        } else {
-            import_export::json::import_from_json(&file_path);
+        if let Err(e) = import_export::import_file(&file_path) {
+            eprintln!("Error importing todos: {}", e);
         }
     }
     // Export TODOs/
@@ -749,16 +3104,26 @@ async fn main() -> Result<(), io::Error> {
         println!("Export options:");
         println!("1. JSON");
         println!("2. Excel");
+        println!("3. CSV");
+        println!("4. Markdown");
         let mut input = String::new();
         io::stdin().read_line(&mut input).unwrap();
-        let input = input.trim();
 
-        if input == "1" {
-            let _workbook = import_export::json::export_to_json();
-        } else if input == "2" {
-            let _workbook = import_export::xls::export_todos_xls();
-        } else {
-            println!("Invalid option");
+        let format = match input.trim() {
+            "1" => Some(ExportFormat::Json),
+            "2" => Some(ExportFormat::Excel),
+            "3" => Some(ExportFormat::Csv),
+            "4" => Some(ExportFormat::Markdown),
+            _ => None,
+        };
+        match format {
+            Some(format) => {
+                let filter = import_export::export::ExportFilter::default();
+                if let Err(e) = import_export::export::export_todos(format, &filter, None) {
+                    eprintln!("Error exporting todos: {}", e);
+                }
+            }
+            None => println!("Invalid option"),
         }
     }
     // PROMPT GEMINI
@@ -775,10 +3140,6 @@ async fn main() -> Result<(), io::Error> {
             ),
         }
     }
-    // Print version
-    else if cli.release {
-        println!("voido {}", env!("CARGO_PKG_VERSION"));
-    }
     // Pass the API key
     else if let Some(key) = cli.apikey {
         let db = database::DBtodo::new().unwrap();
@@ -786,6 +3147,29 @@ async fn main() -> Result<(), io::Error> {
             eprintln!("Error setting API credentials: {}", e);
         })
     }
+    // Batch add todos from stdin, one per line
+    else if cli.stdin || cli.add.as_ref().is_some_and(|words| words == &["-"]) {
+        match arguments::quick_add::batch_add(io::stdin().lock()) {
+            Ok((added, 0)) => println!("✅ {} todo(s) added", added),
+            Ok((added, skipped)) => println!(
+                "✅ {} todo(s) added, {} line(s) skipped (no todo text)",
+                added, skipped
+            ),
+            Err(e) => eprintln!("Error adding todos: {}", e),
+        }
+    }
+    // Add the todos of a voice memo
+    else if let Some(file) = cli.audio {
+        if let Err(e) = arguments::voice_memo::add_from_audio(&file).await {
+            eprintln!("❌ Could not add the voice memo: {}", e);
+        }
+    }
+    // Add a todo for a link to read or review later
+    else if let Some(link) = cli.from_url {
+        if let Err(e) = arguments::from_url::add_from_url(&link, cli.summarize).await {
+            eprintln!("❌ Could not add the link: {}", e);
+        }
+    }
     // Add new todo
     else if let Some(words) = cli.add {
         let text = words.join(" ");
@@ -801,38 +3185,96 @@ async fn main() -> Result<(), io::Error> {
             }
         }
 
+        // Gemini suggests a due date, unless there is one or the configured default gives one
+        let suggest = cli.suggest_due || configs::GeminiConfig::load().suggest_due;
+        let due = match cli.due {
+            None if suggest && configs::defaults_config().due_offset.is_none() => {
+                ai::confirm_due(&text).await
+            }
+            due => due,
+        };
+
         match arguments::add_todo::add_todo(
             text,
             cli.topic,
             cli.priority,
             cli.owner,
-            cli.due,
+            due,
             desc,
             subtasks,
         ) {
-            Ok(_) => println!("✅ Todo added successfully!"),
+            Ok(id) => {
+                println!("✅ Todo added successfully!");
+                if let Some(context) = cli.context
+                    && let Err(e) = arguments::add_todo::set_context(id as i32, &context)
+                {
+                    eprintln!("Error setting the context: {}", e);
+                }
+                if let Some(url) = cli.url
+                    && let Err(e) = arguments::update_todo::set_external_url(id as i32, &url)
+                {
+                    eprintln!("Error setting the link: {}", e);
+                }
+            }
             Err(e) => eprintln!("Error adding todo: {}", e),
         }
     }
     // Delete todo
     else if let Some(id) = cli.delete {
-        match arguments::delete_todo::remove_todo(id) {
-            Ok(_) => {}
-            Err(e) => eprintln!("Error deleting todo: {}", e),
+        let result = picker::resolve_id(id, "Delete")
+            .and_then(|id| id.map_or(Ok(()), arguments::delete_todo::remove_todo));
+        if let Err(e) = result {
+            eprintln!("Error deleting todo: {}", e);
         }
     }
-    // Update todo status
-    else if let (Some(id), Some(status)) = (cli.update_id, cli.status) {
-        if let Err(e) = arguments::update_todo::update_todo(id, status) {
+    // Update todo status and link
+    else if let Some(id) = cli
+        .update_id
+        .filter(|_| cli.status.is_some() || cli.url.is_some())
+    {
+        let action = match &cli.status {
+            Some(status) => format!("Set status to {}", status),
+            None => "Set the link".to_string(),
+        };
+        let result = picker::resolve_id(id, &action).and_then(|id| {
+            let Some(id) = id else {
+                return Ok(());
+            };
+            if let Some(status) = cli.status {
+                arguments::update_todo::update_todo(id, status)?;
+            }
+            if let Some(url) = cli.url {
+                arguments::update_todo::set_external_url(id, &url)?;
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
             eprintln!("Error updating todo: {}", e);
         }
     }
     // UPDATE USING SHORT FORMAT
     else if let Some(id) = cli.done {
-        if let Err(e) = arguments::update_todo::update_todo(id, "Done".to_string()) {
+        let result = picker::resolve_id(id, "Mark as done").and_then(|id| {
+            id.map_or(Ok(()), |id| {
+                arguments::update_todo::update_todo(id, "Done".to_string())
+            })
+        });
+        if let Err(e) = result {
             eprintln!("Error updating todo: {}", e);
         }
     }
+    // Pin / unpin todos
+    else if let Some(id) = cli.pin {
+        match arguments::update_todo::set_pinned(id, true) {
+            Ok(_) => println!("📌 Todo {} pinned", id),
+            Err(e) => eprintln!("Error pinning todo: {}", e),
+        }
+    } else if let Some(id) = cli.unpin {
+        match arguments::update_todo::set_pinned(id, false) {
+            Ok(_) => println!("✅ Todo {} unpinned", id),
+            Err(e) => eprintln!("Error unpinning todo: {}", e),
+        }
+    }
     // Clear all todos
     else if cli.clear {
         match arguments::delete_todo::clear_todos() {
@@ -858,6 +3300,10 @@ async fn main() -> Result<(), io::Error> {
             Err(e) => eprintln!("Error creating database: {}", e),
         }
     }
+    // A link needs a todo to go on
+    else if cli.url.is_some() {
+        eprintln!("❌ --url goes with --add or --update-id");
+    }
 
     Ok(())
 }
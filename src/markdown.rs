@@ -1,9 +1,114 @@
-use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use crate::text_width;
+use pulldown_cmark::{Alignment, Event, LinkType, Options, Parser, Tag, TagEnd};
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
 
+// A link in the notes: `[label](url)`, `<url>` or a bare URL, which is its own label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteLink {
+    pub label: String,
+    pub url: String,
+}
+
+// A checkbox line of the notes (`- [ ]`, `- [x]`), `line` being where it is in the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteTask {
+    pub line: usize,
+    pub text: String,
+    pub done: bool,
+}
+
+// Where the `[ ]` / `[x]` of a list line starts, and whether it is checked
+fn checkbox_at(line: &str) -> Option<(usize, bool)> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+    let bullet = if rest.starts_with(['-', '*', '+']) {
+        1
+    } else {
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 || !rest[digits..].starts_with(['.', ')']) {
+            return None;
+        }
+        digits + 1
+    };
+    let after = &rest[bullet..];
+    let spaces = after.len() - after.trim_start_matches([' ', '\t']).len();
+    if spaces == 0 {
+        return None;
+    }
+    let at = indent + bullet + spaces;
+    let done = match line.get(at..at + 3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    // A box needs text after it to be one
+    let text = &line[at + 3..];
+    (text.starts_with([' ', '\t']) && !text.trim().is_empty()).then_some((at, done))
+}
+
+// Every checkbox of the notes in order, code blocks are left alone
+pub fn note_tasks(notes: &str) -> Vec<NoteTask> {
+    let mut in_fence = false;
+    notes
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+                in_fence = !in_fence;
+                return None;
+            }
+            if in_fence {
+                return None;
+            }
+            let (at, done) = checkbox_at(line)?;
+            Some(NoteTask {
+                line: index,
+                text: line[at + 3..].trim().to_string(),
+                done,
+            })
+        })
+        .collect()
+}
+
+// The notes with the checkbox on `line` ticked or cleared, the rest of the text untouched
+pub fn toggle_note_task(notes: &str, line: usize) -> Option<String> {
+    if !note_tasks(notes).iter().any(|task| task.line == line) {
+        return None;
+    }
+    Some(
+        notes
+            .split_inclusive('\n')
+            .enumerate()
+            .map(
+                |(index, text)| match checkbox_at(text).filter(|_| index == line) {
+                    Some((at, done)) => format!(
+                        "{}{}{}",
+                        &text[..at],
+                        if done { "[ ]" } else { "[x]" },
+                        &text[at + 3..]
+                    ),
+                    None => text.to_string(),
+                },
+            )
+            .collect(),
+    )
+}
+
+// Bare http(s) URLs in plain text, without the punctuation around them
+fn bare_urls(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .filter_map(|word| {
+            let start = word.find("https://").or_else(|| word.find("http://"))?;
+            let url = word[start..].trim_end_matches(|c: char| ".,;:!?)]}'\"".contains(c));
+            url.contains("://").then_some(url)
+        })
+        .filter(|url| !url.ends_with("://"))
+        .collect()
+}
+
 pub struct MarkdownRenderer {
     pub accent_color: Color,
     pub text_color: Color,
@@ -12,6 +117,18 @@ pub struct MarkdownRenderer {
     pub italic_color: Color,
     pub code_color: Color,
     pub heading_color: Color,
+    // Columns the notes are drawn in, tables are narrowed to fit
+    pub width: Option<usize>,
+    // Where links and images point, dimmed after their text: `[docs](→ https://...)`
+    pub show_urls: bool,
+}
+
+// A table being read: the alignment of each column and the text of each cell
+#[derive(Debug, Default)]
+struct TableBuilder {
+    alignments: Vec<Alignment>,
+    rows: Vec<Vec<String>>,
+    has_header: bool,
 }
 
 impl Default for MarkdownRenderer {
@@ -24,6 +141,8 @@ impl Default for MarkdownRenderer {
             italic_color: Color::Rgb(180, 140, 220),
             code_color: Color::Rgb(120, 220, 150),
             heading_color: Color::Rgb(220, 180, 100),
+            width: None,
+            show_urls: true,
         }
     }
 }
@@ -33,20 +152,77 @@ impl MarkdownRenderer {
         Self::default()
     }
 
+    pub fn with_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn with_urls(mut self, show_urls: bool) -> Self {
+        self.show_urls = show_urls;
+        self
+    }
+
     pub fn render(&self, markdown: &str) -> Vec<Line> {
         if markdown.is_empty() {
             return vec![Line::from("")];
         }
 
-        let parser = Parser::new(markdown);
+        let parser = Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_TASKLISTS);
         let mut lines = Vec::new();
+        let mut table: Option<TableBuilder> = None;
+        // Open links and images: where they point, whether it is an image and where its text starts.
+        // None for autolinks, whose text is already the URL
+        let mut targets: Vec<Option<(String, bool, usize)>> = Vec::new();
+        let dimmed = Style::default().fg(self.secondary_color);
         let mut current_line = Vec::new();
         let mut style_stack = Vec::new();
         let mut in_code_block = false;
         let mut code_block_lang = String::new();
 
         for event in parser {
+            // Tables are read whole, then drawn with their columns lined up
+            if let Some(builder) = &mut table {
+                match event {
+                    Event::End(TagEnd::Table) => {
+                        lines.extend(self.render_table(builder));
+                        lines.push(Line::from(""));
+                        table = None;
+                    }
+                    Event::Start(Tag::TableHead) | Event::Start(Tag::TableRow) => {
+                        builder.rows.push(Vec::new())
+                    }
+                    Event::End(TagEnd::TableHead) => builder.has_header = true,
+                    Event::Start(Tag::TableCell) => {
+                        if let Some(row) = builder.rows.last_mut() {
+                            row.push(String::new());
+                        }
+                    }
+                    Event::Text(text) | Event::Code(text) => {
+                        if let Some(cell) = builder.rows.last_mut().and_then(|row| row.last_mut()) {
+                            cell.push_str(&text);
+                        }
+                    }
+                    Event::SoftBreak | Event::HardBreak => {
+                        if let Some(cell) = builder.rows.last_mut().and_then(|row| row.last_mut()) {
+                            cell.push(' ');
+                        }
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
             match event {
+                Event::Start(Tag::Table(alignments)) => {
+                    if !current_line.is_empty() {
+                        lines.push(Line::from(current_line.clone()));
+                        current_line.clear();
+                    }
+                    table = Some(TableBuilder {
+                        alignments,
+                        ..TableBuilder::default()
+                    });
+                }
                 Event::Start(tag) => {
                     let style = self.get_style_for_tag(&tag);
                     style_stack.push(style);
@@ -99,6 +275,21 @@ impl MarkdownRenderer {
                             current_line
                                 .push(Span::styled("• ", Style::default().fg(self.accent_color)));
                         }
+                        Tag::Link {
+                            link_type: LinkType::Autolink | LinkType::Email,
+                            ..
+                        } => targets.push(None),
+                        Tag::Link { dest_url, .. } => {
+                            if self.show_urls {
+                                current_line.push(Span::styled("[", dimmed));
+                            }
+                            targets.push(Some((dest_url.to_string(), false, current_line.len())));
+                        }
+                        Tag::Image { dest_url, .. } => {
+                            let opener = if self.show_urls { "[🖼 " } else { "🖼 " };
+                            current_line.push(Span::styled(opener, dimmed));
+                            targets.push(Some((dest_url.to_string(), true, current_line.len())));
+                        }
                         Tag::BlockQuote(_) => {
                             current_line.push(Span::styled(
                                 "│ ",
@@ -112,6 +303,25 @@ impl MarkdownRenderer {
                     style_stack.pop();
 
                     match tag_end {
+                        TagEnd::Link | TagEnd::Image => {
+                            if let Some(Some((url, image, start))) = targets.pop() {
+                                // An image without alt text still shows where it is
+                                if image && current_line.len() <= start {
+                                    current_line.push(Span::styled(
+                                        "image",
+                                        dimmed.add_modifier(Modifier::ITALIC),
+                                    ));
+                                }
+                                if self.show_urls {
+                                    let close = if url.is_empty() {
+                                        "]".to_string()
+                                    } else {
+                                        format!("](→ {})", url)
+                                    };
+                                    current_line.push(Span::styled(close, dimmed));
+                                }
+                            }
+                        }
                         TagEnd::Heading(_) => {
                             lines.push(Line::from(current_line.clone()));
                             current_line.clear();
@@ -203,6 +413,17 @@ impl MarkdownRenderer {
                 Event::SoftBreak => {
                     current_line.push(Span::raw(" "));
                 }
+                // A checkbox takes the place of the item's bullet
+                Event::TaskListMarker(checked) => {
+                    if current_line.last().is_some_and(|span| span.content == "• ") {
+                        current_line.pop();
+                    }
+                    current_line.push(if checked {
+                        Span::styled("☑ ", Style::default().fg(self.accent_color))
+                    } else {
+                        Span::styled("☐ ", Style::default().fg(self.secondary_color))
+                    });
+                }
                 Event::HardBreak => {
                     lines.push(Line::from(current_line.clone()));
                     current_line.clear();
@@ -239,6 +460,128 @@ impl MarkdownRenderer {
         lines
     }
 
+    // A table in box-drawing borders, the widest columns give way when it doesn't fit
+    fn render_table(&self, table: &TableBuilder) -> Vec<Line<'static>> {
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return Vec::new();
+        }
+        let mut widths = vec![1; columns];
+        for row in &table.rows {
+            for (column, cell) in row.iter().enumerate() {
+                widths[column] = widths[column].max(text_width::width(cell.trim()));
+            }
+        }
+        // Each column takes a border and a space on both sides of its text
+        if let Some(width) = self.width {
+            let room = width.saturating_sub(3 * columns + 1);
+            while widths.iter().sum::<usize>() > room {
+                let Some(widest) =
+                    (0..columns).min_by_key(|&column| std::cmp::Reverse(widths[column]))
+                else {
+                    break;
+                };
+                if widths[widest] <= 3 {
+                    break;
+                }
+                widths[widest] -= 1;
+            }
+        }
+
+        let border = Style::default().fg(self.secondary_color);
+        let rule = |left: &str, middle: &str, right: &str| {
+            let segments = widths
+                .iter()
+                .map(|width| "─".repeat(width + 2))
+                .collect::<Vec<_>>();
+            Line::from(Span::styled(
+                format!("{}{}{}", left, segments.join(middle), right),
+                border,
+            ))
+        };
+
+        let mut lines = vec![rule("┌", "┬", "┐")];
+        for (index, row) in table.rows.iter().enumerate() {
+            let header = index == 0 && table.has_header;
+            let style = if header {
+                Style::default()
+                    .fg(self.heading_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(self.text_color)
+            };
+            let mut spans = vec![Span::styled("│", border)];
+            for (column, width) in widths.iter().enumerate() {
+                let cell = row.get(column).map_or("", |cell| cell.trim());
+                let text = if text_width::width(cell) > *width {
+                    let (head, _) = text_width::split_at_width(cell, width - 1);
+                    format!("{}…", head)
+                } else {
+                    cell.to_string()
+                };
+                let pad = width.saturating_sub(text_width::width(&text));
+                let (left, right) = match table.alignments.get(column) {
+                    Some(Alignment::Right) => (pad, 0),
+                    Some(Alignment::Center) => (pad / 2, pad - pad / 2),
+                    _ => (0, pad),
+                };
+                spans.push(Span::styled(
+                    format!(" {}{}{} ", " ".repeat(left), text, " ".repeat(right)),
+                    style,
+                ));
+                spans.push(Span::styled("│", border));
+            }
+            lines.push(Line::from(spans));
+            if header {
+                lines.push(rule("├", "┼", "┤"));
+            }
+        }
+        lines.push(rule("└", "┴", "┘"));
+        lines
+    }
+
+    // Every link in the notes in reading order, each URL once
+    pub fn links(&self, markdown: &str) -> Vec<NoteLink> {
+        let mut links: Vec<NoteLink> = Vec::new();
+        let mut add = |label: &str, url: &str| {
+            if !url.is_empty() && !links.iter().any(|link| link.url == url) {
+                let label = match label.trim() {
+                    "" => url,
+                    label => label,
+                };
+                links.push(NoteLink {
+                    label: label.to_string(),
+                    url: url.to_string(),
+                });
+            }
+        };
+
+        // The link being read, its URL and the text so far
+        let mut open: Option<(String, String)> = None;
+        for event in Parser::new(markdown) {
+            match event {
+                Event::Start(Tag::Link { dest_url, .. }) => {
+                    open = Some((dest_url.to_string(), String::new()));
+                }
+                Event::End(TagEnd::Link) => {
+                    if let Some((url, label)) = open.take() {
+                        add(&label, &url);
+                    }
+                }
+                Event::Text(text) | Event::Code(text) => match &mut open {
+                    Some((_, label)) => label.push_str(&text),
+                    None => {
+                        for url in bare_urls(&text) {
+                            add(url, url);
+                        }
+                    }
+                },
+                _ => {}
+            }
+        }
+        links
+    }
+
     fn get_style_for_tag(&self, tag: &Tag) -> Style {
         match tag {
             Tag::Emphasis => Style::default()
@@ -259,6 +602,10 @@ impl MarkdownRenderer {
             Tag::BlockQuote(_) => Style::default()
                 .fg(self.secondary_color)
                 .add_modifier(Modifier::ITALIC),
+            // Alt text stands in for the image
+            Tag::Image { .. } => Style::default()
+                .fg(self.secondary_color)
+                .add_modifier(Modifier::ITALIC),
             _ => Style::default().fg(self.text_color),
         }
     }
@@ -440,6 +787,121 @@ mod tests {
         assert_eq!(lines.len(), 1);
     }
 
+    #[test]
+    fn test_links_in_notes() {
+        let renderer = MarkdownRenderer::new();
+        let markdown = "See [the **spec**](https://example.com/spec) and <https://voido.dev>.\n\n\
+            Ticket: (https://tracker.example.com/T-12), again https://example.com/spec.";
+        assert_eq!(
+            renderer.links(markdown),
+            vec![
+                NoteLink {
+                    label: "the spec".to_string(),
+                    url: "https://example.com/spec".to_string(),
+                },
+                NoteLink {
+                    label: "https://voido.dev".to_string(),
+                    url: "https://voido.dev".to_string(),
+                },
+                NoteLink {
+                    label: "https://tracker.example.com/T-12".to_string(),
+                    url: "https://tracker.example.com/T-12".to_string(),
+                },
+            ]
+        );
+        assert!(renderer.links("no links, just http:// here").is_empty());
+    }
+
+    #[test]
+    fn test_table_lines_up_and_fits() {
+        let markdown =
+            "Plans:\n\n| Plan | Price |\n|:-----|------:|\n| Free | 0 |\n| Team plan | 12.50 |\n";
+        let text = |renderer: MarkdownRenderer| {
+            renderer
+                .render(markdown)
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            text(MarkdownRenderer::new()),
+            vec![
+                "Plans:",
+                "",
+                "┌───────────┬───────┐",
+                "│ Plan      │ Price │",
+                "├───────────┼───────┤",
+                "│ Free      │     0 │",
+                "│ Team plan │ 12.50 │",
+                "└───────────┴───────┘",
+            ]
+        );
+        // 16 columns leave 9 for the text, the wide column gives way
+        assert_eq!(
+            text(MarkdownRenderer::new().with_width(16))[5],
+            "│ Free │     0 │"
+        );
+        assert_eq!(
+            text(MarkdownRenderer::new().with_width(16))[6],
+            "│ Tea… │ 12.50 │"
+        );
+    }
+
+    #[test]
+    fn test_note_tasks_toggle_in_place() {
+        let notes = "Launch:\n- [ ] Write the post\r\n  * [X] Book the room\n- [ ]\n```\n- [ ] not a task\n```\n1. [ ] Ship it";
+        let tasks = note_tasks(notes);
+        assert_eq!(
+            tasks
+                .iter()
+                .map(|task| (task.line, task.text.as_str(), task.done))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, "Write the post", false),
+                (2, "Book the room", true),
+                (7, "Ship it", false)
+            ]
+        );
+
+        let toggled = toggle_note_task(notes, 1).unwrap();
+        assert!(toggled.starts_with("Launch:\n- [x] Write the post\r\n  * [X]"));
+        let toggled = toggle_note_task(&toggled, 2).unwrap();
+        assert!(toggled.contains("  * [ ] Book the room\n"));
+        assert_eq!(toggle_note_task(notes, 0), None);
+        assert_eq!(toggle_note_task(notes, 5), None);
+
+        let renderer = MarkdownRenderer::new();
+        let rendered = renderer.render("- [x] Done\n- [ ] Open");
+        assert_eq!(rendered[0].spans[0].content, "☑ ");
+        assert_eq!(rendered[1].spans[0].content, "☐ ");
+    }
+
+    #[test]
+    fn test_link_and_image_destinations() {
+        let markdown =
+            "See [the spec](https://example.com/spec), ![](diagram.png) and <https://a.io>";
+        let text = |renderer: MarkdownRenderer| {
+            renderer.render(markdown)[0]
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        };
+        assert_eq!(
+            text(MarkdownRenderer::new()),
+            "See [the spec](→ https://example.com/spec), [🖼 image](→ diagram.png) and https://a.io"
+        );
+        assert_eq!(
+            text(MarkdownRenderer::new().with_urls(false)),
+            "See the spec, 🖼 image and https://a.io"
+        );
+    }
+
     #[test]
     fn test_code_block() {
         let renderer = MarkdownRenderer::new();
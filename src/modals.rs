@@ -1,17 +1,35 @@
 use ratatui::layout::Alignment;
 use ratatui::prelude::Stylize;
 use ratatui::text::Span;
-use ratatui::widgets::{List, ListItem, ListState, Padding};
+use ratatui::widgets::{
+    List, ListItem, ListState, Padding, Scrollbar, ScrollbarOrientation, ScrollbarState,
+};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::Line,
-    widgets::{Block, Borders, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-use crate::arguments::models::Todo;
-use crate::markdown::MarkdownRenderer;
+use crate::arguments::dependencies::GraphLine;
+use crate::arguments::habits::{self, Habit};
+use crate::arguments::models::{Checklist, Comment, Todo};
+use crate::arguments::report;
+use crate::colors::{Theme, topic_color};
+use crate::configs::{self, WorkflowConfig};
+use crate::detail_search::{self, DetailSearch, Pane};
+use crate::keymap::{KeyContext, search_bindings};
+use crate::markdown::{MarkdownRenderer, NoteLink, NoteTask};
+use crate::settings::settings_items;
+use crate::triage::Triage;
+use crate::ui::{truncate_text, wrap_text};
+
+// Below this width the detail modal stacks its panels vertically
+pub const STACKED_MODAL_WIDTH: u16 = 100;
+
+// The title and blank line above the read-only notes
+const NOTES_HEADER_LINES: usize = 2;
 
 // Dynamic sizing helper function
 pub fn dynamic_rect(width_percent: u16, height_percent: u16, area: Rect) -> Rect {
@@ -31,15 +49,22 @@ pub fn draw_todo_modal(
     state: &mut ListState,
     editing_notes: bool,
     notes_input: &crate::search::InputField,
-    notes_scroll_offset: u16,
+    notes_scroll_offset: &mut u16,
     notes_preview_mode: bool,
+    details_scroll_offset: &mut u16,
+    comments: &[Comment],
+    comments_scroll: &mut u16,
+    search: &mut DetailSearch,
+    related: &[&Todo],
+    related_selected: Option<usize>,
 ) {
-    // Elegant purple color palette
-    let background = Color::Rgb(25, 15, 30); // Deep purple
-    let accent = Color::Rgb(150, 80, 220); // Vibrant purple
-    let border = Color::Rgb(180, 140, 220); // Soft lavender
-    let text_primary = Color::Rgb(230, 220, 240); // Light lavender
-    let text_secondary = Color::Rgb(200, 180, 220); // Muted lavender
+    // Color palette from the configured theme
+    let theme = Theme::current();
+    let background = theme.background;
+    let accent = theme.accent;
+    let border = theme.border;
+    let text_primary = theme.text_primary;
+    let text_secondary = theme.text_secondary;
 
     // Main modal block with elegant styling
     let block = Block::default()
@@ -49,15 +74,24 @@ pub fn draw_todo_modal(
         .style(Style::default().bg(background).fg(text_primary));
 
     let area = centered_rect(100, 100, area);
-    f.render_widget(block, area);
 
-    let inner_area = area.inner(Margin {
-        vertical: 3,
-        horizontal: 4,
-    });
+    // Stack the panels vertically when the terminal is too narrow for side-by-side
+    let stacked = area.width < STACKED_MODAL_WIDTH;
+
+    let inner_area = if stacked {
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 1,
+        })
+    } else {
+        area.inner(Margin {
+            vertical: 3,
+            horizontal: 4,
+        })
+    };
 
     // Create styled text with purple color scheme and better spacing
-    let text = vec![
+    let mut text = vec![
         Line::from(vec![
             "ID: ".fg(text_secondary),
             todo.id.to_string().bold().fg(accent),
@@ -77,7 +111,7 @@ pub fn draw_todo_modal(
         ]),
         Line::from(vec![
             "TOPIC: ".fg(text_secondary),
-            todo.topic.as_str().bold().fg(accent),
+            todo.topic.as_str().bold().fg(topic_color(&todo.topic)),
         ]),
         Line::from(vec![
             "STATUS: ".fg(text_secondary),
@@ -89,70 +123,227 @@ pub fn draw_todo_modal(
                 _ => todo.status.as_str().bold().fg(accent),
             },
         ]),
+    ];
+    if let Some(context) = &todo.context {
+        text.push(Line::from(vec![
+            "CONTEXT: ".fg(text_secondary),
+            format!("@{}", context).bold().fg(text_primary),
+        ]));
+    }
+    if let Some(url) = &todo.external_url {
+        text.push(Line::from(vec![
+            "LINK (b: open): ".fg(text_secondary),
+            url.as_str().underlined().fg(accent),
+        ]));
+    }
+    if let Some(waiting_on) = &todo.waiting_on {
+        text.push(Line::from(vec![
+            "WAITING ON: ".fg(text_secondary),
+            waiting_on.as_str().bold().fg(accent),
+        ]));
+    }
+    // Where the workflow lets the todo go from here
+    if let Some(next) = WorkflowConfig::load().next(&todo.status) {
+        text.push(Line::from(vec![
+            "NEXT: ".fg(text_secondary),
+            next.join(", ").fg(text_primary),
+        ]));
+    }
+    text.extend([
         Line::from(vec![
             "CREATED: ".fg(text_secondary),
-            todo.date_added.as_str().bold().fg(text_primary),
+            todo.created_display().bold().fg(text_primary),
         ]),
         Line::from(vec![
             "DUE: ".fg(text_secondary),
-            todo.due.as_str().bold().fg(text_primary),
+            todo.due_display().bold().fg(text_primary),
         ]),
-        Line::from(vec![
-            "TODO: ".fg(text_secondary),
-            todo.text.as_str().bold().fg(text_primary),
-        ]),
-        Line::from(vec![
-            "DESCRIPTION: ".fg(text_secondary),
-            todo.desc.as_str().bold().fg(text_primary),
-        ]),
-    ];
+    ]);
 
-    // Paragraph with subtle styling
-    let paragraph = Paragraph::new(text)
-        .wrap(Wrap { trim: true })
-        .block(Block::default().style(Style::default().bg(background)));
+    let (main_area, subtasks_area, notes_area) = if stacked {
+        // Narrow terminals: details, subtasks and notes stacked on top of each other
+        let stacked_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(30),
+                    Constraint::Percentage(35),
+                ]
+                .as_ref(),
+            )
+            .split(inner_area);
 
-    // Split the inner area horizontally first with better proportions and spacing
-    let horizontal_layout = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(
-            [
-                Constraint::Percentage(60),
-                Constraint::Min(2),
-                Constraint::Percentage(38),
-            ]
-            .as_ref(),
-        )
-        .split(inner_area);
+        (stacked_layout[0], stacked_layout[1], stacked_layout[2])
+    } else {
+        // Split the inner area horizontally first with better proportions and spacing
+        let horizontal_layout = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Percentage(60),
+                    Constraint::Min(2),
+                    Constraint::Percentage(38),
+                ]
+                .as_ref(),
+            )
+            .split(inner_area);
 
-    // Split the left area vertically for main content and subtasks with more balanced spacing
-    let left_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints(
-            [
-                Constraint::Percentage(50),
-                Constraint::Min(1),
-                Constraint::Percentage(48),
-            ]
-            .as_ref(),
-        )
-        .split(horizontal_layout[0]);
+        // Split the left area vertically for main content and subtasks with more balanced spacing
+        let left_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(50),
+                    Constraint::Min(1),
+                    Constraint::Percentage(48),
+                ]
+                .as_ref(),
+            )
+            .split(horizontal_layout[0]);
+
+        (left_layout[0], left_layout[2], horizontal_layout[2])
+    };
 
     // Render main todo information in the top-left with padding
-    let main_content_area = left_layout[0].inner(Margin {
-        horizontal: 2,
-        vertical: 1,
+    let main_content_area = main_area.inner(Margin {
+        horizontal: if stacked { 1 } else { 2 },
+        vertical: if stacked { 0 } else { 1 },
     });
+
+    // The long fields are wrapped here so the pane knows how far it can scroll
+    let text_width = main_content_area.width.saturating_sub(2) as usize;
+    for (label, value) in [("TODO:", &todo.text), ("DESCRIPTION:", &todo.desc)] {
+        text.push(Line::from(label.fg(text_secondary)));
+        text.extend(
+            value
+                .lines()
+                .flat_map(|line| wrap_text(line, text_width))
+                .map(|line| Line::from(line.bold().fg(text_primary))),
+        );
+    }
+
+    // Open todos like this one, R picks one and Enter opens it
+    if !related.is_empty() {
+        text.push(Line::from(""));
+        text.push(Line::from(
+            "RELATED (R: pick, Enter: open):".fg(text_secondary),
+        ));
+        for (index, other) in related.iter().enumerate() {
+            let line = truncate_text(
+                &format!("#{} {} [{}]", other.id, other.text, other.status),
+                text_width.saturating_sub(2),
+            );
+            text.push(if related_selected == Some(index) {
+                Line::from(vec!["▶ ".fg(accent), line.bold().fg(accent)])
+            } else {
+                Line::from(vec!["  ".into(), line.fg(text_primary)])
+            });
+        }
+    }
+
+    // Rendered once, the search looks through it too. Tables fit inside the margin, border and
+    // padding around the notes
+    let markdown_renderer = MarkdownRenderer::new()
+        .with_width(notes_area.width.saturating_sub(6) as usize)
+        .with_urls(configs::notes_config().show_urls);
+    let mut rendered_notes = if editing_notes || todo.notes.trim().is_empty() {
+        Vec::new()
+    } else {
+        markdown_renderer.render(&todo.notes)
+    };
+
+    // Lines matching the `/` search, in the order `n` steps through them
+    let mut found: Vec<(Pane, usize)> = Vec::new();
+    if search.is_active() {
+        let query = search.query.as_str();
+        found.extend(
+            text.iter()
+                .enumerate()
+                .filter(|(_, line)| detail_search::matches(line, query))
+                .map(|(index, _)| (Pane::Details, index)),
+        );
+        found.extend(
+            rendered_notes
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| detail_search::matches(line, query))
+                .map(|(index, _)| (Pane::Notes, index + NOTES_HEADER_LINES)),
+        );
+        found.extend(
+            todo.subtasks
+                .iter()
+                .enumerate()
+                .filter(|(_, subtask)| {
+                    !detail_search::find(&subtask.text, query).is_empty()
+                        || !detail_search::find(&subtask.note, query).is_empty()
+                })
+                .map(|(index, _)| (Pane::Subtasks, index)),
+        );
+    }
+    search.count = found.len();
+    search.current = search.current.min(found.len().saturating_sub(1));
+    let current = found.get(search.current).copied();
+    if search.jump {
+        match current {
+            Some((Pane::Details, index)) => *details_scroll_offset = index.saturating_sub(1) as u16,
+            Some((Pane::Notes, index)) => *notes_scroll_offset = index.saturating_sub(1) as u16,
+            Some((Pane::Subtasks, index)) => state.select(Some(index)),
+            None => {}
+        }
+        search.jump = false;
+    }
+    let query = search.query.as_str();
+
+    let block = if search.is_active() {
+        block.title(format!(" /{}  {} ", query, search.position()))
+    } else {
+        block
+    };
+    f.render_widget(block, area);
+
+    let text: Vec<Line> = text
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            detail_search::mark(line, query, &found, current, (Pane::Details, index))
+        })
+        .collect();
+
+    // Scroll the details with J/K, the offset is clamped to the content
+    let max_scroll = text.len().saturating_sub(main_content_area.height as usize) as u16;
+    *details_scroll_offset = (*details_scroll_offset).min(max_scroll);
+    let paragraph = Paragraph::new(text)
+        .scroll((*details_scroll_offset, 0))
+        .block(Block::default().style(Style::default().bg(background)));
     f.render_widget(paragraph, main_content_area);
+    if max_scroll > 0 {
+        draw_scrollbar(
+            f,
+            main_content_area,
+            max_scroll as usize + 1,
+            *details_scroll_offset as usize,
+            border,
+        );
+    }
 
     // Create notes section in the right panel with better spacing
-    let notes_area = horizontal_layout[2].inner(Margin {
+    let notes_area = notes_area.inner(Margin {
         horizontal: 1,
         vertical: 0,
     });
 
-    // Create markdown renderer
-    let markdown_renderer = MarkdownRenderer::new();
+    // The comment thread shares the notes panel when the todo has one
+    let notes_area = if comments.is_empty() {
+        notes_area
+    } else {
+        let notes_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+            .split(notes_area);
+        draw_comments(f, notes_layout[1], comments, comments_scroll, &theme);
+        notes_layout[0]
+    };
 
     if editing_notes {
         // Create a block for the notes editing area
@@ -220,7 +411,7 @@ pub fn draw_todo_modal(
         } else {
             0
         };
-        let scroll_offset = (notes_scroll_offset as usize).min(max_scroll);
+        let scroll_offset = (*notes_scroll_offset as usize).min(max_scroll);
 
         // Get visible lines based on scroll offset
         let start_line = scroll_offset;
@@ -252,6 +443,7 @@ pub fn draw_todo_modal(
         }
     } else {
         // Show read-only notes with full markdown rendering
+        // NOTES_HEADER_LINES lines before the notes themselves
         let mut notes_lines = vec![
             Line::from(vec!["NOTES (N to edit): ".fg(text_secondary)]),
             Line::from(""),
@@ -295,10 +487,15 @@ pub fn draw_todo_modal(
                 ]),
             ]);
         } else {
-            // Render markdown content
-            let rendered_markdown = markdown_renderer.render(&todo.notes);
-            notes_lines.extend(rendered_markdown);
+            notes_lines.extend(std::mem::take(&mut rendered_notes));
         }
+        let notes_lines: Vec<Line> = notes_lines
+            .into_iter()
+            .enumerate()
+            .map(|(index, line)| {
+                detail_search::mark(line, query, &found, current, (Pane::Notes, index))
+            })
+            .collect();
 
         // Calculate visible area for read-only mode
         let notes_block = Block::default()
@@ -318,7 +515,7 @@ pub fn draw_todo_modal(
         } else {
             0
         };
-        let scroll_offset = (notes_scroll_offset as usize).min(max_scroll);
+        let scroll_offset = (*notes_scroll_offset as usize).min(max_scroll);
 
         // Get visible lines
         let start_line = scroll_offset;
@@ -351,17 +548,25 @@ pub fn draw_todo_modal(
     }
 
     // Create a list for subtasks with better spacing
+    let today = chrono::Local::now().date_naive();
+    let selected_subtask = state.selected();
     let subtask_items: Vec<ListItem> = todo
         .subtasks
         .iter()
         .enumerate()
         .map(|(index, subtask)| {
+            // Owner and due date, red once an open subtask is overdue
+            let overdue = !subtask.is_done() && subtask.due.is_some_and(|due| due < today);
+            let details = match subtask.details() {
+                details if details.is_empty() => String::new(),
+                details => format!("  {}", details),
+            };
             let line = Line::from(vec![
                 Span::styled(
                     format!("{}. ", index + 1),
                     Style::default().fg(Color::Rgb(180, 140, 220)),
                 ),
-                if subtask.status == "Done" || subtask.status == "Completed" {
+                if subtask.is_done() {
                     Span::styled(
                         subtask.text.as_str(),
                         Style::default()
@@ -371,7 +576,38 @@ pub fn draw_todo_modal(
                 } else {
                     Span::styled(subtask.text.as_str(), Style::default().fg(Color::Red))
                 },
+                Span::styled(
+                    details,
+                    Style::default().fg(if overdue {
+                        Color::Red
+                    } else {
+                        Color::Rgb(220, 180, 100)
+                    }),
+                ),
+                // ID for the `voido subtask` commands
+                Span::styled(
+                    format!("  #{}", subtask.subtask_id),
+                    Style::default().fg(text_secondary),
+                ),
+                // Notes are marked here and shown in full under the selected subtask
+                Span::styled(
+                    if subtask.note.is_empty() { "" } else { "  ✎" },
+                    Style::default().fg(text_secondary),
+                ),
             ]);
+            let line = detail_search::mark(line, query, &found, current, (Pane::Subtasks, index));
+            if selected_subtask == Some(index) && !subtask.note.is_empty() {
+                let note = Line::from(Span::styled(
+                    format!("     {}", subtask.note),
+                    Style::default()
+                        .fg(text_secondary)
+                        .add_modifier(Modifier::ITALIC),
+                ));
+                return ListItem::new(vec![
+                    line,
+                    detail_search::mark(note, query, &found, current, (Pane::Subtasks, index)),
+                ]);
+            }
             ListItem::new(line)
         })
         .collect();
@@ -396,7 +632,227 @@ pub fn draw_todo_modal(
         .repeat_highlight_symbol(true);
 
     // Render subtasks in the bottom-left with proper spacing
-    f.render_stateful_widget(subtask_list, left_layout[2], state);
+    f.render_stateful_widget(subtask_list, subtasks_area, state);
+
+    // The list scrolls with the selection, the scrollbar shows where it is
+    let visible_subtasks = subtasks_area.height.saturating_sub(4) as usize;
+    if todo.subtasks.len() > visible_subtasks {
+        draw_scrollbar(
+            f,
+            subtasks_area.inner(Margin {
+                horizontal: 0,
+                vertical: 1,
+            }),
+            todo.subtasks.len(),
+            state.selected().unwrap_or(0),
+            border,
+        );
+    }
+}
+
+// FOCUS VIEW: one todo on the whole screen, its text, subtasks and rendered notes in a centered column
+pub fn draw_focus_view(
+    f: &mut Frame,
+    area: Rect,
+    todo: &Todo,
+    selected_subtask: Option<usize>,
+    scroll: &mut u16,
+) {
+    let theme = Theme::current();
+    f.render_widget(
+        Block::default().style(Style::default().bg(theme.background)),
+        area,
+    );
+
+    // A readable line length, centered however wide the terminal is
+    let width = area.width.saturating_sub(4).min(90);
+    let column = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + 1,
+        width,
+        height: area.height.saturating_sub(3),
+    };
+    let text_width = width.saturating_sub(2) as usize;
+
+    let mut lines: Vec<Line> = wrap_text(&todo.text, text_width)
+        .into_iter()
+        .map(|line| {
+            Line::from(Span::styled(
+                line,
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        })
+        .collect();
+    let mut meta = vec![
+        todo.priority.clone(),
+        todo.status.clone(),
+        todo.topic.clone(),
+    ];
+    if todo.due.is_some() {
+        meta.push(format!("due {}", todo.due_display()));
+    }
+    lines.push(Line::from(Span::styled(
+        meta.join("  ·  "),
+        Style::default().fg(theme.text_secondary),
+    )));
+
+    if !todo.desc.trim().is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(
+            todo.desc
+                .lines()
+                .flat_map(|line| wrap_text(line, text_width))
+                .map(|line| {
+                    Line::from(Span::styled(line, Style::default().fg(theme.text_primary)))
+                }),
+        );
+    }
+
+    // Remember where the selected subtask is so it can be scrolled into view
+    let mut selected_line = None;
+    if !todo.subtasks.is_empty() {
+        let done = todo
+            .subtasks
+            .iter()
+            .filter(|subtask| subtask.is_done())
+            .count();
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("Subtasks {}/{}", done, todo.subtasks.len()),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (index, subtask) in todo.subtasks.iter().enumerate() {
+            let selected = selected_subtask == Some(index);
+            if selected {
+                selected_line = Some(lines.len());
+            }
+            let mut style = Style::default().fg(theme.text_primary);
+            if subtask.is_done() {
+                style = style
+                    .fg(theme.text_secondary)
+                    .add_modifier(Modifier::CROSSED_OUT);
+            }
+            if selected {
+                style = style.bg(theme.selection).add_modifier(Modifier::BOLD);
+            }
+            let checkbox = if subtask.is_done() { "[x] " } else { "[ ] " };
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}{}",
+                    checkbox,
+                    truncate_text(&subtask.text, text_width.saturating_sub(4).max(1))
+                ),
+                style,
+            )));
+        }
+    }
+
+    let markdown_renderer = MarkdownRenderer::new()
+        .with_width(column.width as usize)
+        .with_urls(configs::notes_config().show_urls);
+    if !todo.notes.trim().is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(markdown_renderer.render(&todo.notes));
+    }
+
+    // Notes lines can still wrap, count the rows they take
+    let rows = lines
+        .iter()
+        .map(|line| line.width().div_ceil(column.width.max(1) as usize).max(1))
+        .sum::<usize>();
+    let max_scroll = rows.saturating_sub(column.height as usize) as u16;
+    if let Some(line) = selected_line.map(|line| line as u16) {
+        if line < *scroll {
+            *scroll = line;
+        } else if line >= *scroll + column.height {
+            *scroll = line + 1 - column.height;
+        }
+    }
+    *scroll = (*scroll).min(max_scroll);
+    f.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((*scroll, 0)),
+        column,
+    );
+
+    let hints = Line::from(Span::styled(
+        "j/k: subtask  ·  d/Space: done  ·  J/K: scroll  ·  Esc: back",
+        Style::default().fg(theme.text_secondary),
+    ))
+    .centered();
+    f.render_widget(
+        Paragraph::new(hints),
+        Rect {
+            y: area.y + area.height.saturating_sub(1),
+            height: 1.min(area.height),
+            ..area
+        },
+    );
+}
+
+// Vertical scrollbar on the right edge of `area`
+// COMMENT THREAD: author and time over each comment, oldest first, scrolled with [ and ]
+fn draw_comments(f: &mut Frame, area: Rect, comments: &[Comment], scroll: &mut u16, theme: &Theme) {
+    let block = Block::default()
+        .title(format!(" Comments ({}) ", comments.len()))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(theme.border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(Style::default().bg(theme.background).fg(theme.text_primary))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+
+    let width = inner.width.saturating_sub(1) as usize;
+    let mut lines = Vec::new();
+    for (index, comment) in comments.iter().enumerate() {
+        if index > 0 {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(vec![
+            comment.author.clone().bold().fg(theme.accent),
+            format!(" · {}", comment.when()).fg(theme.text_secondary),
+        ]));
+        lines.extend(
+            comment
+                .text
+                .lines()
+                .flat_map(|line| wrap_text(line, width))
+                .map(|line| Line::from(line.fg(theme.text_primary))),
+        );
+    }
+
+    let max_scroll = lines.len().saturating_sub(inner.height as usize) as u16;
+    *scroll = (*scroll).min(max_scroll);
+    f.render_widget(
+        Paragraph::new(lines).scroll((*scroll, 0)).block(block),
+        area,
+    );
+    if max_scroll > 0 {
+        draw_scrollbar(
+            f,
+            inner,
+            max_scroll as usize + 1,
+            *scroll as usize,
+            theme.border,
+        );
+    }
+}
+
+fn draw_scrollbar(f: &mut Frame, area: Rect, length: usize, position: usize, color: Color) {
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .style(Style::default().fg(color));
+    let mut scrollbar_state = ScrollbarState::new(length).position(position);
+    f.render_stateful_widget(scrollbar, area, &mut scrollbar_state);
 }
 
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -543,9 +999,113 @@ pub fn draw_priority_modal(f: &mut Frame, area: Rect) {
 
     f.render_widget(paragraph, inner_area);
 }
+
+// Copy to clipboard menu
+pub fn draw_copy_modal(f: &mut Frame, area: Rect) {
+    let background = Color::Rgb(30, 15, 35);
+    let border = Color::Rgb(200, 100, 220);
+    let text_primary = Color::Rgb(230, 220, 240);
+    let text_secondary = Color::Rgb(200, 180, 220);
+    let key_color = Color::Rgb(220, 180, 100);
+
+    let modal_area = dynamic_rect(45, 40, area);
+
+    let block = Block::default()
+        .title(" Copy to Clipboard ")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(background))
+        .border_style(Style::default().fg(border).add_modifier(Modifier::BOLD));
+
+    f.render_widget(block, modal_area);
+
+    let inner_area = modal_area.inner(Margin {
+        horizontal: 3,
+        vertical: 2,
+    });
+
+    let option = |key: &'static str, label: &'static str| {
+        Line::from(vec![
+            Span::styled(
+                key,
+                Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+            ),
+            label.fg(text_secondary),
+        ])
+    };
+
+    let text = vec![
+        Line::from(""),
+        Line::from("What do you want to copy?".fg(text_primary)),
+        Line::from(""),
+        option("m", ": Selected todo as Markdown"),
+        option("t", ": Selected todo as plain text"),
+        Line::from(""),
+        option("M", ": Visible list as Markdown"),
+        option("T", ": Visible list as plain text"),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().style(Style::default().bg(background)));
+
+    f.render_widget(paragraph, inner_area);
+}
+
+// EXPORT MENU: pick the file format for the rows the table is showing
+pub fn draw_export_modal(f: &mut Frame, area: Rect, count: usize) {
+    let background = Color::Rgb(30, 15, 35);
+    let border = Color::Rgb(200, 100, 220);
+    let text_primary = Color::Rgb(230, 220, 240);
+    let text_secondary = Color::Rgb(200, 180, 220);
+    let key_color = Color::Rgb(220, 180, 100);
+
+    let modal_area = dynamic_rect(45, 40, area);
+
+    let block = Block::default()
+        .title(" Export ")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(background))
+        .border_style(Style::default().fg(border).add_modifier(Modifier::BOLD));
+
+    f.render_widget(block, modal_area);
+
+    let inner_area = modal_area.inner(Margin {
+        horizontal: 3,
+        vertical: 2,
+    });
+
+    let option = |key: &'static str, label: &'static str| {
+        Line::from(vec![
+            Span::styled(
+                key,
+                Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+            ),
+            label.fg(text_secondary),
+        ])
+    };
+
+    let text = vec![
+        Line::from(""),
+        Line::from(format!("Export the {} visible todo(s) as", count).fg(text_primary)),
+        Line::from(""),
+        option("j", ": JSON"),
+        option("e", ": Excel"),
+        option("c", ": CSV"),
+        option("m", ": Markdown"),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(Block::default().style(Style::default().bg(background)));
+
+    f.render_widget(paragraph, inner_area);
+}
+
 //
 // MAIN MODAL MENU
-pub fn draw_main_menu_modal(f: &mut Frame, area: Rect) {
+pub fn draw_main_menu_modal(f: &mut Frame, area: Rect, query: &str, scroll: &mut u16) {
     // Theme colors
     let background = Color::Rgb(30, 15, 35);
     let border_color = Color::Rgb(200, 100, 220);
@@ -558,7 +1118,8 @@ pub fn draw_main_menu_modal(f: &mut Frame, area: Rect) {
 
     // Main block for the modal
     let block = Block::default()
-        .title(" VoiDo Menu ")
+        .title(" VoiDo Help ")
+        .title_bottom(Line::from(" [type: Search] [↑/↓: Scroll] [Esc: Close] ").centered())
         .borders(Borders::ALL)
         .style(Style::default().bg(background))
         .border_style(
@@ -568,64 +1129,648 @@ pub fn draw_main_menu_modal(f: &mut Frame, area: Rect) {
         );
     f.render_widget(block, modal_area);
 
-    // Inner layout for content with better spacing
+    // Inner layout: search line on top, the keybindings below
     let inner_area = modal_area.inner(Margin {
         horizontal: 5,
-        vertical: 3,
+        vertical: 2,
     });
+    let [search_area, _, list_area] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(1),
+        Constraint::Min(1),
+    ])
+    .areas(inner_area);
+
+    let search = Line::from(vec![
+        Span::styled("Search: ", Style::default().fg(text_secondary)),
+        Span::styled(
+            format!("{}▏", query),
+            Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(search), search_area);
 
-    // Keybindings data
-    let keybindings = vec![
-        ("Up/Down", "Navigate through the list of TODOs"),
-        ("Enter", "Show detailed view of the selected TODO"),
-        ("Delete / x", "Delete the selected TODO"),
-        ("d", "Mark the selected TODO as 'Done'"),
-        ("p", "Mark the selected TODO as 'Pending'"),
-        ("o", "Mark the selected TODO as 'Ongoing'"),
-        ("P", "Change the priority of the selected TODO"),
-        ("N", "Edit notes (supports Markdown)"),
-        ("Tab", "Toggle preview/edit mode (in notes)"),
-        ("Page Up/Down", "Scroll notes content"),
-        ("M", "Toggle this main menu"),
-        ("q", "Quit the application"),
-        ("A", "Add a new TODO"),
-        ("E", "Export all TODOs to an Excel file"),
-        ("Y", "Confirm an action (e.g., deletion)"),
-        ("N", "Cancel an action"),
+    // Matching keybindings, grouped by where they work
+    let bindings = search_bindings(query);
+    let mut lines = Vec::new();
+    for context in KeyContext::ALL {
+        let group = bindings
+            .iter()
+            .filter(|binding| binding.context == context)
+            .collect::<Vec<_>>();
+        if group.is_empty() {
+            continue;
+        }
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            context.title(),
+            Style::default()
+                .fg(border_color)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for binding in group {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<18}", binding.keys),
+                    Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(binding.action, Style::default().fg(text_primary)),
+            ]));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("No keybinding matches \"{}\"", query),
+            Style::default().fg(text_secondary),
+        )));
+    }
+
+    let max_scroll = lines.len().saturating_sub(list_area.height as usize) as u16;
+    *scroll = (*scroll).min(max_scroll);
+    f.render_widget(Paragraph::new(lines).scroll((*scroll, 0)), list_area);
+    if max_scroll > 0 {
+        draw_scrollbar(
+            f,
+            list_area,
+            max_scroll as usize + 1,
+            *scroll as usize,
+            border_color,
+        );
+    }
+}
+
+// STATS MODAL: progress of every topic and milestone, and the open work of every owner
+pub fn draw_stats_modal(
+    f: &mut Frame,
+    area: Rect,
+    todos: &[Todo],
+    milestones: &[report::MilestoneProgress],
+    habits: &[Habit],
+) {
+    let theme = Theme::current();
+    let modal_area = dynamic_rect(60, 70, area);
+    f.render_widget(ratatui::widgets::Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Stats ")
+        .title_bottom(Line::from(" [Esc: Close] ").centered())
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background))
+        .border_style(
+            Style::default()
+                .fg(theme.border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner_area = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let done = todos.iter().filter(|todo| todo.is_done()).count();
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Overall  ", Style::default().fg(theme.text_secondary)),
+            Span::styled(
+                report::progress_bar(done, todos.len(), 30),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!("  {}/{}", done, todos.len()),
+                Style::default().fg(theme.text_primary),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "By topic",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
     ];
 
-    // Create rows for the table
-    let rows: Vec<Row> = keybindings
+    // Leave room for the counts after the bar
+    let topic_width = 18;
+    let bar_width = (inner_area.width as usize)
+        .saturating_sub(topic_width + 16)
+        .clamp(5, 40);
+    for entry in report::topic_progress(todos) {
+        let color = topic_color(&entry.topic);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{:<width$} ",
+                    truncate_text(&entry.topic, topic_width),
+                    width = topic_width
+                ),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                report::progress_bar(entry.done, entry.total, bar_width),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                format!(
+                    "  {:>3}/{:<3} {:>3}%",
+                    entry.done,
+                    entry.total,
+                    entry.percent()
+                ),
+                Style::default().fg(theme.text_primary),
+            ),
+        ]));
+    }
+
+    if !milestones.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "By milestone",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    // Shorter bars leave room for the target date
+    for entry in milestones {
+        let overdue = entry.target.is_some_and(|target| {
+            target < chrono::Local::now().date_naive() && entry.done < entry.total
+        });
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{:<width$} ",
+                    truncate_text(&entry.name, topic_width),
+                    width = topic_width
+                ),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(
+                report::progress_bar(entry.done, entry.total, bar_width.saturating_sub(10).max(5)),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!(
+                    "  {:>3}/{:<3} {:>3}%  ",
+                    entry.done,
+                    entry.total,
+                    entry.percent()
+                ),
+                Style::default().fg(theme.text_primary),
+            ),
+            Span::styled(
+                entry
+                    .target
+                    .map(crate::dates::format_date)
+                    .unwrap_or_default(),
+                Style::default().fg(if overdue {
+                    Color::Red
+                } else {
+                    theme.text_secondary
+                }),
+            ),
+        ]));
+    }
+
+    // One cell per day (or week) of each habit, newest on the right
+    if !habits.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Habits",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+    for habit in habits {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{:<width$} ",
+                    truncate_text(&habit.text, topic_width),
+                    width = topic_width
+                ),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(
+                format!(
+                    "{:<width$}",
+                    habits::calendar_cells(&habit.calendar),
+                    width = habits::CALENDAR_DAYS
+                ),
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!("  🔥 {} {}", habit.streak, habit.cadence.name()),
+                Style::default().fg(theme.text_primary),
+            ),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "{:<width$} {:>4} {:>6} {:>6} {:>4} {:>5} {:>8}",
+            "By owner",
+            "High",
+            "Medium",
+            "Normal",
+            "Low",
+            "Open",
+            "Subtasks",
+            width = topic_width
+        ),
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )));
+    for entry in report::owner_workload(todos) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "{:<width$} ",
+                    truncate_text(&entry.owner, topic_width),
+                    width = topic_width
+                ),
+                Style::default().fg(theme.text_secondary),
+            ),
+            Span::styled(
+                format!(
+                    "{:>4} {:>6} {:>6} {:>4} {:>5} {:>8}",
+                    entry.high,
+                    entry.medium,
+                    entry.normal,
+                    entry.low,
+                    entry.open,
+                    entry.open_subtasks
+                ),
+                Style::default().fg(theme.text_primary),
+            ),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner_area);
+}
+
+// DEPENDENCY GRAPH: the selected todo and the todos it waits on, as a tree
+pub fn draw_graph_modal(f: &mut Frame, area: Rect, lines: &[GraphLine]) {
+    let theme = Theme::current();
+    let modal_area = dynamic_rect(70, 70, area);
+    f.render_widget(ratatui::widgets::Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Blocked by ")
+        .title_bottom(Line::from(" [Esc: Close] ").centered())
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background))
+        .border_style(
+            Style::default()
+                .fg(theme.border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner_area = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let mut text = lines
         .iter()
-        .map(|(key, desc)| {
-            Row::new(vec![
+        .map(|line| {
+            let style = if line.done {
+                Style::default()
+                    .fg(theme.text_secondary)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::default().fg(theme.text_primary)
+            };
+            Line::from(vec![
+                Span::styled(line.prefix.clone(), Style::default().fg(theme.border)),
+                Span::styled(format!("#{} ", line.id), Style::default().fg(theme.accent)),
                 Span::styled(
-                    *key,
-                    Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+                    format!("[{}] ", line.status),
+                    Style::default().fg(theme.text_secondary),
+                ),
+                Span::styled(
+                    truncate_text(
+                        &line.text,
+                        (inner_area.width as usize)
+                            .saturating_sub(line.prefix.chars().count() + 16)
+                            .max(10),
+                    ),
+                    style,
+                ),
+                Span::styled(
+                    if line.cycle { " ↻" } else { "" },
+                    Style::default().fg(Color::Red),
                 ),
-                Span::styled(*desc, Style::default().fg(text_secondary)),
             ])
         })
+        .collect::<Vec<Line>>();
+    if lines.len() == 1 {
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            "Nothing blocks this todo. Add a dependency with `voido depend add <id> --on <id>`",
+            Style::default().fg(theme.text_secondary),
+        )));
+    }
+
+    f.render_widget(Paragraph::new(text).wrap(Wrap { trim: false }), inner_area);
+}
+
+// INBOX TRIAGE: the captured todo on screen and the key for each decision
+pub fn draw_triage_modal(f: &mut Frame, area: Rect, todo: &Todo, triage: &Triage) {
+    let theme = Theme::current();
+    let modal_area = dynamic_rect(60, 45, area);
+    f.render_widget(ratatui::widgets::Clear, modal_area);
+
+    let (position, total) = triage.position();
+    let block = Block::default()
+        .title(format!(" Inbox {} of {} ", position, total))
+        .title_bottom(Line::from(" [x: Delete] [s: Skip] [Esc: Stop] ").centered())
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background))
+        .border_style(
+            Style::default()
+                .fg(theme.border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .padding(Padding::new(2, 2, 1, 1));
+    let inner_area = block.inner(modal_area);
+    f.render_widget(block, modal_area);
+
+    let text_width = inner_area.width as usize;
+    let mut lines: Vec<Line> = wrap_text(&todo.text, text_width)
+        .into_iter()
+        .map(|line| {
+            Line::from(Span::styled(
+                line,
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ))
+        })
         .collect();
+    lines.push(Line::from(Span::styled(
+        format!(
+            "#{}  ·  added {}  ·  {}  ·  {}  ·  due {}",
+            todo.id,
+            crate::dates::format_timestamp(&todo.date_added),
+            todo.priority,
+            todo.owner,
+            todo.due_display()
+        ),
+        Style::default().fg(theme.text_secondary),
+    )));
+    // New todos get a placeholder description, nothing worth showing here
+    if !todo.desc.trim().is_empty() && todo.desc != "No description provided" {
+        lines.push(Line::from(""));
+        lines.extend(
+            todo.desc
+                .lines()
+                .flat_map(|line| wrap_text(line, text_width))
+                .map(|line| {
+                    Line::from(Span::styled(line, Style::default().fg(theme.text_primary)))
+                }),
+        );
+    }
+
+    let label = |text: &str| {
+        Span::styled(
+            format!("{:<10}", text),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+    let key = |key: String| Span::styled(key, Style::default().fg(theme.accent));
+    let value = |text: String| Span::styled(text, Style::default().fg(theme.text_primary));
+
+    let mut topic_line = vec![label("TOPIC")];
+    for (index, topic) in triage.topics.iter().enumerate() {
+        topic_line.push(key(format!("{} ", index + 1)));
+        topic_line.push(value(format!("{}  ", topic)));
+    }
+    topic_line.push(key("t ".to_string()));
+    topic_line.push(value("type one".to_string()));
+
+    let mut priority_line = vec![label("PRIORITY")];
+    for (shortcut, priority) in [
+        ("h", "High"),
+        ("m", "Medium"),
+        ("l", "Low"),
+        ("n", "Normal"),
+    ] {
+        priority_line.push(key(format!("{} ", shortcut)));
+        priority_line.push(value(format!("{}  ", priority)));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(topic_line));
+    lines.push(Line::from(priority_line));
+    lines.push(Line::from(vec![
+        label("DUE"),
+        key("d ".to_string()),
+        value("today, fri, 2025-03-14...".to_string()),
+    ]));
+
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner_area);
+}
+
+// CHECKLIST PICKER: saved checklists that can be added to the open todo
+pub fn draw_checklist_modal(
+    f: &mut Frame,
+    area: Rect,
+    checklists: &[Checklist],
+    state: &mut ListState,
+) {
+    let theme = Theme::current();
+    let modal_area = dynamic_rect(50, 50, area);
+    f.render_widget(ratatui::widgets::Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Add a Checklist ")
+        .title_bottom(Line::from(" [j/k: Move] [Enter: Add] [Esc: Close] ").centered())
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background))
+        .border_style(
+            Style::default()
+                .fg(theme.border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .padding(Padding::new(2, 2, 1, 1));
+
+    if checklists.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from("No checklists yet".fg(theme.text_primary)),
+            Line::from(""),
+            Line::from("Save one from the command line:".fg(theme.text_secondary)),
+            Line::from("voido checklist save <name> --from <ID>".fg(theme.accent)),
+        ])
+        .block(block)
+        .wrap(Wrap { trim: true });
+        f.render_widget(empty, modal_area);
+        return;
+    }
+
+    let items = checklists
+        .iter()
+        .map(|checklist| {
+            ListItem::new(vec![
+                Line::from(Span::styled(
+                    format!("{} ({})", checklist.name, checklist.items.len()),
+                    Style::default()
+                        .fg(theme.text_primary)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Line::from(Span::styled(
+                    format!("  {}", checklist.items.join(", ")),
+                    Style::default().fg(theme.text_secondary),
+                )),
+            ])
+        })
+        .collect::<Vec<ListItem>>();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(theme.selection).fg(Color::White));
+
+    f.render_stateful_widget(list, modal_area, state);
+}
+
+// LINKS MODAL: the links in the notes, Enter opens one in the browser
+pub fn draw_links_modal(f: &mut Frame, area: Rect, links: &[NoteLink], state: &mut ListState) {
+    let theme = Theme::current();
+    let modal_area = dynamic_rect(60, 50, area);
+    f.render_widget(ratatui::widgets::Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" Links in the Notes ({}) ", links.len()))
+        .title_bottom(Line::from(" [j/k: Move] [Enter: Open] [Esc: Close] ").centered())
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background))
+        .border_style(
+            Style::default()
+                .fg(theme.border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let items = links
+        .iter()
+        .map(|link| {
+            let mut lines = vec![Line::from(Span::styled(
+                link.label.clone(),
+                Style::default()
+                    .fg(theme.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            ))];
+            // A bare URL is its own label
+            if link.label != link.url {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}", link.url),
+                    Style::default().fg(theme.text_secondary),
+                )));
+            }
+            ListItem::new(lines)
+        })
+        .collect::<Vec<ListItem>>();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(theme.selection).fg(Color::White));
+
+    f.render_stateful_widget(list, modal_area, state);
+}
+
+// CHECKBOXES IN THE NOTES, ticked from here without editing the notes
+pub fn draw_note_tasks_modal(f: &mut Frame, area: Rect, tasks: &[NoteTask], state: &mut ListState) {
+    let theme = Theme::current();
+    let modal_area = dynamic_rect(60, 50, area);
+    f.render_widget(ratatui::widgets::Clear, modal_area);
+
+    let done = tasks.iter().filter(|task| task.done).count();
+    let block = Block::default()
+        .title(format!(
+            " Checkboxes in the Notes ({}/{}) ",
+            done,
+            tasks.len()
+        ))
+        .title_bottom(Line::from(" [j/k: Move] [Space/Enter: Tick] [Esc: Close] ").centered())
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background))
+        .border_style(
+            Style::default()
+                .fg(theme.border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let items = tasks
+        .iter()
+        .map(|task| {
+            let (checkbox, style) = if task.done {
+                (
+                    "☑ ",
+                    Style::default()
+                        .fg(theme.text_secondary)
+                        .add_modifier(Modifier::CROSSED_OUT),
+                )
+            } else {
+                ("☐ ", Style::default().fg(theme.text_primary))
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(checkbox, Style::default().fg(theme.accent)),
+                Span::styled(task.text.clone(), style),
+            ]))
+        })
+        .collect::<Vec<ListItem>>();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(theme.selection).fg(Color::White));
+
+    f.render_stateful_widget(list, modal_area, state);
+}
+
+// SETTINGS MODAL
+pub fn draw_settings_modal(f: &mut Frame, area: Rect, state: &mut ListState) {
+    let theme = Theme::current();
+    let key_color = Color::Rgb(220, 180, 100);
+    let config = configs::display_config().clone();
+    let defaults = configs::defaults_config().clone();
+
+    let modal_area = dynamic_rect(60, 70, area);
+    f.render_widget(ratatui::widgets::Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Settings ")
+        .title_bottom(Line::from(" [j/k: Move] [Space/Enter: Change] [Esc: Close] ").centered())
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.background))
+        .border_style(
+            Style::default()
+                .fg(theme.border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .padding(Padding::new(2, 2, 1, 1));
+
+    let items = settings_items()
+        .into_iter()
+        .map(|item| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<24}", item.label()),
+                    Style::default().fg(theme.text_secondary),
+                ),
+                Span::styled(
+                    item.value(&config, &defaults),
+                    Style::default().fg(key_color).add_modifier(Modifier::BOLD),
+                ),
+            ]))
+        })
+        .collect::<Vec<ListItem>>();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(theme.selection).fg(Color::White));
 
-    // Create the table with better spacing
-    let table = Table::new(
-        rows,
-        [
-            // Constraint for key column with more space
-            Constraint::Length(15),
-            // Constraint for description column
-            Constraint::Fill(1),
-        ],
-    )
-    .block(
-        Block::default()
-            .title("Keybindings")
-            .borders(Borders::NONE)
-            .style(Style::default().fg(text_primary)),
-    )
-    .column_spacing(5);
-
-    // Render the table
-    f.render_widget(table, inner_area);
+    f.render_stateful_widget(list, modal_area, state);
 }
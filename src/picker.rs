@@ -0,0 +1,131 @@
+// TODO PICKER: a small fuzzy list drawn below the prompt, used when a command is run without an ID
+use std::error::Error;
+use std::io::{self, IsTerminal};
+
+use crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyModifiers,
+};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use ratatui::{
+    Terminal, TerminalOptions, Viewport,
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState},
+};
+
+use crate::arguments::models::Todo;
+use crate::colors::Theme;
+use crate::database::DBtodo;
+use crate::search::FuzzySearch;
+
+const PICKER_HEIGHT: u16 = 12;
+
+// Use the given ID, or let the user pick an open todo. `None` when the picker was cancelled.
+pub fn resolve_id(id: Option<i32>, action: &str) -> Result<Option<i32>, Box<dyn Error>> {
+    if let Some(id) = id {
+        return Ok(Some(id));
+    }
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        return Err("No ID given and no terminal to pick a todo from".into());
+    }
+
+    let todos = DBtodo::new()?
+        .get_todos()?
+        .into_iter()
+        .filter(|todo| !todo.is_done())
+        .collect::<Vec<Todo>>();
+    if todos.is_empty() {
+        println!("No open todos to pick from");
+        return Ok(None);
+    }
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnableBracketedPaste)?;
+    let picked = run_picker(&todos, action);
+    execute!(io::stdout(), DisableBracketedPaste)?;
+    disable_raw_mode()?;
+    Ok(picked?.map(|index| todos[index].id as i32))
+}
+
+fn run_picker(todos: &[Todo], action: &str) -> Result<Option<usize>, Box<dyn Error>> {
+    let theme = Theme::current();
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(io::stdout()),
+        TerminalOptions {
+            viewport: Viewport::Inline(PICKER_HEIGHT),
+        },
+    )?;
+
+    let mut search = FuzzySearch::new();
+    search.input.title = format!("{} (Enter: pick, Esc: cancel)", action);
+    search.input.focus();
+    search.update_matches(todos);
+
+    let picked = loop {
+        terminal.draw(|f| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(1)])
+                .split(f.area());
+            search.input.render(f, chunks[0]);
+
+            let items = search
+                .matched_indices()
+                .iter()
+                .map(|&index| {
+                    let todo = &todos[index];
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:>4}  ", todo.id),
+                            Style::default().fg(theme.text_secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<8}  ", todo.status),
+                            Style::default().fg(theme.accent),
+                        ),
+                        Span::styled(todo.text.clone(), Style::default().fg(theme.text_primary)),
+                    ]))
+                })
+                .collect::<Vec<ListItem>>();
+            let list = List::new(items).highlight_style(
+                Style::default()
+                    .bg(theme.selection)
+                    .add_modifier(Modifier::BOLD),
+            );
+            let mut state = ListState::default();
+            state.select((!search.matched_indices().is_empty()).then_some(search.selected_match()));
+            f.render_stateful_widget(list, chunks[1], &mut state);
+        })?;
+
+        let key = match event::read()? {
+            Event::Key(key) => key,
+            paste @ Event::Paste(_) => {
+                if search.handle_event(&paste) {
+                    search.update_matches(todos);
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        match key.code {
+            KeyCode::Esc => break None,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break None,
+            KeyCode::Enter => {
+                if let Some(&index) = search.matched_indices().get(search.selected_match()) {
+                    break Some(index);
+                }
+            }
+            _ => {
+                if search.handle_event(&Event::Key(key)) {
+                    search.update_matches(todos);
+                }
+            }
+        }
+    };
+
+    terminal.clear()?;
+    Ok(picked)
+}
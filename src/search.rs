@@ -1,8 +1,10 @@
 use crate::arguments::models::Todo;
+use crate::clipboard;
+use crate::text_width;
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
 use ratatui::{
-    crossterm::event::{self, Event, KeyCode},
+    crossterm::event::{self, Event, KeyCode, KeyModifiers},
     layout::*,
     prelude::*,
     style::*,
@@ -12,6 +14,38 @@ use ratatui::{
 
 use std::fmt;
 
+// Every field the search looks at, joined in one string
+pub fn searchable_text(todo: &Todo) -> String {
+    format!(
+        "{} {} {} {} {} {} {} {} {:?}",
+        todo.id,
+        todo.priority,
+        todo.topic,
+        todo.text,
+        todo.status,
+        todo.owner,
+        todo.notes,
+        todo.due_display(),
+        todo.subtasks
+    )
+}
+
+// (index, score) of the todos the search bar would match, best first
+pub fn fuzzy_ranked(todos: &[Todo], query: &str) -> Vec<(usize, i64)> {
+    let matcher = SkimMatcherV2::default();
+    let mut ranked = todos
+        .iter()
+        .enumerate()
+        .filter_map(|(index, todo)| {
+            matcher
+                .fuzzy_match(&searchable_text(todo), query)
+                .map(|score| (index, score))
+        })
+        .collect::<Vec<(usize, i64)>>();
+    ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    ranked
+}
+
 impl fmt::Debug for FuzzySearch {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("FuzzySearch")
@@ -58,21 +92,9 @@ impl FuzzySearch {
             // TODO: Implement fuzzy matching with SkimMatcherV2
             // Fuzzy match against all todo fields
             for (idx, todo) in todos.iter().enumerate() {
-                let combined_text = format!(
-                    "{} {} {} {} {} {} {} {} {:?}",
-                    todo.id,
-                    todo.priority,
-                    todo.topic,
-                    todo.text,
-                    todo.status,
-                    todo.owner,
-                    todo.notes,
-                    todo.due,
-                    todo.subtasks
-                );
                 if self
                     .matcher
-                    .fuzzy_match(&combined_text, search_text)
+                    .fuzzy_match(&searchable_text(todo), search_text)
                     .is_some()
                 {
                     self.matched_indices.push(idx);
@@ -95,10 +117,9 @@ impl FuzzySearch {
         }
 
         // Handle input changes (typing, backspace, delete, ESC)
-        let input_handled = if let Event::Key(_) = event {
-            self.input.handle_event(event) // Pass all key events to InputField
-        } else {
-            false
+        let input_handled = match event {
+            Event::Key(_) | Event::Paste(_) => self.input.handle_event(event),
+            _ => false,
         };
 
         // Handle navigation
@@ -143,6 +164,8 @@ pub struct InputField {
     pub multiline: bool,
     pub cursor_line: usize,
     pub cursor_col: usize,
+    // Other end of the selection, the cursor is the end being moved
+    pub selection_anchor: Option<usize>,
 }
 
 impl InputField {
@@ -158,6 +181,7 @@ impl InputField {
             multiline: false,
             cursor_line: 0,
             cursor_col: 0,
+            selection_anchor: None,
         }
     }
 
@@ -173,11 +197,13 @@ impl InputField {
             multiline: true,
             cursor_line: 0,
             cursor_col: 0,
+            selection_anchor: None,
         }
     }
 
     pub fn focus(&mut self) {
         self.active = true;
+        self.selection_anchor = None;
         self.cursor_position = self.value.len(); // Move cursor to end
         if self.multiline {
             self.update_cursor_position();
@@ -204,13 +230,13 @@ impl InputField {
         f.render_widget(input_block, area);
 
         if self.multiline {
-            let text = Paragraph::new(self.value.as_str())
+            let text = Paragraph::new(self.styled_lines())
                 .style(Style::default().fg(self.text_color))
                 .wrap(ratatui::widgets::Wrap { trim: false });
             f.render_widget(text, inner_area);
 
             if self.active {
-                let cursor_x = inner_area.x + self.cursor_col as u16;
+                let cursor_x = inner_area.x + self.cursor_display_col() as u16;
                 let cursor_y = inner_area.y + self.cursor_line as u16;
                 if cursor_x < inner_area.x + inner_area.width
                     && cursor_y < inner_area.y + inner_area.height
@@ -219,18 +245,18 @@ impl InputField {
                 }
             }
         } else {
-            let text = Paragraph::new(self.value.as_str())
+            let cursor_col = self.cursor_display_col();
+            let text = Paragraph::new(self.styled_lines())
                 .style(Style::default().fg(self.text_color))
                 .scroll((
                     0,
-                    self.cursor_position
-                        .saturating_sub(inner_area.width as usize) as u16,
+                    cursor_col.saturating_sub(inner_area.width as usize) as u16,
                 ));
 
             f.render_widget(text, inner_area);
 
             if self.active {
-                let cursor_x = inner_area.x + (self.cursor_position as u16).min(inner_area.width);
+                let cursor_x = inner_area.x + (cursor_col as u16).min(inner_area.width);
                 let cursor_y = inner_area.y;
                 f.set_cursor(cursor_x, cursor_y);
             }
@@ -242,115 +268,242 @@ impl InputField {
             return false;
         }
 
-        if let Event::Key(key) = event {
-            match key.code {
-                KeyCode::Char(c) => {
-                    self.value.insert(self.cursor_position, c);
-                    self.cursor_position += 1;
-                    if self.multiline {
-                        self.update_cursor_position();
-                    }
-                    return true;
-                }
-                KeyCode::Backspace => {
-                    if self.cursor_position > 0 {
-                        self.cursor_position -= 1;
-                        self.value.remove(self.cursor_position);
-                        if self.multiline {
-                            self.update_cursor_position();
-                        }
-                        return true;
-                    }
-                }
-                KeyCode::Left => {
-                    if self.cursor_position > 0 {
-                        self.cursor_position -= 1;
-                        if self.multiline {
-                            self.update_cursor_position();
-                        }
-                        return true;
-                    }
-                }
-                KeyCode::Right => {
-                    if self.cursor_position < self.value.len() {
-                        self.cursor_position += 1;
-                        if self.multiline {
-                            self.update_cursor_position();
-                        }
-                        return true;
-                    }
-                }
-                KeyCode::Delete => {
-                    if self.cursor_position < self.value.len() {
-                        self.value.remove(self.cursor_position);
-                        if self.multiline {
-                            self.update_cursor_position();
-                        }
-                        return true;
-                    }
-                }
-                KeyCode::Home => {
-                    if self.multiline {
-                        // Move to beginning of current line
-                        let lines: Vec<&str> = self.value.split('\n').collect();
-                        let mut pos = 0;
-                        for i in 0..self.cursor_line {
-                            pos += lines[i].len() + 1;
-                        }
-                        self.cursor_position = pos;
-                        self.cursor_col = 0;
-                    } else {
-                        self.cursor_position = 0;
-                    }
-                    return true;
+        // Bracketed paste arrives as a single event, newlines included
+        if let Event::Paste(text) = event {
+            self.insert_str(text);
+            return true;
+        }
+
+        let Event::Key(key) = event else {
+            return false;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        match key.code {
+            // Word-wise editing and the clipboard
+            KeyCode::Left if ctrl => {
+                self.move_to(self.prev_word(), shift);
+                true
+            }
+            KeyCode::Right if ctrl => {
+                self.move_to(self.next_word(), shift);
+                true
+            }
+            KeyCode::Char('w') if ctrl => {
+                if !self.delete_selection() {
+                    let start = self.prev_word();
+                    self.delete_range(start, self.cursor_position);
                 }
-                KeyCode::End => {
-                    if self.multiline {
-                        // Move to end of current line
-                        let lines: Vec<&str> = self.value.split('\n').collect();
-                        let mut pos = 0;
-                        for i in 0..self.cursor_line {
-                            pos += lines[i].len() + 1;
-                        }
-                        pos += lines[self.cursor_line].len();
-                        self.cursor_position = pos;
-                        self.cursor_col = lines[self.cursor_line].len();
-                    } else {
-                        self.cursor_position = self.value.len();
+                true
+            }
+            KeyCode::Char('u') if ctrl => {
+                let start = self.value[..self.cursor_position]
+                    .rfind('\n')
+                    .map_or(0, |index| index + 1);
+                self.delete_range(start, self.cursor_position);
+                true
+            }
+            KeyCode::Char('a') if ctrl => {
+                self.selection_anchor = Some(0);
+                self.cursor_position = self.value.len();
+                self.update_cursor_position();
+                true
+            }
+            KeyCode::Char('c') | KeyCode::Char('x') if ctrl => {
+                if let Some(text) = self.selected_text() {
+                    if clipboard::copy_to_clipboard(text).is_ok() && key.code == KeyCode::Char('x')
+                    {
+                        self.delete_selection();
                     }
-                    return true;
                 }
-                KeyCode::Enter => {
-                    if self.multiline {
-                        self.value.insert(self.cursor_position, '\n');
-                        self.cursor_position += 1;
-                        self.update_cursor_position();
-                        return true;
-                    } else {
-                        // Handle enter key if needed for single line
-                        return true;
-                    }
+                true
+            }
+            KeyCode::Char('v') if ctrl => {
+                if let Ok(text) = clipboard::paste_from_clipboard() {
+                    self.insert_str(&text);
                 }
-                KeyCode::Up => {
-                    if self.multiline && self.cursor_line > 0 {
-                        self.move_cursor_up();
-                        return true;
-                    }
+                true
+            }
+            // Other Ctrl keys must not reach the table shortcuts
+            KeyCode::Char(_) if ctrl => true,
+            KeyCode::Char(c) => {
+                self.insert_str(c.encode_utf8(&mut [0; 4]));
+                true
+            }
+            KeyCode::Backspace => {
+                if !self.delete_selection() && self.cursor_position > 0 {
+                    let start = text_width::prev_boundary(&self.value, self.cursor_position);
+                    self.delete_range(start, self.cursor_position);
                 }
-                KeyCode::Down => {
-                    if self.multiline {
-                        self.move_cursor_down();
-                        return true;
-                    }
+                true
+            }
+            KeyCode::Delete => {
+                if !self.delete_selection() && self.cursor_position < self.value.len() {
+                    let end = text_width::next_boundary(&self.value, self.cursor_position);
+                    self.delete_range(self.cursor_position, end);
                 }
-                KeyCode::Esc => {
-                    self.unfocus();
-                    return true;
+                true
+            }
+            KeyCode::Left => {
+                let position = text_width::prev_boundary(&self.value, self.cursor_position);
+                self.move_to(position, shift);
+                true
+            }
+            KeyCode::Right => {
+                let position = if self.cursor_position < self.value.len() {
+                    text_width::next_boundary(&self.value, self.cursor_position)
+                } else {
+                    self.cursor_position
+                };
+                self.move_to(position, shift);
+                true
+            }
+            KeyCode::Home => {
+                let start = if self.multiline {
+                    self.value[..self.cursor_position]
+                        .rfind('\n')
+                        .map_or(0, |index| index + 1)
+                } else {
+                    0
+                };
+                self.move_to(start, shift);
+                true
+            }
+            KeyCode::End => {
+                let end = if self.multiline {
+                    self.value[self.cursor_position..]
+                        .find('\n')
+                        .map_or(self.value.len(), |index| self.cursor_position + index)
+                } else {
+                    self.value.len()
+                };
+                self.move_to(end, shift);
+                true
+            }
+            KeyCode::Enter => {
+                if self.multiline {
+                    self.insert_str("\n");
                 }
-                _ => {}
+                // Single-line fields leave Enter to whoever owns them
+                true
+            }
+            KeyCode::Up if self.multiline && self.cursor_line > 0 => {
+                self.selection_anchor = None;
+                self.move_cursor_up();
+                true
+            }
+            KeyCode::Down if self.multiline => {
+                self.selection_anchor = None;
+                self.move_cursor_down();
+                true
             }
+            KeyCode::Esc => {
+                self.selection_anchor = None;
+                self.unfocus();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Move the cursor, extending the selection when `select` (Shift is held)
+    fn move_to(&mut self, position: usize, select: bool) {
+        if select {
+            self.selection_anchor.get_or_insert(self.cursor_position);
+        } else {
+            self.selection_anchor = None;
+        }
+        self.cursor_position = position;
+        self.update_cursor_position();
+    }
+
+    // Start of the word before the cursor, skipping the spaces in between
+    fn prev_word(&self) -> usize {
+        let before = self.value[..self.cursor_position].trim_end();
+        before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(index, c)| index + c.len_utf8())
+    }
+
+    // End of the word after the cursor, skipping the spaces in between
+    fn next_word(&self) -> usize {
+        let after = &self.value[self.cursor_position..];
+        let start = after.len() - after.trim_start().len();
+        let word = &after[start..];
+        self.cursor_position + start + word.find(char::is_whitespace).unwrap_or(word.len())
+    }
+
+    // Selected byte range, `None` when nothing is selected
+    pub fn selection(&self) -> Option<std::ops::Range<usize>> {
+        let anchor = self.selection_anchor?;
+        match anchor.cmp(&self.cursor_position) {
+            std::cmp::Ordering::Less => Some(anchor..self.cursor_position),
+            std::cmp::Ordering::Greater => Some(self.cursor_position..anchor),
+            std::cmp::Ordering::Equal => None,
         }
-        false
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|range| &self.value[range])
+    }
+
+    fn delete_range(&mut self, start: usize, end: usize) {
+        self.value.replace_range(start..end, "");
+        self.cursor_position = start;
+        self.selection_anchor = None;
+        self.update_cursor_position();
+    }
+
+    // Remove the selected text, false when nothing was selected
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(range) => {
+                self.delete_range(range.start, range.end);
+                true
+            }
+            None => {
+                self.selection_anchor = None;
+                false
+            }
+        }
+    }
+
+    // Insert text at the cursor, replacing the selection. Single-line fields get spaces for line breaks.
+    pub fn insert_str(&mut self, text: &str) {
+        self.delete_selection();
+        let text = if self.multiline {
+            text.replace("\r\n", "\n")
+        } else {
+            text.replace("\r\n", " ").replace(['\n', '\r'], " ")
+        };
+        self.value.insert_str(self.cursor_position, &text);
+        self.cursor_position += text.len();
+        self.update_cursor_position();
+    }
+
+    // The value as lines, with the selected part highlighted
+    fn styled_lines(&self) -> Vec<Line<'_>> {
+        let selected = self.selection().unwrap_or(0..0);
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+        let mut offset = 0;
+        self.value
+            .split('\n')
+            .map(|line| {
+                let start = offset;
+                let end = start + line.len();
+                offset = end + 1;
+                let from = selected.start.clamp(start, end) - start;
+                let to = selected.end.clamp(start, end) - start;
+                Line::from(vec![
+                    Span::raw(&line[..from]),
+                    Span::styled(&line[from..to], highlight),
+                    Span::raw(&line[to..]),
+                ])
+            })
+            .collect()
     }
 
     pub fn set_active(&mut self, active: bool) {
@@ -360,6 +513,23 @@ impl InputField {
     pub fn clear(&mut self) {
         self.value.clear();
         self.cursor_position = 0;
+        self.selection_anchor = None;
+        self.update_cursor_position();
+    }
+
+    // Terminal column of the cursor, `cursor_position` and `cursor_col` are byte offsets
+    fn cursor_display_col(&self) -> usize {
+        if self.multiline {
+            let line = self.value.split('\n').nth(self.cursor_line).unwrap_or("");
+            text_width::width(&line[..self.cursor_col.min(line.len())])
+        } else {
+            text_width::width(&self.value[..self.cursor_position])
+        }
+    }
+
+    // Byte offset in `line` of the given terminal column, keeps the column on wide text
+    fn line_offset_at(line: &str, column: usize) -> usize {
+        text_width::split_at_width(line, column).0.len()
     }
 
     fn update_cursor_position(&mut self) {
@@ -400,9 +570,10 @@ impl InputField {
         }
 
         let lines: Vec<&str> = self.value.split('\n').collect();
+        let column = self.cursor_display_col();
         self.cursor_line -= 1;
         let target_line = lines[self.cursor_line];
-        self.cursor_col = self.cursor_col.min(target_line.len());
+        self.cursor_col = Self::line_offset_at(target_line, column);
 
         // Calculate new cursor position
         let mut pos = 0;
@@ -419,9 +590,10 @@ impl InputField {
             return;
         }
 
+        let column = self.cursor_display_col();
         self.cursor_line += 1;
         let target_line = lines[self.cursor_line];
-        self.cursor_col = self.cursor_col.min(target_line.len());
+        self.cursor_col = Self::line_offset_at(target_line, column);
 
         // Calculate new cursor position
         let mut pos = 0;
@@ -432,3 +604,75 @@ impl InputField {
         self.cursor_position = pos;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::KeyEvent;
+
+    fn press(input: &mut InputField, code: KeyCode) {
+        input.handle_event(&Event::Key(KeyEvent::from(code)));
+    }
+
+    #[test]
+    fn test_input_field_edits_whole_graphemes() {
+        let mut input = InputField::new("Test");
+        input.focus();
+        for c in "日本e\u{301}".chars() {
+            press(&mut input, KeyCode::Char(c));
+        }
+        assert_eq!(input.cursor_display_col(), 5);
+
+        press(&mut input, KeyCode::Backspace);
+        assert_eq!(input.value, "日本");
+        press(&mut input, KeyCode::Left);
+        press(&mut input, KeyCode::Delete);
+        assert_eq!(input.value, "日");
+        assert_eq!(input.cursor_display_col(), 2);
+    }
+
+    #[test]
+    fn test_input_field_word_editing_and_selection() {
+        let ctrl = |code| KeyEvent::new(code, KeyModifiers::CONTROL);
+        let mut input = InputField::new("Test");
+        input.value = "fix the  login bug".to_string();
+        input.focus();
+
+        input.handle_event(&Event::Key(ctrl(KeyCode::Left)));
+        assert_eq!(input.cursor_position, 15);
+        input.handle_event(&Event::Key(ctrl(KeyCode::Left)));
+        assert_eq!(input.cursor_position, 9);
+        input.handle_event(&Event::Key(ctrl(KeyCode::Right)));
+        assert_eq!(input.cursor_position, 14);
+
+        input.handle_event(&Event::Key(ctrl(KeyCode::Char('w'))));
+        assert_eq!(input.value, "fix the   bug");
+
+        // Shift extends a selection, typing replaces it
+        let shift = KeyEvent::new(KeyCode::Home, KeyModifiers::SHIFT);
+        input.handle_event(&Event::Key(shift));
+        assert_eq!(input.selected_text(), Some("fix the  "));
+        press(&mut input, KeyCode::Char('X'));
+        assert_eq!(input.value, "X bug");
+
+        input.handle_event(&Event::Key(ctrl(KeyCode::Char('u'))));
+        assert_eq!(input.value, " bug");
+        assert_eq!(input.cursor_position, 0);
+    }
+
+    #[test]
+    fn test_paste_keeps_newlines_only_in_multiline_fields() {
+        let paste = Event::Paste("first line\r\nsecond line".to_string());
+
+        let mut input = InputField::new("Test");
+        input.focus();
+        assert!(input.handle_event(&paste));
+        assert_eq!(input.value, "first line second line");
+
+        let mut notes = InputField::new_multiline("Notes");
+        notes.focus();
+        notes.handle_event(&paste);
+        assert_eq!(notes.value, "first line\nsecond line");
+        assert_eq!((notes.cursor_line, notes.cursor_col), (1, 11));
+    }
+}
@@ -0,0 +1,81 @@
+// TABLE SELECTION: the row under the cursor and the todo it shows. With a search, the today
+// view or an owner or context filter on, row 3 is not `todos[3]` but the fourth match.
+
+// Index into the todos of the selected row. `shown` holds the todo index of every row while a
+// filter is on, None when the table shows every todo.
+pub fn todo_index(row: Option<usize>, shown: Option<&[usize]>, total: usize) -> Option<usize> {
+    let row = row?;
+    match shown {
+        Some(shown) => shown.get(row).copied(),
+        None => (row < total).then_some(row),
+    }
+}
+
+// The row below, back to the top after the last one. None when the table is empty.
+pub fn next_row(row: Option<usize>, rows: usize) -> Option<usize> {
+    if rows == 0 {
+        return None;
+    }
+    Some(match row {
+        Some(row) if row + 1 < rows => row + 1,
+        _ => 0,
+    })
+}
+
+// The row above, round to the bottom from the first one
+pub fn previous_row(row: Option<usize>, rows: usize) -> Option<usize> {
+    if rows == 0 {
+        return None;
+    }
+    Some(match row {
+        Some(0) => rows - 1,
+        Some(row) => row.min(rows) - 1,
+        None => 0,
+    })
+}
+
+// Where the cursor stays after its row was deleted and `rows` are left
+pub fn row_after_delete(row: usize, rows: usize) -> Option<usize> {
+    (rows > 0).then(|| row.min(rows - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filtered_rows_map_to_their_todos() {
+        // Rows 0 and 1 of a search that matched todos 4 and 1
+        let shown = [4, 1];
+        assert_eq!(todo_index(Some(0), Some(&shown), 6), Some(4));
+        assert_eq!(todo_index(Some(1), Some(&shown), 6), Some(1));
+        assert_eq!(todo_index(Some(2), Some(&shown), 6), None);
+        // A search without matches selects nothing, even with todos left
+        assert_eq!(todo_index(Some(0), Some(&[]), 6), None);
+
+        assert_eq!(todo_index(Some(5), None, 6), Some(5));
+        assert_eq!(todo_index(Some(6), None, 6), None);
+        assert_eq!(todo_index(None, None, 6), None);
+    }
+
+    #[test]
+    fn test_moving_wraps_within_the_shown_rows() {
+        assert_eq!(next_row(Some(0), 2), Some(1));
+        assert_eq!(next_row(Some(1), 2), Some(0));
+        assert_eq!(next_row(None, 2), Some(0));
+        assert_eq!(next_row(Some(0), 0), None);
+
+        assert_eq!(previous_row(Some(1), 2), Some(0));
+        assert_eq!(previous_row(Some(0), 2), Some(1));
+        // A cursor left past the end of a narrowed list comes back to its last row
+        assert_eq!(previous_row(Some(5), 2), Some(1));
+        assert_eq!(previous_row(None, 0), None);
+    }
+
+    #[test]
+    fn test_row_after_delete() {
+        assert_eq!(row_after_delete(1, 3), Some(1));
+        assert_eq!(row_after_delete(2, 2), Some(1));
+        assert_eq!(row_after_delete(0, 0), None);
+    }
+}
@@ -0,0 +1,138 @@
+// READ-ONLY BOARD: the current todos as one column per status, for teammates on the LAN to look
+// at in a browser. The page reloads itself every minute, nothing on it can change a todo.
+use chrono::{Local, NaiveDate};
+
+use crate::arguments::models::Todo;
+use crate::dates;
+use crate::import_export::html::escape;
+
+const REFRESH_SECONDS: u32 = 60;
+// Columns in this order, other statuses get columns of their own after these
+const STATUS_ORDER: [&str; 5] = ["Pending", "Planned", "Ongoing", "Review", "Done"];
+
+const STYLE: &str = r#"body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 1.5rem; color: #222; background: #f4f5f7; }
+h1 { margin: 0; }
+.subtitle { color: #777; margin: 0.25rem 0 1.25rem; }
+.board { display: flex; gap: 1rem; align-items: flex-start; overflow-x: auto; }
+.column { background: #ebecf0; border-radius: 6px; padding: 0.5rem; min-width: 16rem; flex: 1; }
+.column h2 { font-size: 0.95rem; margin: 0.25rem 0.25rem 0.75rem; }
+.column h2 span { color: #777; font-weight: normal; }
+.todo { background: #fff; border-radius: 4px; padding: 0.6rem; margin-bottom: 0.5rem; box-shadow: 0 1px 1px rgba(0, 0, 0, 0.15); }
+.todo .text { margin-bottom: 0.4rem; }
+.todo .meta { color: #777; font-size: 0.8rem; display: flex; flex-wrap: wrap; gap: 0.5rem; }
+.priority-high { border-left: 4px solid #dc5096; }
+.priority-medium { border-left: 4px solid #e0a030; }
+.priority-low { border-left: 4px solid #78dc96; }
+.overdue { color: #c62828; font-weight: bold; }
+.done .text { color: #999; text-decoration: line-through; }"#;
+
+fn todo_card(todo: &Todo, today: NaiveDate) -> String {
+    let mut meta = vec![
+        format!("#{}", todo.id),
+        escape(&todo.topic),
+        escape(&todo.owner),
+    ];
+    if let Some(due) = todo.due {
+        let overdue = !todo.is_done() && due < today;
+        meta.push(format!(
+            "<span{}>due {}</span>",
+            if overdue { " class=\"overdue\"" } else { "" },
+            escape(&dates::format_date(due))
+        ));
+    }
+    if !todo.subtasks.is_empty() {
+        let done = todo
+            .subtasks
+            .iter()
+            .filter(|subtask| subtask.is_done())
+            .count();
+        meta.push(format!("☑ {}/{}", done, todo.subtasks.len()));
+    }
+
+    format!(
+        "<div class=\"todo priority-{}{}\"><div class=\"text\">{}{}</div><div class=\"meta\">{}</div></div>\n",
+        escape(&todo.priority.to_lowercase()),
+        if todo.is_done() { " done" } else { "" },
+        if todo.pinned { "📌 " } else { "" },
+        escape(&todo.text),
+        meta.iter()
+            .map(|item| format!("<span>{}</span>", item))
+            .collect::<String>()
+    )
+}
+
+pub fn render_board(todos: &[Todo], today: NaiveDate) -> String {
+    let mut statuses: Vec<&str> = STATUS_ORDER.to_vec();
+    for todo in todos {
+        if !statuses.contains(&todo.status.as_str()) {
+            statuses.push(&todo.status);
+        }
+    }
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<meta http-equiv=\"refresh\" content=\"{}\">\n<title>VoiDo board</title>\n",
+        REFRESH_SECONDS
+    ));
+    html.push_str(&format!("<style>\n{}\n</style>\n</head>\n<body>\n", STYLE));
+    html.push_str("<h1>VoiDo board</h1>\n");
+    html.push_str(&format!(
+        "<p class=\"subtitle\">{} todos, updated {}</p>\n<div class=\"board\">\n",
+        todos.len(),
+        Local::now().format("%H:%M")
+    ));
+
+    for status in statuses {
+        // Pinned first, then by due date, undated last
+        let mut column: Vec<&Todo> = todos.iter().filter(|todo| todo.status == status).collect();
+        column.sort_by_key(|todo| (!todo.pinned, todo.due.is_none(), todo.due, todo.id));
+        html.push_str(&format!(
+            "<div class=\"column\"><h2>{} <span>{}</span></h2>\n",
+            escape(status),
+            column.len()
+        ));
+        for todo in column {
+            html.push_str(&todo_card(todo, today));
+        }
+        html.push_str("</div>\n");
+    }
+
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(id: usize, text: &str, status: &str, due: Option<NaiveDate>) -> Todo {
+        Todo {
+            id,
+            priority: "High".to_string(),
+            status: status.to_string(),
+            due,
+            ..Todo::sample(text)
+        }
+    }
+
+    #[test]
+    fn test_render_board() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let todos = vec![
+            todo(1, "Fix <login>", "Pending", today.pred_opt()),
+            todo(2, "Later", "Pending", None),
+            todo(3, "Ship", "Blocked", None),
+        ];
+        let html = render_board(&todos, today);
+
+        assert!(html.contains("Fix &lt;login&gt;"));
+        assert!(html.contains("class=\"overdue\""));
+        assert!(html.contains("<h2>Pending <span>2</span></h2>"));
+        assert!(html.contains("<h2>Done <span>0</span></h2>"));
+        // Unknown statuses get their own column after the usual ones
+        assert!(html.find("<h2>Done").unwrap() < html.find("<h2>Blocked").unwrap());
+        // Dated todos come first in a column
+        assert!(html.find("Fix &lt;login&gt;").unwrap() < html.find("Later").unwrap());
+    }
+}
@@ -0,0 +1,190 @@
+// ICS CALENDAR: the due dates as an iCalendar feed that Google Calendar, Thunderbird or Apple
+// Calendar subscribe to. `/calendar.ics` has an event per open todo with a due date, all day or
+// at its due time, `/tasks.ics` has every dated todo as a task for apps with a task list.
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::arguments::models::Todo;
+
+// How often subscribed calendars should come back for changes
+const REFRESH: &str = "PT15M";
+// Todos due at a time are shown as a meeting this long
+const EVENT_MINUTES: i64 = 30;
+// Lines longer than this (in bytes) are folded onto the next one
+const LINE_LIMIT: usize = 75;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entry {
+    Event,
+    Task,
+}
+
+// Commas, semicolons, backslashes and line breaks have to be escaped in text values
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// Content lines end in CRLF, long ones continue on lines starting with a space
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LINE_LIMIT {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn utc(at: &DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn day(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+// iCalendar priorities go from 1 (highest) to 9 (lowest), 0 is none
+fn priority(todo: &Todo) -> u8 {
+    match todo.priority.to_lowercase().as_str() {
+        "high" => 1,
+        "medium" => 5,
+        "low" => 9,
+        _ => 0,
+    }
+}
+
+fn description(todo: &Todo) -> String {
+    let mut lines = vec![format!(
+        "#{} · {} · {} · {}",
+        todo.id, todo.topic, todo.owner, todo.status
+    )];
+    if !todo.desc.is_empty() && todo.desc != "No description provided" {
+        lines.push(todo.desc.clone());
+    }
+    lines.join("\n")
+}
+
+fn push_entry(out: &mut String, todo: &Todo, due: NaiveDate, entry: Entry, now: &DateTime<Utc>) {
+    let (kind, uid) = match entry {
+        Entry::Event => ("VEVENT", format!("voido-todo-{}@voido", todo.id)),
+        Entry::Task => ("VTODO", format!("voido-task-{}@voido", todo.id)),
+    };
+    let mut lines = vec![
+        format!("BEGIN:{}", kind),
+        format!("UID:{}", uid),
+        format!("DTSTAMP:{}", utc(now)),
+        format!(
+            "LAST-MODIFIED:{}",
+            utc(&todo.updated_at.unwrap_or(todo.date_added))
+        ),
+        format!("SUMMARY:{}", escape(&todo.text)),
+        format!("DESCRIPTION:{}", escape(&description(todo))),
+        format!("CATEGORIES:{}", escape(&todo.topic)),
+        format!("PRIORITY:{}", priority(todo)),
+    ];
+    match (entry, todo.due_at) {
+        (Entry::Event, Some(due_at)) => {
+            lines.push(format!("DTSTART:{}", utc(&due_at)));
+            lines.push(format!(
+                "DTEND:{}",
+                utc(&(due_at + Duration::minutes(EVENT_MINUTES)))
+            ));
+        }
+        (Entry::Event, None) => {
+            lines.push(format!("DTSTART;VALUE=DATE:{}", day(due)));
+            lines.push(format!("DTEND;VALUE=DATE:{}", day(due + Duration::days(1))));
+            lines.push("TRANSP:TRANSPARENT".to_string());
+        }
+        (Entry::Task, Some(due_at)) => lines.push(format!("DUE:{}", utc(&due_at))),
+        (Entry::Task, None) => lines.push(format!("DUE;VALUE=DATE:{}", day(due))),
+    }
+    if entry == Entry::Task {
+        lines.push(if todo.is_done() {
+            "STATUS:COMPLETED".to_string()
+        } else {
+            "STATUS:NEEDS-ACTION".to_string()
+        });
+    }
+    lines.push(format!("END:{}", kind));
+
+    for line in lines {
+        push_line(out, &line);
+    }
+}
+
+pub fn render_calendar(todos: &[Todo], entry: Entry, now: &DateTime<Utc>) -> String {
+    let mut out = String::new();
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//VoiDo//Due dates//EN",
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+        "X-WR-CALNAME:VoiDo",
+        &format!("REFRESH-INTERVAL;VALUE=DURATION:{}", REFRESH),
+        &format!("X-PUBLISHED-TTL:{}", REFRESH),
+    ] {
+        push_line(&mut out, line);
+    }
+    for todo in todos {
+        // Done todos leave the calendar, task lists keep them ticked off
+        if entry == Entry::Event && todo.is_done() {
+            continue;
+        }
+        if let Some(due) = todo.due {
+            push_entry(&mut out, todo, due, entry, now);
+        }
+    }
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::demo_todos;
+
+    #[test]
+    fn test_render_calendar() {
+        let now = DateTime::parse_from_rfc3339("2025-03-14T08:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut todos = demo_todos();
+        for (index, todo) in todos.iter_mut().enumerate() {
+            todo.id = index + 1;
+            todo.due = None;
+        }
+        todos[0].due = NaiveDate::from_ymd_opt(2025, 3, 14);
+        todos[0].text = "Ship v2, then rest; really".to_string();
+        todos[1].due = NaiveDate::from_ymd_opt(2025, 3, 15);
+        todos[1].due_at = Some(now + Duration::hours(25));
+        // Done, only on the task list
+        todos[6].due = NaiveDate::from_ymd_opt(2025, 3, 10);
+
+        let calendar = render_calendar(&todos, Entry::Event, &now);
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+        assert!(calendar.contains("SUMMARY:Ship v2\\, then rest\\; really\r\n"));
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20250314\r\nDTEND;VALUE=DATE:20250315\r\n"));
+        assert!(calendar.contains("DTSTART:20250315T090000Z\r\nDTEND:20250315T093000Z\r\n"));
+        assert!(calendar.contains("UID:voido-todo-2@voido\r\n"));
+        assert!(
+            calendar
+                .lines()
+                .all(|line| line.trim_end_matches('\r').len() <= LINE_LIMIT)
+        );
+
+        let tasks = render_calendar(&todos, Entry::Task, &now);
+        assert_eq!(tasks.matches("BEGIN:VTODO").count(), 3);
+        assert!(tasks.contains("DUE:20250315T090000Z\r\n"));
+        assert!(tasks.contains("STATUS:COMPLETED\r\n"));
+    }
+}
@@ -0,0 +1,123 @@
+// PROMETHEUS METRICS: the stats snapshot in the text exposition format, scraped from `/metrics`
+use crate::arguments::report::StatsSnapshot;
+
+// Label values are quoted, backslashes, quotes and line breaks need escaping
+fn label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(String, usize)]) {
+    out.push_str(&format!(
+        "# HELP {} {}\n# TYPE {} {}\n",
+        name, help, name, kind
+    ));
+    for (labels, value) in samples {
+        out.push_str(&format!("{}{} {}\n", name, labels, value));
+    }
+}
+
+fn labelled<'a>(
+    name: &str,
+    counts: impl IntoIterator<Item = (&'a String, &'a usize)>,
+) -> Vec<(String, usize)> {
+    counts
+        .into_iter()
+        .map(|(value, count)| (format!("{{{}=\"{}\"}}", name, label(value)), *count))
+        .collect()
+}
+
+pub fn render_metrics(snapshot: &StatsSnapshot) -> String {
+    let mut out = String::new();
+    metric(
+        &mut out,
+        "voido_todos",
+        "gauge",
+        "Todos by status, archived ones left out",
+        &labelled("status", &snapshot.by_status),
+    );
+    metric(
+        &mut out,
+        "voido_open_todos",
+        "gauge",
+        "Todos not done yet, by priority",
+        &labelled("priority", &snapshot.open_by_priority),
+    );
+    metric(
+        &mut out,
+        "voido_overdue_todos",
+        "gauge",
+        "Open todos past their due date",
+        &[(String::new(), snapshot.overdue)],
+    );
+    metric(
+        &mut out,
+        "voido_completions_today",
+        "gauge",
+        "Todos marked Done today",
+        &[(String::new(), snapshot.completed_today)],
+    );
+    metric(
+        &mut out,
+        "voido_completions_total",
+        "counter",
+        "Todos marked Done since the history started",
+        &[(String::new(), snapshot.completed_total)],
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arguments::models::Todo;
+    use crate::arguments::report::stats_snapshot;
+    use chrono::{Duration, Local, NaiveDate, TimeZone, Utc};
+
+    fn todo(status: &str, priority: &str, due: Option<NaiveDate>) -> Todo {
+        Todo {
+            priority: priority.to_string(),
+            status: status.to_string(),
+            due,
+            ..Todo::sample("Ship")
+        }
+    }
+
+    #[test]
+    fn test_render_metrics() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 14).unwrap();
+        let yesterday = today - Duration::days(1);
+        let todos = vec![
+            todo("Pending", "High", Some(yesterday)),
+            todo("Pending", "Low", None),
+            todo("Done", "High", Some(yesterday)),
+            todo("Waiting \"QA\"", "High", None),
+        ];
+        let noon = |date: NaiveDate| {
+            Local
+                .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let history = vec![
+            (3, "Done".to_string(), noon(yesterday)),
+            (3, "Pending".to_string(), noon(today)),
+            (3, "Done".to_string(), noon(today)),
+        ];
+
+        let snapshot = stats_snapshot(&todos, &history, today);
+        assert_eq!(snapshot.open, 3);
+        assert_eq!(snapshot.open_by_owner.get("Joe"), Some(&3));
+
+        let metrics = render_metrics(&snapshot);
+        assert!(metrics.contains("# TYPE voido_todos gauge\n"));
+        assert!(metrics.contains("voido_todos{status=\"Pending\"} 2\n"));
+        assert!(metrics.contains("voido_todos{status=\"Waiting \\\"QA\\\"\"} 1\n"));
+        assert!(metrics.contains("voido_open_todos{priority=\"High\"} 2\n"));
+        assert!(metrics.contains("voido_overdue_todos 1\n"));
+        assert!(metrics.contains("voido_completions_today 1\n"));
+        assert!(metrics.contains("voido_completions_total 2\n"));
+    }
+}
@@ -0,0 +1,204 @@
+// SERVE MODE: a small read-only HTTP server for tools that poll VoiDo. Every request reads the
+// database again, so the answers follow changes made from the TUI or the CLI meanwhile.
+use std::{error::Error, time::Duration};
+
+use chrono::{Local, Utc};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::arguments::report;
+use crate::database::DBtodo;
+
+pub mod board;
+pub mod calendar;
+pub mod metrics;
+
+// Requests are a line and a few headers, anything bigger is not for us
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, PartialEq, Eq)]
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn text(status: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into(),
+        }
+    }
+}
+
+// "GET /metrics?x=1 HTTP/1.1" → ("GET", "/metrics")
+fn parse_request_line(request: &str) -> Option<(&str, &str)> {
+    let mut parts = request.lines().next()?.split_whitespace();
+    let method = parts.next()?;
+    let target = parts.next()?;
+    parts.next()?.starts_with("HTTP/").then_some(())?;
+    Some((method, target.split(['?', '#']).next().unwrap_or(target)))
+}
+
+fn metrics_response() -> Result<Response, Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    let snapshot = report::stats_snapshot(
+        &db.get_todos()?,
+        &db.status_history()?,
+        Local::now().date_naive(),
+    );
+    let body = metrics::render_metrics(&snapshot);
+    Ok(Response {
+        status: "200 OK",
+        content_type: "text/plain; version=0.0.4; charset=utf-8",
+        body,
+    })
+}
+
+fn board_response() -> Result<Response, Box<dyn Error>> {
+    let todos = DBtodo::new()?.get_todos()?;
+    Ok(Response {
+        status: "200 OK",
+        content_type: "text/html; charset=utf-8",
+        body: board::render_board(&todos, Local::now().date_naive()),
+    })
+}
+
+fn calendar_response(entry: calendar::Entry) -> Result<Response, Box<dyn Error>> {
+    let todos = DBtodo::new()?.get_todos()?;
+    Ok(Response {
+        status: "200 OK",
+        content_type: "text/calendar; charset=utf-8",
+        body: calendar::render_calendar(&todos, entry, &Utc::now()),
+    })
+}
+
+// Pages served next to /metrics, each one asked for on the command line
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pages {
+    pub board: bool,
+    pub calendar: bool,
+}
+
+fn route(method: &str, path: &str, pages: Pages) -> Response {
+    if method != "GET" && method != "HEAD" {
+        return Response::text("405 Method Not Allowed", "VoiDo is read-only here\n");
+    }
+    let result = match path {
+        "/metrics" => metrics_response(),
+        "/" | "/board" if pages.board => board_response(),
+        "/calendar.ics" if pages.calendar => calendar_response(calendar::Entry::Event),
+        "/tasks.ics" if pages.calendar => calendar_response(calendar::Entry::Task),
+        _ => return Response::text("404 Not Found", "Try /metrics\n"),
+    };
+    // The details stay in this terminal, they may name paths on this machine
+    result.unwrap_or_else(|e| {
+        eprintln!("❌ {} {}: {}", method, path, e);
+        Response::text(
+            "500 Internal Server Error",
+            "VoiDo could not read its todos\n",
+        )
+    })
+}
+
+async fn handle(mut stream: TcpStream, pages: Pages) -> Result<(), Box<dyn Error>> {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut buffer)).await??;
+        if read == 0 || request.len() + read > MAX_REQUEST_BYTES {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let (method, response) = match parse_request_line(&request) {
+        Some((method, path)) => {
+            let (method, path) = (method.to_string(), path.to_string());
+            // SQLite reads block, keep them off the threads serving the other requests
+            tokio::task::spawn_blocking(move || {
+                let response = route(&method, &path, pages);
+                (method, response)
+            })
+            .await?
+        }
+        None => (
+            "GET".to_string(),
+            Response::text("400 Bad Request", "Bad request\n"),
+        ),
+    };
+    let mut reply = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    if method != "HEAD" {
+        reply.push_str(&response.body);
+    }
+    stream.write_all(reply.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+pub async fn serve(host: &str, port: u16, pages: Pages) -> Result<(), Box<dyn Error>> {
+    let listener = TcpListener::bind((host, port)).await?;
+    println!(
+        "📡 Serving http://{}:{}/metrics, Ctrl-C to stop",
+        host, port
+    );
+    if pages.board {
+        println!("📋 Board at http://{}:{}/", host, port);
+    }
+    if pages.calendar {
+        println!(
+            "🗓️ Calendar at http://{}:{}/calendar.ics, tasks at /tasks.ics",
+            host, port
+        );
+    }
+    if (pages.board || pages.calendar) && (host == "127.0.0.1" || host == "localhost") {
+        println!("   Only this machine can see it, use --host 0.0.0.0 to share it on the LAN");
+    }
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            // A client that hangs up early is not our problem
+            let _ = handle(stream, pages).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request_line() {
+        assert_eq!(
+            parse_request_line("GET /metrics?name=x HTTP/1.1\r\nHost: localhost\r\n\r\n"),
+            Some(("GET", "/metrics"))
+        );
+        assert_eq!(parse_request_line("GET /metrics"), None);
+        assert_eq!(parse_request_line(""), None);
+        let all = Pages {
+            board: true,
+            calendar: true,
+        };
+        assert_eq!(
+            route("POST", "/metrics", Pages::default()).status,
+            "405 Method Not Allowed"
+        );
+        assert_eq!(route("GET", "/nope", all).status, "404 Not Found");
+        // The board and the calendar are only there when asked for
+        assert_eq!(route("GET", "/", Pages::default()).status, "404 Not Found");
+        assert_eq!(
+            route("GET", "/calendar.ics", Pages::default()).status,
+            "404 Not Found"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+// SETTINGS SCREEN: the options that can be changed from the TUI and written back to config.toml
+// Owner and topic defaults are free text, so they are only set in the file itself
+use crate::colors::Theme;
+use crate::configs::{
+    DATE_FORMAT_PRESETS, DUE_OFFSET_PRESETS, DefaultsConfig, DisplayConfig, PRIORITY_PRESETS,
+};
+use crate::icons::IconSet;
+use crate::ui::{Column, RowNumbers};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsItem {
+    Theme,
+    DateFormat,
+    WeekStart,
+    Density,
+    Icons,
+    Countdown,
+    RowNumbers,
+    Column(Column),
+    DefaultPriority,
+    DefaultDueOffset,
+}
+
+// Everything listed on the settings screen, in display order
+pub fn settings_items() -> Vec<SettingsItem> {
+    let mut items = vec![
+        SettingsItem::Theme,
+        SettingsItem::DateFormat,
+        SettingsItem::WeekStart,
+        SettingsItem::Density,
+        SettingsItem::Icons,
+        SettingsItem::Countdown,
+        SettingsItem::RowNumbers,
+    ];
+    // The icon, countdown and row number columns have settings of their own
+    items.extend(
+        Column::ALL
+            .into_iter()
+            .filter(|column| {
+                !matches!(
+                    column,
+                    Column::Todo | Column::Icons | Column::Countdown | Column::Number
+                )
+            })
+            .map(SettingsItem::Column),
+    );
+    items.extend([
+        SettingsItem::DefaultPriority,
+        SettingsItem::DefaultDueOffset,
+    ]);
+    items
+}
+
+// Next value in a list of options, wrapping around
+fn cycle<'a>(options: &[&'a str], current: &str) -> &'a str {
+    let index = options
+        .iter()
+        .position(|option| *option == current)
+        .map_or(0, |index| (index + 1) % options.len());
+    options[index]
+}
+
+impl SettingsItem {
+    pub fn label(self) -> String {
+        match self {
+            SettingsItem::Theme => "Theme".to_string(),
+            SettingsItem::DateFormat => "Date format".to_string(),
+            SettingsItem::WeekStart => "Week starts on".to_string(),
+            SettingsItem::Density => "Density".to_string(),
+            SettingsItem::Icons => "Priority and status icons".to_string(),
+            SettingsItem::Countdown => "Days left column".to_string(),
+            SettingsItem::RowNumbers => "Row numbers".to_string(),
+            SettingsItem::Column(column) => format!("Show {} column", column.header(false)),
+            SettingsItem::DefaultPriority => "Default priority".to_string(),
+            SettingsItem::DefaultDueOffset => "Default due date".to_string(),
+        }
+    }
+
+    pub fn value(self, config: &DisplayConfig, defaults: &DefaultsConfig) -> String {
+        match self {
+            SettingsItem::Theme => config.theme.clone(),
+            SettingsItem::DateFormat => format!(
+                "{} ({})",
+                config.date_format,
+                chrono::Local::now().format(&config.date_format)
+            ),
+            SettingsItem::WeekStart => match config.week_starts_on {
+                chrono::Weekday::Sun => "Sunday".to_string(),
+                _ => "Monday".to_string(),
+            },
+            SettingsItem::Density => {
+                if config.compact {
+                    "Compact".to_string()
+                } else {
+                    "Comfortable".to_string()
+                }
+            }
+            SettingsItem::Icons => config.icons.name().to_string(),
+            SettingsItem::RowNumbers => config.row_numbers.name().to_string(),
+            SettingsItem::Countdown => {
+                if config.countdown {
+                    "[x]".to_string()
+                } else {
+                    "[ ]".to_string()
+                }
+            }
+            SettingsItem::Column(column) => {
+                if config.hidden_columns.iter().any(|key| key == column.key()) {
+                    "[ ]".to_string()
+                } else {
+                    "[x]".to_string()
+                }
+            }
+            SettingsItem::DefaultPriority => defaults.priority.clone(),
+            SettingsItem::DefaultDueOffset => match defaults.due_offset {
+                None => "none".to_string(),
+                Some(0) => "today".to_string(),
+                Some(days) => format!("in {} day(s)", days),
+            },
+        }
+    }
+
+    // Move the setting to its next value
+    pub fn change(self, config: &mut DisplayConfig, defaults: &mut DefaultsConfig) {
+        match self {
+            SettingsItem::Theme => config.theme = cycle(&Theme::NAMES, &config.theme).to_string(),
+            SettingsItem::DateFormat => {
+                config.date_format = cycle(&DATE_FORMAT_PRESETS, &config.date_format).to_string()
+            }
+            SettingsItem::WeekStart => {
+                config.week_starts_on = match config.week_starts_on {
+                    chrono::Weekday::Sun => chrono::Weekday::Mon,
+                    _ => chrono::Weekday::Sun,
+                }
+            }
+            SettingsItem::Density => config.compact = !config.compact,
+            SettingsItem::Countdown => config.countdown = !config.countdown,
+            SettingsItem::RowNumbers => {
+                config.row_numbers =
+                    RowNumbers::by_name(cycle(&RowNumbers::NAMES, config.row_numbers.name()))
+                        .unwrap_or(RowNumbers::Off)
+            }
+            SettingsItem::Icons => {
+                config.icons = IconSet::by_name(cycle(&IconSet::NAMES, config.icons.name()))
+                    .unwrap_or(IconSet::Off)
+            }
+            SettingsItem::Column(column) => {
+                let key = column.key().to_string();
+                if config.hidden_columns.contains(&key) {
+                    config.hidden_columns.retain(|hidden| *hidden != key);
+                } else {
+                    config.hidden_columns.push(key);
+                }
+            }
+            SettingsItem::DefaultPriority => {
+                let current = defaults.priority.to_lowercase();
+                defaults.priority = cycle(&PRIORITY_PRESETS, &current).to_string()
+            }
+            SettingsItem::DefaultDueOffset => {
+                let index = DUE_OFFSET_PRESETS
+                    .iter()
+                    .position(|offset| *offset == defaults.due_offset)
+                    .map_or(0, |index| (index + 1) % DUE_OFFSET_PRESETS.len());
+                defaults.due_offset = DUE_OFFSET_PRESETS[index];
+            }
+        }
+    }
+}
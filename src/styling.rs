@@ -0,0 +1,271 @@
+// CONDITIONAL ROW STYLING, rules from the [STYLING] config section:
+//
+//   due < today => fg red bold
+//   owner == "me" => bg darkgray
+//   priority >= medium => italic
+//
+// Every matching rule is applied in order, so later rules win on conflicts.
+use chrono::{Duration, Local, NaiveDate};
+use ratatui::style::{Modifier, Style};
+use std::cmp::Ordering;
+
+use crate::arguments::models::Todo;
+use crate::colors::parse_color;
+use crate::dates;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleField {
+    Id,
+    Priority,
+    Topic,
+    Todo,
+    Status,
+    Owner,
+    Due,
+    Created,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone)]
+pub struct StyleRule {
+    pub field: RuleField,
+    pub op: RuleOp,
+    pub value: String,
+    pub style: Style,
+}
+
+// Longest operators first so "<=" is not read as "<"
+const OPERATORS: [(&str, RuleOp); 8] = [
+    (" contains ", RuleOp::Contains),
+    ("==", RuleOp::Eq),
+    ("!=", RuleOp::Ne),
+    ("<=", RuleOp::Le),
+    (">=", RuleOp::Ge),
+    ("<", RuleOp::Lt),
+    (">", RuleOp::Gt),
+    ("~", RuleOp::Contains),
+];
+
+impl StyleRule {
+    pub fn parse(rule: &str) -> Result<Self, String> {
+        let (condition, style) = rule
+            .split_once("=>")
+            .ok_or("expected `<condition> => <style>`")?;
+
+        let (field, op, value) = OPERATORS
+            .iter()
+            .find_map(|(symbol, op)| {
+                condition
+                    .split_once(symbol)
+                    .map(|(field, value)| (field, *op, value))
+            })
+            .ok_or("expected one of ==, !=, <, <=, >, >=, contains")?;
+
+        let field = match field.trim().to_lowercase().as_str() {
+            "id" => RuleField::Id,
+            "priority" => RuleField::Priority,
+            "topic" => RuleField::Topic,
+            "todo" | "text" => RuleField::Todo,
+            "status" => RuleField::Status,
+            "owner" => RuleField::Owner,
+            "due" => RuleField::Due,
+            "created" => RuleField::Created,
+            other => return Err(format!("unknown field `{}`", other)),
+        };
+
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+        if value.is_empty() {
+            return Err("missing value to compare with".to_string());
+        }
+        // Catch bad dates now rather than silently never matching
+        if matches!(field, RuleField::Due | RuleField::Created)
+            && resolve_date(value, Local::now().date_naive()).is_none()
+        {
+            return Err(format!("`{}` is not a date", value));
+        }
+
+        Ok(Self {
+            field,
+            op,
+            value: value.to_string(),
+            style: parse_style(style)?,
+        })
+    }
+
+    pub fn matches(&self, todo: &Todo, today: NaiveDate) -> bool {
+        let ordering = match self.field {
+            RuleField::Due => match (todo.due, resolve_date(&self.value, today)) {
+                (Some(due), Some(date)) => due.cmp(&date),
+                _ => return false,
+            },
+            RuleField::Created => match resolve_date(&self.value, today) {
+                Some(date) => todo
+                    .date_added
+                    .with_timezone(&Local)
+                    .date_naive()
+                    .cmp(&date),
+                None => return false,
+            },
+            RuleField::Id => match self.value.parse::<i64>() {
+                Ok(id) => (todo.id as i64).cmp(&id),
+                Err(_) => return false,
+            },
+            RuleField::Priority => {
+                match (priority_rank(&todo.priority), priority_rank(&self.value)) {
+                    (Some(left), Some(right)) if self.op != RuleOp::Contains => left.cmp(&right),
+                    _ => return compare_text(&todo.priority, self.op, &self.value),
+                }
+            }
+            RuleField::Topic => return compare_text(&todo.topic, self.op, &self.value),
+            RuleField::Todo => return compare_text(&todo.text, self.op, &self.value),
+            RuleField::Status => return compare_text(&todo.status, self.op, &self.value),
+            RuleField::Owner => return compare_text(&todo.owner, self.op, &self.value),
+        };
+        compare(self.op, ordering)
+    }
+}
+
+// Combined style of every rule that matches the todo
+pub fn row_style(rules: &[StyleRule], todo: &Todo, today: NaiveDate) -> Style {
+    rules
+        .iter()
+        .filter(|rule| rule.matches(todo, today))
+        .fold(Style::default(), |style, rule| style.patch(rule.style))
+}
+
+fn compare(op: RuleOp, ordering: Ordering) -> bool {
+    match op {
+        RuleOp::Eq => ordering == Ordering::Equal,
+        RuleOp::Ne => ordering != Ordering::Equal,
+        RuleOp::Lt => ordering == Ordering::Less,
+        RuleOp::Le => ordering != Ordering::Greater,
+        RuleOp::Gt => ordering == Ordering::Greater,
+        RuleOp::Ge => ordering != Ordering::Less,
+        RuleOp::Contains => false,
+    }
+}
+
+// Text fields compare case-insensitively
+fn compare_text(text: &str, op: RuleOp, value: &str) -> bool {
+    let text = text.to_lowercase();
+    let value = value.to_lowercase();
+    match op {
+        RuleOp::Contains => text.contains(&value),
+        _ => compare(op, text.cmp(&value)),
+    }
+}
+
+fn priority_rank(priority: &str) -> Option<u8> {
+    match priority.trim().to_lowercase().as_str() {
+        "low" => Some(0),
+        "normal" => Some(1),
+        "medium" => Some(2),
+        "high" => Some(3),
+        _ => None,
+    }
+}
+
+// `today`, `today+3`, `today-7` or anything the due date parser understands
+fn resolve_date(value: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let value = value.trim().to_lowercase();
+    if let Some(offset) = value.strip_prefix("today") {
+        let offset = offset.trim().replace(' ', "");
+        if offset.is_empty() {
+            return Some(today);
+        }
+        let days = offset
+            .strip_prefix('+')
+            .unwrap_or(&offset)
+            .parse::<i64>()
+            .ok()?;
+        return Some(today + Duration::days(days));
+    }
+    dates::parse_due(&value, today)
+}
+
+// `fg red bold`, `bg #202020 italic`...
+fn parse_style(style: &str) -> Result<Style, String> {
+    let mut result = Style::default();
+    let mut words = style.split_whitespace();
+    while let Some(word) = words.next() {
+        result = match word.to_lowercase().as_str() {
+            "fg" | "bg" => {
+                let color = words
+                    .next()
+                    .ok_or(format!("missing color after `{}`", word))?;
+                let color =
+                    parse_color(color).ok_or_else(|| format!("`{}` is not a color", color))?;
+                if word.eq_ignore_ascii_case("fg") {
+                    result.fg(color)
+                } else {
+                    result.bg(color)
+                }
+            }
+            "bold" => result.add_modifier(Modifier::BOLD),
+            "dim" => result.add_modifier(Modifier::DIM),
+            "italic" => result.add_modifier(Modifier::ITALIC),
+            "underline" | "underlined" => result.add_modifier(Modifier::UNDERLINED),
+            "reversed" => result.add_modifier(Modifier::REVERSED),
+            "strikethrough" | "crossed_out" => result.add_modifier(Modifier::CROSSED_OUT),
+            other => return Err(format!("unknown style `{}`", other)),
+        };
+    }
+    if result == Style::default() {
+        return Err("the rule has no style".to_string());
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    fn todo(priority: &str, owner: &str, due: Option<NaiveDate>) -> Todo {
+        Todo {
+            id: 1,
+            priority: priority.to_string(),
+            owner: owner.to_string(),
+            due,
+            ..Todo::sample("Test")
+        }
+    }
+
+    #[test]
+    fn test_parse_and_match_rules() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let overdue = StyleRule::parse("due < today => fg red bold").unwrap();
+        assert_eq!(
+            overdue.style,
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        );
+        assert!(overdue.matches(&todo("Low", "Joe", today.pred_opt()), today));
+        assert!(!overdue.matches(&todo("Low", "Joe", Some(today)), today));
+        assert!(!overdue.matches(&todo("Low", "Joe", None), today));
+
+        let mine = StyleRule::parse(r#"owner == "me" => bg darkgray"#).unwrap();
+        assert!(mine.matches(&todo("Low", "Me", None), today));
+
+        let urgent = StyleRule::parse("priority >= medium => italic").unwrap();
+        assert!(urgent.matches(&todo("High", "Joe", None), today));
+        assert!(!urgent.matches(&todo("Low", "Joe", None), today));
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        assert!(StyleRule::parse("due < today").is_err());
+        assert!(StyleRule::parse("colour == red => bold").is_err());
+        assert!(StyleRule::parse("due < someday => bold").is_err());
+        assert!(StyleRule::parse("owner == me => fg nocolor").is_err());
+    }
+}
@@ -0,0 +1,399 @@
+// SYNC DAEMON: `voido daemon` keeps running and syncs each backend of [SYNC.schedule] on its own
+// schedule, an interval or a cron expression. A random jitter keeps a few machines from syncing
+// on the same second and a failed sync is retried later each time. Every run is written to
+// sync.log and to the sync state the TUI shows, with the time of the next one.
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::hash::BuildHasher;
+use std::io::Write;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike, Utc};
+
+use crate::configs::{self, SyncConfig};
+use crate::database::DBtodo;
+use crate::dates::{self, Timezone};
+
+// The first retry after a failure, doubled for each failure after it
+const BACKOFF_START: Duration = Duration::from_secs(60);
+const BACKOFF_MAX: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    Every(Duration),
+    Cron(Cron),
+}
+
+// "minute hour day month weekday", each field a bit per value it allows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    // Cron runs on the day or the weekday when both are set, on the one that is set otherwise
+    any_day: bool,
+    any_weekday: bool,
+}
+
+// "*", "5", "1-5", "*/15", "0-30/10" and lists of them like "1,15"
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let number = |value: &str| {
+        value
+            .parse::<u32>()
+            .map_err(|_| format!("\"{}\" is not a number", value))
+    };
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match number(step)? {
+                0 => return Err(format!("\"{}\" steps by 0", part)),
+                step => (range, step),
+            },
+            None => (part, 1),
+        };
+        let (from, to) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((from, to)) => (number(from)?, number(to)?),
+            // "5/15" is every 15 from 5
+            None if step > 1 => (number(range)?, max),
+            None => (number(range)?, number(range)?),
+        };
+        if from < min || to > max || from > to {
+            return Err(format!("\"{}\" is outside {}-{}", part, min, max));
+        }
+        for value in (from..=to).step_by(step as usize) {
+            bits |= 1 << value;
+        }
+    }
+    Ok(bits)
+}
+
+impl Cron {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let fields = expression.split_whitespace().collect::<Vec<&str>>();
+        let [minutes, hours, days, months, weekdays] = fields[..] else {
+            return Err(format!(
+                "\"{}\" needs five fields: minute hour day month weekday",
+                expression
+            ));
+        };
+        let mut weekday_bits = parse_field(weekdays, 0, 7)?;
+        // 7 is Sunday too
+        if weekday_bits & 1 << 7 != 0 {
+            weekday_bits |= 1;
+        }
+        Ok(Self {
+            minutes: parse_field(minutes, 0, 59)?,
+            hours: parse_field(hours, 0, 23)?,
+            days: parse_field(days, 1, 31)?,
+            months: parse_field(months, 1, 12)?,
+            weekdays: weekday_bits,
+            any_day: days == "*",
+            any_weekday: weekdays == "*",
+        })
+    }
+
+    fn on_day(&self, date: NaiveDate) -> bool {
+        let day = self.days & 1 << date.day() != 0;
+        let weekday = self.weekdays & 1 << date.weekday().num_days_from_sunday() != 0;
+        match (self.any_day, self.any_weekday) {
+            (true, _) => weekday,
+            (_, true) => day,
+            _ => day || weekday,
+        }
+    }
+
+    // The first minute after `after` it runs at, none when it never does (February 30th)
+    pub fn next(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut at = after.with_second(0)?.with_nanosecond(0)? + TimeDelta::minutes(1);
+        // Leap days come back every four years
+        let limit = at + TimeDelta::days(4 * 366);
+        while at < limit {
+            if self.months & 1 << at.month() == 0 {
+                let (year, month) = match at.month() {
+                    12 => (at.year() + 1, 1),
+                    month => (at.year(), month + 1),
+                };
+                at = NaiveDate::from_ymd_opt(year, month, 1)?.and_time(NaiveTime::MIN);
+            } else if !self.on_day(at.date()) {
+                at = at.date().succ_opt()?.and_time(NaiveTime::MIN);
+            } else if self.hours & 1 << at.hour() == 0 {
+                at = at.with_minute(0)? + TimeDelta::hours(1);
+            } else if self.minutes & 1 << at.minute() == 0 {
+                at += TimeDelta::minutes(1);
+            } else {
+                return Some(at);
+            }
+        }
+        None
+    }
+}
+
+impl Schedule {
+    // "90s", "30m", "2h", "1d", or a cron expression
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if value.split_whitespace().count() > 1 {
+            let cron = Cron::parse(value)?;
+            cron.next(Utc::now().naive_utc())
+                .ok_or_else(|| format!("\"{}\" never runs", value))?;
+            return Ok(Self::Cron(cron));
+        }
+
+        let unit_at = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
+        let (count, unit) = value.split_at(unit_at);
+        let seconds = match unit {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            _ => {
+                return Err(format!(
+                    "\"{}\" is not an interval like \"30m\" or a cron expression like \"*/15 * * * *\"",
+                    value
+                ));
+            }
+        };
+        let every = count
+            .parse::<u64>()
+            .map_err(|_| format!("\"{}\" has no number", value))?
+            * seconds;
+        if every < 60 {
+            return Err(format!("\"{}\" is less than a minute apart", value));
+        }
+        Ok(Self::Every(Duration::from_secs(every)))
+    }
+
+    // Cron expressions read the clock in the [DISPLAY] timezone
+    pub fn next(&self, after: DateTime<Utc>, timezone: &Timezone) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Every(every) => Some(after + TimeDelta::from_std(*every).ok()?),
+            Self::Cron(cron) => {
+                let mut clock = timezone.clock(&after);
+                loop {
+                    clock = cron.next(clock)?;
+                    // A time the clocks skip over when they go forward isn't a time
+                    if let Some(at) = timezone.utc_at(clock) {
+                        return Some(at);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// How long to wait after `failures` syncs in a row failed
+fn backoff(failures: u32) -> Duration {
+    match failures {
+        0 => Duration::ZERO,
+        failures => BACKOFF_START
+            .saturating_mul(2u32.saturating_pow(failures - 1))
+            .min(BACKOFF_MAX),
+    }
+}
+
+// The next run after one that ended at `now`: the schedule's, unless the backoff says later
+pub fn next_run(
+    schedule: &Schedule,
+    now: DateTime<Utc>,
+    failures: u32,
+    timezone: &Timezone,
+) -> Option<DateTime<Utc>> {
+    let scheduled = schedule.next(now, timezone)?;
+    let retry = now + TimeDelta::from_std(backoff(failures)).ok()?;
+    Some(scheduled.max(retry))
+}
+
+fn jitter(max_seconds: u64) -> TimeDelta {
+    if max_seconds == 0 {
+        return TimeDelta::zero();
+    }
+    let random = std::collections::hash_map::RandomState::new().hash_one(Utc::now());
+    TimeDelta::seconds((random % (max_seconds + 1)) as i64)
+}
+
+#[derive(Debug)]
+struct Job {
+    backend: String,
+    schedule: Schedule,
+    next: DateTime<Utc>,
+    failures: u32,
+}
+
+// A line in sync.log next to the database, and on the terminal
+fn log(line: &str) {
+    println!("{}", line);
+    let Ok(path) = configs::data_dir().map(|dir| dir.join("sync.log")) else {
+        return;
+    };
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "{} {}", Utc::now().to_rfc3339(), line);
+    }
+}
+
+fn show_next_runs(jobs: &[Job], running: bool) {
+    if let Ok(db) = DBtodo::new() {
+        for job in jobs {
+            let _ = db.record_next_run(&job.backend, running.then_some(job.next));
+        }
+    }
+}
+
+pub async fn run() -> Result<(), Box<dyn Error>> {
+    let config = SyncConfig::load();
+    let timezone = dates::timezone();
+    let now = Utc::now();
+    let mut jobs = Vec::new();
+    for (backend, when) in &config.schedule {
+        if !configs::SYNC_BACKENDS.contains(&backend.as_str()) {
+            return Err(format!("[SYNC.schedule] \"{}\" is not a sync backend", backend).into());
+        }
+        let schedule =
+            Schedule::parse(when).map_err(|e| format!("[SYNC.schedule] {}: {}", backend, e))?;
+        // An interval starts with a sync, a cron expression waits for its time
+        let next = match schedule {
+            Schedule::Every(_) => now,
+            Schedule::Cron(_) => schedule.next(now, &timezone).unwrap_or(now),
+        };
+        jobs.push(Job {
+            backend: backend.clone(),
+            schedule,
+            next,
+            failures: 0,
+        });
+    }
+    if jobs.is_empty() {
+        return Err(
+            "Nothing to schedule, add a backend to [SYNC.schedule] in config.toml, e.g. github = \"30m\""
+                .into(),
+        );
+    }
+    println!(
+        "🕒 Sync daemon started for {}, Ctrl+C stops it",
+        config
+            .schedule
+            .iter()
+            .map(|(backend, when)| format!("{} ({})", backend, when))
+            .collect::<Vec<String>>()
+            .join(", ")
+    );
+
+    loop {
+        show_next_runs(&jobs, true);
+        let Some(job) = jobs.iter_mut().min_by_key(|job| job.next) else {
+            return Ok(());
+        };
+        let wait = (job.next - Utc::now()).to_std().unwrap_or_default();
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            _ = tokio::signal::ctrl_c() => {
+                show_next_runs(&jobs, false);
+                println!("👋 Sync daemon stopped");
+                return Ok(());
+            }
+        }
+
+        // Background syncs never stop to ask for a sign-in, the outcome is in the sync state
+        let result = super::run_backend(&job.backend, false, true).await;
+        let now = Utc::now();
+        job.failures = match result {
+            Ok(_) => 0,
+            Err(_) => job.failures + 1,
+        };
+        job.next = next_run(&job.schedule, now, job.failures, &timezone)
+            .unwrap_or(now + TimeDelta::days(1))
+            + jitter(config.jitter);
+        let next = timezone.clock(&job.next).format("%Y-%m-%d %H:%M");
+        match result {
+            Ok(report) => log(&format!(
+                "✅ {}: {}, next sync {}",
+                job.backend,
+                report.summary(),
+                next
+            )),
+            Err(e) => log(&format!(
+                "❌ {} failed ({} in a row): {}, next try {}",
+                job.backend,
+                job.failures,
+                e.to_string().lines().next().unwrap_or_default(),
+                next
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_schedules() {
+        assert_eq!(
+            Schedule::parse("30m"),
+            Ok(Schedule::Every(Duration::from_secs(30 * 60)))
+        );
+        assert_eq!(
+            Schedule::parse("2h"),
+            Ok(Schedule::Every(Duration::from_secs(2 * 60 * 60)))
+        );
+        assert!(Schedule::parse("30s").is_err());
+        assert!(Schedule::parse("soon").is_err());
+        assert!(Schedule::parse("61 * * * *").is_err());
+        assert!(Schedule::parse("* * *").is_err());
+        assert!(Schedule::parse("0 0 30 2 *").is_err());
+        assert!(matches!(
+            Schedule::parse("*/15 8-18 * * 1-5"),
+            Ok(Schedule::Cron(_))
+        ));
+    }
+
+    #[test]
+    fn test_cron_next_run() {
+        let at = |value: &str| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap();
+        // Friday 2025-06-13
+        let cron = Cron::parse("*/15 8-18 * * 1-5").unwrap();
+        assert_eq!(
+            cron.next(at("2025-06-13 09:07")),
+            Some(at("2025-06-13 09:15"))
+        );
+        assert_eq!(
+            cron.next(at("2025-06-13 18:45")),
+            Some(at("2025-06-16 08:00"))
+        );
+
+        // The 1st of the month or any Sunday
+        let cron = Cron::parse("30 6 1 * 0").unwrap();
+        assert_eq!(
+            cron.next(at("2025-06-13 12:00")),
+            Some(at("2025-06-15 06:30"))
+        );
+        assert_eq!(
+            cron.next(at("2025-06-29 12:00")),
+            Some(at("2025-07-01 06:30"))
+        );
+
+        let cron = Cron::parse("0 0 29 2 *").unwrap();
+        assert_eq!(
+            cron.next(at("2025-03-01 00:00")),
+            Some(at("2028-02-29 00:00"))
+        );
+    }
+
+    #[test]
+    fn test_failures_back_off() {
+        let timezone = Timezone::Fixed(chrono::FixedOffset::east_opt(0).unwrap());
+        let now = DateTime::parse_from_rfc3339("2025-06-13T09:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let every = Schedule::parse("5m").unwrap();
+        let after = |failures| next_run(&every, now, failures, &timezone).unwrap() - now;
+        assert_eq!(after(0), TimeDelta::minutes(5));
+        assert_eq!(after(3), TimeDelta::minutes(5));
+        assert_eq!(after(4), TimeDelta::minutes(8));
+        assert_eq!(after(20), TimeDelta::hours(1));
+    }
+}
@@ -0,0 +1,323 @@
+// GIT FORGES: the server the git sync pushes to. github.com unless [GITHUB] remote points
+// somewhere else, a GitLab, a Gitea (Forgejo, Codeberg) or any other git server. With an access
+// token the first three also create the repository through their API.
+use std::error::Error;
+use std::io::{self, Write};
+
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::Deserialize;
+
+use super::keyring;
+
+const TOKEN_KEY: &str = "git_token";
+// Read before the keyring, for scripts and CI
+pub const TOKEN_ENV: &str = "VOIDO_GIT_TOKEN";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Forge {
+    GitHub,
+    GitLab,
+    Gitea,
+    // A plain git server, the repository has to exist already
+    Other,
+}
+
+impl Forge {
+    pub const NAMES: [&str; 3] = ["github", "gitlab", "gitea"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "github" => Some(Self::GitHub),
+            "gitlab" => Some(Self::GitLab),
+            "gitea" | "forgejo" | "codeberg" => Some(Self::Gitea),
+            _ => None,
+        }
+    }
+
+    // Guessed from the host when [GITHUB] forge isn't set
+    pub fn detect(host: &str) -> Self {
+        let host = host.to_lowercase();
+        if host == "github.com" {
+            Self::GitHub
+        } else if host.contains("gitlab") {
+            Self::GitLab
+        } else if host.contains("gitea") || host.contains("forgejo") || host == "codeberg.org" {
+            Self::Gitea
+        } else {
+            Self::Other
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::GitHub => "GitHub",
+            Self::GitLab => "GitLab",
+            Self::Gitea => "Gitea",
+            Self::Other => "Git",
+        }
+    }
+
+    // Git over HTTPS takes the token as the password, some servers want this user with it
+    pub fn token_user(self, remote: &RemoteUrl) -> String {
+        match self {
+            Self::GitHub => "x-access-token".to_string(),
+            Self::GitLab => "oauth2".to_string(),
+            Self::Gitea | Self::Other => remote.owner.clone(),
+        }
+    }
+}
+
+// A remote as git takes it: https://host/owner/repo.git, git@host:owner/repo.git or
+// ssh://git@host:port/owner/repo.git. GitLab owners can be groups with subgroups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteUrl {
+    pub url: String,
+    pub host: String,
+    pub port: Option<u16>,
+    pub ssh: bool,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RemoteUrl {
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (ssh, authority, path) = if let Some(rest) = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+        {
+            let (authority, path) = rest.split_once('/')?;
+            (false, authority, path)
+        } else if let Some(rest) = url.strip_prefix("ssh://") {
+            let (authority, path) = rest.split_once('/')?;
+            (true, authority, path)
+        } else {
+            // scp style, the part after the colon is the path
+            let (authority, path) = url.split_once(':')?;
+            if !authority.contains('@') {
+                return None;
+            }
+            (true, authority, path)
+        };
+
+        let host_port = authority
+            .rsplit_once('@')
+            .map_or(authority, |(_, host)| host);
+        let (host, port) = match host_port.split_once(':') {
+            Some((host, port)) => (host, Some(port.parse().ok()?)),
+            None => (host_port, None),
+        };
+        let path = path.trim_matches('/');
+        let (owner, repo) = path.rsplit_once('/')?;
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        if host.is_empty() || owner.is_empty() || repo.is_empty() {
+            return None;
+        }
+        Some(Self {
+            url: url.to_string(),
+            host: host.to_lowercase(),
+            port,
+            ssh,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    // Where the server answers, to know when it is back
+    pub fn address(&self) -> (String, u16) {
+        let default = match (self.ssh, self.url.starts_with("http://")) {
+            (true, _) => 22,
+            (false, true) => 80,
+            (false, false) => 443,
+        };
+        (self.host.clone(), self.port.unwrap_or(default))
+    }
+
+    // The web address of the server, where its API lives too. The SSH port says nothing about it.
+    pub fn web(&self) -> String {
+        match (self.ssh, self.port) {
+            (false, Some(port)) if self.url.starts_with("http://") => {
+                format!("http://{}:{}", self.host, port)
+            }
+            (false, Some(port)) => format!("https://{}:{}", self.host, port),
+            (false, None) if self.url.starts_with("http://") => format!("http://{}", self.host),
+            _ => format!("https://{}", self.host),
+        }
+    }
+
+    pub fn page(&self) -> String {
+        format!("{}/{}/{}", self.web(), self.owner, self.repo)
+    }
+}
+
+// VOIDO_GIT_TOKEN, or the one `voido sync github --token` stored
+pub fn token() -> Option<String> {
+    std::env::var(TOKEN_ENV)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .or_else(|| keyring::load(TOKEN_KEY))
+}
+
+pub fn save_token() -> Result<(), Box<dyn Error>> {
+    print!("🔑 Access token for the git server: ");
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let token = input.trim();
+    if token.is_empty() {
+        return Err("No token given, nothing stored".into());
+    }
+    keyring::store(TOKEN_KEY, token)?;
+    println!("✓ Token stored, the next sync pushes with it");
+    Ok(())
+}
+
+pub fn logout() -> Result<(), Box<dyn Error>> {
+    keyring::delete(TOKEN_KEY)?;
+    println!("👋 Forgot the git access token");
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Namespace {
+    id: u64,
+}
+
+// Create the repository, private. False when it was there already.
+pub async fn create_repo(
+    forge: Forge,
+    remote: &RemoteUrl,
+    token: &str,
+) -> Result<bool, Box<dyn Error>> {
+    let client = Client::new();
+    let web = remote.web();
+    let request = match forge {
+        Forge::GitHub | Forge::Gitea => {
+            let (api, auth) = match forge {
+                Forge::GitHub if remote.host == "github.com" => (
+                    "https://api.github.com".to_string(),
+                    format!("Bearer {}", token),
+                ),
+                // GitHub Enterprise Server
+                Forge::GitHub => (format!("{}/api/v3", web), format!("Bearer {}", token)),
+                _ => (format!("{}/api/v1", web), format!("token {}", token)),
+            };
+            let authorized = |request: RequestBuilder| {
+                request
+                    .header("Authorization", &auth)
+                    .header("User-Agent", "voido")
+            };
+            let user = authorized(client.get(format!("{}/user", api)))
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<User>()
+                .await?;
+            // The owner is the token's user or one of their organizations
+            let url = if user.login.eq_ignore_ascii_case(&remote.owner) {
+                format!("{}/user/repos", api)
+            } else {
+                format!("{}/orgs/{}/repos", api, remote.owner)
+            };
+            authorized(client.post(url)).json(&serde_json::json!({
+                "name": remote.repo,
+                "private": true,
+            }))
+        }
+        Forge::GitLab => {
+            let api = format!("{}/api/v4", web);
+            let namespace = client
+                .get(format!(
+                    "{}/namespaces/{}",
+                    api,
+                    remote.owner.replace('/', "%2F")
+                ))
+                .header("PRIVATE-TOKEN", token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Namespace>()
+                .await?;
+            client
+                .post(format!("{}/projects", api))
+                .header("PRIVATE-TOKEN", token)
+                .json(&serde_json::json!({
+                    "name": remote.repo,
+                    "path": remote.repo,
+                    "namespace_id": namespace.id,
+                    "visibility": "private",
+                }))
+        }
+        Forge::Other => {
+            return Err(format!(
+                "{} has no API VoiDo knows, create {} there yourself or set [GITHUB] forge",
+                remote.host, remote.url
+            )
+            .into());
+        }
+    };
+
+    let response = request.send().await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(true);
+    }
+    let body = response.text().await.unwrap_or_default();
+    // GitHub says 422, Gitea 409 and GitLab 400 "has already been taken"
+    if status == StatusCode::CONFLICT
+        || (status == StatusCode::UNPROCESSABLE_ENTITY && body.contains("already exists"))
+        || (status == StatusCode::BAD_REQUEST && body.contains("already been taken"))
+    {
+        return Ok(false);
+    }
+    Err(format!(
+        "{} could not create {}/{} ({}): {}",
+        forge.name(),
+        remote.owner,
+        remote.repo,
+        status,
+        body
+    )
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_remote_urls() {
+        let remote = RemoteUrl::parse("git@gitlab.com:acme/team/voido.git").unwrap();
+        assert_eq!(
+            (
+                remote.host.as_str(),
+                remote.owner.as_str(),
+                remote.repo.as_str()
+            ),
+            ("gitlab.com", "acme/team", "voido")
+        );
+        assert!(remote.ssh);
+        assert_eq!(remote.address(), ("gitlab.com".to_string(), 22));
+        assert_eq!(remote.web(), "https://gitlab.com");
+
+        let remote = RemoteUrl::parse("ssh://git@git.example.com:2222/me/todos.git").unwrap();
+        assert_eq!(remote.address(), ("git.example.com".to_string(), 2222));
+        assert_eq!(remote.page(), "https://git.example.com/me/todos");
+
+        let remote = RemoteUrl::parse("http://gitea.local:3000/me/todos").unwrap();
+        assert!(!remote.ssh);
+        assert_eq!(remote.web(), "http://gitea.local:3000");
+        assert_eq!(Forge::detect(&remote.host), Forge::Gitea);
+
+        assert_eq!(RemoteUrl::parse("voido_sync"), None);
+        assert_eq!(RemoteUrl::parse("https://github.com/voido"), None);
+        assert_eq!(Forge::detect("github.com"), Forge::GitHub);
+        assert_eq!(Forge::detect("git.example.com"), Forge::Other);
+    }
+}
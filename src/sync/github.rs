@@ -0,0 +1,896 @@
+use serde_json;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{fs, io};
+
+use super::forge::{self, Forge, RemoteUrl};
+use super::journal::Delta;
+use super::{SyncBackend, SyncReport};
+use crate::arguments::models::Todo;
+use crate::database::{DBtodo, JournalEntry};
+use crate::{configs, data};
+
+// Journal files in the repository before they are folded into a new snapshot
+const COMPACT_AFTER: usize = 100;
+
+// The only files a sync commits: the snapshot and journal of the synced topics. The database
+// changes on every write and holds every topic and the Gemini API key, it never leaves the
+// machine, nor do config.toml and the other profiles' folders.
+const SYNCED_FILES: [&str; 3] = [".gitignore", "voido_BAK.json", "journal"];
+
+// Hands git the access token for HTTPS pushes, from the environment of the push only
+const TOKEN_HELPER: &str = "credential.helper=!f() { test \"$1\" = get && echo \"username=$VOIDO_GIT_USER\" && echo \"password=$VOIDO_GIT_TOKEN\"; }; f";
+
+// A line of a journal file: the change and the todo as it is now, none once deleted
+#[derive(serde::Serialize)]
+struct JournalLine<'a> {
+    #[serde(flatten)]
+    change: JournalEntry,
+    todo: Option<&'a Todo>,
+}
+
+#[derive(Debug)]
+pub struct GitHubSync {
+    config_dir: PathBuf,
+    repo_name: String,
+    git_username: String,
+    // [GITHUB] remote, none for github.com/<git user.name>/<repo_name>
+    remote: Option<RemoteUrl>,
+    forge: Forge,
+    token: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum AuthMethod {
+    SSH,
+    HTTPS,
+    Unknown,
+}
+
+impl GitHubSync {
+    pub fn new(repo_name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        // Each profile syncs its own folder
+        let config_dir = configs::data_dir()?;
+
+        // Create directory if it doesn't exist
+        fs::create_dir_all(&config_dir)?;
+
+        // Get git username
+        let git_username = Command::new("git")
+            .arg("config")
+            .arg("user.name")
+            .output()
+            .ok()
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .unwrap_or_else(|| "your-username".to_string())
+            .trim()
+            .to_string();
+
+        Ok(GitHubSync {
+            config_dir,
+            repo_name: repo_name.to_string(),
+            git_username,
+            remote: None,
+            forge: Forge::GitHub,
+            token: forge::token(),
+        })
+    }
+
+    // The repository set in the config
+    pub fn from_configs() -> Result<Self, Box<dyn std::error::Error>> {
+        let configs = configs::AppConfigs::read_configs_from_file()?;
+        let mut sync = Self::new(&configs.repo_name)?;
+        if !configs.git_remote.trim().is_empty() {
+            let remote = RemoteUrl::parse(&configs.git_remote).ok_or_else(|| {
+                format!(
+                    "[GITHUB] remote \"{}\" is not a git URL like git@host:owner/repo.git",
+                    configs.git_remote
+                )
+            })?;
+            sync.forge =
+                Forge::parse(&configs.git_forge).unwrap_or_else(|| Forge::detect(&remote.host));
+            sync.remote = Some(remote);
+        }
+        Ok(sync)
+    }
+
+    // Point origin at [GITHUB] remote. With a token for the forge's API the repository is
+    // created once, the repository's git config remembers it is there.
+    async fn setup_remote(&self, remote: &RemoteUrl) -> Result<(), Box<dyn std::error::Error>> {
+        let origin = self.origin_url();
+        match &origin {
+            Some(url) if *url == remote.url => {}
+            Some(_) => {
+                self.run_git_command(
+                    &["remote", "set-url", "origin", &remote.url],
+                    "Change remote",
+                )?;
+                println!("✓ Remote changed to {}", remote.url);
+            }
+            None => {
+                self.run_git_command(&["remote", "add", "origin", &remote.url], "Add remote")?;
+                println!("✓ Remote configured: {}", remote.url);
+            }
+        }
+
+        let created = Command::new("git")
+            .args(["config", "--get", "voido.created"])
+            .current_dir(&self.config_dir)
+            .output()?;
+        if String::from_utf8_lossy(&created.stdout).trim() == remote.url {
+            return Ok(());
+        }
+        match (&self.token, self.forge) {
+            (Some(token), Forge::GitHub | Forge::GitLab | Forge::Gitea) => {
+                if forge::create_repo(self.forge, remote, token).await? {
+                    println!(
+                        "📦 Created the {} repository {}",
+                        self.forge.name(),
+                        remote.page()
+                    );
+                }
+                self.run_git_command(
+                    &["config", "voido.created", &remote.url],
+                    "Remember the repository",
+                )?;
+            }
+            _ if origin.as_deref() == Some(remote.url.as_str()) => {}
+            _ => {
+                println!(
+                    "📋 Make sure {} exists (private, without a README), or store an access token with `voido sync github --token` to have it created",
+                    remote.page()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn commit_changes(&self, message: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        // The index is rebuilt from SYNCED_FILES alone, which also drops a todos.db an older
+        // VoiDo committed and records the journal files a new snapshot replaced
+        self.run_git_command(
+            &["rm", "-r", "-q", "--cached", "--ignore-unmatch", "--", "."],
+            "Reset the staged files",
+        )?;
+        let mut add = vec!["add", "--"];
+        add.extend(
+            SYNCED_FILES
+                .iter()
+                .filter(|file| self.config_dir.join(file).exists()),
+        );
+        self.run_git_command(&add, "Stage the snapshot and journal")?;
+
+        let staged = Command::new("git")
+            .args(["diff", "--cached", "--quiet"])
+            .current_dir(&self.config_dir)
+            .status()?;
+        if staged.success() {
+            println!("✓ No changes to commit");
+            return Ok(false);
+        }
+
+        self.run_git_command(&["commit", "-m", message], "Commit changes")?;
+        Ok(true)
+    }
+
+    pub fn backup_todos(&self, todos: &[Todo]) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let backup_path = self.config_dir.join("voido_BAK.json");
+        let json_todos = serde_json::to_string_pretty(todos)?;
+        fs::write(&backup_path, json_todos)?;
+        Ok(backup_path)
+    }
+
+    fn journal_dir(&self) -> PathBuf {
+        self.config_dir.join("journal")
+    }
+
+    fn journal_files(&self) -> usize {
+        fs::read_dir(self.journal_dir())
+            .map(|entries| entries.count())
+            .unwrap_or(0)
+    }
+
+    // The changes since the last sync as one file in journal/, a line per todo. The snapshot
+    // plus the journal files in name order are the whole list. `todos` are the ones this repository
+    // gets, a todo that left them since the last sync is written as deleted. None when no change
+    // is for this repository.
+    pub fn write_journal(
+        &self,
+        changes: &[JournalEntry],
+        todos: &[Todo],
+        synced: impl Fn(&str) -> bool,
+    ) -> Result<Option<(PathBuf, usize)>, Box<dyn std::error::Error>> {
+        let mut lines = Vec::new();
+        for change in changes {
+            let todo = todos.iter().find(|todo| todo.id as i64 == change.todo_id);
+            match todo {
+                Some(_) => lines.push(JournalLine {
+                    change: change.clone(),
+                    todo,
+                }),
+                None if change.action != "insert" && synced(&change.topic) => {
+                    lines.push(JournalLine {
+                        change: JournalEntry {
+                            action: "delete".to_string(),
+                            ..change.clone()
+                        },
+                        todo: None,
+                    })
+                }
+                None => {}
+            }
+        }
+        let Some(last) = lines.last() else {
+            return Ok(None);
+        };
+
+        fs::create_dir_all(self.journal_dir())?;
+        let path = self.journal_dir().join(format!(
+            "{}-{}.jsonl",
+            last.change.changed_at.format("%Y%m%dT%H%M%S%3fZ"),
+            last.change.uuid.get(..8).unwrap_or(&last.change.uuid)
+        ));
+        let mut file = String::new();
+        for line in &lines {
+            file.push_str(&serde_json::to_string(line)?);
+            file.push('\n');
+        }
+        fs::write(&path, file)?;
+        Ok(Some((path, lines.len())))
+    }
+
+    // A new snapshot has every change, the journal starts over
+    fn clear_journal(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.journal_dir().exists() {
+            fs::remove_dir_all(self.journal_dir())?;
+        }
+        Ok(())
+    }
+
+    pub fn init_repo(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if !self.is_git_repo()? {
+            self.run_git_command(&["init"], "Initialize git repository")?;
+            self.run_git_command(&["branch", "-M", "main"], "Rename branch to main")?;
+        }
+
+        // Sign-in tokens stored without a system keyring, the daemon's log, the database, the
+        // settings and the other profiles never leave the machine. Only SYNCED_FILES are staged
+        // anyway, this keeps `git status` in the folder quiet about them.
+        let gitignore = self.config_dir.join(".gitignore");
+        let mut ignored = fs::read_to_string(&gitignore).unwrap_or_default();
+        let before = ignored.len();
+        for entry in [
+            "secrets/",
+            "sync.log",
+            "todos.db*",
+            "config.toml",
+            "profiles/",
+        ] {
+            if !ignored.lines().any(|line| line.trim() == entry) {
+                if !ignored.is_empty() && !ignored.ends_with('\n') {
+                    ignored.push('\n');
+                }
+                ignored.push_str(entry);
+                ignored.push('\n');
+            }
+        }
+        if ignored.len() != before {
+            fs::write(&gitignore, ignored)?;
+        }
+        Ok(())
+    }
+
+    pub fn sync_to_github(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let is_private = true;
+
+        // Check if remote exists
+        if !self.has_remote("origin")? {
+            self.setup_github_repo(is_private)?;
+        }
+
+        // Verify authentication before attempting to push
+        self.verify_github_auth()?;
+
+        // Check if we need to push
+        let status = Command::new("git")
+            .args(["status", "--porcelain", "--branch"])
+            .current_dir(&self.config_dir)
+            .output()?;
+
+        let status_str = String::from_utf8_lossy(&status.stdout);
+        // "## main...origin/main", without the upstream part nothing was pushed yet
+        let tracked = status_str
+            .lines()
+            .next()
+            .is_some_and(|branch| branch.contains("..."));
+        if !tracked || status_str.contains("ahead") || status_str.contains("Initial commit") {
+            self.push_with_retry()?;
+            println!("✓ Changes pushed to {}", self.forge.name());
+        } else {
+            println!("✓ No changes to push (already up-to-date)");
+        }
+
+        Ok(())
+    }
+
+    fn setup_github_repo(&self, is_private: bool) -> Result<(), Box<dyn std::error::Error>> {
+        // First try using GitHub CLI if available
+        if self.is_gh_cli_available() && self.is_gh_authenticated()? {
+            println!("📦 Creating GitHub repository using GitHub CLI...");
+            let privacy_flag = if is_private { "--private" } else { "--public" };
+
+            match self.run_command(
+                "gh",
+                &[
+                    "repo",
+                    "create",
+                    &self.repo_name,
+                    privacy_flag,
+                    "--source=.",
+                    "--remote=origin",
+                ],
+                "Create GitHub repository (using gh CLI)",
+            ) {
+                Ok(_) => {
+                    println!("✓ Repository created successfully with GitHub CLI");
+                    return Ok(());
+                }
+                Err(e) => {
+                    eprintln!("⚠️  GitHub CLI failed: {}", e);
+                    println!("Falling back to manual setup...");
+                }
+            }
+        }
+
+        // Manual setup - try SSH first, then HTTPS
+        self.setup_manual_remote()?;
+        Ok(())
+    }
+
+    fn setup_manual_remote(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Try SSH first (recommended for security)
+        if self.has_ssh_key()? {
+            let ssh_url = format!(
+                "git@github.com:{}/{}.git",
+                self.git_username, self.repo_name
+            );
+            println!("🔐 Setting up SSH remote...");
+
+            match self.run_git_command(&["remote", "add", "origin", &ssh_url], "Add SSH remote") {
+                Ok(_) => {
+                    println!("✓ SSH remote configured");
+                    self.print_manual_repo_instructions(&ssh_url, AuthMethod::SSH);
+                    return Ok(());
+                }
+                Err(_) => {
+                    eprintln!("⚠️  SSH setup failed, trying HTTPS...");
+                }
+            }
+        }
+
+        // Fallback to HTTPS with token
+        let https_url = format!(
+            "https://github.com/{}/{}.git",
+            self.git_username, self.repo_name
+        );
+        self.run_git_command(&["remote", "add", "origin", &https_url], "Add HTTPS remote")?;
+        println!("✓ HTTPS remote configured");
+        self.print_manual_repo_instructions(&https_url, AuthMethod::HTTPS);
+
+        Ok(())
+    }
+
+    fn verify_github_auth(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let auth_method = self.detect_auth_method()?;
+
+        match auth_method {
+            AuthMethod::SSH => self.verify_ssh_auth()?,
+            AuthMethod::HTTPS => self.verify_https_auth()?,
+            AuthMethod::Unknown => {
+                return Err("Unable to determine authentication method. Please check your git remote configuration.".into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn detect_auth_method(&self) -> Result<AuthMethod, Box<dyn std::error::Error>> {
+        Ok(
+            match self.origin_url().as_deref().and_then(RemoteUrl::parse) {
+                Some(remote) if remote.ssh => AuthMethod::SSH,
+                Some(_) => AuthMethod::HTTPS,
+                None => AuthMethod::Unknown,
+            },
+        )
+    }
+
+    fn origin_url(&self) -> Option<String> {
+        Command::new("git")
+            .args(["remote", "get-url", "origin"])
+            .current_dir(&self.config_dir)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn verify_ssh_auth(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // A plain git server has no greeting to check, the push tells
+        if self.forge == Forge::Other {
+            return Ok(());
+        }
+        println!("🔐 Verifying SSH authentication...");
+
+        let (host, port) = self
+            .origin_url()
+            .as_deref()
+            .and_then(RemoteUrl::parse)
+            .map(|remote| remote.address())
+            .unwrap_or_else(|| ("github.com".to_string(), 22));
+        let output = Command::new("ssh")
+            .args(["-T", "-p", &port.to_string(), &format!("git@{}", host)])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()?;
+
+        // GitLab greets on stdout, GitHub and Gitea on stderr
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        if stderr.contains("successfully authenticated") || stdout.contains("Welcome to GitLab") {
+            println!("✓ SSH authentication verified");
+            Ok(())
+        } else {
+            Err(format!(
+                "SSH authentication failed. Please ensure:\n\
+                1. You have generated an SSH key: ssh-keygen -t ed25519 -C \"your_email@example.com\"\n\
+                2. Added it to ssh-agent: ssh-add ~/.ssh/id_ed25519\n\
+                3. Added the public key to your {} account\n\
+                4. Test with: ssh -T git@{}\n\
+                \nError: {}", self.forge.name(), host, stderr
+            ).into())
+        }
+    }
+
+    fn verify_https_auth(&self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🔐 Verifying HTTPS authentication...");
+        if self.token.is_some() {
+            println!("✓ Pushing with the stored access token");
+            return Ok(());
+        }
+
+        // Check if credential helper is configured
+        let output = Command::new("git")
+            .args(["config", "--get", "credential.helper"])
+            .output()?;
+
+        if !output.status.success() || output.stdout.is_empty() {
+            return Err(
+                "No credential helper configured for HTTPS authentication.\n\
+                Please set up authentication:\n\
+                1. Generate a Personal Access Token at: https://github.com/settings/tokens\n\
+                   (or in the settings of your GitLab or Gitea account)\n\
+                2. Store it for VoiDo: voido sync github --token\n\
+                3. Or configure a credential helper: git config --global credential.helper store\n\
+                4. Or use GitHub CLI: gh auth login\n\
+                \nNote: GitHub no longer accepts passwords for Git operations."
+                    .into(),
+            );
+        }
+
+        println!("✓ Credential helper configured");
+        Ok(())
+    }
+
+    fn push_with_retry(&self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("📤 Pushing to {}...", self.forge.name());
+
+        // First attempt
+        match self.push() {
+            Ok(_) => return Ok(()),
+            Err(e) => {
+                let error_str = e.to_string();
+
+                // Handle common authentication errors
+                if error_str.contains("Permission denied")
+                    || error_str.contains("authentication failed")
+                {
+                    return Err(format!(
+                        "Authentication failed. {}\n\
+                        \nFor SSH: Ensure your SSH key is added to GitHub\n\
+                        For HTTPS: Use a Personal Access Token instead of password\n\
+                        \nOriginal error: {}",
+                        self.get_auth_help_message()?,
+                        e
+                    )
+                    .into());
+                }
+
+                if error_str.contains("repository does not exist") {
+                    if let Some(remote) = &self.remote {
+                        return Err(format!(
+                            "Repository {} does not exist.\n\
+                            Please create it, or store an access token with `voido sync github --token` to have it created\n\
+                            \nOriginal error: {}",
+                            remote.page(),
+                            e
+                        )
+                        .into());
+                    }
+                    return Err(format!(
+                        "Repository does not exist on GitHub.\n\
+                        Please create it manually at: https://github.com/new\n\
+                        Repository name: {}\n\
+                        \nOriginal error: {}",
+                        self.repo_name, e
+                    )
+                    .into());
+                }
+
+                return Err(e);
+            }
+        }
+    }
+
+    // Over HTTPS with a token, git gets it from TOKEN_HELPER instead of asking
+    fn push(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let args = ["push", "-u", "origin", "main"];
+        let remote = self.origin_url().as_deref().and_then(RemoteUrl::parse);
+        let (Some(token), Some(remote)) = (&self.token, remote.filter(|remote| !remote.ssh)) else {
+            return self.run_git_command(&args, "Push");
+        };
+        println!("⚡ Push");
+        let output = Command::new("git")
+            .args(["-c", "credential.helper=", "-c", TOKEN_HELPER])
+            .args(args)
+            .env("VOIDO_GIT_USER", self.forge.token_user(&remote))
+            .env(forge::TOKEN_ENV, token)
+            .current_dir(&self.config_dir)
+            .output()?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to Push (git {})\nStderr: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr)
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn get_auth_help_message(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let auth_method = self.detect_auth_method()?;
+
+        let message = match auth_method {
+            AuthMethod::SSH => {
+                "SSH Authentication Help:\n\
+                1. Generate SSH key: ssh-keygen -t ed25519 -C \"your_email@example.com\"\n\
+                2. Add to ssh-agent: ssh-add ~/.ssh/id_ed25519\n\
+                3. Copy public key: cat ~/.ssh/id_ed25519.pub\n\
+                4. Add to GitHub: https://github.com/settings/ssh/new\n\
+                5. Test: ssh -T git@github.com"
+            }
+            AuthMethod::HTTPS => {
+                "HTTPS Authentication Help:\n\
+                1. Create Personal Access Token: https://github.com/settings/tokens\n\
+                2. Select scopes: 'repo' for private repos, 'public_repo' for public\n\
+                3. Use token as password when prompted\n\
+                4. Or configure credential helper: git config --global credential.helper store"
+            }
+            AuthMethod::Unknown => "Please check your git remote configuration",
+        };
+
+        Ok(message.to_string())
+    }
+
+    fn has_ssh_key(&self) -> Result<bool, io::Error> {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        let ssh_dir = Path::new(&home).join(".ssh");
+
+        // Check for common SSH key files
+        let key_files = ["id_ed25519", "id_rsa", "id_ecdsa"];
+        for key_file in &key_files {
+            if ssh_dir.join(key_file).exists() {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn is_gh_cli_available(&self) -> bool {
+        Command::new("gh")
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+
+    fn is_gh_authenticated(&self) -> Result<bool, Box<dyn std::error::Error>> {
+        let output = Command::new("gh")
+            .args(["auth", "status"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()?;
+
+        Ok(output.status.success())
+    }
+
+    fn print_manual_repo_instructions(&self, remote_url: &str, auth_method: AuthMethod) {
+        println!("\n📋 Manual Setup Required:");
+        println!("1. Create a new repository on GitHub:");
+        println!("   → https://github.com/new");
+        println!("   → Repository name: {}", self.repo_name);
+        println!("   → Set as private: Yes");
+        println!("   → Do NOT initialize with README, .gitignore, or license");
+        println!("\n2. Remote URL configured: {}", remote_url);
+
+        match auth_method {
+            AuthMethod::SSH => {
+                println!("\n3. SSH Authentication:");
+                println!("   → Ensure your SSH key is added to GitHub");
+                println!("   → Test with: ssh -T git@github.com");
+            }
+            AuthMethod::HTTPS => {
+                println!("\n3. HTTPS Authentication:");
+                println!(
+                    "   → Create a Personal Access Token at: https://github.com/settings/tokens"
+                );
+                println!("   → Use the token as your password when Git prompts");
+                println!("   → GitHub no longer accepts account passwords for Git operations");
+            }
+            AuthMethod::Unknown => {}
+        }
+
+        println!("\n4. Repository will be available at:");
+        println!(
+            "   → https://github.com/{}/{}",
+            self.git_username, self.repo_name
+        );
+    }
+
+    fn is_git_repo(&self) -> Result<bool, io::Error> {
+        Ok(self.config_dir.join(".git").exists())
+    }
+
+    fn has_remote(&self, remote: &str) -> Result<bool, io::Error> {
+        let output = Command::new("git")
+            .args(["remote", "get-url", remote])
+            .current_dir(&self.config_dir)
+            .output()?;
+
+        Ok(output.status.success())
+    }
+
+    fn run_git_command(
+        &self,
+        args: &[&str],
+        description: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        println!("⚡ {}", description);
+        self.run_command("git", args, description)
+    }
+
+    fn run_command(
+        &self,
+        cmd: &str,
+        args: &[&str],
+        description: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let output = Command::new(cmd)
+            .args(args)
+            .current_dir(&self.config_dir)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return Err(format!(
+                "Failed to {} ({} {})\nStdout: {}\nStderr: {}",
+                description,
+                cmd,
+                args.join(" "),
+                stdout,
+                stderr
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+// `voido sync github`: a snapshot of the todos on the first sync, then a journal file with the
+// changes of each sync, committed and pushed
+impl SyncBackend for GitHubSync {
+    fn name(&self) -> &'static str {
+        self.forge.name()
+    }
+
+    fn key(&self) -> &'static str {
+        "github"
+    }
+
+    async fn sync(
+        &mut self,
+        db: &mut DBtodo,
+        delta: Option<&Delta>,
+    ) -> Result<SyncReport, Box<dyn std::error::Error>> {
+        // Only the topics [SYNC.topics] gives this repository
+        let scope = configs::SyncConfig::load();
+        let key = self.key();
+        let synced = |topic: &str| scope.includes(key, topic);
+        let mut todos = db.get_todos()?;
+        todos.retain(|todo| synced(&todo.topic));
+
+        let pushed = match delta {
+            Some(delta) if delta.changes.is_empty() => 0,
+            Some(delta) if self.journal_files() < COMPACT_AFTER => {
+                todos.extend(
+                    db.get_archived_todos()?
+                        .into_iter()
+                        .filter(|todo| synced(&todo.topic)),
+                );
+                match self.write_journal(&delta.changes, &todos, synced)? {
+                    Some((path, count)) => {
+                        println!("✓ {} changes written to {}", count, path.display());
+                        count
+                    }
+                    None => 0,
+                }
+            }
+            _ => {
+                let path = self.backup_todos(&todos)?;
+                self.clear_journal()?;
+                println!("✓ Todos backed up to: {}", path.display());
+                todos.len()
+            }
+        };
+        self.init_repo()?;
+        let committed = self.commit_changes("Update todo list")?;
+        if let Some(remote) = &self.remote {
+            self.setup_remote(remote).await?;
+        }
+        self.sync_to_github()?;
+        Ok(SyncReport {
+            pushed: if committed { pushed } else { 0 },
+            ..SyncReport::default()
+        })
+    }
+}
+
+// Usage with CLI flag
+pub fn handle_github_sync() -> Result<(), Box<dyn std::error::Error>> {
+    let todos = &data::sample_todos();
+
+    let configs = configs::AppConfigs::read_configs_from_file()?;
+
+    let repo_name = &configs.repo_name;
+
+    let sync = GitHubSync::new(repo_name)?;
+
+    println!("🚀 Starting GitHub sync for repository: {}", repo_name);
+
+    // Step 1: Create backup file
+    let backup_path = sync.backup_todos(todos)?;
+    println!("✓ Todos backed up to: {}", backup_path.display());
+
+    // Step 2: Initialize repository
+    sync.init_repo()?;
+    println!("✓ Git repository initialized");
+
+    // Step 3: Commit changes
+    let has_changes = sync.commit_changes("Update todo list")?;
+    if has_changes {
+        println!("✓ Changes committed");
+    }
+
+    // Step 4: Sync with GitHub
+    match sync.sync_to_github() {
+        Ok(_) => {
+            println!("🎉 Successfully synced with GitHub!");
+            println!(
+                "   Repository: https://github.com/{}/{}",
+                sync.git_username, sync.repo_name
+            );
+        }
+        Err(e) => {
+            eprintln!("❌ Failed to sync with GitHub: {}", e);
+            eprintln!("\n💡 Troubleshooting tips:");
+            eprintln!("   • Ensure you have proper GitHub authentication set up");
+            eprintln!("   • For SSH: Add your SSH key to GitHub");
+            eprintln!("   • For HTTPS: Use a Personal Access Token");
+            eprintln!("   • GitHub no longer accepts passwords for Git operations");
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn todo(id: usize, text: &str, topic: &str) -> Todo {
+        Todo {
+            id,
+            topic: topic.to_string(),
+            ..Todo::sample(text)
+        }
+    }
+
+    fn change(todo_id: i64, topic: &str) -> JournalEntry {
+        JournalEntry {
+            uuid: format!("uuid-{}", todo_id),
+            todo_id,
+            action: "update".to_string(),
+            topic: topic.to_string(),
+            changed_at: Utc::now(),
+        }
+    }
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+
+    #[test]
+    fn test_sync_commits_only_synced_topics() {
+        let dir = std::env::temp_dir().join(format!("voido-git-sync-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("profiles/home")).unwrap();
+        // What is next to the repository and must stay there
+        fs::write(dir.join("todos.db"), "Private: salary talk, apikey").unwrap();
+        fs::write(dir.join("config.toml"), "[GITHUB]").unwrap();
+        fs::write(dir.join("profiles/home/todos.db"), "Private").unwrap();
+
+        let sync = GitHubSync {
+            config_dir: dir.clone(),
+            repo_name: "voido_sync".to_string(),
+            git_username: "joe".to_string(),
+            remote: None,
+            forge: Forge::GitHub,
+            token: None,
+        };
+        sync.init_repo().unwrap();
+        git(&dir, &["config", "user.name", "Joe"]);
+        git(&dir, &["config", "user.email", "joe@example.com"]);
+        // A todo.db committed by an older VoiDo goes out of the tree
+        git(&dir, &["add", "-f", "todos.db"]);
+        git(&dir, &["commit", "-qm", "old sync"]);
+
+        let todos = [
+            todo(1, "Ship v1", "Work"),
+            todo(2, "Salary talk", "Private"),
+        ];
+        let synced = |topic: &str| topic == "Work";
+        sync.backup_todos(&todos[..1]).unwrap();
+        sync.write_journal(
+            &[change(1, "Work"), change(2, "Private")],
+            &todos[..1],
+            synced,
+        )
+        .unwrap();
+        assert!(sync.commit_changes("Update todo list").unwrap());
+
+        let tree = git(&dir, &["ls-tree", "-r", "--name-only", "HEAD"]);
+        let files = tree.lines().collect::<Vec<&str>>();
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0], ".gitignore");
+        assert!(files[1].starts_with("journal/"));
+        assert_eq!(files[2], "voido_BAK.json");
+        // Nothing of the other topic in the committed files
+        assert_eq!(git(&dir, &["grep", "-il", "salary", "HEAD"]), "");
+        // The other profiles' databases are ignored, not left untracked
+        assert_eq!(git(&dir, &["status", "--porcelain"]), "");
+        assert!(!sync.commit_changes("Update todo list").unwrap());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,923 @@
+// GOOGLE TASKS SYNC: the topics mapped in [GOOGLE_TASKS.lists] sync both ways with a Google Tasks
+// list, so the Google Tasks widget on a phone and VoiDo show the same todos. Sign-in is Google's
+// device flow, the refresh token is kept in the system keyring.
+//
+// Each todo is linked to its task. When only one side changed since the last sync that side wins,
+// when both did the newer one wins. Deleting a todo deletes its task, deleting a task archives its
+// todo. Google Tasks has no priorities, owners or times of day, those stay as they are in VoiDo.
+use std::collections::{HashMap, HashSet};
+use std::{error::Error, time::Duration};
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::journal::Delta;
+use super::{SyncBackend, SyncReport, keyring};
+use crate::arguments::add_todo::build_todo;
+use crate::arguments::models::Todo;
+use crate::configs::{GoogleTasksConfig, SyncConfig};
+use crate::database::{DBtodo, SyncLink};
+use crate::dates;
+
+pub const BACKEND: &str = "google";
+const TOKEN_KEY: &str = "google_tasks_refresh_token";
+const DEVICE_URL: &str = "https://oauth2.googleapis.com/device/code";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const TASKS_URL: &str = "https://tasks.googleapis.com/tasks/v1";
+const SCOPE: &str = "https://www.googleapis.com/auth/tasks";
+const NO_DESCRIPTION: &str = "No description provided";
+
+#[derive(Debug, Deserialize)]
+struct DeviceCode {
+    device_code: String,
+    user_code: String,
+    verification_url: String,
+    interval: u64,
+    expires_in: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    refresh_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+impl TokenResponse {
+    fn error_message(self) -> String {
+        self.error_description
+            .or(self.error)
+            .unwrap_or_else(|| "sign-in failed".to_string())
+    }
+}
+
+// One page of a Tasks API collection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    #[serde(default = "Vec::new")]
+    items: Vec<T>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TaskList {
+    id: String,
+    title: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Task {
+    #[serde(default, skip_serializing)]
+    id: String,
+    title: String,
+    #[serde(default)]
+    notes: String,
+    // "needsAction" or "completed"
+    status: String,
+    // RFC 3339, only the date part means anything
+    due: Option<String>,
+    #[serde(default, skip_serializing, with = "dates::optional_timestamp_serde")]
+    updated: Option<DateTime<Utc>>,
+    // Subtasks point at their parent, they are left out of the sync
+    #[serde(default, skip_serializing)]
+    parent: Option<String>,
+    // Only in the changes since a date
+    #[serde(default, skip_serializing)]
+    deleted: bool,
+}
+
+impl Task {
+    fn is_completed(&self) -> bool {
+        self.status == "completed"
+    }
+
+    fn due_date(&self) -> Option<NaiveDate> {
+        self.due
+            .as_deref()
+            .and_then(|due| dates::parse_date(due.get(..10)?))
+    }
+}
+
+// A mapped list with the topic it syncs with and what is in it now
+#[derive(Debug)]
+struct Remote {
+    list_id: String,
+    topic: String,
+    tasks: Vec<Task>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    // Send a todo to the list, over its linked task or as a new one
+    Push {
+        list_id: String,
+        todo_id: usize,
+        task_id: Option<String>,
+    },
+    // Bring a task into the topic, over its linked todo or as a new one
+    Pull {
+        list_id: String,
+        task: Task,
+        todo_id: Option<usize>,
+    },
+    // Both sides agree, remember that they do
+    Link {
+        list_id: String,
+        todo_id: usize,
+        task_id: String,
+    },
+    DeleteTask {
+        list_id: String,
+        task_id: String,
+    },
+    ArchiveTodo {
+        todo_id: usize,
+    },
+    Forget {
+        todo_id: usize,
+    },
+}
+
+fn description(todo: &Todo) -> &str {
+    if todo.desc == NO_DESCRIPTION {
+        ""
+    } else {
+        &todo.desc
+    }
+}
+
+fn todo_to_task(todo: &Todo) -> Task {
+    Task {
+        title: todo.text.clone(),
+        notes: description(todo).to_string(),
+        status: if todo.is_done() {
+            "completed"
+        } else {
+            "needsAction"
+        }
+        .to_string(),
+        due: todo.due.map(|due| format!("{}T00:00:00.000Z", due)),
+        ..Task::default()
+    }
+}
+
+// What a task says about a todo, the rest of the todo stays as it is
+fn apply_task(todo: &mut Todo, task: &Task) {
+    todo.text = task.title.trim().to_string();
+    todo.desc = match task.notes.trim() {
+        "" => NO_DESCRIPTION.to_string(),
+        notes => notes.to_string(),
+    };
+    if task.is_completed() != todo.is_done() {
+        todo.status = if task.is_completed() {
+            "Done"
+        } else {
+            "Pending"
+        }
+        .to_string();
+    }
+    if todo.due != task.due_date() {
+        todo.due = task.due_date();
+        todo.due_at = None;
+    }
+}
+
+fn same(todo: &Todo, task: &Task) -> bool {
+    todo.text == task.title.trim()
+        && description(todo) == task.notes.trim()
+        && todo.is_done() == task.is_completed()
+        && todo.due == task.due_date()
+}
+
+fn touched(todo: &Todo) -> DateTime<Utc> {
+    todo.updated_at.unwrap_or(todo.date_added)
+}
+
+// The task wins when only it changed since `since`, or both did and it changed last
+fn task_wins(todo: &Todo, task: &Task, since: DateTime<Utc>) -> bool {
+    let task_changed = task.updated.is_some_and(|updated| updated > since);
+    let todo_changed = touched(todo) > since;
+    task_changed && (!todo_changed || task.updated > Some(touched(todo)))
+}
+
+fn compare(list_id: &str, todo: &Todo, task: &Task, since: DateTime<Utc>) -> Step {
+    if same(todo, task) {
+        Step::Link {
+            list_id: list_id.to_string(),
+            todo_id: todo.id,
+            task_id: task.id.clone(),
+        }
+    } else if task_wins(todo, task, since) {
+        Step::Pull {
+            list_id: list_id.to_string(),
+            task: task.clone(),
+            todo_id: Some(todo.id),
+        }
+    } else {
+        Step::Push {
+            list_id: list_id.to_string(),
+            todo_id: todo.id,
+            task_id: Some(task.id.clone()),
+        }
+    }
+}
+
+// What a sync knows about the task of a link
+enum Seen<'a> {
+    Changed(&'a Remote, &'a Task),
+    Gone,
+    // Not in the changes since the last sync
+    Unchanged,
+}
+
+// Everything one sync has to do. `todos` has the archived todos too, `list_of` is the list a
+// topic syncs with. A full sync has every task in `remotes`. A delta sync has the tasks changed
+// since the last one, deleted ones too, and the todos changed since in `changed`.
+fn plan(
+    todos: &[Todo],
+    remotes: &[Remote],
+    links: &[SyncLink],
+    list_of: impl Fn(&str) -> Option<String>,
+    changed: Option<&HashSet<usize>>,
+) -> Vec<Step> {
+    let todo_changed = |id: usize| changed.is_none_or(|changed| changed.contains(&id));
+    let todos_by_id = todos
+        .iter()
+        .map(|todo| (todo.id, todo))
+        .collect::<HashMap<usize, &Todo>>();
+    let mut tasks_by_id = HashMap::new();
+    let mut deleted = HashSet::new();
+    for remote in remotes {
+        for task in &remote.tasks {
+            if task.deleted {
+                deleted.insert(task.id.as_str());
+            } else {
+                tasks_by_id.insert(task.id.as_str(), (remote, task));
+            }
+        }
+    }
+
+    let mut steps = Vec::new();
+    let mut linked_todos = HashSet::new();
+    let mut linked_tasks = HashSet::new();
+    for link in links {
+        let todo_id = link.todo_id as usize;
+        let todo = todos_by_id.get(&todo_id).copied();
+        let seen = match tasks_by_id.get(link.remote_id.as_str()) {
+            Some((remote, task)) => Seen::Changed(remote, task),
+            // Missing from everything, or deleted since
+            None if deleted.contains(link.remote_id.as_str()) => Seen::Gone,
+            // A list that wasn't fetched says nothing about its tasks
+            None if changed.is_none()
+                && remotes
+                    .iter()
+                    .any(|remote| remote.list_id == link.remote_list) =>
+            {
+                Seen::Gone
+            }
+            None => Seen::Unchanged,
+        };
+        let delete_task = Step::DeleteTask {
+            list_id: link.remote_list.clone(),
+            task_id: link.remote_id.clone(),
+        };
+        linked_tasks.insert(link.remote_id.as_str());
+
+        match (todo, seen) {
+            (None, Seen::Gone) => steps.push(Step::Forget { todo_id }),
+            (None, _) => {
+                steps.push(delete_task);
+                steps.push(Step::Forget { todo_id });
+            }
+            // Archived todos are left alone on both sides
+            (Some(todo), _) if todo.archived => {
+                linked_todos.insert(todo.id);
+            }
+            // Moved to a topic that syncs somewhere else, or nowhere
+            (Some(todo), seen) if list_of(&todo.topic).as_deref() != Some(&link.remote_list) => {
+                if !matches!(seen, Seen::Gone) {
+                    steps.push(delete_task);
+                }
+                steps.push(Step::Forget { todo_id });
+            }
+            (Some(todo), Seen::Gone) => {
+                steps.push(Step::ArchiveTodo { todo_id: todo.id });
+                steps.push(Step::Forget { todo_id });
+                linked_todos.insert(todo.id);
+            }
+            (Some(todo), Seen::Unchanged) => {
+                linked_todos.insert(todo.id);
+                if todo_changed(todo.id) {
+                    steps.push(Step::Push {
+                        list_id: link.remote_list.clone(),
+                        todo_id: todo.id,
+                        task_id: Some(link.remote_id.clone()),
+                    });
+                }
+            }
+            (Some(todo), Seen::Changed(remote, task)) => {
+                linked_todos.insert(todo.id);
+                let step = compare(&remote.list_id, todo, task, link.synced_at);
+                // Nothing to tell either side when nothing changed since the last sync
+                let changed = touched(todo) > link.synced_at
+                    || task.updated.is_some_and(|updated| updated > link.synced_at);
+                if changed || !matches!(step, Step::Link { .. }) {
+                    steps.push(step);
+                }
+            }
+        }
+    }
+
+    // New on one side. A todo and a task with the same text are paired up instead of copied.
+    for remote in remotes {
+        let mut unlinked = todos
+            .iter()
+            .filter(|todo| !todo.archived && !linked_todos.contains(&todo.id))
+            .filter(|todo| todo_changed(todo.id))
+            .filter(|todo| list_of(&todo.topic).as_deref() == Some(&remote.list_id))
+            .collect::<Vec<&Todo>>();
+        for task in &remote.tasks {
+            if task.deleted || linked_tasks.contains(task.id.as_str()) {
+                continue;
+            }
+            match unlinked
+                .iter()
+                .position(|todo| todo.text.eq_ignore_ascii_case(task.title.trim()))
+            {
+                Some(position) => {
+                    let todo = unlinked.remove(position);
+                    linked_todos.insert(todo.id);
+                    steps.push(compare(
+                        &remote.list_id,
+                        todo,
+                        task,
+                        DateTime::<Utc>::MIN_UTC,
+                    ));
+                }
+                None => steps.push(Step::Pull {
+                    list_id: remote.list_id.clone(),
+                    task: task.clone(),
+                    todo_id: None,
+                }),
+            }
+        }
+        // Todos done before they were ever synced stay out of the list
+        for todo in unlinked.into_iter().filter(|todo| !todo.is_done()) {
+            linked_todos.insert(todo.id);
+            steps.push(Step::Push {
+                list_id: remote.list_id.clone(),
+                todo_id: todo.id,
+                task_id: None,
+            });
+        }
+    }
+    steps
+}
+
+pub struct GoogleTasksSync {
+    config: GoogleTasksConfig,
+    client: Client,
+    // Off for the background syncs, which can't show a sign-in code to anyone
+    interactive: bool,
+}
+
+impl GoogleTasksSync {
+    pub fn new(interactive: bool) -> Result<Self, Box<dyn Error>> {
+        let config = GoogleTasksConfig::load();
+        if config.client_id.is_empty() || config.client_secret.is_empty() {
+            return Err(
+                "Set client_id and client_secret in the [GOOGLE_TASKS] section of config.toml first"
+                    .into(),
+            );
+        }
+        Ok(Self {
+            config,
+            client: Client::new(),
+            interactive,
+        })
+    }
+
+    // DEVICE FLOW SIGN-IN: show the code, then wait until it is entered in the browser
+    async fn sign_in(&self) -> Result<String, Box<dyn Error>> {
+        if !self.interactive {
+            return Err("Not signed in to Google Tasks, run `voido sync google`".into());
+        }
+        let code = self
+            .client
+            .post(DEVICE_URL)
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("scope", SCOPE),
+            ])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<DeviceCode>()
+            .await?;
+        println!(
+            "\n🔑 Open {} and enter the code {}\n",
+            code.verification_url, code.user_code
+        );
+
+        let mut interval = code.interval.max(1);
+        let mut waited = 0;
+        while waited < code.expires_in {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+            waited += interval;
+
+            let token = self
+                .client
+                .post(TOKEN_URL)
+                .form(&[
+                    ("client_id", self.config.client_id.as_str()),
+                    ("client_secret", self.config.client_secret.as_str()),
+                    ("device_code", code.device_code.as_str()),
+                    ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ])
+                .send()
+                .await?
+                .json::<TokenResponse>()
+                .await?;
+            match (&token.access_token, token.error.as_deref()) {
+                (Some(access_token), _) => {
+                    if let Some(refresh_token) = &token.refresh_token {
+                        keyring::store(TOKEN_KEY, refresh_token)?;
+                    }
+                    return Ok(access_token.clone());
+                }
+                (None, Some("authorization_pending")) => {}
+                (None, Some("slow_down")) => interval += 5,
+                (None, _) => return Err(token.error_message().into()),
+            }
+        }
+        Err("The sign-in code expired, run the sync again".into())
+    }
+
+    // A fresh access token from the stored refresh token, signing in when there is none
+    async fn access_token(&self) -> Result<String, Box<dyn Error>> {
+        let Some(refresh_token) = keyring::load(TOKEN_KEY) else {
+            return self.sign_in().await;
+        };
+        let token = self
+            .client
+            .post(TOKEN_URL)
+            .form(&[
+                ("client_id", self.config.client_id.as_str()),
+                ("client_secret", self.config.client_secret.as_str()),
+                ("refresh_token", refresh_token.as_str()),
+                ("grant_type", "refresh_token"),
+            ])
+            .send()
+            .await?
+            .json::<TokenResponse>()
+            .await?;
+        match (&token.access_token, token.error.as_deref()) {
+            (Some(access_token), _) => Ok(access_token.clone()),
+            // Revoked or expired, sign in again
+            (None, Some("invalid_grant")) => {
+                keyring::delete(TOKEN_KEY)?;
+                self.sign_in().await
+            }
+            (None, _) => Err(token.error_message().into()),
+        }
+    }
+
+    // Every item of a collection, following the page tokens
+    async fn get_all<T: for<'de> Deserialize<'de>>(
+        &self,
+        token: &str,
+        url: &str,
+    ) -> Result<Vec<T>, Box<dyn Error>> {
+        let mut items = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut request = self
+                .client
+                .get(url)
+                .bearer_auth(token)
+                .query(&[("maxResults", "100")]);
+            if let Some(page_token) = &page_token {
+                request = request.query(&[("pageToken", page_token)]);
+            }
+            let page = request
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Page<T>>()
+                .await?;
+            items.extend(page.items);
+            match page.next_page_token {
+                Some(next) => page_token = Some(next),
+                None => return Ok(items),
+            }
+        }
+    }
+
+    // The list each mapped topic syncs with, creating the ones Google Tasks doesn't have yet.
+    // When two topics share a list, the first one gets its new tasks. Topics [SYNC.topics] keeps
+    // from Google Tasks are left out.
+    async fn lists(
+        &self,
+        token: &str,
+        scope: &SyncConfig,
+    ) -> Result<Vec<(String, String)>, Box<dyn Error>> {
+        let mut lists = self
+            .get_all::<TaskList>(token, &format!("{}/users/@me/lists", TASKS_URL))
+            .await?;
+        if self.config.lists.is_empty() {
+            println!("Google Tasks lists:");
+            for list in &lists {
+                println!("  • {}", list.title);
+            }
+            return Err("Map topics to these lists in [GOOGLE_TASKS.lists] of config.toml, e.g. Work = \"My Tasks\"".into());
+        }
+
+        let mut mapped: Vec<(String, String)> = Vec::new();
+        for (topic, title) in &self.config.lists {
+            if !scope.includes(BACKEND, topic) {
+                continue;
+            }
+            let list_id = match lists
+                .iter()
+                .find(|list| list.title.eq_ignore_ascii_case(title))
+            {
+                Some(list) => list.id.clone(),
+                None => {
+                    let list = self
+                        .client
+                        .post(format!("{}/users/@me/lists", TASKS_URL))
+                        .bearer_auth(token)
+                        .json(&serde_json::json!({ "title": title }))
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .json::<TaskList>()
+                        .await?;
+                    println!("📋 Created the Google Tasks list \"{}\"", title);
+                    let id = list.id.clone();
+                    lists.push(list);
+                    id
+                }
+            };
+            if !mapped.iter().any(|(_, id)| *id == list_id) {
+                mapped.push((topic.clone(), list_id));
+            }
+        }
+        Ok(mapped)
+    }
+
+    // The tasks of a list, or only the ones changed (or deleted) since `since`
+    async fn tasks(
+        &self,
+        token: &str,
+        list_id: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Task>, Box<dyn Error>> {
+        let mut url = format!(
+            "{}/lists/{}/tasks?showCompleted=true&showHidden=true",
+            TASKS_URL, list_id
+        );
+        if let Some(since) = since {
+            url.push_str(&format!(
+                "&showDeleted=true&updatedMin={}",
+                since.to_rfc3339_opts(SecondsFormat::Millis, true)
+            ));
+        }
+        Ok(self
+            .get_all::<Task>(token, &url)
+            .await?
+            .into_iter()
+            .filter(|task| task.deleted || (task.parent.is_none() && !task.title.trim().is_empty()))
+            .collect())
+    }
+
+    async fn send_task(
+        &self,
+        token: &str,
+        list_id: &str,
+        task_id: Option<&str>,
+        task: &Task,
+    ) -> Result<Task, Box<dyn Error>> {
+        let request = match task_id {
+            Some(task_id) => self
+                .client
+                .patch(format!("{}/lists/{}/tasks/{}", TASKS_URL, list_id, task_id)),
+            None => self
+                .client
+                .post(format!("{}/lists/{}/tasks", TASKS_URL, list_id)),
+        };
+        Ok(request
+            .bearer_auth(token)
+            .json(task)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Task>()
+            .await?)
+    }
+
+    async fn delete_task(
+        &self,
+        token: &str,
+        list_id: &str,
+        task_id: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let response = self
+            .client
+            .delete(format!("{}/lists/{}/tasks/{}", TASKS_URL, list_id, task_id))
+            .bearer_auth(token)
+            .send()
+            .await?;
+        // Already gone is as good as deleted
+        if response.status() != reqwest::StatusCode::NOT_FOUND {
+            response.error_for_status()?;
+        }
+        Ok(())
+    }
+}
+
+fn link(todo_id: usize, task_id: &str, list_id: &str) -> SyncLink {
+    SyncLink {
+        todo_id: todo_id as i64,
+        remote_id: task_id.to_string(),
+        remote_list: list_id.to_string(),
+        synced_at: Utc::now(),
+    }
+}
+
+impl SyncBackend for GoogleTasksSync {
+    fn name(&self) -> &'static str {
+        "Google Tasks"
+    }
+
+    fn key(&self) -> &'static str {
+        BACKEND
+    }
+
+    async fn sync(
+        &mut self,
+        db: &mut DBtodo,
+        delta: Option<&Delta>,
+    ) -> Result<SyncReport, Box<dyn Error>> {
+        let token = self.access_token().await?;
+        let scope = SyncConfig::load();
+        let lists = self.lists(&token, &scope).await?;
+        let links = db.sync_links(BACKEND)?;
+        // A list without links was just mapped, everything in it is compared once
+        let delta = delta.filter(|_| {
+            lists
+                .iter()
+                .all(|(_, list_id)| links.iter().any(|link| link.remote_list == *list_id))
+        });
+        // A minute early, for the clocks that disagree a little
+        let since = delta.map(|delta| delta.since - chrono::Duration::minutes(1));
+
+        let mut remotes = Vec::new();
+        for (topic, list_id) in lists {
+            remotes.push(Remote {
+                tasks: self.tasks(&token, &list_id, since).await?,
+                list_id,
+                topic,
+            });
+        }
+        let list_of = |topic: &str| {
+            if !scope.includes(BACKEND, topic) {
+                return None;
+            }
+            let title = self.config.list_for(topic)?;
+            // The list the first synced topic with this title was fetched as
+            let (first, _) = self.config.lists.iter().find(|(known, list)| {
+                list.eq_ignore_ascii_case(title) && scope.includes(BACKEND, known)
+            })?;
+            remotes
+                .iter()
+                .find(|remote| remote.topic.eq_ignore_ascii_case(first))
+                .map(|remote| remote.list_id.clone())
+        };
+
+        let mut todos = db.get_todos()?;
+        todos.extend(db.get_archived_todos()?);
+        let changed = delta.map(Delta::todo_ids);
+        let steps = plan(&todos, &remotes, &links, list_of, changed.as_ref());
+
+        let mut report = SyncReport::default();
+        for step in steps {
+            match step {
+                Step::Push {
+                    list_id,
+                    todo_id,
+                    task_id,
+                } => {
+                    let Some(todo) = todos.iter().find(|todo| todo.id == todo_id) else {
+                        continue;
+                    };
+                    let task = self
+                        .send_task(&token, &list_id, task_id.as_deref(), &todo_to_task(todo))
+                        .await?;
+                    db.save_sync_link(BACKEND, &link(todo_id, &task.id, &list_id))?;
+                    report.pushed += 1;
+                }
+                Step::Pull {
+                    list_id,
+                    task,
+                    todo_id,
+                } => {
+                    let todo_id =
+                        match todo_id.and_then(|id| todos.iter().find(|todo| todo.id == id)) {
+                            Some(todo) => {
+                                let mut todo = todo.clone();
+                                apply_task(&mut todo, &task);
+                                db.apply_synced(&todo)?;
+                                todo.id
+                            }
+                            None => {
+                                let topic = remotes
+                                    .iter()
+                                    .find(|remote| remote.list_id == list_id)
+                                    .map(|remote| remote.topic.clone());
+                                let mut todo = build_todo(
+                                    task.title.trim().to_string(),
+                                    topic,
+                                    None,
+                                    None,
+                                    task.due_date(),
+                                    None,
+                                    Vec::new(),
+                                );
+                                // Only the date the task has, not the configured default offset
+                                todo.due = task.due_date();
+                                apply_task(&mut todo, &task);
+                                db.add_todo(&todo)? as usize
+                            }
+                        };
+                    db.save_sync_link(BACKEND, &link(todo_id, &task.id, &list_id))?;
+                    report.pulled += 1;
+                }
+                Step::Link {
+                    list_id,
+                    todo_id,
+                    task_id,
+                } => db.save_sync_link(BACKEND, &link(todo_id, &task_id, &list_id))?,
+                Step::DeleteTask { list_id, task_id } => {
+                    self.delete_task(&token, &list_id, &task_id).await?;
+                    report.deleted += 1;
+                }
+                Step::ArchiveTodo { todo_id } => {
+                    db.archive_todos(&[todo_id as i32])?;
+                    report.deleted += 1;
+                }
+                Step::Forget { todo_id } => db.delete_sync_link(BACKEND, todo_id as i64)?,
+            }
+        }
+        Ok(report)
+    }
+}
+
+// Forget the sign-in, the next sync asks for a new one
+pub fn logout() -> Result<(), Box<dyn Error>> {
+    keyring::delete(TOKEN_KEY)?;
+    println!("👋 Signed out of Google Tasks");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::demo_todos;
+
+    fn task(id: &str, title: &str, updated: DateTime<Utc>) -> Task {
+        Task {
+            id: id.to_string(),
+            title: title.to_string(),
+            status: "needsAction".to_string(),
+            updated: Some(updated),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn test_plan_sync() {
+        let synced = dates::parse_timestamp("2025-03-10T12:00:00Z").unwrap();
+        let before = synced - chrono::Duration::days(1);
+        let after = synced + chrono::Duration::hours(1);
+
+        let mut todos = demo_todos();
+        for (index, todo) in todos.iter_mut().enumerate() {
+            todo.id = index + 1;
+            todo.topic = "Work".to_string();
+            todo.date_added = before;
+            todo.updated_at = None;
+            todo.due = None;
+            todo.desc = NO_DESCRIPTION.to_string();
+        }
+        // #1 changed here, #2 there, #3 nowhere, #4 is gone here, #5 is gone there
+        todos[0].updated_at = Some(after);
+        todos.retain(|todo| todo.id != 4);
+        // #6 is new here, #7 is done and new so it stays here, #8 is on both sides already
+        let remote = Remote {
+            list_id: "L1".to_string(),
+            topic: "Work".to_string(),
+            tasks: vec![
+                task("t1", "Old text", before),
+                task("t2", "Renamed on the phone", after),
+                task("t3", &todos[2].text, before),
+                task("t4", "Gone here", before),
+                task("t9", "New on the phone", after),
+                task("t8", "read \"the pragmatic programmer\"", after),
+            ],
+        };
+        let links = [1, 2, 3, 4, 5]
+            .map(|id| SyncLink {
+                todo_id: id,
+                remote_id: format!("t{}", id),
+                remote_list: "L1".to_string(),
+                synced_at: synced,
+            })
+            .to_vec();
+        let list_of = |topic: &str| (topic == "Work").then(|| "L1".to_string());
+        let steps = plan(&todos, &[remote], &links, list_of, None);
+
+        let push = |todo_id: usize, task_id: Option<&str>| Step::Push {
+            list_id: "L1".to_string(),
+            todo_id,
+            task_id: task_id.map(str::to_string),
+        };
+        assert_eq!(steps[0], push(1, Some("t1")));
+        assert!(matches!(&steps[1], Step::Pull { todo_id: Some(2), task, .. } if task.id == "t2"));
+        // #3 agrees and neither side changed: nothing to do
+        assert_eq!(
+            steps[2],
+            Step::DeleteTask {
+                list_id: "L1".to_string(),
+                task_id: "t4".to_string()
+            }
+        );
+        assert_eq!(steps[3], Step::Forget { todo_id: 4 });
+        assert_eq!(steps[4], Step::ArchiveTodo { todo_id: 5 });
+        assert_eq!(steps[5], Step::Forget { todo_id: 5 });
+        assert!(matches!(&steps[6], Step::Pull { todo_id: None, task, .. } if task.id == "t9"));
+        // Same text, paired up: the phone's copy is newer
+        assert!(matches!(&steps[7], Step::Pull { todo_id: Some(8), task, .. } if task.id == "t8"));
+        assert_eq!(steps[8], push(6, None));
+        assert_eq!(steps.len(), 9);
+
+        // Since the last sync #1 and #6 changed here and #4 went, the phone deleted t2 and added t9
+        let changed = HashSet::from([1, 4, 6]);
+        let mut deleted = task("t2", "", after);
+        deleted.deleted = true;
+        let new_task = task("t9", "New on the phone", after);
+        let delta = Remote {
+            list_id: "L1".to_string(),
+            topic: "Work".to_string(),
+            tasks: vec![deleted, new_task.clone()],
+        };
+        let steps = plan(&todos, &[delta], &links[..4], list_of, Some(&changed));
+        assert_eq!(
+            steps,
+            vec![
+                push(1, Some("t1")),
+                Step::ArchiveTodo { todo_id: 2 },
+                Step::Forget { todo_id: 2 },
+                Step::DeleteTask {
+                    list_id: "L1".to_string(),
+                    task_id: "t4".to_string()
+                },
+                Step::Forget { todo_id: 4 },
+                Step::Pull {
+                    list_id: "L1".to_string(),
+                    task: new_task,
+                    todo_id: None
+                },
+                push(6, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_task_round_trip() {
+        let mut todo = demo_todos().remove(0);
+        let task = todo_to_task(&todo);
+        assert_eq!(task.status, "needsAction");
+        assert_eq!(task.due_date(), todo.due);
+        assert!(same(&todo, &task));
+
+        let done = Task {
+            status: "completed".to_string(),
+            notes: String::new(),
+            due: None,
+            ..task
+        };
+        apply_task(&mut todo, &done);
+        assert_eq!(todo.status, "Done");
+        assert_eq!(todo.desc, NO_DESCRIPTION);
+        assert_eq!(todo.due, None);
+        assert!(
+            serde_json::to_string(&done)
+                .unwrap()
+                .contains("\"due\":null")
+        );
+    }
+}
@@ -0,0 +1,108 @@
+// CHANGE JOURNAL: what changed since a backend's last sync, so it only has to send and look at
+// that instead of every todo. The triggers in the database write an entry with its own UUID for
+// every insert, update and delete, this turns them into one net change per todo.
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::error::Error;
+
+use chrono::{DateTime, Utc};
+
+use crate::database::{DBtodo, JournalEntry};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    // When the last sync started, remote changes since then are new too
+    pub since: DateTime<Utc>,
+    pub changes: Vec<JournalEntry>,
+}
+
+impl Delta {
+    pub fn todo_ids(&self) -> HashSet<usize> {
+        self.changes
+            .iter()
+            .map(|change| change.todo_id as usize)
+            .collect()
+    }
+}
+
+// One entry per todo, the last one, in the order of their last change. A todo added since is an
+// insert however often it changed after, one added and deleted since never happened. The topic
+// is the one it had at the last sync.
+pub fn collapse(entries: Vec<JournalEntry>) -> Vec<JournalEntry> {
+    let mut firsts = HashMap::new();
+    let mut last = HashMap::new();
+    for (position, entry) in entries.into_iter().enumerate() {
+        firsts
+            .entry(entry.todo_id)
+            .or_insert_with(|| (entry.action.clone(), entry.topic.clone()));
+        last.insert(entry.todo_id, (position, entry));
+    }
+
+    let mut changes = last
+        .into_values()
+        .filter_map(|(position, mut entry)| {
+            let (first_action, first_topic) = &firsts[&entry.todo_id];
+            match (first_action.as_str(), entry.action.as_str()) {
+                ("insert", "delete") => return None,
+                ("insert", _) => entry.action = "insert".to_string(),
+                _ => {}
+            }
+            entry.topic = first_topic.clone();
+            Some((position, entry))
+        })
+        .collect::<Vec<(usize, JournalEntry)>>();
+    changes.sort_by_key(|(position, _)| *position);
+    changes.into_iter().map(|(_, entry)| entry).collect()
+}
+
+// The changes a backend hasn't seen, None when it never synced and needs everything
+pub fn delta(db: &DBtodo, backend: &str) -> Result<Option<Delta>, Box<dyn Error>> {
+    let Some(cursor) = db.sync_cursor(backend)? else {
+        return Ok(None);
+    };
+    Ok(Some(Delta {
+        since: cursor.synced_at,
+        changes: collapse(db.journal_since(cursor.journal_id)?),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collapse_journal() {
+        let entry = |todo_id: i64, action: &str| JournalEntry {
+            uuid: format!("{}-{}", todo_id, action),
+            todo_id,
+            action: action.to_string(),
+            topic: format!("Topic {}", action),
+            changed_at: DateTime::<Utc>::MIN_UTC,
+        };
+        let changes = collapse(vec![
+            entry(1, "update"),
+            entry(2, "insert"),
+            entry(3, "insert"),
+            entry(1, "update"),
+            entry(2, "update"),
+            entry(4, "update"),
+            entry(3, "delete"),
+            entry(4, "delete"),
+        ]);
+        let summary = changes
+            .iter()
+            .map(|change| (change.todo_id, change.action.as_str(), change.uuid.as_str()))
+            .collect::<Vec<_>>();
+        // #4 was in its topic before the first change since the last sync
+        assert_eq!(changes[2].topic, "Topic update");
+        // #3 came and went between two syncs
+        assert_eq!(
+            summary,
+            vec![
+                (1, "update", "1-update"),
+                (2, "insert", "2-update"),
+                (4, "delete", "4-delete")
+            ]
+        );
+    }
+}
@@ -0,0 +1,145 @@
+// SECRETS: sign-in tokens go in the system keyring through its command line tool, `security` on
+// macOS and `secret-tool` (libsecret) on Linux. Without one they go in a file in the data folder
+// that only the user can read. Each profile keeps its own.
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::{error::Error, fs};
+
+use crate::configs;
+
+const SERVICE: &str = "voido";
+
+fn account(key: &str) -> String {
+    format!("{}:{}", configs::active_profile().unwrap_or("default"), key)
+}
+
+fn fallback_path(key: &str) -> Result<PathBuf, Box<dyn Error>> {
+    Ok(configs::data_dir()?.join("secrets").join(key))
+}
+
+fn has_tool(tool: &str) -> bool {
+    Command::new(tool)
+        .arg("--help")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+pub fn store(key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    let account = account(key);
+    if cfg!(target_os = "macos") && has_tool("security") {
+        // `-w` last and without a value reads the secret from stdin, on the command line any
+        // user could see it in `ps`. It asks twice, to retype it.
+        let mut child = Command::new("security")
+            .args([
+                "add-generic-password",
+                "-U",
+                "-s",
+                SERVICE,
+                "-a",
+                &account,
+                "-w",
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(format!("{}\n{}\n", value, value).as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    } else if cfg!(target_os = "linux") && has_tool("secret-tool") {
+        let mut child = Command::new("secret-tool")
+            .args([
+                "store",
+                "--label",
+                &format!("VoiDo {}", account),
+                "service",
+                SERVICE,
+                "account",
+                &account,
+            ])
+            .stdin(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(value.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    // No keyring to talk to (or it is locked), keep it where only this user can read it
+    let path = fallback_path(key)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // Created unreadable to others, not narrowed after the secret is in it
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    // A file left by an older VoiDo may have been created with wider permissions
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(value.as_bytes())?;
+    Ok(())
+}
+
+pub fn load(key: &str) -> Option<String> {
+    let account = account(key);
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", SERVICE, "-a", &account, "-w"])
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+    } else if cfg!(target_os = "linux") {
+        Command::new("secret-tool")
+            .args(["lookup", "service", SERVICE, "account", &account])
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+    } else {
+        None
+    };
+    output
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .or_else(|| fs::read_to_string(fallback_path(key).ok()?).ok())
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn delete(key: &str) -> Result<(), Box<dyn Error>> {
+    let account = account(key);
+    if cfg!(target_os = "macos") {
+        let _ = Command::new("security")
+            .args(["delete-generic-password", "-s", SERVICE, "-a", &account])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+    } else if cfg!(target_os = "linux") {
+        let _ = Command::new("secret-tool")
+            .args(["clear", "service", SERVICE, "account", &account])
+            .stderr(Stdio::null())
+            .status();
+    }
+    let path = fallback_path(key)?;
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+// SYNC: other places the todos live too. Each backend pushes the local changes and pulls the
+// remote ones its own way, `voido sync <backend>` runs one of them.
+use std::error::Error;
+use std::future::Future;
+
+use chrono::Utc;
+
+use crate::database::DBtodo;
+use journal::Delta;
+
+pub mod daemon;
+pub mod forge;
+pub mod github;
+pub mod google_tasks;
+pub mod journal;
+pub mod keyring;
+pub mod queue;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    // Todos sent to the backend, new or changed
+    pub pushed: usize,
+    // Todos added or changed from the backend
+    pub pulled: usize,
+    // Todos deleted on one side and taken off the other
+    pub deleted: usize,
+}
+
+impl SyncReport {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} pushed, {} pulled, {} deleted",
+            self.pushed, self.pulled, self.deleted
+        )
+    }
+}
+
+pub trait SyncBackend {
+    fn name(&self) -> &'static str;
+
+    // What `voido sync` calls it, and the key of its sync state and links in the database
+    fn key(&self) -> &'static str;
+
+    // Only the changes in `delta` need to go out, all of them when there is none
+    fn sync(
+        &mut self,
+        db: &mut DBtodo,
+        delta: Option<&Delta>,
+    ) -> impl Future<Output = Result<SyncReport, Box<dyn Error>>>;
+}
+
+// `full` compares everything, as the first sync does, instead of the changes since the last one
+pub async fn run(backend: &mut impl SyncBackend, full: bool) -> Result<SyncReport, Box<dyn Error>> {
+    let mut db = DBtodo::new()?;
+    let started = Utc::now();
+    let head = db.journal_head()?;
+    let delta = if full {
+        None
+    } else {
+        journal::delta(&db, backend.key())?
+    };
+    println!("🔄 Syncing with {}...", backend.name());
+    match backend.sync(&mut db, delta.as_ref()).await {
+        Ok(report) => {
+            db.record_sync(backend.key(), head, started)?;
+            println!("✅ Synced with {}: {}", backend.name(), report.summary());
+            Ok(report)
+        }
+        // The changes stay queued until a sync goes through
+        Err(e) => {
+            db.record_sync_error(backend.key(), &e.to_string())?;
+            Err(e)
+        }
+    }
+}
+
+// `--dry-run`: the local changes the next sync would send, without contacting the backend, so
+// what it would pull is not known
+fn preview(key: &str, full: bool) -> Result<SyncReport, Box<dyn Error>> {
+    let db = DBtodo::new()?;
+    match journal::delta(&db, key)?.filter(|_| !full) {
+        None => println!(
+            "🧪 Dry run: every todo would be compared with {}, nothing was sent",
+            key
+        ),
+        Some(delta) if delta.changes.is_empty() => {
+            println!("🧪 Dry run: no local changes to send to {}", key)
+        }
+        Some(delta) => {
+            println!(
+                "🧪 Dry run: {} local change(s) would go to {}, nothing was sent:",
+                delta.changes.len(),
+                key
+            );
+            let todos = crate::arguments::diff::all_todos(&db)?;
+            for change in &delta.changes {
+                // Deleted todos have no text left
+                let text = todos
+                    .iter()
+                    .find(|todo| todo.id as i64 == change.todo_id)
+                    .map_or("", |todo| todo.text.as_str());
+                println!("  {:>4}  {:<6}  {}", change.todo_id, change.action, text);
+            }
+        }
+    }
+    Ok(SyncReport::default())
+}
+
+// Sync a backend by its key, `background` ones never stop to ask for a sign-in
+pub async fn run_backend(
+    key: &str,
+    full: bool,
+    background: bool,
+) -> Result<SyncReport, Box<dyn Error>> {
+    if DBtodo::is_dry_run() {
+        return preview(key, full);
+    }
+    match key {
+        "github" => run(&mut github::GitHubSync::from_configs()?, full).await,
+        "google" => run(&mut google_tasks::GoogleTasksSync::new(!background)?, full).await,
+        _ => Err(format!("\"{}\" is not a sync backend", key).into()),
+    }
+}
@@ -0,0 +1,237 @@
+// OFFLINE QUEUE: every change made after a backend's last successful sync waits in the sync
+// journal. The stats bar shows whether anything is waiting or the last sync failed, and while the
+// TUI is open the queue is flushed in the background as soon as the backend is reachable again.
+use std::collections::HashMap;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+use super::forge::RemoteUrl;
+use crate::configs;
+use crate::database::{DBtodo, SyncState};
+
+// How often the stats bar looks at the journal
+const STATUS_EVERY: Duration = Duration::from_secs(5);
+// How often the servers are tried while changes are queued or the last sync failed
+const PROBE_EVERY: Duration = Duration::from_secs(30);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncStatus {
+    Synced,
+    // Todos changed since the last sync
+    Pending(usize),
+    // The backend whose last sync failed
+    Error(String),
+}
+
+// One status for every backend: a failure first, then anything queued
+pub fn status(states: &[SyncState]) -> Option<SyncStatus> {
+    if states.is_empty() {
+        return None;
+    }
+    if let Some(state) = states.iter().find(|state| state.error.is_some()) {
+        return Some(SyncStatus::Error(state.backend.clone()));
+    }
+    Some(match states.iter().map(|state| state.pending).max() {
+        Some(pending) if pending > 0 => SyncStatus::Pending(pending),
+        _ => SyncStatus::Synced,
+    })
+}
+
+// The daemon's next sync of any backend, a time already gone means it isn't running anymore
+pub fn next_run(states: &[SyncState], now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    states
+        .iter()
+        .filter_map(|state| state.next_run)
+        .filter(|next_run| *next_run > now)
+        .min()
+}
+
+// The server a backend talks to, to know when it is back
+fn address(backend: &str) -> Option<(String, u16)> {
+    match backend {
+        "github" => Some(
+            configs::AppConfigs::read_configs_from_file()
+                .ok()
+                .and_then(|configs| RemoteUrl::parse(&configs.git_remote))
+                .map(|remote| remote.address())
+                .unwrap_or_else(|| ("github.com".to_string(), 443)),
+        ),
+        "google" => Some(("tasks.googleapis.com".to_string(), 443)),
+        _ => None,
+    }
+}
+
+fn reachable(address: (String, u16)) -> bool {
+    address
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addresses| addresses.next())
+        .is_some_and(|address| TcpStream::connect_timeout(&address, PROBE_TIMEOUT).is_ok())
+}
+
+// Sync in a child process so its output stays out of the TUI
+fn flush(backend: &str) {
+    let Ok(exe) = std::env::current_exe() else {
+        return;
+    };
+    let mut command = Command::new(exe);
+    if let Some(profile) = configs::active_profile() {
+        command.args(["--profile", profile]);
+    }
+    let _ = command
+        .args(["sync", backend, "--background"])
+        // Git asks for credentials on the terminal otherwise
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+}
+
+#[derive(Debug)]
+enum Work {
+    // Which backends answered
+    Probe(Vec<(String, bool)>),
+    Flush,
+}
+
+#[derive(Debug)]
+pub struct SyncWatcher {
+    pub status: Option<SyncStatus>,
+    pub next_run: Option<DateTime<Utc>>,
+    states: Vec<SyncState>,
+    // Whether each backend was reachable when last seen. A failed sync counts as offline, so
+    // the changes it left queued go out once the server answers.
+    online: HashMap<String, bool>,
+    status_checked: Option<Instant>,
+    probed: Option<Instant>,
+    worker: Option<JoinHandle<Work>>,
+}
+
+impl SyncWatcher {
+    pub fn new() -> Self {
+        let mut watcher = Self {
+            status: None,
+            next_run: None,
+            states: Vec::new(),
+            online: HashMap::new(),
+            status_checked: None,
+            probed: None,
+            worker: None,
+        };
+        watcher.refresh();
+        watcher.online = watcher
+            .states
+            .iter()
+            .map(|state| (state.backend.clone(), state.error.is_none()))
+            .collect();
+        watcher
+    }
+
+    pub fn refresh(&mut self) {
+        self.status_checked = Some(Instant::now());
+        if let Ok(states) = DBtodo::new().and_then(|db| db.sync_states()) {
+            self.status = status(&states);
+            self.next_run = next_run(&states, Utc::now());
+            self.states = states;
+        }
+    }
+
+    fn due(last: Option<Instant>, every: Duration) -> bool {
+        last.is_none_or(|last| last.elapsed() >= every)
+    }
+
+    // Called while the TUI waits for input. True once a background flush finished, the todos
+    // may have changed.
+    pub fn tick(&mut self) -> bool {
+        if let Some(worker) = self.worker.take_if(|worker| worker.is_finished()) {
+            match worker.join() {
+                Ok(Work::Flush) => {
+                    self.refresh();
+                    return true;
+                }
+                Ok(Work::Probe(results)) => {
+                    // Back online with changes waiting
+                    let back = results
+                        .iter()
+                        .filter(|(backend, online)| {
+                            *online && self.online.get(backend) == Some(&false)
+                        })
+                        .map(|(backend, _)| backend.clone())
+                        .collect::<Vec<String>>();
+                    self.online.extend(results);
+                    if !back.is_empty() {
+                        self.worker = Some(thread::spawn(move || {
+                            for backend in back {
+                                flush(&backend);
+                            }
+                            Work::Flush
+                        }));
+                    }
+                }
+                Err(_) => {}
+            }
+        }
+        if self.worker.is_some() {
+            return false;
+        }
+
+        if Self::due(self.status_checked, STATUS_EVERY) {
+            self.refresh();
+        }
+        let queued = self
+            .states
+            .iter()
+            .filter(|state| state.pending > 0 || state.error.is_some())
+            .filter_map(|state| Some((state.backend.clone(), address(&state.backend)?)))
+            .collect::<Vec<(String, (String, u16))>>();
+        if !queued.is_empty() && Self::due(self.probed, PROBE_EVERY) {
+            self.probed = Some(Instant::now());
+            self.worker = Some(thread::spawn(move || {
+                Work::Probe(
+                    queued
+                        .into_iter()
+                        .map(|(backend, address)| (backend, reachable(address)))
+                        .collect(),
+                )
+            }));
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_status() {
+        let state = |backend: &str, pending: usize, error: Option<&str>| SyncState {
+            backend: backend.to_string(),
+            error: error.map(str::to_string),
+            pending,
+            next_run: None,
+        };
+        assert_eq!(status(&[]), None);
+        assert_eq!(
+            status(&[state("github", 0, None), state("google", 0, None)]),
+            Some(SyncStatus::Synced)
+        );
+        assert_eq!(
+            status(&[state("github", 2, None), state("google", 5, None)]),
+            Some(SyncStatus::Pending(5))
+        );
+        assert_eq!(
+            status(&[
+                state("github", 2, None),
+                state("google", 5, Some("offline"))
+            ]),
+            Some(SyncStatus::Error("google".to_string()))
+        );
+    }
+}
@@ -0,0 +1,56 @@
+// DISPLAY WIDTH: how many terminal columns text takes. CJK characters and most emoji
+// take two, combining marks none, so layout works on graphemes and their width
+// instead of bytes or chars.
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+pub fn width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+// Byte offset of the grapheme boundary before `index`, 0 at the start
+pub fn prev_boundary(text: &str, index: usize) -> usize {
+    text[..index]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(offset, _)| offset)
+}
+
+// Byte offset of the grapheme boundary after `index`, the text length at the end
+pub fn next_boundary(text: &str, index: usize) -> usize {
+    text[index..]
+        .graphemes(true)
+        .next()
+        .map_or(text.len(), |grapheme| index + grapheme.len())
+}
+
+// Longest run of whole graphemes that fits in `max` columns, and what is left
+pub fn split_at_width(text: &str, max: usize) -> (&str, &str) {
+    let mut used = 0;
+    for (offset, grapheme) in text.grapheme_indices(true) {
+        used += width(grapheme);
+        if used > max {
+            return text.split_at(offset);
+        }
+    }
+    (text, "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_and_combined_graphemes() {
+        assert_eq!(width("日本語"), 6);
+        assert_eq!(width("e\u{301}"), 1);
+        assert_eq!(split_at_width("日本語", 5), ("日本", "語"));
+        assert_eq!(split_at_width("abc", 5), ("abc", ""));
+
+        let text = "ae\u{301}日";
+        assert_eq!(next_boundary(text, 1), 4);
+        assert_eq!(prev_boundary(text, 4), 1);
+        assert_eq!(prev_boundary(text, 0), 0);
+        assert_eq!(next_boundary(text, text.len()), text.len());
+    }
+}
@@ -0,0 +1,125 @@
+// INBOX TRIAGE: the todos captured with `voido q` one at a time, a key per decision. Giving one a
+// topic or deleting it takes it out of the inbox, priority and due date can be set on the way.
+use std::collections::HashMap;
+
+use crate::arguments::models::Todo;
+
+// Known topics get the keys 1 to 9
+pub const TOPIC_KEYS: usize = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriageField {
+    Topic,
+    Due,
+}
+
+#[derive(Debug, Default)]
+pub struct Triage {
+    // Inbox todos still to go through, the first one is on screen
+    queue: Vec<usize>,
+    total: usize,
+    // Topics for the number keys, the most used first
+    pub topics: Vec<String>,
+    // What the prompt under the todo asks for
+    pub field: Option<TriageField>,
+    pub sorted: usize,
+    pub deleted: usize,
+    pub skipped: usize,
+}
+
+pub fn is_inbox(todo: &Todo, inbox: &str) -> bool {
+    !todo.is_done() && todo.topic.eq_ignore_ascii_case(inbox)
+}
+
+impl Triage {
+    // Oldest capture first
+    pub fn new(todos: &[Todo], inbox: &str) -> Self {
+        let mut queue = todos
+            .iter()
+            .filter(|todo| is_inbox(todo, inbox))
+            .map(|todo| todo.id)
+            .collect::<Vec<usize>>();
+        queue.sort_unstable();
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for todo in todos {
+            if !todo.topic.eq_ignore_ascii_case(inbox) {
+                *counts.entry(todo.topic.as_str()).or_default() += 1;
+            }
+        }
+        let mut topics = counts.into_iter().collect::<Vec<(&str, usize)>>();
+        topics.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        Self {
+            total: queue.len(),
+            queue,
+            topics: topics
+                .into_iter()
+                .take(TOPIC_KEYS)
+                .map(|(topic, _)| topic.to_string())
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    pub fn current(&self) -> Option<usize> {
+        self.queue.first().copied()
+    }
+
+    // "3 of 7"
+    pub fn position(&self) -> (usize, usize) {
+        (self.total - self.queue.len() + 1, self.total)
+    }
+
+    pub fn topic_for(&self, key: char) -> Option<&str> {
+        let index = key.to_digit(10)?.checked_sub(1)?;
+        self.topics.get(index as usize).map(String::as_str)
+    }
+
+    // The todo on screen is dealt with, the next one comes up
+    pub fn advance(&mut self) {
+        if !self.queue.is_empty() {
+            self.queue.remove(0);
+        }
+        self.field = None;
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "📭 Inbox triaged: {} sorted, {} deleted, {} skipped",
+            self.sorted, self.deleted, self.skipped
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::demo_todos;
+
+    #[test]
+    fn test_triage_queue() {
+        let mut todos = demo_todos();
+        for (index, todo) in todos.iter_mut().enumerate() {
+            todo.id = index + 1;
+        }
+        todos[7].topic = "Inbox".to_string();
+        todos[2].topic = "inbox".to_string();
+        // Done todos are already sorted out
+        todos[6].topic = "Inbox".to_string();
+
+        let mut triage = Triage::new(&todos, "Inbox");
+        assert_eq!(triage.current(), Some(3));
+        assert_eq!(triage.position(), (1, 2));
+        // Work has three todos, Home and Health one each
+        assert_eq!(triage.topics, vec!["Work", "Health", "Home"]);
+        assert_eq!(triage.topic_for('1'), Some("Work"));
+        assert_eq!(triage.topic_for('0'), None);
+        assert_eq!(triage.topic_for('4'), None);
+
+        triage.advance();
+        assert_eq!((triage.current(), triage.position()), (Some(8), (2, 2)));
+        triage.advance();
+        assert_eq!(triage.current(), None);
+    }
+}
@@ -1,33 +1,411 @@
-use crate::arguments::models::Todo;
+use crate::arguments::models::{REVIEW_STATUS, Todo};
+use crate::arguments::stale::{DEFAULT_STALE_DAYS, is_stale};
+use crate::colors::{self, Theme, topic_color};
+use crate::configs;
+#[cfg(feature = "sync")]
+use crate::dates;
+use crate::icons::IconSet;
 use crate::modals::{
-    centered_rect, draw_delete_confirmation, draw_main_menu_modal, draw_priority_modal,
-    draw_todo_modal,
+    centered_rect, draw_checklist_modal, draw_copy_modal, draw_delete_confirmation,
+    draw_export_modal, draw_focus_view, draw_graph_modal, draw_links_modal, draw_main_menu_modal,
+    draw_note_tasks_modal, draw_priority_modal, draw_settings_modal, draw_stats_modal,
+    draw_todo_modal, draw_triage_modal,
 };
 use crate::search::InputField;
+use crate::styling::row_style;
+#[cfg(feature = "sync")]
+use crate::sync::queue::SyncStatus;
+use crate::text_width;
 use crate::{App, database};
+use chrono::NaiveDate;
 use ratatui::layout::Alignment;
 use ratatui::prelude::Stylize;
-use ratatui::text::Span;
+use ratatui::text::{Span, Text};
 use ratatui::{
     Frame, Terminal,
     backend::CrosstermBackend,
-    layout::{Constraint, Direction, Layout, Margin, Rect},
+    layout::{Constraint, Direction, Flex, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
     text::Line,
-    widgets::{Block, Borders, Paragraph, Row, Table, Wrap},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
 };
 
+// Terminal widths below which the layout starts dropping columns
+pub const WIDE_LAYOUT_WIDTH: u16 = 120;
+pub const NARROW_LAYOUT_WIDTH: u16 = 90;
+
+// Lines a long todo can take in the comfortable density
+pub const MAX_ROW_LINES: usize = 2;
+
+// TABLE COLUMNS
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Number,
+    Id,
+    Icons,
+    Priority,
+    Topic,
+    Todo,
+    Subtasks,
+    Created,
+    Due,
+    Countdown,
+    Status,
+    Owner,
+}
+
+impl Column {
+    pub const ALL: [Column; 12] = [
+        Column::Number,
+        Column::Id,
+        Column::Icons,
+        Column::Priority,
+        Column::Topic,
+        Column::Todo,
+        Column::Subtasks,
+        Column::Created,
+        Column::Due,
+        Column::Countdown,
+        Column::Status,
+        Column::Owner,
+    ];
+
+    // Name used for the column in config.toml
+    pub fn key(self) -> &'static str {
+        match self {
+            Column::Number => "number",
+            Column::Id => "id",
+            Column::Icons => "icons",
+            Column::Priority => "priority",
+            Column::Topic => "topic",
+            Column::Todo => "todo",
+            Column::Subtasks => "subtasks",
+            Column::Created => "created",
+            Column::Due => "due",
+            Column::Countdown => "countdown",
+            Column::Status => "status",
+            Column::Owner => "owner",
+        }
+    }
+
+    pub fn header(self, short: bool) -> &'static str {
+        match (self, short) {
+            (Column::Number, _) => "#",
+            (Column::Id, _) => "ID",
+            (Column::Icons, _) => "",
+            (Column::Priority, false) => "PRIORITY",
+            (Column::Priority, true) => "PRI",
+            (Column::Topic, _) => "TOPIC",
+            (Column::Todo, _) => "TODO",
+            (Column::Subtasks, false) => "SUBt",
+            (Column::Subtasks, true) => "ST",
+            (Column::Created, _) => "CREATED",
+            (Column::Due, false) => "DUE DATE",
+            (Column::Due, true) => "DUE",
+            (Column::Countdown, false) => "DAYS",
+            (Column::Countdown, true) => "D",
+            (Column::Status, _) => "STATUS",
+            (Column::Owner, _) => "OWNER",
+        }
+    }
+
+    pub fn constraint(self, short: bool) -> Constraint {
+        match (self, short) {
+            (Column::Number, _) => Constraint::Length(4), // # (row numbers up to 999)
+            (Column::Id, _) => Constraint::Length(5),     // ID (tiny, fixed)
+            (Column::Icons, _) => Constraint::Length(5),  // two glyphs, emoji take two cells each
+            (Column::Priority, false) => Constraint::Length(9), // PRIORITY (short text, fixed enough)
+            (Column::Priority, true) => Constraint::Length(6),
+            (Column::Topic, false) => Constraint::Min(10), // TOPIC (short phrases, expand if room)
+            (Column::Topic, true) => Constraint::Min(6),
+            (Column::Todo, _) => Constraint::Fill(3), // TODO (long text, gets majority of space)
+            (Column::Subtasks, false) => Constraint::Length(6), // SUBs (tiny numbers, fixed)
+            (Column::Subtasks, true) => Constraint::Length(5),
+            (Column::Created, _) => Constraint::Length(12), // CREATED (YYYY-MM-DD)
+            (Column::Due, _) => Constraint::Length(17),     // DUE (YYYY-MM-DD HH:MM)
+            (Column::Countdown, _) => Constraint::Length(5), // DAYS (-999 to 9999)
+            (Column::Status, false) => Constraint::Min(10), // STATUS (labels like "In Progress")
+            (Column::Status, true) => Constraint::Length(8),
+            (Column::Owner, _) => Constraint::Min(10), // OWNER (names, can expand)
+        }
+    }
+}
+
+// ROW NUMBERS: the place of each row in the table, or with relative numbers how far it is from the
+// selected one, which shows its own place
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowNumbers {
+    Off,
+    Absolute,
+    Relative,
+}
+
+impl RowNumbers {
+    pub const NAMES: [&str; 3] = ["off", "absolute", "relative"];
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "off" => Some(Self::Off),
+            "absolute" => Some(Self::Absolute),
+            "relative" => Some(Self::Relative),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Absolute => "absolute",
+            Self::Relative => "relative",
+        }
+    }
+
+    // Number shown on the row at `index`, counting from 1
+    pub fn number(self, index: usize, selected: Option<usize>) -> usize {
+        match (self, selected) {
+            (Self::Relative, Some(selected)) if index != selected => index.abs_diff(selected),
+            _ => index + 1,
+        }
+    }
+}
+
+// A column's width and alignment from [DISPLAY.columns], each unset one keeps the built-in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColumnLayout {
+    // Fixed width, min and max are ignored with it
+    pub width: Option<u16>,
+    pub min: Option<u16>,
+    pub max: Option<u16>,
+    pub align: Option<Alignment>,
+}
+
+pub fn parse_alignment(name: &str) -> Option<Alignment> {
+    match name.trim().to_lowercase().as_str() {
+        "left" => Some(Alignment::Left),
+        "center" | "centre" => Some(Alignment::Center),
+        "right" => Some(Alignment::Right),
+        _ => None,
+    }
+}
+
+impl ColumnLayout {
+    // A min lets the column grow from there and a max lets it shrink, with both it keeps its
+    // usual width between the two
+    fn constraint(&self, default: Constraint) -> Constraint {
+        let usual = match default {
+            Constraint::Length(width) | Constraint::Min(width) | Constraint::Max(width) => width,
+            _ => self.max.unwrap_or_default(),
+        };
+        match (self.width, self.min, self.max) {
+            (Some(width), _, _) => Constraint::Length(width),
+            (None, Some(min), Some(max)) => Constraint::Length(usual.clamp(min, max.max(min))),
+            (None, Some(min), None) => Constraint::Min(min),
+            (None, None, Some(max)) => Constraint::Max(max),
+            (None, None, None) => default,
+        }
+    }
+}
+
+// Row numbers line up on the right unless [DISPLAY.columns] says otherwise
+fn column_alignment(column: Column, layouts: &[(String, ColumnLayout)]) -> Alignment {
+    column_layout(column, layouts)
+        .align
+        .unwrap_or(match column {
+            Column::Number => Alignment::Right,
+            _ => Alignment::Left,
+        })
+}
+
+fn column_layout(column: Column, layouts: &[(String, ColumnLayout)]) -> ColumnLayout {
+    layouts
+        .iter()
+        .find(|(key, _)| key == column.key())
+        .map(|(_, layout)| *layout)
+        .unwrap_or_default()
+}
+
+pub fn column_constraints(
+    columns: &[Column],
+    short: bool,
+    layouts: &[(String, ColumnLayout)],
+) -> Vec<Constraint> {
+    columns
+        .iter()
+        .map(|&column| column_layout(column, layouts).constraint(column.constraint(short)))
+        .collect()
+}
+
+// Drop the least important columns first as the terminal gets narrower,
+// and skip the ones hidden in the settings (the TODO column always stays).
+// With icons on, the icon column takes the place of the priority and status text.
+pub fn visible_columns(
+    width: u16,
+    hidden: &[String],
+    icons: IconSet,
+    countdown: bool,
+    row_numbers: RowNumbers,
+) -> Vec<Column> {
+    let icons = icons != IconSet::Off;
+    Column::ALL
+        .into_iter()
+        .filter(|column| *column == Column::Todo || !hidden.iter().any(|key| key == column.key()))
+        .filter(|column| match column {
+            Column::Number => row_numbers != RowNumbers::Off,
+            Column::Icons => icons,
+            Column::Countdown => countdown,
+            Column::Priority | Column::Status => !icons,
+            Column::Created => width >= WIDE_LAYOUT_WIDTH,
+            Column::Owner => width >= NARROW_LAYOUT_WIDTH,
+            _ => true,
+        })
+        .collect()
+}
+
+// Width the table gives the TODO column, mirrors how ratatui lays out the columns
+fn todo_column_width(
+    columns: &[Column],
+    constraints: &[Constraint],
+    spacing: u16,
+    table_width: u16,
+) -> usize {
+    let Some(index) = columns.iter().position(|column| *column == Column::Todo) else {
+        return 0;
+    };
+    Layout::horizontal(constraints.iter().copied())
+        .flex(Flex::Start)
+        .spacing(spacing)
+        .split(Rect::new(0, 0, table_width, 1))[index]
+        .width as usize
+}
+
+// Word-wrap text to `width` columns, words longer than a line are split
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return vec![text.to_string()];
+    }
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word;
+        loop {
+            let line_len = text_width::width(&line);
+            let word_len = text_width::width(word);
+            let needed = if line.is_empty() {
+                word_len
+            } else {
+                line_len + 1 + word_len
+            };
+            if needed <= width {
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                break;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+                continue;
+            }
+            // A single word wider than the column, always take one grapheme so this ends
+            let (head, rest) = match text_width::split_at_width(word, width) {
+                ("", _) => word.split_at(text_width::next_boundary(word, 0)),
+                split => split,
+            };
+            lines.push(head.to_string());
+            word = rest;
+            if word.is_empty() {
+                break;
+            }
+        }
+    }
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// Cut text to `width` columns, ending with an ellipsis when something was cut
+pub fn truncate_text(text: &str, width: usize) -> String {
+    if text_width::width(text) <= width {
+        return text.to_string();
+    }
+    let (head, _) = text_width::split_at_width(text, width.saturating_sub(1));
+    format!("{}…", head)
+}
+
+// Green with two weeks or more to go, fading to red on the due date and after it
+pub fn countdown_color(days: i64) -> Color {
+    const GREEN: (f32, f32, f32) = (120.0, 220.0, 150.0);
+    const RED: (f32, f32, f32) = (220.0, 80.0, 100.0);
+    let t = 1.0 - days.clamp(0, 14) as f32 / 14.0;
+    let mix = |from: f32, to: f32| (from + (to - from) * t).round() as u8;
+    Color::Rgb(
+        mix(GREEN.0, RED.0),
+        mix(GREEN.1, RED.1),
+        mix(GREEN.2, RED.2),
+    )
+}
+
+// TODO text as table lines: truncated when compact, wrapped on up to `max_lines` otherwise
+fn todo_lines(text: &str, width: usize, compact: bool, max_lines: usize) -> Vec<String> {
+    if compact || width == 0 {
+        return vec![truncate_text(text, width.max(1))];
+    }
+    let mut lines = wrap_text(text, width);
+    if lines.len() > max_lines {
+        let rest = lines.split_off(max_lines - 1).join(" ");
+        lines.push(truncate_text(&rest, width));
+    }
+    lines
+}
+
 // MAIN UI
+// Title of the table while it shows the today view or one owner's todos
+fn table_title(app: &App) -> String {
+    let mut parts = Vec::new();
+    if app.today_view {
+        parts.push("TODAY: due, overdue and ongoing (T: show all)".to_string());
+    }
+    if let Some(owner) = &app.owner_filter {
+        parts.push(format!(
+            "OWNER: {} (@: next, u: mine)",
+            app.owner_label(owner)
+        ));
+    }
+    if let Some(context) = &app.context_filter {
+        parts.push(format!("@{} and anywhere (g: next)", context));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" {} ", parts.join(" · "))
+    }
+}
+
+// Link of the selected todo on the table's bottom border, `b` opens it
+fn selected_link(app: &App) -> Line<'static> {
+    let link = app
+        .selected_index()
+        .and_then(|index| app.todos[index].external_url.as_ref());
+    match link {
+        Some(url) => {
+            Line::from(format!(" 🔗 {} (b: open) ", truncate_text(url, 60))).right_aligned()
+        }
+        None => Line::default(),
+    }
+}
+
 pub fn draw_ui(f: &mut Frame, app: &mut App) {
     let area = f.size();
 
     // Color palette
-    let background = Color::Rgb(25, 15, 30);
-    let accent = Color::Rgb(150, 80, 220);
-    let border = Color::Rgb(180, 140, 220);
-    let text_primary = Color::Rgb(230, 220, 240);
-    let text_secondary = Color::Rgb(200, 180, 220);
-    let highlight = Color::Rgb(50, 30, 60);
+    let theme = Theme::current();
+    let background = theme.background;
+    let accent = theme.accent;
+    let border = theme.border;
+    let text_primary = theme.text_primary;
+    let text_secondary = theme.text_secondary;
+    let highlight = theme.highlight;
 
     // Handle modal states first
     if app.show_delete_confirmation {
@@ -35,14 +413,81 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
         return;
     }
     if app.show_main_menu_modal {
-        draw_main_menu_modal(f, area);
+        draw_main_menu_modal(f, area, &app.help_query, &mut app.help_scroll);
         return;
     }
     if app.show_priority_modal {
         draw_priority_modal(f, area);
         return;
     }
+    if app.show_copy_modal {
+        draw_copy_modal(f, area);
+        return;
+    }
+    if app.show_export_modal {
+        draw_export_modal(f, area, app.visible_todos().len());
+        return;
+    }
+    if app.show_stats_modal {
+        draw_stats_modal(f, area, &app.todos, &app.milestones, &app.habits);
+        return;
+    }
+    if app.show_checklist_modal {
+        draw_checklist_modal(f, area, &app.checklists, &mut app.checklist_state);
+        return;
+    }
+    if app.show_links_modal {
+        draw_links_modal(f, area, &app.note_links, &mut app.links_state);
+        return;
+    }
+    if app.show_note_tasks_modal {
+        draw_note_tasks_modal(f, area, &app.note_tasks, &mut app.note_tasks_state);
+        return;
+    }
+    if app.show_graph_modal {
+        draw_graph_modal(f, area, &app.graph_lines);
+        return;
+    }
+    if app.show_settings_modal {
+        draw_settings_modal(f, area, &mut app.settings_state);
+        return;
+    }
+    if let Some(triage) = &app.triage
+        && let Some(todo) = triage
+            .current()
+            .and_then(|id| app.todos.iter().find(|todo| todo.id == id))
+    {
+        draw_triage_modal(f, area, todo, triage);
+        if app.triage_input.active {
+            let input_area = Rect {
+                x: area.x + area.width / 6,
+                y: area.y + area.height.saturating_sub(5),
+                width: area.width - area.width / 3,
+                height: 3.min(area.height),
+            };
+            f.render_widget(ratatui::widgets::Clear, input_area);
+            app.triage_input.render(f, input_area);
+        }
+        return;
+    }
+    if app.focus_mode
+        && let Some(todo) = &app.selected_todo
+    {
+        draw_focus_view(
+            f,
+            area,
+            todo,
+            app.subtask_state.selected(),
+            &mut app.focus_scroll,
+        );
+        return;
+    }
     if app.show_modal {
+        let related = app
+            .related
+            .iter()
+            .filter_map(|id| app.todos.iter().find(|todo| todo.id == *id))
+            .collect::<Vec<&Todo>>();
         draw_todo_modal(
             f,
             area,
@@ -50,20 +495,63 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
             &mut app.subtask_state,
             app.editing_notes,
             &app.notes_input,
-            app.notes_scroll_offset,
+            &mut app.notes_scroll_offset,
             app.notes_preview_mode,
+            &mut app.details_scroll_offset,
+            &app.comments,
+            &mut app.comments_scroll,
+            &mut app.detail_search,
+            &related,
+            app.related_selected,
         );
+        // The modal covers the shortcuts bar, its messages go on the bottom border instead
+        if let Some(message) = &app.status_message {
+            f.render_widget(
+                Paragraph::new(Line::from(Span::styled(
+                    format!(" {} ", message),
+                    Style::default().fg(Color::Rgb(220, 180, 100)),
+                )))
+                .alignment(Alignment::Center),
+                Rect {
+                    y: area.y + area.height.saturating_sub(1),
+                    height: 1.min(area.height),
+                    ..area
+                },
+            );
+        }
+        let input_area = Rect {
+            x: area.x + area.width / 6,
+            y: area.y + area.height.saturating_sub(5),
+            width: area.width - area.width / 3,
+            height: 3.min(area.height),
+        };
+        for input in [
+            &mut app.subtask_note_input,
+            &mut app.review_input,
+            &mut app.detail_search_input,
+        ] {
+            if input.active {
+                f.render_widget(ratatui::widgets::Clear, input_area);
+                input.render(f, input_area);
+            }
+        }
         return;
     }
 
     // Main layout with fixed search bar
+    let quick_add_height = if app.quick_add_input.active || app.review_input.active {
+        3
+    } else {
+        0
+    };
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Search bar
-            Constraint::Min(1),    // Table
-            Constraint::Length(2), // Stats
-            Constraint::Length(1), // Shortcuts
+            Constraint::Length(3),                // Search bar
+            Constraint::Min(1),                   // Table
+            Constraint::Length(quick_add_height), // Quick-add bar (only while typing)
+            Constraint::Length(2),                // Stats
+            Constraint::Length(1),                // Shortcuts
         ])
         .split(area);
 
@@ -78,184 +566,610 @@ pub fn draw_ui(f: &mut Frame, app: &mut App) {
         .input
         .render(f, search_block.inner(layout[0]));
 
-    // Prepare table rows
-    let rows = if app.fuzzy_search.input.active {
-        app.filtered_indices
-            .iter()
-            .map(|&i| &app.todos[i])
-            .map(|todo| {
-                let subtasks_finished = todo
-                    .subtasks
-                    .iter()
-                    .filter(|subtask| subtask.status == "Done" || subtask.status == "Completed")
-                    .count();
+    // Pick the columns that fit the current terminal width
+    let icons = configs::display_config().icons;
+    let columns = visible_columns(
+        area.width,
+        &configs::display_config().hidden_columns,
+        icons,
+        configs::display_config().countdown,
+        configs::display_config().row_numbers,
+    );
+    let short_headers = area.width < NARROW_LAYOUT_WIDTH;
 
-                Row::new(vec![
-                    todo.id.to_string().fg(text_primary),
-                    match todo.priority.to_lowercase().as_str() {
-                        "high" => todo.priority.clone().fg(Color::Rgb(220, 80, 150)),
-                        "medium" => todo.priority.clone().fg(Color::Rgb(180, 120, 120)),
-                        "low" => todo.priority.clone().fg(Color::Rgb(120, 220, 150)),
-                        _ => todo.priority.clone().fg(Color::Rgb(120, 80, 200)),
-                    },
-                    todo.topic.clone().fg(text_primary),
-                    // Highlight the todos with notes in them
-                    if todo.notes.is_empty() {
-                        todo.text.clone().fg(text_primary)
-                    } else {
-                        format!("{} [✏️]", todo.text).fg(text_primary)
-                    },
-                    format!("{}/{}", subtasks_finished, todo.subtasks.len()).fg(text_secondary),
-                    todo.date_added.clone().fg(text_secondary),
-                    todo.due.clone().fg(text_secondary),
-                    match todo.status.as_str() {
-                        "Done" | "Completed" => todo.status.clone().fg(Color::Rgb(120, 220, 150)),
-                        "Ongoing" => todo.status.clone().fg(Color::Rgb(220, 180, 100)),
-                        "Planned" => todo.status.clone().fg(accent),
-                        "Pending" => todo.status.clone().fg(Color::Rgb(220, 100, 120)),
-                        _ => todo.status.clone().fg(text_primary),
-                    },
-                    todo.owner
-                        .clone()
-                        .fg(text_primary)
-                        .add_modifier(Modifier::ITALIC),
-                ])
-            })
-            .collect::<Vec<_>>()
+    // Compact density drops the column spacing and the table border to fit more rows
+    let compact = configs::display_config().compact;
+    let column_spacing = if compact { 0 } else { 1 };
+    let table_borders = if compact { Borders::NONE } else { Borders::ALL };
+    let table_width = if compact {
+        layout[1].width
     } else {
-        app.todos
-            .iter()
-            .map(|todo| {
+        layout[1].width.saturating_sub(2)
+    };
+    let layouts = configs::display_config().columns.clone();
+    let constraints = column_constraints(&columns, short_headers, &layouts);
+    let alignments: Vec<Alignment> = columns
+        .iter()
+        .map(|&column| column_alignment(column, &layouts))
+        .collect();
+    let todo_width = todo_column_width(&columns, &constraints, column_spacing, table_width);
+
+    let today = chrono::Local::now().date_naive();
+
+    // Build the content of a table cell for the given column
+    let owners = app.owners.clone();
+    let habits = app.habits.clone();
+    let row_numbers = configs::display_config().row_numbers;
+    let selected = app.state.selected();
+    let todo_span = |column: Column, todo: &Todo, index: usize| -> Span<'static> {
+        match column {
+            Column::Number => match row_numbers.number(index, selected) {
+                number if Some(index) == selected => number.to_string().fg(accent),
+                number => number.to_string().fg(text_secondary),
+            },
+            Column::Id => todo.id.to_string().fg(text_primary),
+            Column::Icons => format!(
+                "{} {}",
+                icons.priority(&todo.priority),
+                icons.status(&todo.status)
+            )
+            .fg(text_primary),
+            Column::Priority => match todo.priority.to_lowercase().as_str() {
+                "high" => todo.priority.clone().fg(Color::Rgb(220, 80, 150)),
+                "medium" => todo.priority.clone().fg(Color::Rgb(180, 120, 120)),
+                "low" => todo.priority.clone().fg(Color::Rgb(120, 220, 150)),
+                _ => todo.priority.clone().fg(Color::Rgb(120, 80, 200)),
+            },
+            Column::Topic => todo.topic.clone().fg(topic_color(&todo.topic)),
+            Column::Todo => {
+                // Pinned todos get a marker in front of the text
+                let text = if todo.pinned {
+                    format!("📌 {}", todo.text)
+                } else {
+                    todo.text.clone()
+                };
+                // Highlight the todos with notes in them
+                let text = if todo.notes.is_empty() {
+                    text
+                } else {
+                    format!("{} [✏️]", text)
+                };
+                // Where it can be done, after the text
+                let text = match &todo.context {
+                    Some(context) => format!("{} @{}", text, context),
+                    None => text,
+                };
+                // Tracked somewhere else, the link shows under the table when selected
+                let text = if todo.external_url.is_some() {
+                    format!("{} 🔗", text)
+                } else {
+                    text
+                };
+                // Habits show their streak, 🔁 until it gets going
+                let text = match habits.iter().find(|habit| habit.todo_id == todo.id) {
+                    Some(habit) if habit.streak > 0 => format!("{} 🔥{}", text, habit.streak),
+                    Some(_) => format!("{} 🔁", text),
+                    None => text,
+                };
+                // Flag todos nobody has touched in a while
+                if is_stale(todo, DEFAULT_STALE_DAYS, today) {
+                    format!("{} 💤", text).fg(text_secondary)
+                } else {
+                    text.fg(text_primary)
+                }
+            }
+            Column::Subtasks => {
                 let subtasks_finished = todo
                     .subtasks
                     .iter()
-                    .filter(|subtask| subtask.status == "Done" || subtask.status == "Completed")
+                    .filter(|subtask| subtask.is_done())
                     .count();
-
-                Row::new(vec![
-                    todo.id.to_string().fg(text_primary),
-                    match todo.priority.to_lowercase().as_str() {
-                        "high" => todo.priority.clone().fg(Color::Rgb(220, 80, 150)),
-                        "medium" => todo.priority.clone().fg(Color::Rgb(180, 120, 120)),
-                        "low" => todo.priority.clone().fg(Color::Rgb(120, 220, 150)),
-                        _ => todo.priority.clone().fg(Color::Rgb(120, 80, 200)),
-                    },
-                    todo.topic.clone().fg(text_primary),
-                    // Highlight the todos with notes in them
-                    if todo.notes.is_empty() {
-                        todo.text.clone().fg(text_primary)
-                    } else {
-                        format!("{} [✏️]", todo.text).fg(text_primary)
-                    },
-                    format!("{}/{}", subtasks_finished, todo.subtasks.len()).fg(text_secondary),
-                    todo.date_added.clone().fg(text_secondary),
-                    todo.due.clone().fg(text_secondary),
-                    match todo.status.as_str() {
-                        "Done" | "Completed" => todo.status.clone().fg(Color::Rgb(120, 220, 150)),
-                        "Ongoing" => todo.status.clone().fg(Color::Rgb(220, 180, 100)),
-                        "Planned" => todo.status.clone().fg(accent),
-                        "Pending" => todo.status.clone().fg(Color::Rgb(220, 100, 120)),
-                        _ => todo.status.clone().fg(text_primary),
-                    },
-                    todo.owner
-                        .clone()
-                        .fg(text_primary)
-                        .add_modifier(Modifier::ITALIC),
-                ])
-            })
-            .collect::<Vec<_>>()
+                format!("{}/{}", subtasks_finished, todo.subtasks.len()).fg(text_secondary)
+            }
+            Column::Created => todo.created_display().fg(text_secondary),
+            Column::Due => todo.due_display().fg(text_secondary),
+            // Done todos have nothing left to count down to
+            Column::Countdown => match todo.days_left(today) {
+                Some(days) if !todo.is_done() => days.to_string().fg(countdown_color(days)),
+                _ => "".fg(text_secondary),
+            },
+            Column::Status => match todo.status.as_str() {
+                "Done" | "Completed" => todo.status.clone().fg(Color::Rgb(120, 220, 150)),
+                "Ongoing" => todo.status.clone().fg(Color::Rgb(220, 180, 100)),
+                "Planned" | "Review" => todo.status.clone().fg(accent),
+                "Pending" => todo.status.clone().fg(Color::Rgb(220, 100, 120)),
+                _ => todo.status.clone().fg(text_primary),
+            },
+            // The display name and color from `voido owners` when they are set
+            Column::Owner => {
+                let owner = owners
+                    .iter()
+                    .find(|owner| owner.name.eq_ignore_ascii_case(&todo.owner));
+                owner
+                    .map_or(todo.owner.as_str(), |owner| owner.label())
+                    .to_string()
+                    .fg(owner
+                        .and_then(|owner| colors::parse_color(&owner.color))
+                        .unwrap_or(text_primary))
+                    .add_modifier(Modifier::ITALIC)
+            }
+        }
     };
 
+    // Prepare table rows, with the [STYLING] rules applied on top of the column colors
+    let styling = configs::styling_config();
+    let expanded_row = app.state.selected().filter(|_| app.expand_selected);
+    let rows = app
+        .visible_todos()
+        .into_iter()
+        .enumerate()
+        .map(|(index, todo)| {
+            let style = row_style(&styling.rules, todo, today);
+            // The expanded row shows every line, whatever the density
+            let (compact, max_lines) = if expanded_row == Some(index) {
+                (false, usize::MAX)
+            } else {
+                (compact, MAX_ROW_LINES)
+            };
+            let mut height = 1;
+            let cells = columns
+                .iter()
+                .zip(&alignments)
+                .map(|(&column, &align)| {
+                    let span = todo_span(column, todo, index).patch_style(style);
+                    if column != Column::Todo {
+                        return Cell::from(Line::from(span).alignment(align));
+                    }
+                    let lines = todo_lines(&span.content, todo_width, compact, max_lines);
+                    height = lines.len();
+                    Cell::from(Text::from(
+                        lines
+                            .into_iter()
+                            .map(|line| Line::from(Span::styled(line, span.style)).alignment(align))
+                            .collect::<Vec<_>>(),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            Row::new(cells).height(height as u16).style(style)
+        })
+        .collect::<Vec<_>>();
+    drop(styling);
+
     // Create and render table
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(5),  // ID (tiny, fixed)
-            Constraint::Length(9),  // PRIORITY (short text, fixed enough)
-            Constraint::Min(10),    // TOPIC (short phrases, expand if room)
-            Constraint::Fill(3),    // TODO (long text, gets majority of space)
-            Constraint::Length(6),  // SUBs (tiny numbers, fixed)
-            Constraint::Length(12), // CREATED (YYYY-MM-DD)
-            Constraint::Length(12), // DUE (YYYY-MM-DD)
-            Constraint::Min(10),    // STATUS (labels like "In Progress")
-            Constraint::Min(10),    // OWNER (names, can expand)
-        ],
-    )
-    .header(
-        Row::new(vec![
-            "ID", "PRIORITY", "TOPIC", "TODO", "SUBt", "CREATED", "DUE DATE", "STATUS", "OWNER",
-        ])
-        .style(Style::default().fg(accent).add_modifier(Modifier::BOLD)),
-    )
-    .block(
-        Block::default()
-            .title("")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(border))
-            .style(Style::default().bg(background)),
-    )
-    .highlight_style(Style::default().bg(highlight).fg(text_primary))
-    .row_highlight_style(
-        Style::default()
-            .bg(Color::Rgb(120, 80, 190))
-            .fg(Color::White),
-    )
-    .column_spacing(1);
+    let table = Table::new(rows, constraints)
+        .header(
+            Row::new(columns.iter().zip(&alignments).map(|(column, &align)| {
+                Cell::from(Line::from(column.header(short_headers)).alignment(align))
+            }))
+            .style(Style::default().fg(accent).add_modifier(Modifier::BOLD)),
+        )
+        .block(
+            Block::default()
+                .title(table_title(app))
+                .title_bottom(selected_link(app))
+                .borders(table_borders)
+                .border_style(Style::default().fg(border))
+                .style(Style::default().bg(background)),
+        )
+        .highlight_style(Style::default().bg(highlight).fg(text_primary))
+        .row_highlight_style(Style::default().bg(theme.selection).fg(Color::White))
+        .column_spacing(column_spacing);
 
-    f.render_stateful_widget(table, layout[1], &mut app.state);
+    if app.todos.is_empty() {
+        draw_empty_state(f, layout[1], &theme);
+    } else {
+        f.render_stateful_widget(table, layout[1], &mut app.state);
+    }
+
+    if app.quick_add_input.active {
+        app.quick_add_input.render(f, layout[2]);
+    } else if app.review_input.active {
+        app.review_input.render(f, layout[2]);
+    }
 
     // Stats area
-    let stats = calculate_stats(&app.todos);
+    let mut stats = calculate_stats(
+        &app.todos,
+        &configs::display_config().stats,
+        chrono::Local::now().date_naive(),
+    );
+    // Where the selection is, once there is more than one row to move through
+    let row_count = app.visible_todos().len();
+    if let Some(selected) = app.state.selected().filter(|_| row_count > 1) {
+        if !stats.spans.is_empty() {
+            stats.spans.push(Span::raw(" | "));
+        }
+        stats.spans.push(Span::styled(
+            format!("row {} of {}", (selected + 1).min(row_count), row_count),
+            Style::default().fg(Color::Rgb(140, 140, 160)),
+        ));
+    }
+    #[cfg(feature = "sync")]
+    if let Some(status) = &app.sync_watcher.status {
+        let (label, color) = match status {
+            SyncStatus::Synced => ("✓ Synced".to_string(), Color::Rgb(120, 220, 150)),
+            SyncStatus::Pending(count) => {
+                (format!("⇅ {} to sync", count), Color::Rgb(220, 180, 100))
+            }
+            SyncStatus::Error(backend) => (
+                format!("⚠ {} sync failed", backend),
+                Color::Rgb(220, 100, 120),
+            ),
+        };
+        if !stats.spans.is_empty() {
+            stats.spans.push(Span::raw(" | "));
+        }
+        stats
+            .spans
+            .push(Span::styled(label, Style::default().fg(color)));
+        // `voido daemon` is running
+        if let Some(next_run) = &app.sync_watcher.next_run {
+            stats.spans.push(Span::styled(
+                format!(
+                    " · next {}",
+                    dates::timezone().clock(next_run).format("%H:%M")
+                ),
+                Style::default().fg(Color::Rgb(140, 140, 160)),
+            ));
+        }
+    }
     let stats_widget = Paragraph::new(stats).alignment(Alignment::Center).block(
         Block::default()
             .border_style(Style::default().fg(border))
             .style(Style::default().bg(background)),
     );
-    f.render_widget(stats_widget, layout[2]);
+    f.render_widget(stats_widget, layout[3]);
 
-    // Shortcuts area
-    let shortcuts = get_shortcuts_text();
+    // Shortcuts area (temporarily replaced by the latest status message)
+    let shortcuts = match &app.status_message {
+        Some(message) => Line::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Rgb(220, 180, 100)),
+        )),
+        None => match (app.merge_source, &app.moving_subtask) {
+            (Some(id), _) => Line::from(Span::styled(
+                format!(
+                    " Merging todo {}: select the todo to keep and press [m], [Esc] to cancel ",
+                    id
+                ),
+                Style::default().fg(Color::Rgb(220, 180, 100)),
+            )),
+            (None, Some((_, text))) => Line::from(Span::styled(
+                format!(
+                    " Moving '{}': select the todo to move it to and press [v], [Esc] to cancel ",
+                    truncate_text(text, 30)
+                ),
+                Style::default().fg(Color::Rgb(220, 180, 100)),
+            )),
+            (None, None) => get_shortcuts_text(),
+        },
+    };
     let shortcuts_widget = Paragraph::new(shortcuts)
         .alignment(Alignment::Center)
         .style(Style::default().fg(text_secondary))
         .block(Block::default().style(Style::default().bg(background)));
-    f.render_widget(shortcuts_widget, layout[3]);
+    f.render_widget(shortcuts_widget, layout[4]);
 }
 
-pub fn calculate_stats(todos: &[Todo]) -> Line {
-    let done = todos.iter().filter(|t| t.status == "Done").count();
-    let ongoing = todos.iter().filter(|t| t.status == "Ongoing").count();
-    let pending = todos.iter().filter(|t| t.status == "Pending").count();
+// EMPTY STATE: first run or an emptied database, a few pointers instead of a blank table
+fn draw_empty_state(f: &mut Frame, area: Rect, theme: &Theme) {
+    let key = |key: &'static str| key.fg(theme.accent).add_modifier(Modifier::BOLD);
+    let hint = |key_span: Span<'static>, text: &'static str| {
+        Line::from(vec![key_span, Span::raw("  "), text.fg(theme.text_primary)])
+    };
 
-    Line::from(vec![
-        Span::raw(" TOTAL: "),
-        Span::styled(
-            todos.len().to_string(),
-            Style::default().fg(Color::Rgb(150, 80, 220)),
-        ),
-        Span::raw(" | Done: "),
-        Span::styled(
-            done.to_string(),
-            Style::default().fg(Color::Rgb(120, 220, 150)),
+    let lines = vec![
+        Line::from("No todos yet".fg(theme.accent).add_modifier(Modifier::BOLD)),
+        Line::from(""),
+        hint(
+            key("a"),
+            "add one: text #topic !priority @owner due:date ctx:place",
         ),
-        Span::raw(" | ONGOING: "),
-        Span::styled(
-            ongoing.to_string(),
-            Style::default().fg(Color::Rgb(220, 180, 100)),
+        hint(key("\\"), "see every key"),
+        hint(key("S"), "pick a theme and the columns"),
+        hint(key("q"), "quit, then `voido --help` for the commands"),
+        Line::from(""),
+        hint(
+            key("X"),
+            "fill the list with a few demo todos to try things out",
         ),
-        Span::raw(" | PENDING: "),
-        Span::styled(
-            pending.to_string(),
-            Style::default().fg(Color::Rgb(220, 100, 120)),
+        Line::from(
+            "They go into your database, `voido --demo` shows them without saving anything"
+                .fg(theme.text_secondary),
         ),
-    ])
+    ];
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .style(Style::default().bg(theme.background));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let [content] = Layout::vertical([Constraint::Length(lines.len() as u16)])
+        .flex(Flex::Center)
+        .areas(inner);
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        content,
+    );
+}
+
+// STATS BAR COUNTERS, picked and ordered by [DISPLAY] stats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stat {
+    Total,
+    Open,
+    Done,
+    Ongoing,
+    Pending,
+    Planned,
+    Review,
+    Overdue,
+    DueToday,
+    HighPriority,
+    Pinned,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 11] = [
+        Stat::Total,
+        Stat::Open,
+        Stat::Done,
+        Stat::Ongoing,
+        Stat::Pending,
+        Stat::Planned,
+        Stat::Review,
+        Stat::Overdue,
+        Stat::DueToday,
+        Stat::HighPriority,
+        Stat::Pinned,
+    ];
+
+    // What the bar shows when the config doesn't say
+    pub const DEFAULT: [Stat; 4] = [Stat::Total, Stat::Done, Stat::Ongoing, Stat::Pending];
+
+    // Name used for the counter in config.toml
+    pub fn key(self) -> &'static str {
+        match self {
+            Stat::Total => "total",
+            Stat::Open => "open",
+            Stat::Done => "done",
+            Stat::Ongoing => "ongoing",
+            Stat::Pending => "pending",
+            Stat::Planned => "planned",
+            Stat::Review => "review",
+            Stat::Overdue => "overdue",
+            Stat::DueToday => "due_today",
+            Stat::HighPriority => "high_priority",
+            Stat::Pinned => "pinned",
+        }
+    }
+
+    pub fn by_key(key: &str) -> Option<Stat> {
+        let key = key.trim().to_lowercase();
+        Stat::ALL.into_iter().find(|stat| stat.key() == key)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Stat::Total => "TOTAL",
+            Stat::Open => "OPEN",
+            Stat::Done => "DONE",
+            Stat::Ongoing => "ONGOING",
+            Stat::Pending => "PENDING",
+            Stat::Planned => "PLANNED",
+            Stat::Review => "REVIEW",
+            Stat::Overdue => "OVERDUE",
+            Stat::DueToday => "DUE TODAY",
+            Stat::HighPriority => "HIGH",
+            Stat::Pinned => "PINNED",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Stat::Total => Color::Rgb(150, 80, 220),
+            Stat::Open => Color::Rgb(100, 180, 220),
+            Stat::Done => Color::Rgb(120, 220, 150),
+            Stat::Ongoing | Stat::DueToday | Stat::Pinned => Color::Rgb(220, 180, 100),
+            Stat::Pending | Stat::HighPriority => Color::Rgb(220, 100, 120),
+            Stat::Planned => Color::Rgb(140, 140, 160),
+            Stat::Review => Color::Rgb(180, 140, 220),
+            Stat::Overdue => Color::Rgb(220, 80, 100),
+        }
+    }
+
+    // Only the counters on the bar are computed
+    fn count(self, todos: &[Todo], today: NaiveDate) -> usize {
+        let open = || todos.iter().filter(|todo| !todo.is_done());
+        match self {
+            Stat::Total => todos.len(),
+            Stat::Open => open().count(),
+            Stat::Done => todos.iter().filter(|todo| todo.is_done()).count(),
+            Stat::Ongoing => todos.iter().filter(|t| t.status == "Ongoing").count(),
+            Stat::Pending => todos.iter().filter(|t| t.status == "Pending").count(),
+            Stat::Planned => todos.iter().filter(|t| t.status == "Planned").count(),
+            Stat::Review => todos.iter().filter(|t| t.status == REVIEW_STATUS).count(),
+            Stat::Overdue => open()
+                .filter(|todo| todo.due.is_some_and(|due| due < today))
+                .count(),
+            Stat::DueToday => open().filter(|todo| todo.due == Some(today)).count(),
+            Stat::HighPriority => open()
+                .filter(|todo| todo.priority.eq_ignore_ascii_case("high"))
+                .count(),
+            Stat::Pinned => todos.iter().filter(|todo| todo.pinned).count(),
+        }
+    }
+}
+
+pub fn calculate_stats(todos: &[Todo], stats: &[Stat], today: NaiveDate) -> Line<'static> {
+    let mut spans = Vec::new();
+    for stat in stats {
+        spans.push(Span::raw(if spans.is_empty() {
+            format!(" {}: ", stat.label())
+        } else {
+            format!(" | {}: ", stat.label())
+        }));
+        spans.push(Span::styled(
+            stat.count(todos, today).to_string(),
+            Style::default().fg(stat.color()),
+        ));
+    }
+    Line::from(spans)
 }
 
 fn get_shortcuts_text() -> Line<'static> {
     Line::from(vec![
         Span::raw(" [↑/↓: Navigate] "),
         Span::raw(" [Enter: Details] "),
+        Span::raw(" [a: Quick add] "),
         Span::raw(" [\\: Menu] "),
         Span::raw(" [q: Quit] "),
     ])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_and_truncate_todo_text() {
+        assert_eq!(
+            wrap_text("Fix the login bug today", 10),
+            vec!["Fix the", "login bug", "today"]
+        );
+        assert_eq!(wrap_text("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(truncate_text("Fix the login bug", 8), "Fix the…");
+        // Wide characters take two columns
+        assert_eq!(
+            wrap_text("日本語の テスト", 6),
+            vec!["日本語", "の", "テスト"]
+        );
+        assert_eq!(truncate_text("日本語のテスト", 6), "日本…");
+        assert_eq!(
+            todo_lines("Fix the login bug today", 10, false, 2),
+            vec!["Fix the", "login bug…"]
+        );
+        assert_eq!(
+            todo_lines("Fix the login bug", 10, true, 2),
+            vec!["Fix the l…"]
+        );
+    }
+
+    #[test]
+    fn test_icon_column_replaces_priority_and_status() {
+        let columns = visible_columns(
+            WIDE_LAYOUT_WIDTH,
+            &[],
+            IconSet::Emoji,
+            false,
+            RowNumbers::Off,
+        );
+        assert!(columns.contains(&Column::Icons));
+        assert!(!columns.contains(&Column::Priority));
+        assert!(!columns.contains(&Column::Status));
+
+        let columns = visible_columns(WIDE_LAYOUT_WIDTH, &[], IconSet::Off, false, RowNumbers::Off);
+        assert!(!columns.contains(&Column::Icons));
+        assert!(columns.contains(&Column::Priority));
+        assert!(!columns.contains(&Column::Countdown));
+        assert!(!columns.contains(&Column::Number));
+    }
+
+    #[test]
+    fn test_stats_bar_shows_the_configured_counters() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        let todo = |status: &str, priority: &str, due: Option<NaiveDate>| Todo {
+            priority: priority.to_string(),
+            status: status.to_string(),
+            due,
+            ..Todo::sample("Ship")
+        };
+        let todos = [
+            todo("Pending", "High", today.pred_opt()),
+            todo("Done", "High", today.pred_opt()),
+            todo("Ongoing", "Low", Some(today)),
+        ];
+        let text = |stats: &[Stat]| {
+            calculate_stats(&todos, stats, today)
+                .spans
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect::<String>()
+        };
+        assert_eq!(
+            text(&[
+                Stat::Total,
+                Stat::Overdue,
+                Stat::DueToday,
+                Stat::HighPriority
+            ]),
+            " TOTAL: 3 | OVERDUE: 1 | DUE TODAY: 1 | HIGH: 1"
+        );
+        assert_eq!(text(&[]), "");
+        assert_eq!(Stat::by_key(" Due_Today"), Some(Stat::DueToday));
+    }
+
+    #[test]
+    fn test_column_layout_overrides_the_constraints() {
+        let layouts = vec![
+            (
+                "id".to_string(),
+                ColumnLayout {
+                    width: Some(3),
+                    min: Some(8),
+                    ..ColumnLayout::default()
+                },
+            ),
+            (
+                "created".to_string(),
+                ColumnLayout {
+                    min: Some(8),
+                    max: Some(10),
+                    ..ColumnLayout::default()
+                },
+            ),
+            (
+                "owner".to_string(),
+                ColumnLayout {
+                    max: Some(12),
+                    align: Some(Alignment::Right),
+                    ..ColumnLayout::default()
+                },
+            ),
+        ];
+        let columns = [Column::Id, Column::Topic, Column::Created, Column::Owner];
+        assert_eq!(
+            column_constraints(&columns, false, &layouts),
+            vec![
+                Constraint::Length(3),
+                Constraint::Min(10),
+                Constraint::Length(10),
+                Constraint::Max(12),
+            ]
+        );
+        assert_eq!(parse_alignment("Centre"), Some(Alignment::Center));
+        assert_eq!(parse_alignment("middle"), None);
+    }
+
+    #[test]
+    fn test_relative_row_numbers_count_from_the_selection() {
+        assert_eq!(RowNumbers::Absolute.number(4, Some(1)), 5);
+        assert_eq!(RowNumbers::Relative.number(4, Some(1)), 3);
+        assert_eq!(RowNumbers::Relative.number(0, Some(3)), 3);
+        // The selected row shows where it is
+        assert_eq!(RowNumbers::Relative.number(3, Some(3)), 4);
+        assert_eq!(RowNumbers::Relative.number(2, None), 3);
+    }
+
+    #[test]
+    fn test_countdown_color_goes_from_green_to_red() {
+        assert_eq!(countdown_color(30), Color::Rgb(120, 220, 150));
+        assert_eq!(countdown_color(0), Color::Rgb(220, 80, 100));
+        assert_eq!(countdown_color(-3), countdown_color(0));
+        assert_eq!(countdown_color(7), Color::Rgb(170, 150, 125));
+    }
+}